@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS "idx_upload_sessions_expires_at";
+DROP INDEX IF EXISTS "idx_upload_sessions_user_id";
+DROP TABLE IF EXISTS "upload_sessions";
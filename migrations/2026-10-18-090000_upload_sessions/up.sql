@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS "upload_sessions" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "filename" VARCHAR(255) NOT NULL,
+    "original_filename" VARCHAR(255) NOT NULL,
+    "file_size" BIGINT NOT NULL,
+    "content_type" VARCHAR(100) NOT NULL,
+    "file_path" VARCHAR(500) NOT NULL,
+    "storage_upload_id" VARCHAR(1024) NOT NULL,
+    "upload_offset" BIGINT NOT NULL DEFAULT 0,
+    "part_etags" TEXT[] NOT NULL DEFAULT '{}',
+    "expires_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "idx_upload_sessions_user_id" ON "upload_sessions"("user_id");
+CREATE INDEX IF NOT EXISTS "idx_upload_sessions_expires_at" ON "upload_sessions"("expires_at");
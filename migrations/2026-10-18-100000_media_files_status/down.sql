@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE IF EXISTS "media_files" DROP COLUMN IF EXISTS "status";
+
+DROP TYPE IF EXISTS media_status;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TYPE media_status AS ENUM ('PENDING', 'READY');
+
+ALTER TABLE IF EXISTS "media_files"
+    ADD COLUMN IF NOT EXISTS "status" media_status NOT NULL DEFAULT 'READY';
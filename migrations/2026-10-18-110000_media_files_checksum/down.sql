@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS "idx_media_files_user_id_checksum";
+
+ALTER TABLE "media_files" DROP COLUMN IF EXISTS "checksum";
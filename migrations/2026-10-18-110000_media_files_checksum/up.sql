@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE "media_files" ADD COLUMN "checksum" VARCHAR(64) NULL;
+
+CREATE INDEX IF NOT EXISTS "idx_media_files_user_id_checksum" ON "media_files"("user_id", "checksum");
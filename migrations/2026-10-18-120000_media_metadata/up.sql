@@ -0,0 +1,22 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS "media_metadata" (
+    "media_id" UUID PRIMARY KEY REFERENCES "media_files"("id") ON DELETE CASCADE,
+    "taken_at" TIMESTAMP NULL,
+    "camera_make" VARCHAR(255) NULL,
+    "camera_model" VARCHAR(255) NULL,
+    "orientation" SMALLINT NULL,
+    "width" INTEGER NULL,
+    "height" INTEGER NULL,
+    "latitude" DOUBLE PRECISION NULL,
+    "longitude" DOUBLE PRECISION NULL,
+    "exposure_time" VARCHAR(32) NULL,
+    "f_number" DOUBLE PRECISION NULL,
+    "iso" INTEGER NULL,
+    "focal_length" DOUBLE PRECISION NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "idx_media_metadata_taken_at" ON "media_metadata"("taken_at");
+
+SELECT diesel_manage_updated_at('media_metadata');
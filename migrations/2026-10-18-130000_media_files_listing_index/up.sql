@@ -0,0 +1,3 @@
+-- Your SQL goes here
+CREATE INDEX IF NOT EXISTS "idx_media_files_user_id_uploaded_at_id"
+    ON "media_files"("user_id", "uploaded_at", "id");
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "album_media";
+DROP TABLE IF EXISTS "albums";
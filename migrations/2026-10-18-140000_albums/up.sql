@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS "albums" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "name" VARCHAR(255) NOT NULL,
+    "description" TEXT NULL,
+    "cover_media_id" UUID NULL REFERENCES "media_files"("id") ON DELETE SET NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "idx_albums_user_id" ON "albums"("user_id");
+
+SELECT diesel_manage_updated_at('albums');
+
+CREATE TABLE IF NOT EXISTS "album_media" (
+    "album_id" UUID NOT NULL REFERENCES "albums"("id") ON DELETE CASCADE,
+    "media_id" UUID NOT NULL REFERENCES "media_files"("id") ON DELETE CASCADE,
+    "position" INTEGER NOT NULL,
+    "added_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY ("album_id", "media_id")
+);
+
+CREATE INDEX IF NOT EXISTS "idx_album_media_album_id_position" ON "album_media"("album_id", "position");
+CREATE INDEX IF NOT EXISTS "idx_album_media_media_id" ON "album_media"("media_id");
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "album_shares";
+DROP TABLE IF EXISTS "media_shares";
+DROP TYPE IF EXISTS share_role;
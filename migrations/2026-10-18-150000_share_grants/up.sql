@@ -0,0 +1,28 @@
+-- Your SQL goes here
+CREATE TYPE share_role AS ENUM ('VIEWER', 'CONTRIBUTOR');
+
+CREATE TABLE IF NOT EXISTS "media_shares" (
+    "media_id" UUID NOT NULL REFERENCES "media_files"("id") ON DELETE CASCADE,
+    "grantee_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "role" share_role NOT NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY ("media_id", "grantee_id")
+);
+
+CREATE INDEX IF NOT EXISTS "idx_media_shares_grantee_id" ON "media_shares"("grantee_id");
+
+SELECT diesel_manage_updated_at('media_shares');
+
+CREATE TABLE IF NOT EXISTS "album_shares" (
+    "album_id" UUID NOT NULL REFERENCES "albums"("id") ON DELETE CASCADE,
+    "grantee_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "role" share_role NOT NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY ("album_id", "grantee_id")
+);
+
+CREATE INDEX IF NOT EXISTS "idx_album_shares_grantee_id" ON "album_shares"("grantee_id");
+
+SELECT diesel_manage_updated_at('album_shares');
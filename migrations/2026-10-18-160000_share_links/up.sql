@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS "share_links" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "slug" VARCHAR(64) NOT NULL UNIQUE,
+    "owner_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "media_id" UUID REFERENCES "media_files"("id") ON DELETE CASCADE,
+    "album_id" UUID REFERENCES "albums"("id") ON DELETE CASCADE,
+    "password_hash" VARCHAR(255),
+    "allow_download" BOOLEAN NOT NULL DEFAULT FALSE,
+    "expires_at" TIMESTAMP WITH TIME ZONE,
+    "view_count" BIGINT NOT NULL DEFAULT 0,
+    "last_viewed_at" TIMESTAMP WITH TIME ZONE,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    -- A link points to exactly one media file or album
+    CONSTRAINT "share_links_single_target" CHECK (("media_id" IS NULL) <> ("album_id" IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS "idx_share_links_owner_id" ON "share_links"("owner_id");
+
+SELECT diesel_manage_updated_at('share_links');
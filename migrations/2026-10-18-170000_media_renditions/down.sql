@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "media_files" ADD COLUMN IF NOT EXISTS "thumbnail_path" VARCHAR(500) NULL;
+
+UPDATE "media_files"
+SET "thumbnail_path" = "media_renditions"."file_path"
+FROM "media_renditions"
+WHERE "media_renditions"."media_id" = "media_files"."id"
+  AND "media_renditions"."name" = 'medium';
+
+DROP TABLE IF EXISTS "media_renditions";
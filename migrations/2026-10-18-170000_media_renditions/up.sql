@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS "media_renditions" (
+    "media_id" UUID NOT NULL REFERENCES "media_files"("id") ON DELETE CASCADE,
+    "name" VARCHAR(32) NOT NULL,
+    "file_path" VARCHAR(500) NOT NULL,
+    "content_type" VARCHAR(100) NOT NULL,
+    "width" INTEGER NULL,
+    "height" INTEGER NULL,
+    "file_size" BIGINT NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY ("media_id", "name")
+);
+
+-- Existing thumbnails were generated at 300px, which is the medium rendition
+INSERT INTO "media_renditions" ("media_id", "name", "file_path", "content_type")
+SELECT "id", 'medium', "thumbnail_path", 'image/jpeg'
+FROM "media_files"
+WHERE "thumbnail_path" IS NOT NULL;
+
+ALTER TABLE "media_files" DROP COLUMN IF EXISTS "thumbnail_path";
+
+SELECT diesel_manage_updated_at('media_renditions');
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "media_metadata"
+    DROP COLUMN IF EXISTS "duration_seconds",
+    DROP COLUMN IF EXISTS "video_codec",
+    DROP COLUMN IF EXISTS "frame_rate";
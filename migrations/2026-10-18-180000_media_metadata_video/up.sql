@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE "media_metadata"
+    ADD COLUMN IF NOT EXISTS "duration_seconds" DOUBLE PRECISION NULL,
+    ADD COLUMN IF NOT EXISTS "video_codec" VARCHAR(32) NULL,
+    ADD COLUMN IF NOT EXISTS "frame_rate" DOUBLE PRECISION NULL;
@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TYPE job_status AS ENUM ('PENDING', 'RUNNING', 'SUCCEEDED', 'DEAD');
+
+CREATE TABLE IF NOT EXISTS "jobs" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "user_id" UUID NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "job_type" VARCHAR(64) NOT NULL,
+    "payload" JSONB NOT NULL,
+    "status" job_status NOT NULL DEFAULT 'PENDING',
+    "attempts" INTEGER NOT NULL DEFAULT 0,
+    "max_attempts" INTEGER NOT NULL,
+    "run_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "locked_at" TIMESTAMP WITH TIME ZONE NULL,
+    "last_error" TEXT NULL,
+    "completed_at" TIMESTAMP WITH TIME ZONE NULL,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Workers only look for due pending jobs and for running jobs whose worker stopped
+CREATE INDEX IF NOT EXISTS "idx_jobs_pending_run_at" ON "jobs"("run_at") WHERE "status" = 'PENDING';
+CREATE INDEX IF NOT EXISTS "idx_jobs_running_locked_at" ON "jobs"("locked_at") WHERE "status" = 'RUNNING';
+CREATE INDEX IF NOT EXISTS "idx_jobs_user_id_created_at" ON "jobs"("user_id", "created_at");
+
+SELECT diesel_manage_updated_at('jobs');
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM "media_renditions" WHERE "content_type" <> 'image/jpeg';
+
+ALTER TABLE "media_renditions" DROP CONSTRAINT "media_renditions_pkey";
+ALTER TABLE "media_renditions" ADD PRIMARY KEY ("media_id", "name");
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Every rendition size is stored once per image format
+ALTER TABLE "media_renditions" DROP CONSTRAINT "media_renditions_pkey";
+ALTER TABLE "media_renditions" ADD PRIMARY KEY ("media_id", "name", "content_type");
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "media_files" DROP COLUMN IF EXISTS "detected_content_type";
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE "media_files" ADD COLUMN "detected_content_type" VARCHAR(100) NULL;
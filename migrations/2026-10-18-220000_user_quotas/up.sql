@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Missing quotas fall back to the defaults the server is configured with
+ALTER TABLE "users" ADD COLUMN "quota_bytes" BIGINT NULL;
+ALTER TABLE "users" ADD COLUMN "quota_items" BIGINT NULL;
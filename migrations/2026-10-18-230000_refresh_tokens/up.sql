@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- Opaque refresh tokens, only their SHA-256 hash is stored. Every refresh replaces the token
+-- with a new one of the same family, a family stands for one login.
+CREATE TABLE IF NOT EXISTS "refresh_tokens" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "family_id" UUID NOT NULL,
+    "token_hash" VARCHAR(64) NOT NULL UNIQUE,
+    "expires_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Set once the token was exchanged for a new one, using it again revokes the family
+    "used_at" TIMESTAMP WITH TIME ZONE,
+    "revoked_at" TIMESTAMP WITH TIME ZONE,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "idx_refresh_tokens_family_id" ON "refresh_tokens"("family_id");
+CREATE INDEX IF NOT EXISTS "idx_refresh_tokens_user_id" ON "refresh_tokens"("user_id");
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+-- Devices a user is logged in from. The id of a session is the family id of its refresh tokens
+-- and the `sid` claim of its access tokens.
+CREATE TABLE IF NOT EXISTS "sessions" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "device_name" VARCHAR(100),
+    "user_agent" TEXT,
+    "ip_address" VARCHAR(45),
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "last_seen_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    "revoked_at" TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS "idx_sessions_user_id" ON "sessions"("user_id");
+
+-- Logins made before sessions existed keep working as sessions without device details
+INSERT INTO "sessions" ("id", "user_id", "created_at", "last_seen_at", "revoked_at")
+SELECT "family_id", "user_id", MIN("created_at"), MAX("created_at"), MAX("revoked_at")
+FROM "refresh_tokens"
+GROUP BY "family_id", "user_id"
+ON CONFLICT ("id") DO NOTHING;
+
+ALTER TABLE "refresh_tokens"
+    ADD CONSTRAINT "fk_refresh_tokens_family_id"
+    FOREIGN KEY ("family_id") REFERENCES "sessions"("id") ON DELETE CASCADE;
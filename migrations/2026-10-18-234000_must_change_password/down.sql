@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE "users" DROP COLUMN IF EXISTS "must_change_password";
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Set when an admin resets the password, the user has to pick a new one at the next login
+ALTER TABLE "users" ADD COLUMN "must_change_password" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS "password_reset_tokens";
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS "users_email_key";
+ALTER TABLE "users" DROP COLUMN IF EXISTS "email";
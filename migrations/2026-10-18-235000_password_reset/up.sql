@@ -0,0 +1,17 @@
+-- Your SQL goes here
+-- Address password reset links are sent to, optional for users created before it existed
+ALTER TABLE "users" ADD COLUMN "email" VARCHAR(255) NULL;
+ALTER TABLE "users" ADD CONSTRAINT "users_email_key" UNIQUE ("email");
+
+-- Single-use tokens emailed to reset a forgotten password, only their SHA-256 hash is stored
+CREATE TABLE IF NOT EXISTS "password_reset_tokens" (
+    "id" UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    "user_id" UUID NOT NULL REFERENCES "users"("id") ON DELETE CASCADE,
+    "token_hash" VARCHAR(64) NOT NULL UNIQUE,
+    "expires_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Set once the password was reset with the token, or with another token of the user
+    "used_at" TIMESTAMP WITH TIME ZONE,
+    "created_at" TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS "idx_password_reset_tokens_user_id" ON "password_reset_tokens"("user_id");
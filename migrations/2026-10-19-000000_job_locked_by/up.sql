@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Worker running the job, only that worker may record its outcome
+ALTER TABLE "jobs" ADD COLUMN "locked_by" UUID NULL;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS "idx_media_files_user_id_checksum_ready";
+
+CREATE INDEX IF NOT EXISTS "idx_media_files_user_id_checksum" ON "media_files"("user_id", "checksum");
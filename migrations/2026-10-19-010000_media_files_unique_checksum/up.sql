@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- Copies stored before duplicates were refused keep their content, only the oldest one stays
+-- findable by its checksum
+UPDATE "media_files" SET "checksum" = NULL
+WHERE "id" IN (
+    SELECT "id" FROM (
+        SELECT "id", ROW_NUMBER() OVER (
+            PARTITION BY "user_id", "checksum" ORDER BY "uploaded_at", "id"
+        ) AS "copy"
+        FROM "media_files"
+        WHERE "status" = 'READY' AND "checksum" IS NOT NULL
+    ) AS "copies"
+    WHERE "copy" > 1
+);
+
+-- Two concurrent uploads of the same content both pass the duplicate lookup, only one is kept
+DROP INDEX IF EXISTS "idx_media_files_user_id_checksum";
+CREATE UNIQUE INDEX IF NOT EXISTS "idx_media_files_user_id_checksum_ready"
+    ON "media_files"("user_id", "checksum") WHERE "status" = 'READY';
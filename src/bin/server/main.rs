@@ -1,24 +1,54 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context;
 
 use diesel::{
     PgConnection,
     r2d2::{ConnectionManager, Pool},
 };
 use lib::{
+    albums::infrastructure::DieselAlbumRepository,
     api::http_server::HttpServer,
+    jobs::infrastructure::{
+        AppJobHandler, DieselJobRepository, JobWorkerConfig, spawn_job_workers,
+    },
     media::{
-        domain::ImageThumbnailService,
-        infrastructure::{DieselMediaRepository, MinioStorageService},
+        application::commands::{
+            cleanup_expired_pending_uploads_command_handler,
+            cleanup_expired_upload_sessions_command_handler,
+            cleanup_stale_multipart_uploads_command_handler,
+        },
+        domain::{
+            ExifMetadataService, ImageFormatDecoder, ImageThumbnailService, RenditionConfig,
+            ThumbnailDecodeConfig, VideoThumbnailService,
+        },
+        infrastructure::{
+            DieselMediaRepository, DieselUploadSessionRepository, FfmpegFrameExtractor,
+            FfmpegFrameExtractorConfig, HmacMediaUrlSigner, HmacMediaUrlSignerConfig,
+            MinioStorageService,
+        },
+    },
+    sharing::{
+        domain::ShareGrantAuthorizationService,
+        infrastructure::{DieselShareGrantRepository, DieselShareLinkRepository},
     },
     users::{
         application::create_user::create_user_command_handler,
         infrastructure::{
-            DieselUserRepository,
+            AppMailer, DieselPasswordResetTokenRepository, DieselRefreshTokenRepository,
+            DieselSessionRepository, DieselUserRepository, FileMailer, FileMailerConfig,
+            JwtKeyAlgorithm, SmtpMailer, SmtpMailerConfig,
+            jwt_keys::{generate_jwt_key, load_jwt_keys, remove_old_jwt_keys},
             jwt_token_service::{JwtTokenConfig, JwtTokenService},
         },
     },
 };
 
+#[cfg(feature = "raw")]
+use lib::media::infrastructure::RawPreviewDecoder;
+#[cfg(feature = "heif")]
+use lib::media::infrastructure::{HeifCommandDecoder, HeifCommandDecoderConfig};
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
@@ -31,9 +61,12 @@ async fn main() -> anyhow::Result<()> {
             "create-admin" => {
                 return create_admin_user().await;
             }
+            "jwt-keys" => {
+                return jwt_keys(&args[2..]);
+            }
             _ => {
                 eprintln!("Unknown command: {}", args[1]);
-                eprintln!("Available commands: create-admin");
+                eprintln!("Available commands: create-admin, jwt-keys");
                 std::process::exit(1);
             }
         }
@@ -46,24 +79,117 @@ async fn main() -> anyhow::Result<()> {
     // Services
     let user_repository = DieselUserRepository::new(connection_pool.clone());
     let login_token_service = JwtTokenService::new(JwtTokenConfig::new());
+    let refresh_token_repository = DieselRefreshTokenRepository::new(connection_pool.clone());
+    let session_repository = DieselSessionRepository::new(connection_pool.clone());
+    let password_reset_token_repository =
+        DieselPasswordResetTokenRepository::new(connection_pool.clone());
+    let mailer = create_mailer()?;
 
     // Media services
     let media_repository = DieselMediaRepository::new((*connection_pool).clone());
     let storage_service = create_storage_service().await?;
-    let thumbnail_service = ImageThumbnailService::new(
-        DieselMediaRepository::new((*connection_pool).clone()),
-        create_storage_service().await?,
+    let media_url_signer = HmacMediaUrlSigner::new(
+        HmacMediaUrlSignerConfig::new()
+            .map_err(|e| anyhow::anyhow!("Failed to create media URL signer: {}", e))?,
+    );
+    let upload_session_repository = DieselUploadSessionRepository::new((*connection_pool).clone());
+    let album_repository = DieselAlbumRepository::new((*connection_pool).clone());
+    let share_grant_repository = DieselShareGrantRepository::new((*connection_pool).clone());
+    let share_link_repository = DieselShareLinkRepository::new((*connection_pool).clone());
+    let authorization_service = ShareGrantAuthorizationService::new(
+        DieselShareGrantRepository::new((*connection_pool).clone()),
     );
 
+    let job_repository = DieselJobRepository::new((*connection_pool).clone());
+
     let server = HttpServer::new(
         user_repository,
         login_token_service,
+        refresh_token_repository,
+        session_repository,
+        password_reset_token_repository,
+        mailer,
         media_repository,
         storage_service,
-        thumbnail_service,
+        media_url_signer,
+        upload_session_repository,
+        album_repository,
+        share_grant_repository,
+        share_link_repository,
+        authorization_service,
+        job_repository,
     )
     .await?;
 
+    // Media processing runs in the background so uploads return as soon as the file is stored
+    let job_handler = AppJobHandler::new(
+        DieselMediaRepository::new((*connection_pool).clone()),
+        create_storage_service().await?,
+        VideoThumbnailService::new(
+            ImageThumbnailService::new(
+                DieselMediaRepository::new((*connection_pool).clone()),
+                create_storage_service().await?,
+                RenditionConfig::new(),
+                ThumbnailDecodeConfig::new(),
+            )
+            .with_decoders(image_format_decoders()),
+            FfmpegFrameExtractor::new(FfmpegFrameExtractorConfig::new()),
+            DieselMediaRepository::new((*connection_pool).clone()),
+        ),
+        ExifMetadataService::new(DieselMediaRepository::new((*connection_pool).clone())),
+    );
+    spawn_job_workers(
+        JobWorkerConfig::new(),
+        Arc::new(DieselJobRepository::new((*connection_pool).clone())),
+        Arc::new(job_handler),
+    );
+
+    // Abandoned and interrupted uploads keep their parts in the storage until they are aborted
+    let cleanup_upload_session_repository =
+        DieselUploadSessionRepository::new((*connection_pool).clone());
+    let cleanup_media_repository = DieselMediaRepository::new((*connection_pool).clone());
+    let cleanup_storage_service = create_storage_service().await?;
+    let cleanup_interval = std::env::var("UPLOAD_SESSION_CLEANUP_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(3600);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(cleanup_interval));
+        loop {
+            interval.tick().await;
+            match cleanup_expired_upload_sessions_command_handler(
+                &cleanup_upload_session_repository,
+                &cleanup_storage_service,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Cleaned up {} expired upload sessions", count),
+                Err(e) => tracing::error!("Failed to clean up expired upload sessions: {}", e),
+            }
+
+            // Presigned uploads that were never confirmed reserve quota until they are removed
+            match cleanup_expired_pending_uploads_command_handler(
+                &cleanup_media_repository,
+                &cleanup_storage_service,
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Cleaned up {} expired presigned uploads", count),
+                Err(e) => tracing::error!("Failed to clean up expired presigned uploads: {}", e),
+            }
+
+            // Uploads interrupted by a crash or restart have no session to clean them up
+            match cleanup_stale_multipart_uploads_command_handler(&cleanup_storage_service).await
+            {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Aborted {} stale multipart uploads", count),
+                Err(e) => tracing::error!("Failed to abort stale multipart uploads: {}", e),
+            }
+        }
+    });
+
     server.run().await
 }
 
@@ -72,7 +198,11 @@ async fn create_admin_user() -> anyhow::Result<()> {
 
     // Get admin credentials from environment variables
     let admin_username = std::env::var("ADMIN_USERNAME").unwrap_or_else(|_| "admin".to_string());
-    let admin_password = std::env::var("ADMIN_PASSWORD").unwrap_or_else(|_| "admin".to_string());
+    let admin_email = std::env::var("ADMIN_EMAIL").ok();
+    let admin_password = std::env::var("ADMIN_PASSWORD").ok();
+    // The default password has to be replaced at the first login
+    let must_change_password = admin_password.is_none();
+    let admin_password = admin_password.unwrap_or_else(|| "admin".to_string());
 
     // Establish connection
     let connection_pool = Arc::new(establish_connection());
@@ -82,8 +212,10 @@ async fn create_admin_user() -> anyhow::Result<()> {
     match create_user_command_handler(
         lib::users::application::create_user::CreateUserCommand {
             username: admin_username.clone(),
+            email: admin_email,
             password: admin_password,
             role: Some(lib::users::domain::Role::Admin),
+            must_change_password,
         },
         &user_repository,
     )
@@ -94,7 +226,9 @@ async fn create_admin_user() -> anyhow::Result<()> {
                 "Admin user created successfully with username: {}",
                 admin_username
             );
-            println!("Please change the default password on first login!");
+            if must_change_password {
+                println!("The default password has to be changed on first login");
+            }
         }
         Err(e) => {
             eprintln!("Failed to create admin user: {}", e);
@@ -105,6 +239,74 @@ async fn create_admin_user() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Keys kept by `jwt-keys rotate` when `--keep` is not given, the new one and the previous one
+/// so tokens signed before the rotation stay valid until they expire
+const DEFAULT_JWT_KEYS_KEPT: usize = 2;
+
+/// Manages the signing keys of `JWT_KEY_DIR`, the server picks changes up on restart
+fn jwt_keys(args: &[String]) -> anyhow::Result<()> {
+    let usage = "Usage: jwt-keys generate [eddsa|rs256] | rotate [eddsa|rs256] [--keep N] | list";
+    let key_dir = std::env::var("JWT_KEY_DIR")
+        .map(std::path::PathBuf::from)
+        .context("JWT_KEY_DIR must be set in the environment variables")?;
+
+    let algorithm = |arg: Option<&String>| match arg {
+        Some(arg) if !arg.starts_with("--") => arg.parse::<JwtKeyAlgorithm>(),
+        _ => Ok(JwtKeyAlgorithm::EdDsa),
+    };
+
+    match args.first().map(String::as_str) {
+        Some("generate") => {
+            let kid = generate_jwt_key(&key_dir, algorithm(args.get(1))?)?;
+            println!("Generated key {}", kid);
+        }
+        Some("rotate") => {
+            let keep = match args.iter().position(|arg| arg == "--keep") {
+                Some(index) => args
+                    .get(index + 1)
+                    .and_then(|keep| keep.parse::<usize>().ok())
+                    .filter(|keep| *keep > 0)
+                    .with_context(|| format!("--keep expects a positive number. {}", usage))?,
+                None => DEFAULT_JWT_KEYS_KEPT,
+            };
+            let kid = generate_jwt_key(&key_dir, algorithm(args.get(1))?)?;
+            match std::env::var("JWT_SIGNING_KEY_ID") {
+                Ok(signing_kid) if !signing_kid.trim().is_empty() => println!(
+                    "Generated key {}, new tokens are signed with {} until JWT_SIGNING_KEY_ID is unset",
+                    kid, signing_kid
+                ),
+                _ => println!("Generated key {}, new tokens are signed with it", kid),
+            }
+            for removed in remove_old_jwt_keys(&key_dir, keep)? {
+                println!("Removed key {}", removed);
+            }
+            println!("Restart the server to pick up the new keys");
+        }
+        Some("list") => {
+            let config = JwtTokenConfig {
+                secret_key: None,
+                keys: load_jwt_keys(&key_dir)?,
+                signing_key_id: std::env::var("JWT_SIGNING_KEY_ID").ok(),
+            };
+            let signing_kid = config.signing_key().map(|key| key.kid.clone());
+            for key in &config.keys {
+                let signing = if Some(&key.kid) == signing_kid.as_ref() {
+                    " (signing)"
+                } else {
+                    ""
+                };
+                println!("{} {}{}", key.kid, key.algorithm, signing);
+            }
+        }
+        _ => {
+            eprintln!("{}", usage);
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
 fn establish_connection() -> Pool<ConnectionManager<PgConnection>> {
     let database_url = std::env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set in the environment variables");
@@ -116,6 +318,17 @@ fn establish_connection() -> Pool<ConnectionManager<PgConnection>> {
         .unwrap_or_else(|e| panic!("Error creating connection pool for {}: {}", &database_url, e))
 }
 
+/// Sends emails through SMTP when `SMTP_HOST` is set, writes them to files otherwise
+fn create_mailer() -> anyhow::Result<AppMailer> {
+    if std::env::var("SMTP_HOST").is_ok() {
+        let mailer = SmtpMailer::new(SmtpMailerConfig::new())
+            .map_err(|e| anyhow::anyhow!("Failed to create SMTP mailer: {}", e))?;
+        Ok(AppMailer::Smtp(Box::new(mailer)))
+    } else {
+        Ok(AppMailer::File(FileMailer::new(FileMailerConfig::new())))
+    }
+}
+
 async fn create_storage_service() -> anyhow::Result<MinioStorageService> {
     let minio_endpoint =
         std::env::var("MINIO_ENDPOINT").unwrap_or_else(|_| "http://localhost:9000".to_string());
@@ -134,3 +347,16 @@ async fn create_storage_service() -> anyhow::Result<MinioStorageService> {
     .await
     .map_err(|e| anyhow::anyhow!("Failed to create storage service: {}", e))
 }
+
+/// Decoders of the image formats enabled through cargo features
+#[allow(unused_mut, clippy::vec_init_then_push)]
+fn image_format_decoders() -> Vec<Arc<dyn ImageFormatDecoder>> {
+    let mut decoders: Vec<Arc<dyn ImageFormatDecoder>> = Vec::new();
+    #[cfg(feature = "heif")]
+    decoders.push(Arc::new(HeifCommandDecoder::new(
+        HeifCommandDecoderConfig::new(),
+    )));
+    #[cfg(feature = "raw")]
+    decoders.push(Arc::new(RawPreviewDecoder::new()));
+    decoders
+}
@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::{AlbumError, AlbumId, AlbumRepository},
+    media::domain::{MediaId, MediaRepository, MediaStatus},
+    sharing::domain::{AuthorizationService, Permission},
+};
+
+#[derive(Debug)]
+pub struct AddAlbumMediaCommand {
+    pub album_id: AlbumId,
+    pub user_id: Uuid,
+    pub media_ids: Vec<MediaId>,
+}
+
+pub async fn add_album_media_command_handler<
+    AR: AlbumRepository + ?Sized,
+    MR: MediaRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    command: AddAlbumMediaCommand,
+    album_repository: &AR,
+    media_repository: &MR,
+    authorization_service: &AS,
+) -> Result<(), AlbumError> {
+    let album = album_repository
+        .get_album_by_id(command.album_id)
+        .await?
+        .ok_or(AlbumError::AlbumNotFound)?;
+
+    if !authorization_service
+        .can_access_album(command.user_id, &album, Permission::Contribute)
+        .await
+        .map_err(|e| AlbumError::InternalServerError(e.to_string()))?
+    {
+        return Err(AlbumError::AlbumNotFound);
+    }
+
+    let mut seen = HashSet::new();
+    let media_ids: Vec<MediaId> = command
+        .media_ids
+        .into_iter()
+        .filter(|media_id| seen.insert(*media_id))
+        .collect();
+
+    // Owner and contributors can only add their own ready media files
+    for media_id in &media_ids {
+        let media_file = media_repository
+            .get_media_file_by_id(*media_id)
+            .await
+            .map_err(|_| AlbumError::InternalServerError("Database error".to_string()))?
+            .ok_or(AlbumError::MediaFileNotFound)?;
+
+        if media_file.user_id != command.user_id || media_file.status != MediaStatus::Ready {
+            return Err(AlbumError::MediaFileNotFound);
+        }
+    }
+
+    album_repository
+        .add_album_media(album.id, media_ids)
+        .await?;
+
+    Ok(())
+}
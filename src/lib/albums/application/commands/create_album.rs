@@ -0,0 +1,50 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::albums::domain::{Album, AlbumError, AlbumRepository, NewAlbum};
+
+#[derive(Debug)]
+pub struct CreateAlbumCommand {
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct AlbumResult {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub cover_media_id: Option<Uuid>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn create_album_command_handler<AR: AlbumRepository + ?Sized>(
+    command: CreateAlbumCommand,
+    album_repository: &AR,
+) -> Result<AlbumResult, AlbumError> {
+    let album = album_repository
+        .create_album(NewAlbum {
+            user_id: command.user_id,
+            name: command.name,
+            description: command.description,
+        })
+        .await?;
+
+    Ok(album.into())
+}
+
+impl From<Album> for AlbumResult {
+    fn from(album: Album) -> Self {
+        AlbumResult {
+            id: album.id,
+            name: album.name,
+            description: album.description,
+            cover_media_id: album.cover_media_id,
+            created_at: album.created_at,
+            updated_at: album.updated_at,
+        }
+    }
+}
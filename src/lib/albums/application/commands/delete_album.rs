@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::albums::domain::{AlbumError, AlbumId, AlbumRepository};
+
+#[derive(Debug)]
+pub struct DeleteAlbumCommand {
+    pub album_id: AlbumId,
+    pub user_id: Uuid,
+}
+
+/// Deletes the album only, its media files are kept
+pub async fn delete_album_command_handler<AR: AlbumRepository + ?Sized>(
+    command: DeleteAlbumCommand,
+    album_repository: &AR,
+) -> Result<(), AlbumError> {
+    let album = album_repository
+        .get_album_by_id(command.album_id)
+        .await?
+        .ok_or(AlbumError::AlbumNotFound)?;
+
+    // Albums of other users are reported as missing so their IDs are not disclosed
+    if album.user_id != command.user_id {
+        return Err(AlbumError::AlbumNotFound);
+    }
+
+    album_repository.delete_album(album.id).await?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+pub mod add_album_media;
+pub mod create_album;
+pub mod delete_album;
+pub mod remove_album_media;
+pub mod reorder_album_media;
+pub mod set_album_cover;
+
+pub use add_album_media::*;
+pub use create_album::*;
+pub use delete_album::*;
+pub use remove_album_media::*;
+pub use reorder_album_media::*;
+pub use set_album_cover::*;
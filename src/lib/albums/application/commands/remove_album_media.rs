@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::{AlbumError, AlbumId, AlbumRepository},
+    media::domain::MediaId,
+};
+
+#[derive(Debug)]
+pub struct RemoveAlbumMediaCommand {
+    pub album_id: AlbumId,
+    pub user_id: Uuid,
+    pub media_id: MediaId,
+}
+
+/// Removes a media file from the album, the media file itself is kept
+pub async fn remove_album_media_command_handler<AR: AlbumRepository + ?Sized>(
+    command: RemoveAlbumMediaCommand,
+    album_repository: &AR,
+) -> Result<(), AlbumError> {
+    let album = album_repository
+        .get_album_by_id(command.album_id)
+        .await?
+        .ok_or(AlbumError::AlbumNotFound)?;
+
+    if album.user_id != command.user_id {
+        return Err(AlbumError::AlbumNotFound);
+    }
+
+    album_repository
+        .remove_album_media(album.id, command.media_id)
+        .await?;
+
+    Ok(())
+}
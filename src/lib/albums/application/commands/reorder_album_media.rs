@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::{AlbumError, AlbumId, AlbumRepository},
+    media::domain::MediaId,
+};
+
+#[derive(Debug)]
+pub struct ReorderAlbumMediaCommand {
+    pub album_id: AlbumId,
+    pub user_id: Uuid,
+    /// Every media file of the album, in the new order
+    pub media_ids: Vec<MediaId>,
+}
+
+pub async fn reorder_album_media_command_handler<AR: AlbumRepository + ?Sized>(
+    command: ReorderAlbumMediaCommand,
+    album_repository: &AR,
+) -> Result<(), AlbumError> {
+    let album = album_repository
+        .get_album_by_id(command.album_id)
+        .await?
+        .ok_or(AlbumError::AlbumNotFound)?;
+
+    if album.user_id != command.user_id {
+        return Err(AlbumError::AlbumNotFound);
+    }
+
+    let current: HashSet<MediaId> = album_repository
+        .get_album_media(album.id)
+        .await?
+        .into_iter()
+        .map(|album_media| album_media.media_file.id)
+        .collect();
+    let requested: HashSet<MediaId> = command.media_ids.iter().copied().collect();
+
+    // A partial order would leave the position of the missing media files undefined
+    if requested.len() != command.media_ids.len() || requested != current {
+        return Err(AlbumError::InvalidMediaOrder);
+    }
+
+    album_repository
+        .reorder_album_media(album.id, command.media_ids)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::{
+    albums::{
+        application::commands::create_album::AlbumResult,
+        domain::{AlbumError, AlbumId, AlbumRepository},
+    },
+    media::domain::MediaId,
+};
+
+#[derive(Debug)]
+pub struct SetAlbumCoverCommand {
+    pub album_id: AlbumId,
+    pub user_id: Uuid,
+    /// `None` removes the cover
+    pub media_id: Option<MediaId>,
+}
+
+pub async fn set_album_cover_command_handler<AR: AlbumRepository + ?Sized>(
+    command: SetAlbumCoverCommand,
+    album_repository: &AR,
+) -> Result<AlbumResult, AlbumError> {
+    let album = album_repository
+        .get_album_by_id(command.album_id)
+        .await?
+        .ok_or(AlbumError::AlbumNotFound)?;
+
+    if album.user_id != command.user_id {
+        return Err(AlbumError::AlbumNotFound);
+    }
+
+    // The cover has to be one of the album media files
+    if let Some(media_id) = command.media_id {
+        let in_album = album_repository
+            .get_album_media(album.id)
+            .await?
+            .iter()
+            .any(|album_media| album_media.media_file.id == media_id);
+        if !in_album {
+            return Err(AlbumError::MediaFileNotFound);
+        }
+    }
+
+    let album = album_repository
+        .set_album_cover(album.id, command.media_id)
+        .await?;
+
+    Ok(album.into())
+}
@@ -0,0 +1,86 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::{AlbumError, AlbumId, AlbumMedia, AlbumRepository},
+    sharing::domain::{AuthorizationService, Permission},
+};
+
+#[derive(Debug)]
+pub struct GetAlbumQuery {
+    pub album_id: AlbumId,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct GetAlbumResult {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub cover_media_id: Option<Uuid>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+    /// Media files of the album in display order
+    pub media: Vec<AlbumMediaResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct AlbumMediaResult {
+    pub media_id: Uuid,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub uploaded_at: Option<chrono::NaiveDateTime>,
+    pub added_at: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn get_album_query_handler<
+    AR: AlbumRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    query: GetAlbumQuery,
+    album_repository: &AR,
+    authorization_service: &AS,
+) -> Result<GetAlbumResult, AlbumError> {
+    let album = album_repository
+        .get_album_by_id(query.album_id)
+        .await?
+        .ok_or(AlbumError::AlbumNotFound)?;
+
+    if !authorization_service
+        .can_access_album(query.user_id, &album, Permission::View)
+        .await
+        .map_err(|e| AlbumError::InternalServerError(e.to_string()))?
+    {
+        return Err(AlbumError::AlbumNotFound);
+    }
+
+    let media = album_repository.get_album_media(album.id).await?;
+
+    Ok(GetAlbumResult {
+        id: album.id,
+        name: album.name,
+        description: album.description,
+        cover_media_id: album.cover_media_id,
+        created_at: album.created_at,
+        updated_at: album.updated_at,
+        media: media
+            .into_iter()
+            .map(|album_media| album_media.into())
+            .collect(),
+    })
+}
+
+impl From<AlbumMedia> for AlbumMediaResult {
+    fn from(album_media: AlbumMedia) -> Self {
+        AlbumMediaResult {
+            media_id: album_media.media_file.id,
+            original_filename: album_media.media_file.original_filename,
+            file_size: album_media.media_file.file_size,
+            content_type: album_media.media_file.content_type,
+            uploaded_at: album_media.media_file.uploaded_at,
+            added_at: album_media.added_at,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+
+use crate::albums::{
+    application::commands::create_album::AlbumResult,
+    domain::{AlbumError, AlbumRepository},
+};
+
+#[derive(Debug)]
+pub struct GetAlbumsQuery {
+    pub user_id: Uuid,
+}
+
+pub async fn get_albums_query_handler<AR: AlbumRepository + ?Sized>(
+    query: GetAlbumsQuery,
+    album_repository: &AR,
+) -> Result<Vec<AlbumResult>, AlbumError> {
+    let albums = album_repository
+        .get_albums_by_user_id(query.user_id)
+        .await?;
+
+    Ok(albums.into_iter().map(|album| album.into()).collect())
+}
@@ -0,0 +1,5 @@
+pub mod get_album;
+pub mod get_albums;
+
+pub use get_album::*;
+pub use get_albums::*;
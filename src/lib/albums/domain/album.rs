@@ -0,0 +1,44 @@
+use uuid::Uuid;
+
+use crate::media::domain::{MediaFile, MediaId};
+
+pub type AlbumId = Uuid;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Album {
+    pub id: AlbumId,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Media file shown as the album thumbnail, always one of the album media files
+    pub cover_media_id: Option<MediaId>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewAlbum {
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Media file of an album, albums are listed by ascending `position`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AlbumMedia {
+    pub media_file: MediaFile,
+    pub position: i32,
+    pub added_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AlbumError {
+    #[error("Album not found")]
+    AlbumNotFound,
+    #[error("Media file not found")]
+    MediaFileNotFound,
+    #[error("The new order must contain every media file of the album exactly once")]
+    InvalidMediaOrder,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::media::domain::MediaId;
+
+use super::album::{Album, AlbumError, AlbumId, AlbumMedia, NewAlbum};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AlbumRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+    #[error("Album not found")]
+    AlbumNotFound,
+    #[error("Media file not found")]
+    MediaFileNotFound,
+}
+
+impl From<AlbumRepositoryError> for AlbumError {
+    fn from(error: AlbumRepositoryError) -> Self {
+        match error {
+            AlbumRepositoryError::AlbumNotFound => AlbumError::AlbumNotFound,
+            AlbumRepositoryError::MediaFileNotFound => AlbumError::MediaFileNotFound,
+            AlbumRepositoryError::InternalServerError => {
+                AlbumError::InternalServerError("Database error".to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait AlbumRepository: Send + Sync {
+    async fn create_album(&self, album: NewAlbum) -> Result<Album, AlbumRepositoryError>;
+    async fn get_album_by_id(&self, id: AlbumId) -> Result<Option<Album>, AlbumRepositoryError>;
+    async fn get_albums_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<Album>, AlbumRepositoryError>;
+    async fn delete_album(&self, id: AlbumId) -> Result<(), AlbumRepositoryError>;
+    async fn set_album_cover(
+        &self,
+        id: AlbumId,
+        cover_media_id: Option<MediaId>,
+    ) -> Result<Album, AlbumRepositoryError>;
+    /// Returns the media files of the album ordered by position
+    async fn get_album_media(&self, id: AlbumId) -> Result<Vec<AlbumMedia>, AlbumRepositoryError>;
+    /// Appends the media files after the last one of the album, keeping the given order.
+    /// Media files already in the album keep their position.
+    async fn add_album_media(
+        &self,
+        id: AlbumId,
+        media_ids: Vec<MediaId>,
+    ) -> Result<(), AlbumRepositoryError>;
+    /// Removes a media file from the album, and clears the cover if it was that media file
+    async fn remove_album_media(
+        &self,
+        id: AlbumId,
+        media_id: MediaId,
+    ) -> Result<(), AlbumRepositoryError>;
+    /// Sets the position of every media file of the album to its index in `media_ids`
+    async fn reorder_album_media(
+        &self,
+        id: AlbumId,
+        media_ids: Vec<MediaId>,
+    ) -> Result<(), AlbumRepositoryError>;
+}
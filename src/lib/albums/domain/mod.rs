@@ -0,0 +1,5 @@
+pub mod album;
+pub mod album_repository;
+
+pub use album::*;
+pub use album_repository::*;
@@ -0,0 +1,266 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use uuid::Uuid;
+
+use super::models::{AlbumMediaModel, AlbumModel, NewAlbumMediaModel, NewAlbumModel};
+use crate::albums::domain::{
+    Album, AlbumId, AlbumMedia, AlbumRepository, AlbumRepositoryError, NewAlbum,
+};
+use crate::media::{MediaId, infrastructure::models::MediaFileModel};
+
+pub struct DieselAlbumRepository {
+    connection_pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselAlbumRepository {
+    pub fn new(connection_pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+#[async_trait]
+impl AlbumRepository for DieselAlbumRepository {
+    async fn create_album(&self, album: NewAlbum) -> Result<Album, AlbumRepositoryError> {
+        use crate::schema::albums::dsl::*;
+
+        let new_album_model: NewAlbumModel = album.into();
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        let created_album = diesel::insert_into(albums)
+            .values(&new_album_model)
+            .returning(AlbumModel::as_returning())
+            .get_result(&mut conn)
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        Ok(created_album.into())
+    }
+
+    async fn get_album_by_id(
+        &self,
+        album_id: AlbumId,
+    ) -> Result<Option<Album>, AlbumRepositoryError> {
+        use crate::schema::albums::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        let result = albums
+            .filter(id.eq(album_id))
+            .select(AlbumModel::as_select())
+            .first::<AlbumModel>(&mut conn)
+            .optional()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        Ok(result.map(|model| model.into()))
+    }
+
+    async fn get_albums_by_user_id(
+        &self,
+        user_uuid: Uuid,
+    ) -> Result<Vec<Album>, AlbumRepositoryError> {
+        use crate::schema::albums::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        let results = albums
+            .filter(user_id.eq(user_uuid))
+            .order((created_at.desc(), id.desc()))
+            .select(AlbumModel::as_select())
+            .load::<AlbumModel>(&mut conn)
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn delete_album(&self, album_id: AlbumId) -> Result<(), AlbumRepositoryError> {
+        use crate::schema::albums::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        // The album_media rows are removed by the ON DELETE CASCADE
+        let deleted_rows = diesel::delete(albums.filter(id.eq(album_id)))
+            .execute(&mut conn)
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        if deleted_rows == 0 {
+            Err(AlbumRepositoryError::AlbumNotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn set_album_cover(
+        &self,
+        album_id: AlbumId,
+        cover: Option<MediaId>,
+    ) -> Result<Album, AlbumRepositoryError> {
+        use crate::schema::albums::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        let updated_album = diesel::update(albums.filter(id.eq(album_id)))
+            .set(cover_media_id.eq(cover))
+            .returning(AlbumModel::as_returning())
+            .get_result(&mut conn)
+            .optional()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?
+            .ok_or(AlbumRepositoryError::AlbumNotFound)?;
+
+        Ok(updated_album.into())
+    }
+
+    async fn get_album_media(
+        &self,
+        album: AlbumId,
+    ) -> Result<Vec<AlbumMedia>, AlbumRepositoryError> {
+        use crate::schema::{album_media, media_files};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        let results = album_media::table
+            .inner_join(media_files::table)
+            .filter(album_media::album_id.eq(album))
+            .order((album_media::position.asc(), album_media::added_at.asc()))
+            .select((AlbumMediaModel::as_select(), MediaFileModel::as_select()))
+            .load::<(AlbumMediaModel, MediaFileModel)>(&mut conn)
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|row| row.into()).collect())
+    }
+
+    async fn add_album_media(
+        &self,
+        album: AlbumId,
+        media_ids: Vec<MediaId>,
+    ) -> Result<(), AlbumRepositoryError> {
+        use crate::schema::album_media::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        conn.transaction(|conn| {
+            let last_position: Option<i32> = album_media
+                .filter(album_id.eq(album))
+                .select(diesel::dsl::max(position))
+                .first(conn)?;
+            let first_position = last_position.map_or(0, |last| last + 1);
+
+            let new_rows: Vec<NewAlbumMediaModel> = media_ids
+                .iter()
+                .zip(first_position..)
+                .map(|(new_media_id, new_position)| NewAlbumMediaModel {
+                    album_id: album,
+                    media_id: *new_media_id,
+                    position: new_position,
+                })
+                .collect();
+
+            diesel::insert_into(album_media)
+                .values(&new_rows)
+                .on_conflict((album_id, media_id))
+                .do_nothing()
+                .execute(conn)
+        })
+        .map_err(|e| match e {
+            DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info)
+                if info.constraint_name() == Some("album_media_album_id_fkey") =>
+            {
+                AlbumRepositoryError::AlbumNotFound
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                AlbumRepositoryError::MediaFileNotFound
+            }
+            _ => AlbumRepositoryError::InternalServerError,
+        })?;
+
+        Ok(())
+    }
+
+    async fn remove_album_media(
+        &self,
+        album: AlbumId,
+        removed_media_id: MediaId,
+    ) -> Result<(), AlbumRepositoryError> {
+        use crate::schema::{album_media, albums};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        conn.transaction(|conn| {
+            let deleted_rows = diesel::delete(
+                album_media::table
+                    .filter(album_media::album_id.eq(album))
+                    .filter(album_media::media_id.eq(removed_media_id)),
+            )
+            .execute(conn)?;
+
+            if deleted_rows == 0 {
+                return Err(DieselError::NotFound);
+            }
+
+            diesel::update(
+                albums::table
+                    .filter(albums::id.eq(album))
+                    .filter(albums::cover_media_id.eq(removed_media_id)),
+            )
+            .set(albums::cover_media_id.eq(None::<Uuid>))
+            .execute(conn)?;
+
+            Ok(())
+        })
+        .map_err(|e| match e {
+            DieselError::NotFound => AlbumRepositoryError::MediaFileNotFound,
+            _ => AlbumRepositoryError::InternalServerError,
+        })
+    }
+
+    async fn reorder_album_media(
+        &self,
+        album: AlbumId,
+        media_ids: Vec<MediaId>,
+    ) -> Result<(), AlbumRepositoryError> {
+        use crate::schema::album_media::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| AlbumRepositoryError::InternalServerError)?;
+
+        conn.transaction(|conn| {
+            for (ordered_media_id, new_position) in media_ids.iter().zip(0..) {
+                diesel::update(
+                    album_media
+                        .filter(album_id.eq(album))
+                        .filter(media_id.eq(ordered_media_id)),
+                )
+                .set(position.eq(new_position))
+                .execute(conn)?;
+            }
+            Ok(())
+        })
+        .map_err(|_: DieselError| AlbumRepositoryError::InternalServerError)
+    }
+}
@@ -0,0 +1,39 @@
+use super::models::{AlbumMediaModel, AlbumModel, NewAlbumModel};
+use crate::{
+    albums::domain::{Album, AlbumMedia, NewAlbum},
+    media::infrastructure::models::MediaFileModel,
+};
+
+impl From<AlbumModel> for Album {
+    fn from(model: AlbumModel) -> Self {
+        Album {
+            id: model.id,
+            user_id: model.user_id,
+            name: model.name,
+            description: model.description,
+            cover_media_id: model.cover_media_id,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<NewAlbum> for NewAlbumModel {
+    fn from(new_album: NewAlbum) -> Self {
+        NewAlbumModel {
+            user_id: new_album.user_id,
+            name: new_album.name,
+            description: new_album.description,
+        }
+    }
+}
+
+impl From<(AlbumMediaModel, MediaFileModel)> for AlbumMedia {
+    fn from((album_media, media_file): (AlbumMediaModel, MediaFileModel)) -> Self {
+        AlbumMedia {
+            media_file: media_file.into(),
+            position: album_media.position,
+            added_at: album_media.added_at,
+        }
+    }
+}
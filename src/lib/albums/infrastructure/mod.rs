@@ -0,0 +1,5 @@
+pub mod diesel_album_repository;
+pub mod mappers;
+pub mod models;
+
+pub use diesel_album_repository::*;
@@ -0,0 +1,41 @@
+use diesel::prelude::*;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable, Identifiable, Debug)]
+#[diesel(table_name = crate::schema::albums)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AlbumModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub cover_media_id: Option<Uuid>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::albums)]
+pub struct NewAlbumModel {
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::album_media)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AlbumMediaModel {
+    pub album_id: Uuid,
+    pub media_id: Uuid,
+    pub position: i32,
+    pub added_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::album_media)]
+pub struct NewAlbumMediaModel {
+    pub album_id: Uuid,
+    pub media_id: Uuid,
+    pub position: i32,
+}
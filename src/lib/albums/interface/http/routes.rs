@@ -0,0 +1,393 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, put},
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    albums::{
+        application::{
+            commands::{
+                add_album_media::{AddAlbumMediaCommand, add_album_media_command_handler},
+                create_album::{AlbumResult, CreateAlbumCommand, create_album_command_handler},
+                delete_album::{DeleteAlbumCommand, delete_album_command_handler},
+                remove_album_media::{RemoveAlbumMediaCommand, remove_album_media_command_handler},
+                reorder_album_media::{
+                    ReorderAlbumMediaCommand, reorder_album_media_command_handler,
+                },
+                set_album_cover::{SetAlbumCoverCommand, set_album_cover_command_handler},
+            },
+            queries::{
+                get_album::{GetAlbumQuery, GetAlbumResult, get_album_query_handler},
+                get_albums::{GetAlbumsQuery, get_albums_query_handler},
+            },
+        },
+        domain::{AlbumError, AlbumId},
+    },
+    api::{
+        domain::{
+            errors::{ApiError, ApiErrorBody},
+            response_body::ApiResponseBody,
+        },
+        http_server::AppState,
+    },
+    media::domain::MediaId,
+    protected,
+    shared::interface::http::ValidatedJson,
+    users::domain::Claims,
+};
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct CreateAlbumRequestBody {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Name must be between 1 and 255 characters"
+    ))]
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct AlbumMediaRequestBody {
+    #[validate(length(
+        min = 1,
+        max = 1000,
+        message = "Between 1 and 1000 media IDs must be sent"
+    ))]
+    media_ids: Vec<Uuid>,
+}
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct SetAlbumCoverRequestBody {
+    /// Media file of the album to use as cover, `null` to remove the cover
+    media_id: Option<Uuid>,
+}
+
+fn parse_album_id(album_id: &str) -> Result<AlbumId, ApiError> {
+    Uuid::parse_str(album_id)
+        .map_err(|_| ApiError::BadRequestError("Invalid album ID format".to_string()))
+}
+
+fn parse_media_id(media_id: &str) -> Result<MediaId, ApiError> {
+    Uuid::parse_str(media_id)
+        .map_err(|_| ApiError::BadRequestError("Invalid media ID format".to_string()))
+}
+
+fn album_error_to_api_error(error: AlbumError) -> ApiError {
+    match error {
+        AlbumError::AlbumNotFound => ApiError::NotFoundError(error.to_string()),
+        AlbumError::MediaFileNotFound => ApiError::NotFoundError(error.to_string()),
+        AlbumError::InvalidMediaOrder => ApiError::BadRequestError(error.to_string()),
+        AlbumError::InternalServerError(msg) => {
+            tracing::error!("Internal server error, {}", msg);
+            ApiError::InternalServerError("Internal server error".to_string())
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "",
+    description = "Create an album",
+    tag = "albums",
+    request_body = CreateAlbumRequestBody,
+    responses(
+        (status = 201, description = "Album created", body = ApiResponseBody<AlbumResult>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn create_album(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedJson(body): ValidatedJson<CreateAlbumRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<AlbumResult>>), ApiError> {
+    let command = CreateAlbumCommand {
+        user_id: claims.sub,
+        name: body.name,
+        description: body.description,
+    };
+
+    create_album_command_handler(command, state.album_repository.as_ref())
+        .await
+        .map(|album| (StatusCode::CREATED, ApiResponseBody::new(album).into()))
+        .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    get,
+    path = "",
+    description = "Get the user's albums",
+    tag = "albums",
+    responses(
+        (status = 200, description = "Albums retrieved successfully", body = ApiResponseBody<Vec<AlbumResult>>),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_albums(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<AlbumResult>>>), ApiError> {
+    let query = GetAlbumsQuery {
+        user_id: claims.sub,
+    };
+
+    get_albums_query_handler(query, state.album_repository.as_ref())
+        .await
+        .map(|albums| (StatusCode::OK, ApiResponseBody::new(albums).into()))
+        .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{album_id}",
+    description = "Get an album, owned by or shared with the user, with its media files in display order",
+    tag = "albums",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    responses(
+        (status = 200, description = "Album retrieved successfully", body = ApiResponseBody<GetAlbumResult>),
+        (status = 400, description = "Invalid album ID format", body = ApiErrorBody),
+        (status = 404, description = "Album not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_album(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<GetAlbumResult>>), ApiError> {
+    let query = GetAlbumQuery {
+        album_id: parse_album_id(&album_id)?,
+        user_id: claims.sub,
+    };
+
+    get_album_query_handler(
+        query,
+        state.album_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    .map(|album| (StatusCode::OK, ApiResponseBody::new(album).into()))
+    .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{album_id}",
+    description = "Delete an album, its media files are kept",
+    tag = "albums",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    responses(
+        (status = 204, description = "Album deleted"),
+        (status = 400, description = "Invalid album ID format", body = ApiErrorBody),
+        (status = 404, description = "Album not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn delete_album(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let command = DeleteAlbumCommand {
+        album_id: parse_album_id(&album_id)?,
+        user_id: claims.sub,
+    };
+
+    delete_album_command_handler(command, state.album_repository.as_ref())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{album_id}/media",
+    description = "Add media files at the end of an album, media files already in the album keep their position",
+    tag = "albums",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    request_body = AlbumMediaRequestBody,
+    responses(
+        (status = 204, description = "Media files added"),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 404, description = "Album or media file not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn add_album_media(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+    ValidatedJson(body): ValidatedJson<AlbumMediaRequestBody>,
+) -> Result<StatusCode, ApiError> {
+    let command = AddAlbumMediaCommand {
+        album_id: parse_album_id(&album_id)?,
+        user_id: claims.sub,
+        media_ids: body.media_ids,
+    };
+
+    add_album_media_command_handler(
+        command,
+        state.album_repository.as_ref(),
+        state.media_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    .map(|_| StatusCode::NO_CONTENT)
+    .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    put,
+    path = "/{album_id}/media",
+    description = "Reorder the media files of an album. Every media file of the album must be sent exactly once, in the new order",
+    tag = "albums",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    request_body = AlbumMediaRequestBody,
+    responses(
+        (status = 204, description = "Media files reordered"),
+        (status = 400, description = "Invalid request or incomplete order", body = ApiErrorBody),
+        (status = 404, description = "Album not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn reorder_album_media(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+    ValidatedJson(body): ValidatedJson<AlbumMediaRequestBody>,
+) -> Result<StatusCode, ApiError> {
+    let command = ReorderAlbumMediaCommand {
+        album_id: parse_album_id(&album_id)?,
+        user_id: claims.sub,
+        media_ids: body.media_ids,
+    };
+
+    reorder_album_media_command_handler(command, state.album_repository.as_ref())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{album_id}/media/{media_id}",
+    description = "Remove a media file from an album, the media file itself is kept",
+    tag = "albums",
+    params(
+        ("album_id" = String, Path, description = "ID of the album"),
+        ("media_id" = String, Path, description = "ID of the media file")
+    ),
+    responses(
+        (status = 204, description = "Media file removed from the album"),
+        (status = 400, description = "Invalid album or media ID format", body = ApiErrorBody),
+        (status = 404, description = "Album not found or media file not in the album", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn remove_album_media(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((album_id, media_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    let command = RemoveAlbumMediaCommand {
+        album_id: parse_album_id(&album_id)?,
+        user_id: claims.sub,
+        media_id: parse_media_id(&media_id)?,
+    };
+
+    remove_album_media_command_handler(command, state.album_repository.as_ref())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(album_error_to_api_error)
+}
+
+#[utoipa::path(
+    put,
+    path = "/{album_id}/cover",
+    description = "Set or remove the cover image of an album",
+    tag = "albums",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    request_body = SetAlbumCoverRequestBody,
+    responses(
+        (status = 200, description = "Album cover updated", body = ApiResponseBody<AlbumResult>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 404, description = "Album not found or media file not in the album", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn set_album_cover(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+    ValidatedJson(body): ValidatedJson<SetAlbumCoverRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<AlbumResult>>), ApiError> {
+    let command = SetAlbumCoverCommand {
+        album_id: parse_album_id(&album_id)?,
+        user_id: claims.sub,
+        media_id: body.media_id,
+    };
+
+    set_album_cover_command_handler(command, state.album_repository.as_ref())
+        .await
+        .map(|album| (StatusCode::OK, ApiResponseBody::new(album).into()))
+        .map_err(album_error_to_api_error)
+}
+
+pub fn api_routes(state: AppState) -> axum::Router<AppState> {
+    axum::Router::new()
+        .route("/", get(get_albums).post(create_album))
+        .route("/{album_id}", get(get_album).delete(delete_album))
+        .route(
+            "/{album_id}/media",
+            put(reorder_album_media).post(add_album_media),
+        )
+        .route("/{album_id}/media/{media_id}", delete(remove_album_media))
+        .route("/{album_id}/cover", put(set_album_cover))
+        .route_layer(protected!(state.clone()))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_album,
+        get_albums,
+        get_album,
+        delete_album,
+        add_album_media,
+        reorder_album_media,
+        remove_album_media,
+        set_album_cover
+    ),
+    tags(
+        (name = "albums", description = "Album management API")
+    )
+)]
+pub struct ApiDoc;
+
+pub fn combine_openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
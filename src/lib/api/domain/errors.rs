@@ -21,6 +21,7 @@ pub enum ApiError {
     UnauthorizedError(String),
     ConflictError(String),
     ForbiddenError(String),
+    PayloadTooLargeError(String),
 }
 
 impl IntoResponse for ApiError {
@@ -58,6 +59,11 @@ impl IntoResponse for ApiError {
                 Json(ApiErrorBody::new(message)),
             )
                 .into_response(),
+            PayloadTooLargeError(message) => (
+                axum::http::StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ApiErrorBody::new(message)),
+            )
+                .into_response(),
         }
     }
 }
@@ -11,7 +11,20 @@ use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberI
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
-    api::routes::{api_routes, combine_openapi}, media::domain::{FileStorageService, MediaRepository, ThumbnailService}, shared::interface::http::mw_concurrency_semaphore, users::domain::{LoginTokenService, UserRepository}
+    albums::domain::AlbumRepository,
+    api::routes::{api_routes, combine_openapi, well_known_routes},
+    jobs::domain::JobRepository,
+    media::domain::{
+        FileStorageService, MediaRepository, MediaSizeLimits, MediaTypeAllowlist, MediaUrlSigner,
+        UploadSessionRepository,
+    },
+    shared::interface::http::mw_concurrency_semaphore,
+    sharing::domain::{AuthorizationService, ShareGrantRepository, ShareLinkRepository},
+    users::domain::{
+        AuthTokenConfig, LoginTokenService, Mailer, PasswordResetConfig,
+        PasswordResetTokenRepository, QuotaConfig, RefreshTokenRepository, SessionCache,
+        SessionRepository, UserRepository,
+    },
 };
 
 // State that every handlers share (used for services)
@@ -19,9 +32,25 @@ use crate::{
 pub struct AppState {
     pub user_repository: Arc<dyn UserRepository>,
     pub login_token_service: Arc<dyn LoginTokenService>,
+    pub refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    pub session_repository: Arc<dyn SessionRepository>,
+    pub password_reset_token_repository: Arc<dyn PasswordResetTokenRepository>,
+    pub mailer: Arc<dyn Mailer>,
     pub media_repository: Arc<dyn MediaRepository>,
     pub storage_service: Arc<dyn FileStorageService>,
-    pub thumbnail_service: Arc<dyn ThumbnailService>,
+    pub media_url_signer: Arc<dyn MediaUrlSigner>,
+    pub upload_session_repository: Arc<dyn UploadSessionRepository>,
+    pub album_repository: Arc<dyn AlbumRepository>,
+    pub share_grant_repository: Arc<dyn ShareGrantRepository>,
+    pub share_link_repository: Arc<dyn ShareLinkRepository>,
+    pub authorization_service: Arc<dyn AuthorizationService>,
+    pub job_repository: Arc<dyn JobRepository>,
+    pub media_type_allowlist: Arc<MediaTypeAllowlist>,
+    pub media_size_limits: Arc<MediaSizeLimits>,
+    pub quota_config: Arc<QuotaConfig>,
+    pub auth_token_config: Arc<AuthTokenConfig>,
+    pub session_cache: Arc<SessionCache>,
+    pub password_reset_config: Arc<PasswordResetConfig>,
     pub max_concurrent_requests_semaphore: Arc<tokio::sync::Semaphore>,
 }
 
@@ -31,12 +60,23 @@ pub struct HttpServer {
 }
 
 impl HttpServer {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         user_repository: impl UserRepository + 'static,
         login_token_service: impl LoginTokenService + 'static,
+        refresh_token_repository: impl RefreshTokenRepository + 'static,
+        session_repository: impl SessionRepository + 'static,
+        password_reset_token_repository: impl PasswordResetTokenRepository + 'static,
+        mailer: impl Mailer + 'static,
         media_repository: impl MediaRepository + 'static,
         storage_service: impl FileStorageService + 'static,
-        thumbnail_service: impl ThumbnailService + 'static,
+        media_url_signer: impl MediaUrlSigner + 'static,
+        upload_session_repository: impl UploadSessionRepository + 'static,
+        album_repository: impl AlbumRepository + 'static,
+        share_grant_repository: impl ShareGrantRepository + 'static,
+        share_link_repository: impl ShareLinkRepository + 'static,
+        authorization_service: impl AuthorizationService + 'static,
+        job_repository: impl JobRepository + 'static,
     ) -> anyhow::Result<Self> {
         dotenvy::dotenv().context("Failed to load .env file")?;
 
@@ -48,10 +88,28 @@ impl HttpServer {
         let state = AppState {
             user_repository: Arc::new(user_repository),
             login_token_service: Arc::new(login_token_service),
+            refresh_token_repository: Arc::new(refresh_token_repository),
+            session_repository: Arc::new(session_repository),
+            password_reset_token_repository: Arc::new(password_reset_token_repository),
+            mailer: Arc::new(mailer),
             media_repository: Arc::new(media_repository),
             storage_service: Arc::new(storage_service),
-            thumbnail_service: Arc::new(thumbnail_service),
-            max_concurrent_requests_semaphore: Arc::new(tokio::sync::Semaphore::new(max_concurrent_requests)),
+            media_url_signer: Arc::new(media_url_signer),
+            upload_session_repository: Arc::new(upload_session_repository),
+            album_repository: Arc::new(album_repository),
+            share_grant_repository: Arc::new(share_grant_repository),
+            share_link_repository: Arc::new(share_link_repository),
+            authorization_service: Arc::new(authorization_service),
+            job_repository: Arc::new(job_repository),
+            media_type_allowlist: Arc::new(MediaTypeAllowlist::new()),
+            media_size_limits: Arc::new(MediaSizeLimits::new()),
+            quota_config: Arc::new(QuotaConfig::new()),
+            auth_token_config: Arc::new(AuthTokenConfig::new()),
+            session_cache: Arc::new(SessionCache::new()),
+            password_reset_config: Arc::new(PasswordResetConfig::new()),
+            max_concurrent_requests_semaphore: Arc::new(tokio::sync::Semaphore::new(
+                max_concurrent_requests,
+            )),
         };
 
         // Initialize tracing for the application
@@ -77,6 +135,7 @@ impl HttpServer {
         let router = axum::Router::new()
             .layer(cors)
             .nest("/api", api_routes(state.clone()))
+            .nest("/.well-known", well_known_routes())
             .layer(from_fn_with_state(state.clone(), mw_concurrency_semaphore))
             .with_state(state)
             .merge(SwaggerUi::new("/doc").url("/api-docs/openapi.json", combine_openapi(&port)))
@@ -96,7 +155,11 @@ impl HttpServer {
                 .local_addr()
                 .expect("Failed to get local address")
         );
-        axum::serve(self.listener, self.router.into_make_service())
+        // The peer address is recorded for the sessions logged in from it
+        let service = self
+            .router
+            .into_make_service_with_connect_info::<std::net::SocketAddr>();
+        axum::serve(self.listener, service)
             .await
             .context("received error when running server")?;
         Ok(())
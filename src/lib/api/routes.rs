@@ -2,11 +2,15 @@ use axum::routing::{get, post};
 use utoipa::{OpenApi, openapi::ServerBuilder};
 
 use crate::{
+    albums,
     api::{http_server::AppState, routes::health::health_check},
-    media,
+    jobs, media, sharing,
     users::{
         self,
-        interface::http::routes::{LoginApiDoc, login_user},
+        interface::http::routes::{
+            LoginApiDoc, confirm_password_reset, get_jwks, login_user, logout, refresh_token,
+            request_password_reset,
+        },
     },
 };
 
@@ -16,15 +20,38 @@ pub fn api_routes(state: AppState) -> axum::Router<AppState> {
     axum::Router::new()
         .route("/healthz", get(health_check))
         .route("/login", post(login_user))
+        .route("/auth/refresh", post(refresh_token))
+        .route("/auth/logout", post(logout))
+        .route("/auth/password-reset", post(request_password_reset))
+        .route("/auth/password-reset/confirm", post(confirm_password_reset))
         .nest("/user", users::interface::http::api_routes(state.clone()))
         .nest("/media", media::interface::http::api_routes(state.clone()))
+        .nest(
+            "/albums",
+            albums::interface::http::api_routes(state.clone()),
+        )
+        .nest(
+            "/shares",
+            sharing::interface::http::api_routes(state.clone()),
+        )
+        .nest("/public", sharing::interface::http::public_routes())
+        .nest("/jobs", jobs::interface::http::api_routes(state.clone()))
+}
+
+/// Routes served at `/.well-known`, outside of `/api` where other services expect them
+pub fn well_known_routes() -> axum::Router<AppState> {
+    axum::Router::new().route("/jwks.json", get(get_jwks))
 }
 
 pub fn combine_openapi(port: &u16) -> utoipa::openapi::OpenApi {
     let mut doc = health::swagger::ApiDoc::openapi()
         .merge_from(LoginApiDoc::openapi())
         .nest("/users", users::interface::http::ApiDoc::openapi())
-        .nest("/media", media::interface::http::ApiDoc::openapi());
+        .nest("/media", media::interface::http::ApiDoc::openapi())
+        .nest("/albums", albums::interface::http::ApiDoc::openapi())
+        .nest("/shares", sharing::interface::http::ApiDoc::openapi())
+        .nest("/public", sharing::interface::http::PublicApiDoc::openapi())
+        .nest("/jobs", jobs::interface::http::ApiDoc::openapi());
 
     doc.servers = Some(vec![
         ServerBuilder::new()
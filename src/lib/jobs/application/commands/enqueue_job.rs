@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use crate::jobs::domain::{
+    DEFAULT_JOB_MAX_ATTEMPTS, Job, JobPayload, JobQueueError, JobRepository, NewJob,
+};
+
+#[derive(Debug)]
+pub struct EnqueueJobCommand {
+    pub user_id: Option<Uuid>,
+    pub payload: JobPayload,
+}
+
+pub async fn enqueue_job_command_handler<JR: JobRepository + ?Sized>(
+    command: EnqueueJobCommand,
+    job_repository: &JR,
+) -> Result<Job, JobQueueError> {
+    let job = job_repository
+        .enqueue_job(NewJob {
+            user_id: command.user_id,
+            payload: command.payload,
+            max_attempts: DEFAULT_JOB_MAX_ATTEMPTS,
+        })
+        .await?;
+
+    Ok(job)
+}
@@ -0,0 +1,7 @@
+pub mod enqueue_job;
+pub mod process_job;
+pub mod retry_job;
+
+pub use enqueue_job::*;
+pub use process_job::*;
+pub use retry_job::*;
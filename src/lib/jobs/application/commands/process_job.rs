@@ -0,0 +1,79 @@
+use uuid::Uuid;
+
+use crate::jobs::domain::{
+    Job, JobError, JobHandler, JobRepository, JobRepositoryError, JobRetryPolicy, JobStatus,
+};
+
+#[derive(Debug)]
+pub struct ProcessJobCommand {
+    /// Job claimed by the worker, its attempts already count this run
+    pub job: Job,
+    /// Worker that claimed the job
+    pub worker_id: Uuid,
+}
+
+/// Runs a claimed job and records the outcome, failed jobs are retried with backoff until they
+/// run out of attempts and are dead-lettered. Returns the status the job ended up in, or
+/// `LockLost` when another worker took the job over in the meantime.
+pub async fn process_job_command_handler<JR: JobRepository + ?Sized, JH: JobHandler + ?Sized>(
+    command: ProcessJobCommand,
+    job_repository: &JR,
+    job_handler: &JH,
+    retry_policy: &JobRetryPolicy,
+) -> Result<JobStatus, JobRepositoryError> {
+    let ProcessJobCommand { job, worker_id } = command;
+
+    // Taken over from a worker that stopped while running it on its last attempt
+    if job.attempts > job.max_attempts {
+        tracing::error!("Job {} ran out of attempts, dead-lettering it", job.id);
+        job_repository
+            .fail_job(
+                job.id,
+                worker_id,
+                "The worker running the last attempt stopped".to_string(),
+                None,
+            )
+            .await?;
+        return Ok(JobStatus::Dead);
+    }
+
+    let error = match job_handler.handle(&job).await {
+        Ok(()) => {
+            job_repository.complete_job(job.id, worker_id).await?;
+            return Ok(JobStatus::Succeeded);
+        }
+        Err(error) => error,
+    };
+
+    let retry_at = match &error {
+        JobError::Retryable(_) if job.attempts < job.max_attempts => {
+            Some(chrono::Utc::now().naive_utc() + retry_policy.retry_delay(job.attempts))
+        }
+        _ => None,
+    };
+
+    match retry_at {
+        Some(retry_at) => tracing::warn!(
+            "Job {} failed on attempt {}, retrying at {}: {}",
+            job.id,
+            job.attempts,
+            retry_at,
+            error
+        ),
+        None => tracing::error!(
+            "Job {} failed on attempt {}, dead-lettering it: {}",
+            job.id,
+            job.attempts,
+            error
+        ),
+    }
+
+    job_repository
+        .fail_job(job.id, worker_id, error.to_string(), retry_at)
+        .await?;
+
+    Ok(match retry_at {
+        Some(_) => JobStatus::Pending,
+        None => JobStatus::Dead,
+    })
+}
@@ -0,0 +1,28 @@
+use crate::jobs::{
+    application::queries::get_job::JobResult,
+    domain::{JobId, JobQueueError, JobRepository, JobStatus},
+};
+
+#[derive(Debug)]
+pub struct RetryJobCommand {
+    pub job_id: JobId,
+}
+
+/// Puts a dead-lettered job back in the queue with a fresh set of attempts
+pub async fn retry_job_command_handler<JR: JobRepository + ?Sized>(
+    command: RetryJobCommand,
+    job_repository: &JR,
+) -> Result<JobResult, JobQueueError> {
+    let job = job_repository
+        .get_job_by_id(command.job_id)
+        .await?
+        .ok_or(JobQueueError::JobNotFound)?;
+
+    if job.status != JobStatus::Dead {
+        return Err(JobQueueError::JobNotDead);
+    }
+
+    let job = job_repository.requeue_job(job.id).await?;
+
+    Ok(job.into())
+}
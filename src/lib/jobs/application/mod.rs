@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod queries;
+
+pub use commands::*;
+pub use queries::*;
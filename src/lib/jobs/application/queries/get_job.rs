@@ -0,0 +1,62 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::jobs::domain::{Job, JobId, JobQueueError, JobRepository, JobStatus};
+
+#[derive(Debug)]
+pub struct GetJobQuery {
+    pub job_id: JobId,
+    pub user_id: Uuid,
+    /// Admins may see the jobs of every user
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq)]
+pub struct JobResult {
+    pub id: Uuid,
+    pub job_type: String,
+    /// Input of the job, e.g. `{"type": "generate_thumbnail", "media_id": "..."}`
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// Next time the job runs while it is pending
+    pub run_at: chrono::NaiveDateTime,
+    pub last_error: Option<String>,
+    pub completed_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn get_job_query_handler<JR: JobRepository + ?Sized>(
+    query: GetJobQuery,
+    job_repository: &JR,
+) -> Result<JobResult, JobQueueError> {
+    let job = job_repository
+        .get_job_by_id(query.job_id)
+        .await?
+        .filter(|job| query.is_admin || job.user_id == Some(query.user_id))
+        .ok_or(JobQueueError::JobNotFound)?;
+
+    Ok(job.into())
+}
+
+impl From<Job> for JobResult {
+    fn from(job: Job) -> Self {
+        JobResult {
+            id: job.id,
+            job_type: job.payload.job_type().to_string(),
+            payload: serde_json::to_value(&job.payload).unwrap_or_default(),
+            status: job.status,
+            attempts: job.attempts,
+            max_attempts: job.max_attempts,
+            run_at: job.run_at,
+            last_error: job.last_error,
+            completed_at: job.completed_at,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use uuid::Uuid;
+
+use crate::jobs::{
+    application::queries::get_job::JobResult,
+    domain::{JobQueueError, JobRepository, JobStatus},
+};
+
+pub const JOBS_DEFAULT_PAGE_SIZE: i64 = 50;
+pub const JOBS_MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug)]
+pub struct GetJobsQuery {
+    pub user_id: Uuid,
+    /// Admins see the jobs of every user
+    pub is_admin: bool,
+    pub status: Option<JobStatus>,
+    /// Defaults to [`JOBS_DEFAULT_PAGE_SIZE`], capped at [`JOBS_MAX_PAGE_SIZE`]
+    pub limit: Option<i64>,
+}
+
+pub async fn get_jobs_query_handler<JR: JobRepository + ?Sized>(
+    query: GetJobsQuery,
+    job_repository: &JR,
+) -> Result<Vec<JobResult>, JobQueueError> {
+    let user_id = (!query.is_admin).then_some(query.user_id);
+    let limit = query
+        .limit
+        .unwrap_or(JOBS_DEFAULT_PAGE_SIZE)
+        .clamp(1, JOBS_MAX_PAGE_SIZE);
+
+    let jobs = job_repository
+        .get_jobs(user_id, query.status, limit)
+        .await?;
+
+    Ok(jobs.into_iter().map(|job| job.into()).collect())
+}
@@ -0,0 +1,5 @@
+pub mod get_job;
+pub mod get_jobs;
+
+pub use get_job::*;
+pub use get_jobs::*;
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::media::MediaId;
+
+pub type JobId = Uuid;
+
+/// Attempts a job gets before it is dead-lettered
+pub const DEFAULT_JOB_MAX_ATTEMPTS: i32 = 5;
+
+/// Work to run in the background, stored as JSON so it survives restarts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobPayload {
+    /// Extracts the metadata and generates the renditions of an uploaded media file
+    GenerateThumbnail { media_id: MediaId },
+}
+
+impl JobPayload {
+    pub fn job_type(&self) -> &'static str {
+        match self {
+            JobPayload::GenerateThumbnail { .. } => "generate_thumbnail",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for its first run or for its next retry
+    Pending,
+    Running,
+    Succeeded,
+    /// Failed on every attempt, or with an error retrying would not fix
+    Dead,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    pub id: JobId,
+    /// User the job was enqueued for, `None` for system jobs
+    pub user_id: Option<Uuid>,
+    pub payload: JobPayload,
+    pub status: JobStatus,
+    /// Runs started so far, including the current one
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// The job is not picked up before this time
+    pub run_at: chrono::NaiveDateTime,
+    /// When a worker took the job, a job locked for too long is taken over by another worker
+    pub locked_at: Option<chrono::NaiveDateTime>,
+    /// Worker running the job, only that worker may record its outcome
+    pub locked_by: Option<Uuid>,
+    pub last_error: Option<String>,
+    pub completed_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewJob {
+    pub user_id: Option<Uuid>,
+    pub payload: JobPayload,
+    pub max_attempts: i32,
+}
+
+/// Exponential backoff between the attempts of a failing job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobRetryPolicy {
+    pub base_delay_seconds: u64,
+    pub max_delay_seconds: u64,
+}
+
+impl JobRetryPolicy {
+    /// Delay before the next attempt after `attempts` failed ones, doubling every time
+    pub fn retry_delay(&self, attempts: i32) -> chrono::Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 32) as u32;
+        let delay = self
+            .base_delay_seconds
+            .saturating_mul(2u64.saturating_pow(exponent))
+            .min(self.max_delay_seconds);
+        chrono::Duration::seconds(delay as i64)
+    }
+}
+
+impl Default for JobRetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_seconds: 30,
+            max_delay_seconds: 3600,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobQueueError {
+    #[error("Job not found")]
+    JobNotFound,
+    #[error("Only dead jobs can be retried")]
+    JobNotDead,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
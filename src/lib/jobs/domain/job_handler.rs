@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use super::Job;
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    /// Temporary failure, e.g. the storage being unreachable
+    #[error("{0}")]
+    Retryable(String),
+    /// Running the job again would fail the same way
+    #[error("{0}")]
+    Permanent(String),
+}
+
+/// Runs the jobs taken from the queue
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    async fn handle(&self, job: &Job) -> Result<(), JobError>;
+}
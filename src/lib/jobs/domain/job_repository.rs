@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{Job, JobId, JobQueueError, JobStatus, NewJob};
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+    #[error("Job not found")]
+    JobNotFound,
+    #[error("Job is no longer locked by this worker")]
+    LockLost,
+}
+
+impl From<JobRepositoryError> for JobQueueError {
+    fn from(error: JobRepositoryError) -> Self {
+        match error {
+            JobRepositoryError::JobNotFound => JobQueueError::JobNotFound,
+            JobRepositoryError::InternalServerError => {
+                JobQueueError::InternalServerError("Database error".to_string())
+            }
+            JobRepositoryError::LockLost => {
+                JobQueueError::InternalServerError("Job lock lost".to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    async fn enqueue_job(&self, job: NewJob) -> Result<Job, JobRepositoryError>;
+    async fn get_job_by_id(&self, id: JobId) -> Result<Option<Job>, JobRepositoryError>;
+    /// Returns the newest jobs of the user, or of every user when `user_id` is `None`
+    async fn get_jobs(
+        &self,
+        user_id: Option<Uuid>,
+        status: Option<JobStatus>,
+        limit: i64,
+    ) -> Result<Vec<Job>, JobRepositoryError>;
+    /// Marks up to `limit` due jobs as running by `worker_id` and counts the attempt. Jobs other
+    /// workers hold are skipped, unless they have been running for longer than `lock_timeout`.
+    async fn claim_jobs(
+        &self,
+        worker_id: Uuid,
+        limit: i64,
+        lock_timeout: chrono::Duration,
+    ) -> Result<Vec<Job>, JobRepositoryError>;
+    /// Fails with `LockLost` when the job was taken over by another worker in the meantime
+    async fn complete_job(&self, id: JobId, worker_id: Uuid) -> Result<(), JobRepositoryError>;
+    /// Records a failed attempt, the job runs again at `retry_at` or is dead-lettered when it is
+    /// `None`. Fails with `LockLost` when the job was taken over by another worker in the meantime.
+    async fn fail_job(
+        &self,
+        id: JobId,
+        worker_id: Uuid,
+        error: String,
+        retry_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), JobRepositoryError>;
+    /// Puts a job back in the queue with its attempts reset
+    async fn requeue_job(&self, id: JobId) -> Result<Job, JobRepositoryError>;
+}
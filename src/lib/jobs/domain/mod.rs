@@ -0,0 +1,7 @@
+pub mod job;
+pub mod job_handler;
+pub mod job_repository;
+
+pub use job::*;
+pub use job_handler::*;
+pub use job_repository::*;
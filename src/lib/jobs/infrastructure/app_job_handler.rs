@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    jobs::domain::{Job, JobError, JobHandler, JobPayload},
+    media::{
+        application::commands::generate_thumbnail::{
+            GenerateThumbnailCommand, GenerateThumbnailError, generate_thumbnail_command_handler,
+        },
+        domain::{
+            FileStorageService, MediaMetadataService, MediaRepository, ThumbnailError,
+            ThumbnailService,
+        },
+    },
+};
+
+/// Runs every kind of job with the services of the context it belongs to
+pub struct AppJobHandler {
+    media_repository: Arc<dyn MediaRepository>,
+    storage_service: Arc<dyn FileStorageService>,
+    thumbnail_service: Arc<dyn ThumbnailService>,
+    metadata_service: Arc<dyn MediaMetadataService>,
+}
+
+impl AppJobHandler {
+    pub fn new(
+        media_repository: impl MediaRepository + 'static,
+        storage_service: impl FileStorageService + 'static,
+        thumbnail_service: impl ThumbnailService + 'static,
+        metadata_service: impl MediaMetadataService + 'static,
+    ) -> Self {
+        Self {
+            media_repository: Arc::new(media_repository),
+            storage_service: Arc::new(storage_service),
+            thumbnail_service: Arc::new(thumbnail_service),
+            metadata_service: Arc::new(metadata_service),
+        }
+    }
+}
+
+#[async_trait]
+impl JobHandler for AppJobHandler {
+    async fn handle(&self, job: &Job) -> Result<(), JobError> {
+        match &job.payload {
+            JobPayload::GenerateThumbnail { media_id } => {
+                match generate_thumbnail_command_handler(
+                    GenerateThumbnailCommand {
+                        media_id: *media_id,
+                    },
+                    self.media_repository.as_ref(),
+                    self.storage_service.as_ref(),
+                    self.thumbnail_service.as_ref(),
+                    self.metadata_service.as_ref(),
+                )
+                .await
+                {
+                    Ok(()) => Ok(()),
+                    // Deleted before the job ran, there is nothing left to do
+                    Err(GenerateThumbnailError::MediaFileNotFound) => {
+                        tracing::info!(
+                            "Media {} no longer exists, skipping job {}",
+                            media_id,
+                            job.id
+                        );
+                        Ok(())
+                    }
+                    // Decoding the same file again would fail the same way
+                    Err(GenerateThumbnailError::ThumbnailError(
+                        e @ ThumbnailError::ImageProcessingError(_),
+                    )) => Err(JobError::Permanent(e.to_string())),
+                    Err(e) => Err(JobError::Retryable(e.to_string())),
+                }
+            }
+        }
+    }
+}
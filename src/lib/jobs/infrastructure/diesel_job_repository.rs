@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use uuid::Uuid;
+
+use super::models::{JobModel, NewJobModel, RowJobStatus};
+use crate::jobs::domain::{Job, JobId, JobRepository, JobRepositoryError, JobStatus, NewJob};
+
+pub struct DieselJobRepository {
+    connection_pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselJobRepository {
+    pub fn new(connection_pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+fn to_jobs(models: Vec<JobModel>) -> Result<Vec<Job>, JobRepositoryError> {
+    models.into_iter().map(Job::try_from).collect()
+}
+
+#[async_trait]
+impl JobRepository for DieselJobRepository {
+    async fn enqueue_job(&self, job: NewJob) -> Result<Job, JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let new_job_model = NewJobModel::try_from(job)?;
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        diesel::insert_into(jobs::table)
+            .values(&new_job_model)
+            .returning(JobModel::as_returning())
+            .get_result(&mut conn)
+            .map_err(|_| JobRepositoryError::InternalServerError)?
+            .try_into()
+    }
+
+    async fn get_job_by_id(&self, id: JobId) -> Result<Option<Job>, JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        jobs::table
+            .find(id)
+            .select(JobModel::as_select())
+            .first::<JobModel>(&mut conn)
+            .optional()
+            .map_err(|_| JobRepositoryError::InternalServerError)?
+            .map(Job::try_from)
+            .transpose()
+    }
+
+    async fn get_jobs(
+        &self,
+        user_id: Option<Uuid>,
+        status: Option<JobStatus>,
+        limit: i64,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        let mut query = jobs::table.into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(jobs::user_id.eq(user_id));
+        }
+        if let Some(status) = status {
+            query = query.filter(jobs::status.eq(RowJobStatus::from(status)));
+        }
+
+        let results = query
+            .order((jobs::created_at.desc(), jobs::id.desc()))
+            .limit(limit)
+            .select(JobModel::as_select())
+            .load::<JobModel>(&mut conn)
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        to_jobs(results)
+    }
+
+    async fn claim_jobs(
+        &self,
+        worker_id: Uuid,
+        limit: i64,
+        lock_timeout: chrono::Duration,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        let now = chrono::Utc::now().naive_utc();
+        let stale_before = now - lock_timeout;
+
+        let results = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                // Rows locked by other workers are skipped instead of waited for, so every
+                // worker gets different jobs
+                let job_ids: Vec<Uuid> = jobs::table
+                    .filter(
+                        jobs::status
+                            .eq(RowJobStatus::Pending)
+                            .and(jobs::run_at.le(now))
+                            .or(jobs::status
+                                .eq(RowJobStatus::Running)
+                                .and(jobs::locked_at.lt(stale_before))),
+                    )
+                    .order(jobs::run_at.asc())
+                    .limit(limit)
+                    .select(jobs::id)
+                    .for_update()
+                    .skip_locked()
+                    .load(conn)?;
+
+                if job_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                diesel::update(jobs::table.filter(jobs::id.eq_any(job_ids)))
+                    .set((
+                        jobs::status.eq(RowJobStatus::Running),
+                        jobs::locked_at.eq(now),
+                        jobs::locked_by.eq(worker_id),
+                        jobs::attempts.eq(jobs::attempts + 1),
+                    ))
+                    .returning(JobModel::as_returning())
+                    .get_results(conn)
+            })
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        to_jobs(results)
+    }
+
+    async fn complete_job(&self, id: JobId, worker_id: Uuid) -> Result<(), JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        let updated_rows = diesel::update(
+            jobs::table.filter(
+                jobs::id
+                    .eq(id)
+                    .and(jobs::locked_by.eq(worker_id))
+                    .and(jobs::status.eq(RowJobStatus::Running)),
+            ),
+        )
+        .set((
+            jobs::status.eq(RowJobStatus::Succeeded),
+            jobs::locked_at.eq(None::<chrono::NaiveDateTime>),
+            jobs::locked_by.eq(None::<Uuid>),
+            jobs::last_error.eq(None::<String>),
+            jobs::completed_at.eq(diesel::dsl::now),
+        ))
+        .execute(&mut conn)
+        .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        // Nothing matches once the lock timed out and another worker took the job over
+        if updated_rows == 0 {
+            Err(JobRepositoryError::LockLost)
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn fail_job(
+        &self,
+        id: JobId,
+        worker_id: Uuid,
+        error: String,
+        retry_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        let (status, run_at) = match retry_at {
+            Some(retry_at) => (RowJobStatus::Pending, retry_at),
+            None => (RowJobStatus::Dead, chrono::Utc::now().naive_utc()),
+        };
+
+        let updated_rows = diesel::update(
+            jobs::table.filter(
+                jobs::id
+                    .eq(id)
+                    .and(jobs::locked_by.eq(worker_id))
+                    .and(jobs::status.eq(RowJobStatus::Running)),
+            ),
+        )
+        .set((
+            jobs::status.eq(status),
+            jobs::run_at.eq(run_at),
+            jobs::locked_at.eq(None::<chrono::NaiveDateTime>),
+            jobs::locked_by.eq(None::<Uuid>),
+            jobs::last_error.eq(Some(error)),
+        ))
+        .execute(&mut conn)
+        .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        // Nothing matches once the lock timed out and another worker took the job over
+        if updated_rows == 0 {
+            Err(JobRepositoryError::LockLost)
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn requeue_job(&self, id: JobId) -> Result<Job, JobRepositoryError> {
+        use crate::schema::jobs;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| JobRepositoryError::InternalServerError)?;
+
+        diesel::update(jobs::table.find(id))
+            .set((
+                jobs::status.eq(RowJobStatus::Pending),
+                jobs::attempts.eq(0),
+                jobs::run_at.eq(diesel::dsl::now),
+                jobs::locked_at.eq(None::<chrono::NaiveDateTime>),
+                jobs::locked_by.eq(None::<Uuid>),
+            ))
+            .returning(JobModel::as_returning())
+            .get_result(&mut conn)
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => JobRepositoryError::JobNotFound,
+                _ => JobRepositoryError::InternalServerError,
+            })?
+            .try_into()
+    }
+}
@@ -0,0 +1,113 @@
+use std::{env, sync::Arc, time::Duration};
+
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::jobs::{
+    application::commands::process_job::{ProcessJobCommand, process_job_command_handler},
+    domain::{JobHandler, JobRepository, JobRepositoryError, JobRetryPolicy},
+};
+
+#[derive(Clone)]
+pub struct JobWorkerConfig {
+    pub workers: usize,
+    /// How long an idle worker waits before looking for new jobs
+    pub poll_interval: Duration,
+    /// Running jobs locked for longer are assumed to belong to a stopped worker
+    pub lock_timeout: chrono::Duration,
+    pub retry_policy: JobRetryPolicy,
+}
+
+impl JobWorkerConfig {
+    pub fn new() -> Self {
+        let env_u64 = |name: &str| env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        let default_retry_policy = JobRetryPolicy::default();
+
+        JobWorkerConfig {
+            workers: env::var("JOB_WORKERS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(4),
+            poll_interval: Duration::from_millis(
+                env_u64("JOB_POLL_INTERVAL_MILLISECONDS").unwrap_or(1000),
+            ),
+            lock_timeout: chrono::Duration::seconds(
+                env_u64("JOB_LOCK_TIMEOUT_SECONDS").unwrap_or(900) as i64,
+            ),
+            retry_policy: JobRetryPolicy {
+                base_delay_seconds: env_u64("JOB_RETRY_BASE_DELAY_SECONDS")
+                    .unwrap_or(default_retry_policy.base_delay_seconds),
+                max_delay_seconds: env_u64("JOB_RETRY_MAX_DELAY_SECONDS")
+                    .unwrap_or(default_retry_policy.max_delay_seconds),
+            },
+        }
+    }
+}
+
+impl Default for JobWorkerConfig {
+    fn default() -> Self {
+        JobWorkerConfig::new()
+    }
+}
+
+/// Starts the workers, each one runs a single job at a time
+pub fn spawn_job_workers(
+    config: JobWorkerConfig,
+    job_repository: Arc<dyn JobRepository>,
+    job_handler: Arc<dyn JobHandler>,
+) -> Vec<JoinHandle<()>> {
+    (0..config.workers)
+        .map(|worker| {
+            let config = config.clone();
+            let job_repository = job_repository.clone();
+            let job_handler = job_handler.clone();
+            tokio::spawn(async move {
+                // Locks the claimed jobs, so a worker whose job was taken over cannot record its
+                // outcome
+                let worker_id = Uuid::new_v4();
+                tracing::info!("Job worker {} started as {}", worker, worker_id);
+                loop {
+                    let jobs = match job_repository
+                        .claim_jobs(worker_id, 1, config.lock_timeout)
+                        .await
+                    {
+                        Ok(jobs) => jobs,
+                        Err(e) => {
+                            tracing::error!("Job worker {} failed to claim jobs: {}", worker, e);
+                            Vec::new()
+                        }
+                    };
+
+                    if jobs.is_empty() {
+                        tokio::time::sleep(config.poll_interval).await;
+                        continue;
+                    }
+
+                    for job in jobs {
+                        let job_id = job.id;
+                        match process_job_command_handler(
+                            ProcessJobCommand { job, worker_id },
+                            job_repository.as_ref(),
+                            job_handler.as_ref(),
+                            &config.retry_policy,
+                        )
+                        .await
+                        {
+                            Ok(_) => {}
+                            Err(JobRepositoryError::LockLost) => tracing::warn!(
+                                "Job {} was taken over by another worker, dropping its outcome",
+                                job_id
+                            ),
+                            // The job stays locked and is taken over once the lock times out
+                            Err(e) => tracing::error!(
+                                "Failed to record the outcome of job {}: {}",
+                                job_id,
+                                e
+                            ),
+                        }
+                    }
+                }
+            })
+        })
+        .collect()
+}
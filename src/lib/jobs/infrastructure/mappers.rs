@@ -0,0 +1,44 @@
+use super::models::{JobModel, NewJobModel};
+use crate::jobs::domain::{Job, JobRepositoryError, NewJob};
+
+impl TryFrom<JobModel> for Job {
+    type Error = JobRepositoryError;
+
+    fn try_from(model: JobModel) -> Result<Self, Self::Error> {
+        // Payloads are only written by `NewJob`, one that no longer parses is a bug
+        let payload = serde_json::from_value(model.payload).map_err(|e| {
+            tracing::error!("Invalid payload of job {}: {}", model.id, e);
+            JobRepositoryError::InternalServerError
+        })?;
+
+        Ok(Job {
+            id: model.id,
+            user_id: model.user_id,
+            payload,
+            status: model.status.into(),
+            attempts: model.attempts,
+            max_attempts: model.max_attempts,
+            run_at: model.run_at,
+            locked_at: model.locked_at,
+            locked_by: model.locked_by,
+            last_error: model.last_error,
+            completed_at: model.completed_at,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        })
+    }
+}
+
+impl TryFrom<NewJob> for NewJobModel {
+    type Error = JobRepositoryError;
+
+    fn try_from(new_job: NewJob) -> Result<Self, Self::Error> {
+        Ok(NewJobModel {
+            user_id: new_job.user_id,
+            job_type: new_job.payload.job_type().to_string(),
+            payload: serde_json::to_value(&new_job.payload)
+                .map_err(|_| JobRepositoryError::InternalServerError)?,
+            max_attempts: new_job.max_attempts,
+        })
+    }
+}
@@ -0,0 +1,9 @@
+pub mod app_job_handler;
+pub mod diesel_job_repository;
+pub mod job_worker_pool;
+pub mod mappers;
+pub mod models;
+
+pub use app_job_handler::*;
+pub use diesel_job_repository::*;
+pub use job_worker_pool::*;
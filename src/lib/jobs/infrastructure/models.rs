@@ -0,0 +1,93 @@
+use std::io::Write;
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{jobs::domain::JobStatus, persistence::domain::schema::sql_types};
+
+#[derive(Queryable, Selectable, Identifiable, Debug)]
+#[diesel(table_name = crate::schema::jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct JobModel {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub job_type: String,
+    pub payload: serde_json::Value,
+    pub status: RowJobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: chrono::NaiveDateTime,
+    pub locked_at: Option<chrono::NaiveDateTime>,
+    pub last_error: Option<String>,
+    pub completed_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+    pub locked_by: Option<Uuid>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::jobs)]
+pub struct NewJobModel {
+    pub user_id: Option<Uuid>,
+    pub job_type: String,
+    pub payload: serde_json::Value,
+    pub max_attempts: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel::FromSqlRow, diesel::AsExpression)]
+#[diesel(sql_type = sql_types::JobStatus)]
+pub enum RowJobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Dead,
+}
+
+impl From<JobStatus> for RowJobStatus {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Pending => RowJobStatus::Pending,
+            JobStatus::Running => RowJobStatus::Running,
+            JobStatus::Succeeded => RowJobStatus::Succeeded,
+            JobStatus::Dead => RowJobStatus::Dead,
+        }
+    }
+}
+
+impl From<RowJobStatus> for JobStatus {
+    fn from(row_status: RowJobStatus) -> Self {
+        match row_status {
+            RowJobStatus::Pending => JobStatus::Pending,
+            RowJobStatus::Running => JobStatus::Running,
+            RowJobStatus::Succeeded => JobStatus::Succeeded,
+            RowJobStatus::Dead => JobStatus::Dead,
+        }
+    }
+}
+
+impl diesel::serialize::ToSql<sql_types::JobStatus, diesel::pg::Pg> for RowJobStatus {
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        match *self {
+            RowJobStatus::Pending => out.write_all(b"PENDING")?,
+            RowJobStatus::Running => out.write_all(b"RUNNING")?,
+            RowJobStatus::Succeeded => out.write_all(b"SUCCEEDED")?,
+            RowJobStatus::Dead => out.write_all(b"DEAD")?,
+        }
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+impl diesel::deserialize::FromSql<sql_types::JobStatus, diesel::pg::Pg> for RowJobStatus {
+    fn from_sql(bytes: diesel::pg::PgValue) -> diesel::deserialize::Result<Self> {
+        match std::str::from_utf8(bytes.as_bytes())? {
+            "PENDING" => Ok(RowJobStatus::Pending),
+            "RUNNING" => Ok(RowJobStatus::Running),
+            "SUCCEEDED" => Ok(RowJobStatus::Succeeded),
+            "DEAD" => Ok(RowJobStatus::Dead),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
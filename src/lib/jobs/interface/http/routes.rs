@@ -0,0 +1,176 @@
+use crate::shared::interface::http::mw_require_role;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        domain::{
+            errors::{ApiError, ApiErrorBody},
+            response_body::ApiResponseBody,
+        },
+        http_server::AppState,
+    },
+    jobs::{
+        application::{
+            commands::retry_job::{RetryJobCommand, retry_job_command_handler},
+            queries::{
+                get_job::{GetJobQuery, JobResult, get_job_query_handler},
+                get_jobs::{GetJobsQuery, get_jobs_query_handler},
+            },
+        },
+        domain::{JobQueueError, JobStatus},
+    },
+    protected, require_roles,
+    shared::interface::openapi::security::SecurityAddon,
+    users::domain::{Claims, Role},
+};
+
+fn parse_job_id(job_id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(job_id)
+        .map_err(|_| ApiError::BadRequestError("Invalid job ID format".to_string()))
+}
+
+fn job_error_to_api_error(error: JobQueueError) -> ApiError {
+    match error {
+        JobQueueError::JobNotFound => ApiError::NotFoundError(error.to_string()),
+        JobQueueError::JobNotDead => ApiError::ConflictError(error.to_string()),
+        JobQueueError::InternalServerError(msg) => {
+            tracing::error!("Internal server error, {}", msg);
+            ApiError::InternalServerError("Internal server error".to_string())
+        }
+    }
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetJobsParams {
+    /// Only jobs with this status
+    status: Option<JobStatus>,
+    /// Number of jobs, between 1 and 200 (default 50)
+    limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "",
+    description = "List the most recent background jobs of the current user, admins see the jobs of every user",
+    tag = "jobs",
+    params(GetJobsParams),
+    responses(
+        (status = 200, description = "Jobs, newest first", body = ApiResponseBody<Vec<JobResult>>),
+        (status = 401, description = "Unauthorized", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_jobs(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<GetJobsParams>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<JobResult>>>), ApiError> {
+    let query = GetJobsQuery {
+        user_id: claims.sub,
+        is_admin: claims.role == Role::Admin,
+        status: params.status,
+        limit: params.limit,
+    };
+
+    get_jobs_query_handler(query, state.job_repository.as_ref())
+        .await
+        .map_err(job_error_to_api_error)
+        .map(|jobs| (StatusCode::OK, ApiResponseBody::new(jobs).into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{job_id}",
+    description = "Get the status of a background job",
+    tag = "jobs",
+    params(
+        ("job_id" = String, Path, description = "ID of the job")
+    ),
+    responses(
+        (status = 200, description = "Job found", body = ApiResponseBody<JobResult>),
+        (status = 400, description = "Invalid job ID format", body = ApiErrorBody),
+        (status = 404, description = "Job not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_job(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(job_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<JobResult>>), ApiError> {
+    let query = GetJobQuery {
+        job_id: parse_job_id(&job_id)?,
+        user_id: claims.sub,
+        is_admin: claims.role == Role::Admin,
+    };
+
+    get_job_query_handler(query, state.job_repository.as_ref())
+        .await
+        .map_err(job_error_to_api_error)
+        .map(|job| (StatusCode::OK, ApiResponseBody::new(job).into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{job_id}/retry",
+    description = "Put a dead-lettered job back in the queue with a fresh set of attempts. Admin only",
+    tag = "jobs",
+    params(
+        ("job_id" = String, Path, description = "ID of the job")
+    ),
+    responses(
+        (status = 200, description = "Job queued again", body = ApiResponseBody<JobResult>),
+        (status = 400, description = "Invalid job ID format", body = ApiErrorBody),
+        (status = 403, description = "Forbidden", body = ApiErrorBody),
+        (status = 404, description = "Job not found", body = ApiErrorBody),
+        (status = 409, description = "The job is not dead", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn retry_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<JobResult>>), ApiError> {
+    let command = RetryJobCommand {
+        job_id: parse_job_id(&job_id)?,
+    };
+
+    retry_job_command_handler(command, state.job_repository.as_ref())
+        .await
+        .map_err(job_error_to_api_error)
+        .map(|job| (StatusCode::OK, ApiResponseBody::new(job).into()))
+}
+
+pub fn api_routes(state: AppState) -> axum::Router<AppState> {
+    axum::Router::new()
+        .route("/{job_id}/retry", post(retry_job))
+        .route_layer(require_roles!(&[Role::Admin]))
+        .route("/", get(get_jobs))
+        .route("/{job_id}", get(get_job))
+        .route_layer(protected!(state.clone()))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_jobs, get_job, retry_job),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "jobs", description = "Background job API")
+    )
+)]
+pub struct ApiDoc;
+
+pub fn combine_openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
@@ -1,7 +1,10 @@
+pub mod albums;
 pub mod api;
+pub mod jobs;
 pub mod media;
 pub mod persistence;
 pub mod shared;
+pub mod sharing;
 pub mod users;
 
 pub use persistence::domain::schema;
@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::media::domain::{
+    FileStorageError, FileStorageService, UploadSessionError, UploadSessionId,
+    UploadSessionRepository, UploadSessionRepositoryError,
+};
+
+pub struct AbortUploadSessionCommand {
+    pub upload_session_id: UploadSessionId,
+    pub user_id: Uuid,
+}
+
+pub async fn abort_upload_session_command_handler<
+    SR: UploadSessionRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+>(
+    upload_session_repository: &SR,
+    storage_service: &FS,
+    command: AbortUploadSessionCommand,
+) -> Result<(), UploadSessionError> {
+    let session = upload_session_repository
+        .get_upload_session_by_id(command.upload_session_id)
+        .await
+        .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?
+        .filter(|session| session.user_id == command.user_id)
+        .ok_or(UploadSessionError::NotFound)?;
+
+    match storage_service
+        .abort_multipart_upload(&session.file_path, &session.storage_upload_id)
+        .await
+    {
+        // Already gone from the storage, only the session row is left
+        Ok(()) | Err(FileStorageError::NotFound) => {}
+        Err(e) => {
+            return Err(UploadSessionError::StorageError(format!(
+                "An error occurred while aborting the multipart upload: {}",
+                e
+            )));
+        }
+    }
+
+    upload_session_repository
+        .delete_upload_session(session.id)
+        .await
+        .map_err(|e| match e {
+            UploadSessionRepositoryError::UploadSessionNotFound => UploadSessionError::NotFound,
+            e => UploadSessionError::InternalServerError(e.to_string()),
+        })
+}
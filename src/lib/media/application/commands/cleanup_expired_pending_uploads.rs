@@ -0,0 +1,45 @@
+use crate::media::domain::{
+    FileStorageError, FileStorageService, MediaDeleteError, MediaRepository,
+    pending_media_files_expired_before,
+};
+
+/// Deletes the objects and the pending media files of presigned uploads that were never
+/// confirmed. Returns the number of media files that were cleaned up.
+pub async fn cleanup_expired_pending_uploads_command_handler<
+    MR: MediaRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+>(
+    media_repository: &MR,
+    storage_service: &FS,
+) -> Result<usize, MediaDeleteError> {
+    let expired_media_files = media_repository
+        .get_pending_media_files_uploaded_before(pending_media_files_expired_before())
+        .await
+        .map_err(|e| MediaDeleteError::InternalServerError(e.to_string()))?;
+
+    let mut cleaned_up = 0;
+
+    for media_file in expired_media_files {
+        match storage_service.delete_file(&media_file.file_path).await {
+            // Most abandoned uploads never sent the object
+            Ok(()) | Err(FileStorageError::NotFound) => {}
+            Err(e) => {
+                // Keep the media file so the next run retries the delete
+                tracing::warn!(
+                    "Failed to delete {} of expired upload {}: {}",
+                    media_file.file_path,
+                    media_file.id,
+                    e
+                );
+                continue;
+            }
+        }
+
+        match media_repository.delete_media_file(media_file.id).await {
+            Ok(()) => cleaned_up += 1,
+            Err(e) => tracing::warn!("Failed to delete expired upload {}: {}", media_file.id, e),
+        }
+    }
+
+    Ok(cleaned_up)
+}
@@ -0,0 +1,48 @@
+use crate::media::domain::{
+    FileStorageError, FileStorageService, UploadSessionError, UploadSessionRepository,
+};
+
+/// Aborts the multipart uploads of every expired session and removes the sessions.
+/// Returns the number of sessions that were cleaned up.
+pub async fn cleanup_expired_upload_sessions_command_handler<
+    SR: UploadSessionRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+>(
+    upload_session_repository: &SR,
+    storage_service: &FS,
+) -> Result<usize, UploadSessionError> {
+    let expired_sessions = upload_session_repository
+        .get_expired_upload_sessions()
+        .await
+        .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?;
+
+    let mut cleaned_up = 0;
+
+    for session in expired_sessions {
+        match storage_service
+            .abort_multipart_upload(&session.file_path, &session.storage_upload_id)
+            .await
+        {
+            Ok(()) | Err(FileStorageError::NotFound) => {}
+            Err(e) => {
+                // Keep the session so the next run retries the abort
+                tracing::warn!(
+                    "Failed to abort multipart upload of expired session {}: {}",
+                    session.id,
+                    e
+                );
+                continue;
+            }
+        }
+
+        match upload_session_repository
+            .delete_upload_session(session.id)
+            .await
+        {
+            Ok(()) => cleaned_up += 1,
+            Err(e) => tracing::warn!("Failed to delete expired session {}: {}", session.id, e),
+        }
+    }
+
+    Ok(cleaned_up)
+}
@@ -0,0 +1,37 @@
+use crate::media::domain::{FileStorageError, FileStorageService, STALE_MULTIPART_UPLOAD_HOURS};
+
+/// Aborts the multipart uploads started more than `STALE_MULTIPART_UPLOAD_HOURS` ago that were
+/// never completed, e.g. because the server stopped in the middle of an upload.
+/// Returns the number of uploads that were aborted.
+pub async fn cleanup_stale_multipart_uploads_command_handler<FS: FileStorageService + ?Sized>(
+    storage_service: &FS,
+) -> Result<usize, FileStorageError> {
+    let stale_before =
+        chrono::Utc::now().naive_utc() - chrono::Duration::hours(STALE_MULTIPART_UPLOAD_HOURS);
+    let incomplete_uploads = storage_service.list_incomplete_multipart_uploads().await?;
+
+    let mut aborted = 0;
+
+    // Uploads without a start time cannot be told apart from running ones
+    for upload in incomplete_uploads
+        .into_iter()
+        .filter(|upload| upload.initiated_at.is_some_and(|at| at < stale_before))
+    {
+        match storage_service
+            .abort_multipart_upload(&upload.file_path, &upload.upload_id)
+            .await
+        {
+            Ok(()) => aborted += 1,
+            // Completed or aborted since it was listed
+            Err(FileStorageError::NotFound) => {}
+            Err(e) => tracing::warn!(
+                "Failed to abort stale multipart upload {} of {}: {}",
+                upload.upload_id,
+                upload.file_path,
+                e
+            ),
+        }
+    }
+
+    Ok(aborted)
+}
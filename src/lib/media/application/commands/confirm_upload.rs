@@ -0,0 +1,178 @@
+use uuid::Uuid;
+
+use crate::{
+    media::{
+        application::commands::upload_media::{
+            UploadMediaResult, existing_media_by_checksum, remaining_quota_bytes,
+            sniff_stored_content_type, stored_file_checksum,
+        },
+        domain::{
+            FileStorageError, FileStorageService, MediaConfirmError, MediaFile, MediaId,
+            MediaRepository, MediaRepositoryError, MediaStatus, MediaUploadError, StorageUsage,
+            content_type_matches,
+        },
+    },
+    users::domain::{QuotaConfig, UserRepository},
+};
+
+pub struct ConfirmUploadCommand {
+    pub media_id: MediaId,
+    pub user_id: Uuid,
+}
+
+pub async fn confirm_upload_command_handler<
+    MR: MediaRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+    UR: UserRepository + ?Sized,
+>(
+    media_repository: &MR,
+    storage_service: &FS,
+    user_repository: &UR,
+    quota_config: &QuotaConfig,
+    command: ConfirmUploadCommand,
+) -> Result<UploadMediaResult, MediaConfirmError> {
+    let media_file = media_repository
+        .get_media_file_by_id(command.media_id)
+        .await
+        .map_err(|e| MediaConfirmError::InternalServerError(e.to_string()))?
+        .filter(|media_file| media_file.user_id == command.user_id)
+        // An expired upload no longer reserves quota and is about to be cleaned up
+        .filter(|media_file| !media_file.is_expired_pending())
+        .ok_or(MediaConfirmError::MediaFileNotFound)?;
+
+    // Confirming twice is harmless, the client may have retried after a lost response
+    if media_file.status == MediaStatus::Ready {
+        return Ok(media_file.into());
+    }
+
+    let metadata = storage_service
+        .get_file_metadata(&media_file.file_path)
+        .await
+        .map_err(|e| match e {
+            FileStorageError::NotFound => MediaConfirmError::FileNotUploaded,
+            e => MediaConfirmError::StorageError(format!(
+                "An error occurred while reading the uploaded file: {}",
+                e
+            )),
+        })?;
+
+    let stored_content_type_matches = metadata
+        .content_type
+        .as_deref()
+        .is_none_or(|content_type| content_type == media_file.content_type);
+
+    if metadata.file_size != media_file.file_size as u64 || !stored_content_type_matches {
+        return Err(MediaConfirmError::FileMismatch);
+    }
+
+    // The content type the client sent to the storage is not trusted either
+    let detected_content_type =
+        sniff_stored_content_type(storage_service, &media_file.file_path, metadata.file_size)
+            .await
+            .map_err(|e| {
+                MediaConfirmError::StorageError(format!(
+                    "An error occurred while reading the uploaded file: {}",
+                    e
+                ))
+            })?;
+    if !content_type_matches(&media_file.content_type, detected_content_type) {
+        discard_upload(media_repository, storage_service, &media_file).await;
+        return Err(MediaConfirmError::ContentTypeMismatch {
+            claimed: media_file.content_type,
+            detected: detected_content_type.map(str::to_string),
+        });
+    }
+
+    // The object went straight to the storage, so it is hashed now
+    let checksum = stored_file_checksum(storage_service, &media_file.file_path)
+        .await
+        .map_err(|e| {
+            MediaConfirmError::StorageError(format!(
+                "An error occurred while reading the uploaded file: {}",
+                e
+            ))
+        })?;
+    let existing_media = existing_media_by_checksum(media_repository, command.user_id, &checksum)
+        .await
+        .map_err(|e| MediaConfirmError::InternalServerError(e.to_string()))?;
+    if let Some(existing_media) = existing_media {
+        // The content is already stored under the existing media file
+        discard_upload(media_repository, storage_service, &media_file).await;
+        return Ok(UploadMediaResult {
+            duplicate: true,
+            ..existing_media.into()
+        });
+    }
+
+    // The quota may have been lowered since the upload was requested
+    let fits_quota = remaining_quota_bytes(
+        media_repository,
+        user_repository,
+        quota_config,
+        command.user_id,
+        StorageUsage {
+            bytes: media_file.file_size,
+            items: 1,
+        },
+    )
+    .await
+    .map(|remaining_bytes| metadata.file_size <= remaining_bytes)
+    .or_else(|e| match e {
+        MediaUploadError::QuotaExceeded => Ok(false),
+        MediaUploadError::InternalServerError(msg) => {
+            Err(MediaConfirmError::InternalServerError(msg))
+        }
+        e => Err(MediaConfirmError::InternalServerError(e.to_string())),
+    })?;
+    if !fits_quota {
+        discard_upload(media_repository, storage_service, &media_file).await;
+        return Err(MediaConfirmError::QuotaExceeded);
+    }
+
+    match media_repository
+        .mark_media_file_ready(
+            media_file.id,
+            checksum.clone(),
+            detected_content_type.map(str::to_string),
+        )
+        .await
+    {
+        Ok(media_file) => Ok(media_file.into()),
+        Err(MediaRepositoryError::DuplicateChecksum) => {
+            // A concurrent upload of the same content was stored first
+            discard_upload(media_repository, storage_service, &media_file).await;
+            existing_media_by_checksum(media_repository, command.user_id, &checksum)
+                .await
+                .map_err(|e| MediaConfirmError::InternalServerError(e.to_string()))?
+                .map(|existing_media| UploadMediaResult {
+                    duplicate: true,
+                    ..existing_media.into()
+                })
+                .ok_or_else(|| MediaConfirmError::InternalServerError("Database error".to_string()))
+        }
+        Err(MediaRepositoryError::MediaFileNotFound) => Err(MediaConfirmError::MediaFileNotFound),
+        Err(e) => Err(MediaConfirmError::InternalServerError(e.to_string())),
+    }
+}
+
+/// Removes the stored file and the pending media file of an upload that is not kept
+async fn discard_upload<MR: MediaRepository + ?Sized, FS: FileStorageService + ?Sized>(
+    media_repository: &MR,
+    storage_service: &FS,
+    media_file: &MediaFile,
+) {
+    if let Err(e) = storage_service.delete_file(&media_file.file_path).await {
+        tracing::warn!(
+            "Failed to delete {} of a rejected upload: {}",
+            media_file.file_path,
+            e
+        );
+    }
+    if let Err(e) = media_repository.delete_media_file(media_file.id).await {
+        tracing::warn!(
+            "Failed to delete rejected media file {}: {}",
+            media_file.id,
+            e
+        );
+    }
+}
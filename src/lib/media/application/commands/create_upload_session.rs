@@ -0,0 +1,140 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    media::{
+        application::commands::upload_media::remaining_quota_bytes,
+        domain::{
+            FileStorageService, MediaRepository, MediaSizeLimits, MediaTypeAllowlist,
+            MediaUploadError, NewUploadSession, StorageUsage, UPLOAD_SESSION_CHUNK_SIZE,
+            UPLOAD_SESSION_TTL_HOURS, UploadSession, UploadSessionError, UploadSessionRepository,
+        },
+    },
+    users::domain::{QuotaConfig, UserRepository},
+};
+
+pub struct CreateUploadSessionCommand {
+    pub user_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub file_size: u64,
+    pub content_type: String,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct UploadSessionResult {
+    pub id: Uuid,
+    pub file_size: i64,
+    /// Number of bytes already stored, the next chunk has to start here
+    pub upload_offset: i64,
+    /// Size every chunk but the last one has to have
+    pub chunk_size: u64,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_upload_session_command_handler<
+    SR: UploadSessionRepository + ?Sized,
+    MR: MediaRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+    UR: UserRepository + ?Sized,
+>(
+    upload_session_repository: &SR,
+    media_repository: &MR,
+    storage_service: &FS,
+    user_repository: &UR,
+    media_type_allowlist: &MediaTypeAllowlist,
+    media_size_limits: &MediaSizeLimits,
+    quota_config: &QuotaConfig,
+    command: CreateUploadSessionCommand,
+) -> Result<UploadSessionResult, UploadSessionError> {
+    if !media_type_allowlist.is_allowed(&command.content_type) {
+        return Err(UploadSessionError::InvalidFileType);
+    }
+
+    // Chunks beyond the declared size are rejected, the assembled file is checked on finalize
+    let max_bytes = media_size_limits.max_bytes_for(&command.content_type);
+    if command.file_size > max_bytes {
+        return Err(UploadSessionError::FileTooLarge { max_bytes });
+    }
+
+    // Checked again on finalize, other uploads may have finished in the meantime
+    let remaining_bytes = remaining_quota_bytes(
+        media_repository,
+        user_repository,
+        quota_config,
+        command.user_id,
+        StorageUsage::default(),
+    )
+    .await
+    .map_err(quota_error_to_upload_session_error)?;
+    if command.file_size > remaining_bytes {
+        return Err(UploadSessionError::QuotaExceeded);
+    }
+
+    let file_path = format!("media/{}/{}", command.user_id, command.filename);
+
+    let storage_upload_id = storage_service
+        .create_multipart_upload(&file_path, &command.content_type)
+        .await
+        .map_err(|e| {
+            UploadSessionError::StorageError(format!(
+                "An error occurred while creating the multipart upload: {}",
+                e
+            ))
+        })?;
+
+    let new_session = NewUploadSession {
+        user_id: command.user_id,
+        filename: command.filename,
+        original_filename: command.original_filename,
+        file_size: command.file_size as i64,
+        content_type: command.content_type,
+        file_path: file_path.clone(),
+        storage_upload_id: storage_upload_id.clone(),
+        expires_at: (chrono::Utc::now() + chrono::Duration::hours(UPLOAD_SESSION_TTL_HOURS))
+            .naive_utc(),
+    };
+
+    match upload_session_repository
+        .create_upload_session(new_session)
+        .await
+    {
+        Ok(session) => Ok(session.into()),
+        Err(e) => {
+            // Do not leave the multipart upload behind if the session could not be saved
+            if let Err(abort_error) = storage_service
+                .abort_multipart_upload(&file_path, &storage_upload_id)
+                .await
+            {
+                tracing::warn!(
+                    "Failed to abort multipart upload {}: {}",
+                    storage_upload_id,
+                    abort_error
+                );
+            }
+            Err(UploadSessionError::InternalServerError(e.to_string()))
+        }
+    }
+}
+
+pub(crate) fn quota_error_to_upload_session_error(err: MediaUploadError) -> UploadSessionError {
+    match err {
+        MediaUploadError::QuotaExceeded => UploadSessionError::QuotaExceeded,
+        MediaUploadError::InternalServerError(msg) => UploadSessionError::InternalServerError(msg),
+        err => UploadSessionError::InternalServerError(err.to_string()),
+    }
+}
+
+impl From<UploadSession> for UploadSessionResult {
+    fn from(session: UploadSession) -> Self {
+        UploadSessionResult {
+            id: session.id,
+            file_size: session.file_size,
+            upload_offset: session.upload_offset,
+            chunk_size: UPLOAD_SESSION_CHUNK_SIZE,
+            expires_at: session.expires_at,
+        }
+    }
+}
@@ -32,7 +32,7 @@ pub async fn delete_media_command_handler<
         .await
         .map_err(|e| match e {
             MediaRepositoryError::MediaFileNotFound => MediaDeleteError::MediaFileNotFound,
-            MediaRepositoryError::InternalServerError => {
+            _ => {
                 MediaDeleteError::InternalServerError("Database error".to_string())
             }
         })?
@@ -55,13 +55,36 @@ pub async fn delete_media_command_handler<
             }
         })?;
 
+    // Renditions are derived from the original, a leftover one is only wasted space
+    let renditions = media_repository
+        .get_media_renditions_by_media_ids(vec![media_file.id])
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to get renditions of media {}: {:?}", media_file.id, e);
+            Vec::new()
+        });
+    let mut rendition_paths: Vec<String> = renditions
+        .into_iter()
+        .map(|rendition| rendition.file_path)
+        .collect();
+    rendition_paths.sort();
+    rendition_paths.dedup();
+    for rendition_path in rendition_paths {
+        match storage_service.delete_file(&rendition_path).await {
+            Ok(()) | Err(crate::media::FileStorageError::NotFound) => {}
+            Err(error) => {
+                tracing::error!("Failed to delete rendition {}: {:?}", rendition_path, error)
+            }
+        }
+    }
+
     // Delete media file record from database
     media_repository
         .delete_media_file(command.media_id)
         .await
         .map_err(|e| match e {
             MediaRepositoryError::MediaFileNotFound => MediaDeleteError::MediaFileNotFound,
-            MediaRepositoryError::InternalServerError => {
+            _ => {
                 MediaDeleteError::InternalServerError("Database error".to_string())
             }
         })?;
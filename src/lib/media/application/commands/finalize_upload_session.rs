@@ -0,0 +1,215 @@
+use uuid::Uuid;
+
+use crate::{
+    media::{
+        application::commands::{
+            create_upload_session::quota_error_to_upload_session_error,
+            upload_media::{
+                UploadMediaResult, existing_media_by_checksum, remaining_quota_bytes,
+                sniff_stored_content_type, stored_file_checksum,
+            },
+        },
+        domain::{
+            FileStorageService, MediaRepository, MediaRepositoryError, MediaSizeLimits,
+            MediaStatus, NewMediaFile, StorageUsage, UploadSession, UploadSessionError,
+            UploadSessionId, UploadSessionRepository, UploadedPart, content_type_matches,
+        },
+    },
+    users::domain::{QuotaConfig, UserRepository},
+};
+
+pub struct FinalizeUploadSessionCommand {
+    pub upload_session_id: UploadSessionId,
+    pub user_id: Uuid,
+}
+
+pub async fn finalize_upload_session_command_handler<
+    SR: UploadSessionRepository + ?Sized,
+    MR: MediaRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+    UR: UserRepository + ?Sized,
+>(
+    upload_session_repository: &SR,
+    media_repository: &MR,
+    storage_service: &FS,
+    user_repository: &UR,
+    media_size_limits: &MediaSizeLimits,
+    quota_config: &QuotaConfig,
+    command: FinalizeUploadSessionCommand,
+) -> Result<UploadMediaResult, UploadSessionError> {
+    let session = upload_session_repository
+        .get_upload_session_by_id(command.upload_session_id)
+        .await
+        .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?
+        .filter(|session| session.user_id == command.user_id && !session.is_expired())
+        .ok_or(UploadSessionError::NotFound)?;
+
+    if !session.is_complete() {
+        return Err(UploadSessionError::Incomplete);
+    }
+
+    let parts = session
+        .part_etags
+        .iter()
+        .enumerate()
+        .map(|(index, e_tag)| UploadedPart {
+            part_number: index as i32 + 1,
+            e_tag: e_tag.clone(),
+        })
+        .collect();
+
+    storage_service
+        .complete_multipart_upload(&session.file_path, &session.storage_upload_id, parts)
+        .await
+        .map_err(|e| {
+            UploadSessionError::StorageError(format!(
+                "An error occurred while completing the multipart upload: {}",
+                e
+            ))
+        })?;
+
+    let stored = storage_service
+        .get_file_metadata(&session.file_path)
+        .await
+        .map_err(|e| {
+            UploadSessionError::StorageError(format!(
+                "An error occurred while reading the uploaded file: {}",
+                e
+            ))
+        })?;
+
+    // The size limit may have been lowered since the session was created
+    let max_bytes = media_size_limits.max_bytes_for(&session.content_type);
+    if stored.file_size > max_bytes {
+        discard_upload(upload_session_repository, storage_service, &session).await;
+        return Err(UploadSessionError::FileTooLarge { max_bytes });
+    }
+
+    // The chunks were not sniffed on their way in, the assembled file has to start like its type
+    let detected_content_type =
+        sniff_stored_content_type(storage_service, &session.file_path, stored.file_size)
+            .await
+            .map_err(|e| {
+                UploadSessionError::StorageError(format!(
+                    "An error occurred while reading the uploaded file: {}",
+                    e
+                ))
+            })?;
+    if !content_type_matches(&session.content_type, detected_content_type) {
+        discard_upload(upload_session_repository, storage_service, &session).await;
+        return Err(UploadSessionError::ContentTypeMismatch {
+            claimed: session.content_type,
+            detected: detected_content_type.map(str::to_string),
+        });
+    }
+
+    // The parts were hashed separately by the storage, the assembled file is read once more
+    let checksum = stored_file_checksum(storage_service, &session.file_path)
+        .await
+        .map_err(|e| {
+            UploadSessionError::StorageError(format!(
+                "An error occurred while reading the uploaded file: {}",
+                e
+            ))
+        })?;
+    let existing_media = existing_media_by_checksum(media_repository, session.user_id, &checksum)
+        .await
+        .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?;
+    if let Some(existing_media) = existing_media {
+        // The content is already stored under the existing media file
+        discard_upload(upload_session_repository, storage_service, &session).await;
+        return Ok(UploadMediaResult {
+            duplicate: true,
+            ..existing_media.into()
+        });
+    }
+
+    let fits_quota = remaining_quota_bytes(
+        media_repository,
+        user_repository,
+        quota_config,
+        session.user_id,
+        StorageUsage::default(),
+    )
+    .await
+    .map(|remaining_bytes| stored.file_size <= remaining_bytes)
+    .or_else(|e| match quota_error_to_upload_session_error(e) {
+        UploadSessionError::QuotaExceeded => Ok(false),
+        e => Err(e),
+    })?;
+    if !fits_quota {
+        discard_upload(upload_session_repository, storage_service, &session).await;
+        return Err(UploadSessionError::QuotaExceeded);
+    }
+
+    let new_media_file = NewMediaFile {
+        user_id: session.user_id,
+        filename: session.filename.clone(),
+        original_filename: session.original_filename.clone(),
+        file_size: stored.file_size as i64,
+        content_type: session.content_type.clone(),
+        file_path: session.file_path.clone(),
+        status: MediaStatus::Ready,
+        checksum: Some(checksum.clone()),
+        detected_content_type: detected_content_type.map(str::to_string),
+    };
+
+    let created_media = match media_repository.create_media_file(new_media_file).await {
+        Ok(created_media) => created_media,
+        Err(e) => {
+            // Nothing refers to the assembled file without its media file
+            discard_upload(upload_session_repository, storage_service, &session).await;
+            if !matches!(e, MediaRepositoryError::DuplicateChecksum) {
+                return Err(UploadSessionError::InternalServerError(e.to_string()));
+            }
+            // A concurrent upload of the same content was stored first
+            return existing_media_by_checksum(media_repository, session.user_id, &checksum)
+                .await
+                .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?
+                .map(|existing_media| UploadMediaResult {
+                    duplicate: true,
+                    ..existing_media.into()
+                })
+                .ok_or_else(|| UploadSessionError::InternalServerError(e.to_string()));
+        }
+    };
+
+    // The object is already stored, a leftover session row is harmless and expires anyway
+    if let Err(e) = upload_session_repository
+        .delete_upload_session(session.id)
+        .await
+    {
+        tracing::warn!(
+            "Failed to delete finalized upload session {}: {}",
+            session.id,
+            e
+        );
+    }
+
+    Ok(created_media.into())
+}
+
+/// Removes the assembled file and the session of an upload that is not kept
+async fn discard_upload<SR: UploadSessionRepository + ?Sized, FS: FileStorageService + ?Sized>(
+    upload_session_repository: &SR,
+    storage_service: &FS,
+    session: &UploadSession,
+) {
+    if let Err(e) = storage_service.delete_file(&session.file_path).await {
+        tracing::warn!(
+            "Failed to delete {} of a rejected upload: {}",
+            session.file_path,
+            e
+        );
+    }
+    if let Err(e) = upload_session_repository
+        .delete_upload_session(session.id)
+        .await
+    {
+        tracing::warn!(
+            "Failed to delete rejected upload session {}: {}",
+            session.id,
+            e
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+
+use crate::media::domain::{
+    ByteRange, FileStorageError, FileStorageService, METADATA_MAX_READ_BYTES, MediaId,
+    MediaMetadataService, MediaRepository, MediaStatus, ThumbnailError, ThumbnailService,
+};
+
+#[derive(Debug)]
+pub struct GenerateThumbnailCommand {
+    pub media_id: MediaId,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GenerateThumbnailError {
+    #[error("Media file not found")]
+    MediaFileNotFound,
+    #[error("Storage error: {0}")]
+    StorageError(String),
+    #[error(transparent)]
+    ThumbnailError(#[from] ThumbnailError),
+    #[error("Internal server error: {0}")]
+    InternalServerError(String),
+}
+
+/// Extracts the metadata and generates the renditions of an uploaded media file. Metadata is
+/// best effort, only failing to generate the renditions is an error. The original is streamed,
+/// only the start of it is read in memory for the metadata.
+pub async fn generate_thumbnail_command_handler<
+    MR: MediaRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+    TS: ThumbnailService + ?Sized,
+    MS: MediaMetadataService + ?Sized,
+>(
+    command: GenerateThumbnailCommand,
+    media_repository: &MR,
+    storage_service: &FS,
+    thumbnail_service: &TS,
+    metadata_service: &MS,
+) -> Result<(), GenerateThumbnailError> {
+    let media_file = media_repository
+        .get_media_file_by_id(command.media_id)
+        .await
+        .map_err(|e| GenerateThumbnailError::InternalServerError(e.to_string()))?
+        .filter(|media_file| media_file.status == MediaStatus::Ready)
+        .ok_or(GenerateThumbnailError::MediaFileNotFound)?;
+
+    let storage_error = |e: FileStorageError| match e {
+        FileStorageError::NotFound => GenerateThumbnailError::MediaFileNotFound,
+        e => GenerateThumbnailError::StorageError(e.to_string()),
+    };
+
+    let metadata_length = (media_file.file_size.max(0) as u64).min(METADATA_MAX_READ_BYTES);
+    if metadata_length > 0 {
+        let metadata_stream = storage_service
+            .get_file_range_stream(
+                &media_file.file_path,
+                ByteRange {
+                    start: 0,
+                    end: metadata_length - 1,
+                },
+            )
+            .await
+            .map_err(storage_error)?;
+        let chunks = metadata_stream
+            .try_collect::<Vec<Bytes>>()
+            .await
+            .map_err(|e| GenerateThumbnailError::StorageError(e.to_string()))?;
+
+        if let Err(e) = metadata_service
+            .extract_metadata(media_file.id, &chunks.concat(), &media_file.content_type)
+            .await
+        {
+            tracing::warn!(
+                "Failed to extract metadata for media {}: {}",
+                media_file.id,
+                e
+            );
+        }
+    }
+
+    let file_stream = storage_service
+        .get_file_stream(&media_file.file_path)
+        .await
+        .map_err(storage_error)?;
+
+    thumbnail_service
+        .generate_renditions(
+            media_file.id,
+            &media_file.file_path,
+            file_stream,
+            &media_file.content_type,
+        )
+        .await?;
+
+    Ok(())
+}
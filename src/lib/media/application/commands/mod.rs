@@ -1,5 +1,25 @@
+pub mod abort_upload_session;
+pub mod cleanup_expired_pending_uploads;
+pub mod cleanup_expired_upload_sessions;
+pub mod cleanup_stale_multipart_uploads;
+pub mod confirm_upload;
+pub mod create_upload_session;
 pub mod delete_media;
+pub mod finalize_upload_session;
+pub mod generate_thumbnail;
+pub mod request_upload;
+pub mod upload_chunk;
 pub mod upload_media;
 
+pub use abort_upload_session::*;
+pub use cleanup_expired_pending_uploads::*;
+pub use cleanup_expired_upload_sessions::*;
+pub use cleanup_stale_multipart_uploads::*;
+pub use confirm_upload::*;
+pub use create_upload_session::*;
 pub use delete_media::*;
+pub use finalize_upload_session::*;
+pub use generate_thumbnail::*;
+pub use request_upload::*;
+pub use upload_chunk::*;
 pub use upload_media::*;
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    media::{
+        application::commands::upload_media::remaining_quota_bytes,
+        domain::{
+            FileStorageService, MediaRepository, MediaSizeLimits, MediaStatus, MediaTypeAllowlist,
+            MediaUploadError, NewMediaFile, PRESIGNED_UPLOAD_TTL_SECONDS, StorageUsage,
+        },
+    },
+    users::domain::{QuotaConfig, UserRepository},
+};
+
+pub struct RequestUploadCommand {
+    pub user_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub file_size: u64,
+    pub content_type: String,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct RequestUploadResult {
+    /// ID of the pending media file, used to confirm the upload
+    pub media_id: Uuid,
+    /// Presigned URL the file has to be sent to
+    pub upload_url: String,
+    /// HTTP method to use with the upload URL
+    pub method: String,
+    /// Content type the upload has to be sent with, it is part of the signature
+    pub content_type: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+pub async fn request_upload_command_handler<
+    MR: MediaRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+    UR: UserRepository + ?Sized,
+>(
+    media_repository: &MR,
+    storage_service: &FS,
+    user_repository: &UR,
+    media_type_allowlist: &MediaTypeAllowlist,
+    media_size_limits: &MediaSizeLimits,
+    quota_config: &QuotaConfig,
+    command: RequestUploadCommand,
+) -> Result<RequestUploadResult, MediaUploadError> {
+    if !media_type_allowlist.is_allowed(&command.content_type) {
+        return Err(MediaUploadError::InvalidFileType);
+    }
+
+    // The presigned URL is bound to the declared size, so checking it is enough
+    let max_bytes = media_size_limits.max_bytes_for(&command.content_type);
+    if command.file_size > max_bytes {
+        return Err(MediaUploadError::FileTooLarge { max_bytes });
+    }
+
+    // The pending media file counts towards the usage, so it reserves its size until confirmed
+    let remaining_bytes = remaining_quota_bytes(
+        media_repository,
+        user_repository,
+        quota_config,
+        command.user_id,
+        StorageUsage::default(),
+    )
+    .await?;
+    if command.file_size > remaining_bytes {
+        return Err(MediaUploadError::QuotaExceeded);
+    }
+
+    let file_path = format!("media/{}/{}", command.user_id, command.filename);
+    let expires_in = Duration::from_secs(PRESIGNED_UPLOAD_TTL_SECONDS);
+
+    // Signing happens locally, so it is done before anything is stored
+    let presigned_upload = storage_service
+        .create_presigned_upload(
+            &file_path,
+            &command.content_type,
+            command.file_size,
+            expires_in,
+        )
+        .await
+        .map_err(|e| {
+            MediaUploadError::StorageError(format!(
+                "An error occurred while presigning the upload: {}",
+                e
+            ))
+        })?;
+
+    let new_media_file = NewMediaFile {
+        user_id: command.user_id,
+        filename: command.filename,
+        original_filename: command.original_filename,
+        file_size: command.file_size as i64,
+        content_type: command.content_type.clone(),
+        file_path,
+        status: MediaStatus::Pending,
+        checksum: None,
+        detected_content_type: None,
+    };
+
+    let created_media = media_repository
+        .create_media_file(new_media_file)
+        .await
+        .map_err(|e| MediaUploadError::InternalServerError(e.to_string()))?;
+
+    Ok(RequestUploadResult {
+        media_id: created_media.id,
+        upload_url: presigned_upload.url,
+        method: presigned_upload.method,
+        content_type: command.content_type,
+        expires_at: (chrono::Utc::now()
+            + chrono::Duration::seconds(PRESIGNED_UPLOAD_TTL_SECONDS as i64))
+        .naive_utc(),
+    })
+}
@@ -0,0 +1,78 @@
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::media::{
+    application::commands::create_upload_session::UploadSessionResult,
+    domain::{
+        FileStorageService, UPLOAD_SESSION_CHUNK_SIZE, UploadSessionError, UploadSessionId,
+        UploadSessionRepository, UploadSessionRepositoryError,
+    },
+};
+
+pub struct UploadChunkCommand {
+    pub upload_session_id: UploadSessionId,
+    pub user_id: Uuid,
+    /// Offset the client believes the chunk starts at
+    pub offset: u64,
+    pub data: Bytes,
+}
+
+pub async fn upload_chunk_command_handler<
+    SR: UploadSessionRepository + ?Sized,
+    FS: FileStorageService + ?Sized,
+>(
+    upload_session_repository: &SR,
+    storage_service: &FS,
+    command: UploadChunkCommand,
+) -> Result<UploadSessionResult, UploadSessionError> {
+    let session = upload_session_repository
+        .get_upload_session_by_id(command.upload_session_id)
+        .await
+        .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?
+        .filter(|session| session.user_id == command.user_id && !session.is_expired())
+        .ok_or(UploadSessionError::NotFound)?;
+
+    let current_offset = session.upload_offset as u64;
+    if command.offset != current_offset {
+        return Err(UploadSessionError::OffsetMismatch { current_offset });
+    }
+
+    // Every chunk is one multipart part, so all but the last one need the same size
+    let remaining = session.file_size as u64 - current_offset;
+    let chunk_size = command.data.len() as u64;
+    let expected_size = remaining.min(UPLOAD_SESSION_CHUNK_SIZE);
+    if chunk_size != expected_size {
+        return Err(UploadSessionError::InvalidChunkSize);
+    }
+
+    let part_number = session.part_etags.len() as i32 + 1;
+    let e_tag = storage_service
+        .upload_part(
+            &session.file_path,
+            &session.storage_upload_id,
+            part_number,
+            command.data,
+        )
+        .await
+        .map_err(|e| {
+            UploadSessionError::StorageError(format!(
+                "An error occurred while uploading part {}: {}",
+                part_number, e
+            ))
+        })?;
+
+    let updated_session = upload_session_repository
+        .append_uploaded_part(session.id, session.upload_offset, chunk_size as i64, e_tag)
+        .await
+        .map_err(|e| match e {
+            UploadSessionRepositoryError::UploadSessionNotFound => UploadSessionError::NotFound,
+            UploadSessionRepositoryError::Conflict => UploadSessionError::OffsetMismatch {
+                current_offset: current_offset + chunk_size,
+            },
+            UploadSessionRepositoryError::InternalServerError => {
+                UploadSessionError::InternalServerError("Database error".to_string())
+            }
+        })?;
+
+    Ok(updated_session.into())
+}
@@ -1,23 +1,37 @@
-use std::pin::Pin;
+use std::{
+    pin::Pin,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 use bytes::Bytes;
 use futures_core::Stream;
+use futures_util::{StreamExt, TryStreamExt};
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::media::domain::{
-    FileStorageService, MediaFile, MediaRepository, MediaRepositoryError, MediaUploadError,
-    NewMediaFile,
+use crate::{
+    media::domain::{
+        ByteRange, CONTENT_SNIFF_BYTES, FileStorageError, FileStorageService, MediaFile,
+        MediaRepository, MediaRepositoryError, MediaSizeLimits, MediaStatus, MediaTypeAllowlist,
+        MediaUploadError, NewMediaFile, StorageUsage, content_type_matches, sniff_content_type,
+    },
+    users::domain::{QuotaConfig, UserRepository},
 };
 
+type FileDataStream = Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync>>;
+
 pub struct UploadMediaCommand {
     pub user_id: Uuid,
     pub filename: String,
     pub original_filename: String,
     pub file_size: Option<u64>,
     pub content_type: String,
-    pub file_data: Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync>>,
+    pub file_data: FileDataStream,
 }
 
 #[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
@@ -27,31 +41,90 @@ pub struct UploadMediaResult {
     pub original_filename: String,
     pub file_size: i64,
     pub content_type: String,
+    pub checksum: Option<String>,
+    /// Content type recognized from the content, the declared one is kept as `content_type`
+    pub detected_content_type: Option<String>,
+    /// The user already had a file with the same content, the existing one is returned
+    pub duplicate: bool,
     pub uploaded_at: Option<chrono::NaiveDateTime>,
 }
 
 pub async fn upload_media_command_handler<
     MR: MediaRepository + ?Sized,
     FS: FileStorageService + ?Sized,
+    UR: UserRepository + ?Sized,
 >(
     media_repository: &MR,
     storage_service: &FS,
+    user_repository: &UR,
+    media_type_allowlist: &MediaTypeAllowlist,
+    media_size_limits: &MediaSizeLimits,
+    quota_config: &QuotaConfig,
     command: UploadMediaCommand,
 ) -> Result<UploadMediaResult, MediaUploadError> {
-    // Validate file type (only allow images and videos)
-    if !is_valid_media_type(&command.content_type) {
+    // Validate file type (only allow configured images and videos)
+    if !media_type_allowlist.is_allowed(&command.content_type) {
         return Err(MediaUploadError::InvalidFileType);
     }
 
-    // Check file size (max 100MB)
-    //const MAX_FILE_SIZE: usize = 100 * 1024 * 1024;
-    //if command.file_data.len() > MAX_FILE_SIZE {
-    //    return Err(MediaUploadError::FileTooLarge);
-    //}
+    // The declared content type is not trusted, the content has to start like one
+    let (head, file_data) = read_head(command.file_data, CONTENT_SNIFF_BYTES)
+        .await
+        .map_err(|e| {
+            MediaUploadError::StorageError(format!("Failed to read the uploaded file: {}", e))
+        })?;
+    let detected_content_type = sniff_content_type(&head);
+    if !content_type_matches(&command.content_type, detected_content_type) {
+        return Err(MediaUploadError::ContentTypeMismatch {
+            claimed: command.content_type,
+            detected: detected_content_type.map(str::to_string),
+        });
+    }
+
+    // The declared size is checked up-front, the streamed bytes are counted as clients may lie
+    let max_file_bytes = media_size_limits.max_bytes_for(&command.content_type);
+    if command
+        .file_size
+        .is_some_and(|file_size| file_size > max_file_bytes)
+    {
+        return Err(MediaUploadError::FileTooLarge {
+            max_bytes: max_file_bytes,
+        });
+    }
+    let remaining_bytes = remaining_quota_bytes(
+        media_repository,
+        user_repository,
+        quota_config,
+        command.user_id,
+        StorageUsage::default(),
+    )
+    .await?;
+    if command
+        .file_size
+        .is_some_and(|file_size| file_size > remaining_bytes)
+    {
+        return Err(MediaUploadError::QuotaExceeded);
+    }
+    let limiter = Arc::new(UploadLimiter {
+        max_file_bytes,
+        declared_bytes: command.file_size,
+        remaining_quota_bytes: remaining_bytes,
+        received_bytes: AtomicU64::new(0),
+        exceeded: OnceLock::new(),
+    });
+    let file_data = limiter.clone().limit(file_data);
 
     // Generate unique file path
     let file_path = format!("media/{}/{}", command.user_id, command.filename);
-    //let file_size = command.file_data.len() as i64;
+
+    // Hash the content while it is streamed to the storage
+    let hasher = Arc::new(Mutex::new(Sha256::new()));
+    let stream_hasher = hasher.clone();
+    let file_data = Box::pin(file_data.inspect_ok(move |chunk| {
+        if let Ok(mut hasher) = stream_hasher.lock() {
+            hasher.update(chunk);
+        }
+    }));
 
     // Store file in MinIO
     let upload_result = storage_service
@@ -59,16 +132,53 @@ pub async fn upload_media_command_handler<
             &file_path,
             &command.content_type,
             command.file_size,
-            command.file_data,
+            file_data,
         )
         .await
-        .map_err(|e| {
-            MediaUploadError::StorageError(format!(
+        .map_err(|e| match limiter.exceeded.get() {
+            Some(error) => error.clone(),
+            None => MediaUploadError::StorageError(format!(
                 "An error occurred while uploading media file: {}",
                 e
-            ))
+            )),
         })?;
 
+    // A shorter body is only noticed once the stream ended
+    if let Some(declared) = command.file_size
+        && declared != upload_result.file_size
+    {
+        discard_stored_file(storage_service, &file_path).await;
+        return Err(MediaUploadError::FileSizeMismatch {
+            declared,
+            received: upload_result.file_size,
+        });
+    }
+
+    let checksum = hasher
+        .lock()
+        .map(|hasher| hex::encode(hasher.clone().finalize()))
+        .map_err(|_| MediaUploadError::InternalServerError("Checksum poisoned".to_string()))?;
+
+    let existing_media =
+        match existing_media_by_checksum(media_repository, command.user_id, &checksum).await {
+            Ok(existing_media) => existing_media,
+            Err(e) => {
+                discard_stored_file(storage_service, &file_path).await;
+                return Err(MediaUploadError::InternalServerError(e.to_string()));
+            }
+        };
+
+    if let Some(existing_media) = existing_media {
+        // The content is already stored under the existing media file
+        if let Err(e) = storage_service.delete_file(&file_path).await {
+            tracing::warn!("Failed to delete duplicate upload {}: {}", file_path, e);
+        }
+        return Ok(UploadMediaResult {
+            duplicate: true,
+            ..existing_media.into()
+        });
+    }
+
     // Create media file record in database
     let new_media_file = NewMediaFile {
         user_id: command.user_id,
@@ -76,27 +186,201 @@ pub async fn upload_media_command_handler<
         original_filename: command.original_filename,
         file_size: upload_result.file_size as i64,
         content_type: command.content_type,
-        file_path,
-        thumbnail_path: None,
+        file_path: file_path.clone(),
+        status: MediaStatus::Ready,
+        checksum: Some(checksum.clone()),
+        detected_content_type: detected_content_type.map(str::to_string),
     };
 
-    let created_media = media_repository
-        .create_media_file(new_media_file)
-        .await
-        .map_err(|e| match e {
-            MediaRepositoryError::InternalServerError => {
-                MediaUploadError::InternalServerError("Database error".to_string())
-            }
-            MediaRepositoryError::MediaFileNotFound => {
-                MediaUploadError::InternalServerError("Unexpected error".to_string())
-            }
-        })?;
+    let created_media = match media_repository.create_media_file(new_media_file).await {
+        Ok(created_media) => created_media,
+        Err(e) => {
+            // Without its record nothing refers to the stored file
+            discard_stored_file(storage_service, &file_path).await;
+            return match e {
+                MediaRepositoryError::DuplicateChecksum => {
+                    // A concurrent upload of the same content was stored first
+                    existing_media_by_checksum(media_repository, command.user_id, &checksum)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|existing_media| UploadMediaResult {
+                            duplicate: true,
+                            ..existing_media.into()
+                        })
+                        .ok_or_else(|| {
+                            MediaUploadError::InternalServerError("Database error".to_string())
+                        })
+                }
+                MediaRepositoryError::InternalServerError => Err(
+                    MediaUploadError::InternalServerError("Database error".to_string()),
+                ),
+                MediaRepositoryError::MediaFileNotFound => Err(
+                    MediaUploadError::InternalServerError("Unexpected error".to_string()),
+                ),
+            };
+        }
+    };
 
     Ok(created_media.into())
 }
 
-fn is_valid_media_type(content_type: &str) -> bool {
-    content_type.starts_with("image/") || content_type.starts_with("video/")
+/// Removes a stored file the upload failed after, nothing would refer to it otherwise
+async fn discard_stored_file<FS: FileStorageService + ?Sized>(
+    storage_service: &FS,
+    file_path: &str,
+) {
+    if let Err(e) = storage_service.delete_file(file_path).await {
+        tracing::warn!("Failed to delete {} of a failed upload: {}", file_path, e);
+    }
+}
+
+/// Counts the bytes of an upload while they are streamed and fails the stream as soon as one
+/// of the limits is passed, which makes the storage abort the upload
+struct UploadLimiter {
+    max_file_bytes: u64,
+    declared_bytes: Option<u64>,
+    remaining_quota_bytes: u64,
+    received_bytes: AtomicU64,
+    /// Error of the first limit passed, the storage only sees an I/O error
+    exceeded: OnceLock<MediaUploadError>,
+}
+
+impl UploadLimiter {
+    fn limit(self: Arc<Self>, file_data: FileDataStream) -> FileDataStream {
+        Box::pin(file_data.map(move |chunk| {
+            let chunk = chunk?;
+            let received = self
+                .received_bytes
+                .fetch_add(chunk.len() as u64, Ordering::Relaxed)
+                + chunk.len() as u64;
+            match self.check(received) {
+                Some(error) => {
+                    let message = error.to_string();
+                    let _ = self.exceeded.set(error);
+                    Err(std::io::Error::other(message))
+                }
+                None => Ok(chunk),
+            }
+        }))
+    }
+
+    fn check(&self, received: u64) -> Option<MediaUploadError> {
+        if received > self.max_file_bytes {
+            return Some(MediaUploadError::FileTooLarge {
+                max_bytes: self.max_file_bytes,
+            });
+        }
+        if let Some(declared) = self.declared_bytes
+            && received > declared
+        {
+            return Some(MediaUploadError::FileSizeMismatch { declared, received });
+        }
+        if received > self.remaining_quota_bytes {
+            return Some(MediaUploadError::QuotaExceeded);
+        }
+        None
+    }
+}
+
+/// Bytes the user may still store, fails when they have no room for another media file.
+/// `counted` is what the file being checked already adds to the usage, for pending media files.
+pub async fn remaining_quota_bytes<MR: MediaRepository + ?Sized, UR: UserRepository + ?Sized>(
+    media_repository: &MR,
+    user_repository: &UR,
+    quota_config: &QuotaConfig,
+    user_id: Uuid,
+    counted: StorageUsage,
+) -> Result<u64, MediaUploadError> {
+    let user = user_repository
+        .get_by_id(user_id)
+        .await
+        .map_err(|e| MediaUploadError::InternalServerError(e.to_string()))?
+        .ok_or_else(|| MediaUploadError::InternalServerError("User not found".to_string()))?;
+    let quota = quota_config.quota_for(&user);
+    let usage = media_repository
+        .get_storage_usage(user_id)
+        .await
+        .map_err(|e| MediaUploadError::InternalServerError(e.to_string()))?;
+    let usage = StorageUsage {
+        bytes: usage.bytes - counted.bytes,
+        items: usage.items - counted.items,
+    };
+
+    if usage.items >= quota.max_items || usage.bytes >= quota.max_bytes {
+        return Err(MediaUploadError::QuotaExceeded);
+    }
+    Ok((quota.max_bytes - usage.bytes) as u64)
+}
+
+/// Recognizes the content type of a stored file from its first bytes, for uploads that were
+/// not streamed through the server
+pub async fn sniff_stored_content_type<FS: FileStorageService + ?Sized>(
+    storage_service: &FS,
+    file_path: &str,
+    file_size: u64,
+) -> Result<Option<&'static str>, FileStorageError> {
+    let head_length = file_size.min(CONTENT_SNIFF_BYTES as u64);
+    if head_length == 0 {
+        return Ok(None);
+    }
+    let chunks = storage_service
+        .get_file_range_stream(
+            file_path,
+            ByteRange {
+                start: 0,
+                end: head_length - 1,
+            },
+        )
+        .await?
+        .try_collect::<Vec<Bytes>>()
+        .await?;
+    Ok(sniff_content_type(&chunks.concat()))
+}
+
+/// SHA-256 of a stored file, for uploads that were not streamed through the server in one piece
+pub async fn stored_file_checksum<FS: FileStorageService + ?Sized>(
+    storage_service: &FS,
+    file_path: &str,
+) -> Result<String, FileStorageError> {
+    let mut hasher = Sha256::new();
+    let mut file_data = storage_service.get_file_stream(file_path).await?;
+    while let Some(chunk) = file_data.try_next().await? {
+        hasher.update(&chunk);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Ready media file of the user already holding the content with the given checksum
+pub async fn existing_media_by_checksum<MR: MediaRepository + ?Sized>(
+    media_repository: &MR,
+    user_id: Uuid,
+    checksum: &str,
+) -> Result<Option<MediaFile>, MediaRepositoryError> {
+    Ok(media_repository
+        .get_media_files_by_checksums(user_id, vec![checksum.to_string()])
+        .await?
+        .into_iter()
+        .next())
+}
+
+/// Reads chunks until `size` bytes are buffered or the stream ends, returns them along with a
+/// stream that yields the whole content again
+async fn read_head(
+    mut file_data: FileDataStream,
+    size: usize,
+) -> Result<(Vec<u8>, FileDataStream), std::io::Error> {
+    let mut head = Vec::with_capacity(size);
+    let mut chunks = Vec::new();
+    while head.len() < size {
+        let Some(chunk) = file_data.try_next().await? else {
+            break;
+        };
+        head.extend_from_slice(&chunk[..chunk.len().min(size - head.len())]);
+        chunks.push(Ok(chunk));
+    }
+    let file_data = Box::pin(futures_util::stream::iter(chunks).chain(file_data));
+    Ok((head, file_data))
 }
 
 impl From<MediaFile> for UploadMediaResult {
@@ -107,6 +391,9 @@ impl From<MediaFile> for UploadMediaResult {
             original_filename: media_file.original_filename,
             file_size: media_file.file_size,
             content_type: media_file.content_type,
+            checksum: media_file.checksum,
+            detected_content_type: media_file.detected_content_type,
+            duplicate: false,
             uploaded_at: media_file.uploaded_at,
         }
     }
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::media::domain::{MediaRepository, MediaRepositoryError};
+
+pub struct CheckMediaChecksumsQuery {
+    pub user_id: Uuid,
+    /// Hex encoded SHA-256 checksums of the files the client wants to upload
+    pub checksums: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct ExistingMediaChecksumResult {
+    pub checksum: String,
+    pub media_id: Uuid,
+}
+
+pub async fn check_media_checksums_query_handler<MR: MediaRepository + ?Sized>(
+    query: CheckMediaChecksumsQuery,
+    media_repository: &MR,
+) -> Result<Vec<ExistingMediaChecksumResult>, MediaRepositoryError> {
+    // Stored checksums are lowercase hex
+    let mut checksums: Vec<String> = query
+        .checksums
+        .into_iter()
+        .map(|checksum| checksum.to_lowercase())
+        .collect();
+    checksums.sort();
+    checksums.dedup();
+
+    let mut existing: Vec<ExistingMediaChecksumResult> = media_repository
+        .get_media_files_by_checksums(query.user_id, checksums)
+        .await?
+        .into_iter()
+        .filter_map(|media_file| {
+            media_file
+                .checksum
+                .map(|checksum| ExistingMediaChecksumResult {
+                    checksum,
+                    media_id: media_file.id,
+                })
+        })
+        .collect();
+
+    // Report every checksum once, even if the user already has several copies of it
+    let mut seen = HashSet::new();
+    existing.retain(|result| seen.insert(result.checksum.clone()));
+    Ok(existing)
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    media::{
+        application::queries::get_media_files::{MediaMetadataResult, group_rendition_names},
+        domain::{MediaId, MediaRepository, MediaRepositoryError, MediaStatus},
+    },
+    sharing::domain::{AuthorizationService, Permission},
+};
+
+pub struct GetMediaFileQuery {
+    pub media_id: MediaId,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq)]
+pub struct GetMediaFileResult {
+    pub id: Uuid,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub checksum: Option<String>,
+    pub uploaded_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+    pub metadata: Option<MediaMetadataResult>,
+    /// Names of the generated renditions, from the smallest to the largest
+    pub renditions: Vec<String>,
+}
+
+pub async fn get_media_file_query_handler<
+    MR: MediaRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    query: GetMediaFileQuery,
+    media_repository: &MR,
+    authorization_service: &AS,
+) -> Result<GetMediaFileResult, MediaRepositoryError> {
+    let media_file = media_repository
+        .get_media_file_by_id(query.media_id)
+        .await?
+        .filter(|media_file| media_file.status == MediaStatus::Ready)
+        .ok_or(MediaRepositoryError::MediaFileNotFound)?;
+
+    if !authorization_service
+        .can_access_media(query.user_id, &media_file, Permission::View)
+        .await
+        .map_err(|_| MediaRepositoryError::InternalServerError)?
+    {
+        return Err(MediaRepositoryError::MediaFileNotFound);
+    }
+
+    // Where the photo was taken is only shown to the owner, like downloads through share links
+    let is_owner = media_file.user_id == query.user_id;
+    let metadata = media_repository
+        .get_media_metadata_by_media_ids(vec![media_file.id])
+        .await?
+        .into_iter()
+        .next()
+        .map(|metadata| {
+            if is_owner {
+                metadata
+            } else {
+                metadata.without_location()
+            }
+        });
+    let renditions = media_repository
+        .get_media_renditions_by_media_ids(vec![media_file.id])
+        .await?;
+
+    Ok(GetMediaFileResult {
+        id: media_file.id,
+        original_filename: media_file.original_filename,
+        file_size: media_file.file_size,
+        content_type: media_file.content_type,
+        checksum: media_file.checksum,
+        uploaded_at: media_file.uploaded_at,
+        updated_at: media_file.updated_at,
+        metadata: metadata.map(|metadata| metadata.into()),
+        renditions: group_rendition_names(renditions)
+            .remove(&media_file.id)
+            .unwrap_or_default(),
+    })
+}
@@ -1,32 +1,146 @@
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use serde::Serialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::media::domain::{MediaFile, MediaRepository, MediaRepositoryError};
+use crate::media::domain::{
+    MEDIA_FILES_DEFAULT_PAGE_SIZE, MEDIA_FILES_MAX_PAGE_SIZE, MediaFile, MediaFileCursor,
+    MediaFileFilter, MediaFilePageRequest, MediaFileSort, MediaMetadata, MediaRendition,
+    MediaRepository, MediaRepositoryError,
+};
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Default)]
 pub struct GetMediaFilesQuery {
     pub user_id: Uuid,
+    pub filter: MediaFileFilter,
+    pub sort: MediaFileSort,
+    /// `next_cursor` of the previous page, `None` for the first page
+    pub cursor: Option<String>,
+    /// Defaults to [`MEDIA_FILES_DEFAULT_PAGE_SIZE`], capped at [`MEDIA_FILES_MAX_PAGE_SIZE`]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetMediaFilesError {
+    #[error("Invalid cursor")]
+    InvalidCursor,
+    #[error(transparent)]
+    RepositoryError(#[from] MediaRepositoryError),
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq)]
+pub struct GetMediaFilesPageResult {
+    pub items: Vec<GetMediaFilesResult>,
+    /// Pass it as `cursor` to get the next page, `null` on the last page
+    pub next_cursor: Option<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq)]
 pub struct GetMediaFilesResult {
     pub id: Uuid,
     pub original_filename: String,
     pub file_size: i64,
     pub content_type: String,
     pub uploaded_at: Option<chrono::NaiveDateTime>,
+    /// Missing until the metadata has been extracted, or when the file has none
+    pub metadata: Option<MediaMetadataResult>,
+    /// Names of the generated renditions, from the smallest to the largest, any of them can be
+    /// passed as `size` to the thumbnail endpoint
+    pub renditions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq)]
+pub struct MediaMetadataResult {
+    pub taken_at: Option<chrono::NaiveDateTime>,
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub orientation: Option<i16>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    pub iso: Option<i32>,
+    pub focal_length: Option<f64>,
+    pub duration_seconds: Option<f64>,
+    pub video_codec: Option<String>,
+    pub frame_rate: Option<f64>,
 }
 
 pub async fn get_media_files_query_handler<MR: MediaRepository + ?Sized>(
     query: GetMediaFilesQuery,
     media_repository: &MR,
-) -> Result<Vec<GetMediaFilesResult>, MediaRepositoryError> {
-    let media_files = media_repository
-        .get_media_files_by_user_id(query.user_id)
+) -> Result<GetMediaFilesPageResult, GetMediaFilesError> {
+    let cursor = query
+        .cursor
+        .map(|cursor| MediaFileCursor::decode(&cursor).ok_or(GetMediaFilesError::InvalidCursor))
+        .transpose()?;
+
+    let page = media_repository
+        .get_media_files_by_user_id(
+            query.user_id,
+            MediaFilePageRequest {
+                filter: query.filter,
+                sort: query.sort,
+                cursor,
+                limit: query
+                    .limit
+                    .unwrap_or(MEDIA_FILES_DEFAULT_PAGE_SIZE)
+                    .clamp(1, MEDIA_FILES_MAX_PAGE_SIZE),
+            },
+        )
         .await?;
 
-    Ok(media_files.into_iter().map(|media| media.into()).collect())
+    let media_ids: Vec<Uuid> = page.media_files.iter().map(|media| media.id).collect();
+    // Only the media of the user are listed, so their location is kept
+    let mut metadata: HashMap<Uuid, MediaMetadata> = media_repository
+        .get_media_metadata_by_media_ids(media_ids.clone())
+        .await?
+        .into_iter()
+        .map(|metadata| (metadata.media_id, metadata))
+        .collect();
+    let mut renditions = group_rendition_names(
+        media_repository
+            .get_media_renditions_by_media_ids(media_ids)
+            .await?,
+    );
+
+    Ok(GetMediaFilesPageResult {
+        items: page
+            .media_files
+            .into_iter()
+            .map(|media| {
+                let media_metadata = metadata.remove(&media.id);
+                let media_renditions = renditions.remove(&media.id).unwrap_or_default();
+                GetMediaFilesResult {
+                    metadata: media_metadata.map(|metadata| metadata.into()),
+                    renditions: media_renditions,
+                    ..media.into()
+                }
+            })
+            .collect(),
+        next_cursor: page.next_cursor.map(|cursor| cursor.encode()),
+    })
+}
+
+/// Rendition names per media file, ordered by size
+pub(crate) fn group_rendition_names(
+    mut renditions: Vec<MediaRendition>,
+) -> HashMap<Uuid, Vec<String>> {
+    renditions.sort_by(|a, b| {
+        (a.width.max(a.height), &a.name).cmp(&(b.width.max(b.height), &b.name))
+    });
+    let mut names: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for rendition in renditions {
+        let media_names = names.entry(rendition.media_id).or_default();
+        // Every size is stored once per format
+        if !media_names.contains(&rendition.name) {
+            media_names.push(rendition.name);
+        }
+    }
+    names
 }
 
 impl From<MediaFile> for GetMediaFilesResult {
@@ -37,6 +151,30 @@ impl From<MediaFile> for GetMediaFilesResult {
             file_size: media_file.file_size,
             content_type: media_file.content_type,
             uploaded_at: media_file.uploaded_at,
+            metadata: None,
+            renditions: Vec::new(),
+        }
+    }
+}
+
+impl From<MediaMetadata> for MediaMetadataResult {
+    fn from(metadata: MediaMetadata) -> Self {
+        MediaMetadataResult {
+            taken_at: metadata.taken_at,
+            camera_make: metadata.camera_make,
+            camera_model: metadata.camera_model,
+            orientation: metadata.orientation,
+            width: metadata.width,
+            height: metadata.height,
+            latitude: metadata.latitude,
+            longitude: metadata.longitude,
+            exposure_time: metadata.exposure_time,
+            f_number: metadata.f_number,
+            iso: metadata.iso,
+            focal_length: metadata.focal_length,
+            duration_seconds: metadata.duration_seconds,
+            video_codec: metadata.video_codec,
+            frame_rate: metadata.frame_rate,
         }
     }
 }
@@ -0,0 +1,39 @@
+use uuid::Uuid;
+
+use crate::media::domain::{MediaId, MediaRepository, MediaRepositoryError, MediaUrlSigner};
+
+pub struct GetMediaSignedUrlQuery {
+    pub media_id: MediaId,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetMediaSignedUrlResult {
+    pub media_id: MediaId,
+    pub expires_at: u64,
+    pub signature: String,
+}
+
+pub async fn get_media_signed_url_query_handler(
+    query: GetMediaSignedUrlQuery,
+    media_repository: &dyn MediaRepository,
+    url_signer: &dyn MediaUrlSigner,
+) -> Result<GetMediaSignedUrlResult, MediaRepositoryError> {
+    let media_file = media_repository
+        .get_media_file_by_id(query.media_id)
+        .await?
+        .ok_or(MediaRepositoryError::MediaFileNotFound)?;
+
+    // Only the owner can hand out links to a media file
+    if media_file.user_id != query.user_id {
+        return Err(MediaRepositoryError::MediaFileNotFound);
+    }
+
+    let signed_url = url_signer.sign(media_file.id);
+
+    Ok(GetMediaSignedUrlResult {
+        media_id: signed_url.media_id,
+        expires_at: signed_url.expires_at,
+        signature: signed_url.signature,
+    })
+}
@@ -1,16 +1,54 @@
-use crate::media::{
-    FileStorageError, FileStorageService, FileStream, MediaId, MediaRepository,
-    MediaRepositoryError,
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use uuid::Uuid;
+
+use crate::{
+    media::{
+        ByteRange, ByteRangeError, FileStorageError, FileStorageService, FileStream,
+        METADATA_MAX_READ_BYTES, MediaId, MediaRepository, MediaRepositoryError, MediaStatus,
+        MediaUrlSigner, MetadataStrippingError, location_metadata_ranges, redact_stream,
+    },
+    sharing::domain::{AuthorizationService, Permission},
 };
 
+/// How the requester proved it may read the media file
+pub enum MediaStreamAccess {
+    /// Authenticated user, has to own the media file or have it shared with them
+    User(Uuid),
+    /// Signed URL issued through `get_media_signed_url_query_handler`
+    SignedUrl { expires_at: u64, signature: String },
+}
+
 pub struct GetMediaStreamQuery {
     pub media_id: MediaId,
+    pub access: MediaStreamAccess,
+    /// Raw value of the `Range` header, if the client sent one
+    pub range: Option<String>,
+    /// Raw value of the `If-Range` header, if the client sent one
+    pub if_range: Option<String>,
+    /// Removes the location the photo was taken at from the streamed bytes
+    pub strip_metadata: bool,
+}
+
+pub struct GetMediaStreamResult {
+    pub stream: FileStream,
+    pub content_type: String,
+    pub total_size: u64,
+    pub e_tag: String,
+    /// Range being served, `None` when the whole file is returned
+    pub range: Option<ByteRange>,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum GetMediaStreamError {
     #[error("Media not found")]
     NotFound,
+    #[error("Invalid or expired media URL")]
+    InvalidSignature,
+    #[error("Range not satisfiable")]
+    RangeNotSatisfiable { total_size: u64 },
+    #[error("{0}")]
+    MetadataNotStrippable(MetadataStrippingError),
     #[error("Internal server error: {0}")]
     InternalError(String),
 }
@@ -33,17 +71,107 @@ pub async fn get_media_stream_query_handler(
     query: GetMediaStreamQuery,
     media_storage: &dyn FileStorageService,
     media_repo: &dyn MediaRepository,
-) -> Result<FileStream, GetMediaStreamError> {
-    let media_path = media_repo
-        .get_media_path_by_id(query.media_id)
+    url_signer: &dyn MediaUrlSigner,
+    authorization_service: &dyn AuthorizationService,
+) -> Result<GetMediaStreamResult, GetMediaStreamError> {
+    if let MediaStreamAccess::SignedUrl {
+        expires_at,
+        signature,
+    } = &query.access
+        && !url_signer.verify(query.media_id, *expires_at, signature)
+    {
+        return Err(GetMediaStreamError::InvalidSignature);
+    }
+
+    let media_file = media_repo
+        .get_media_file_by_id(query.media_id)
         .await
         .map_err(|e| match e {
             MediaRepositoryError::MediaFileNotFound => GetMediaStreamError::NotFound,
-            _ => GetMediaStreamError::InternalError("Failed to retrieve media path".to_string()),
-        })?;
+            _ => GetMediaStreamError::InternalError("Failed to retrieve media file".to_string()),
+        })?
+        .filter(|media_file| media_file.status == MediaStatus::Ready)
+        .ok_or(GetMediaStreamError::NotFound)?;
 
-    media_storage
-        .get_file_stream(&media_path)
-        .await
-        .map_err(|e| e.into())
+    // Do not reveal whether media owned by someone else exists
+    if let MediaStreamAccess::User(user_id) = query.access
+        && !authorization_service
+            .can_access_media(user_id, &media_file, Permission::View)
+            .await
+            .map_err(|e| GetMediaStreamError::InternalError(e.to_string()))?
+    {
+        return Err(GetMediaStreamError::NotFound);
+    }
+
+    let total_size = media_file.file_size.max(0) as u64;
+    // Stored objects are never modified in place, so the media id is a strong validator
+    let e_tag = match query.strip_metadata {
+        true => format!("\"{}-stripped\"", media_file.id),
+        false => format!("\"{}\"", media_file.id),
+    };
+
+    // A stale If-Range validator means the client must receive the whole file again
+    let range_header = match &query.if_range {
+        Some(if_range) if if_range.trim() != e_tag => None,
+        _ => query.range.as_deref(),
+    };
+
+    let range = match range_header.map(|header| ByteRange::parse(header, total_size)) {
+        Some(Ok(range)) => range,
+        Some(Err(ByteRangeError::NotSatisfiable)) => {
+            return Err(GetMediaStreamError::RangeNotSatisfiable { total_size });
+        }
+        None => None,
+    };
+
+    // Located before streaming starts, so a file that cannot be stripped is never sent
+    let location_ranges = match query.strip_metadata {
+        true => Some(location_metadata(media_storage, &media_file.file_path, total_size).await?),
+        false => None,
+    };
+
+    let stream = match range {
+        Some(range) => {
+            media_storage
+                .get_file_range_stream(&media_file.file_path, range)
+                .await?
+        }
+        None => media_storage.get_file_stream(&media_file.file_path).await?,
+    };
+    let stream = match location_ranges {
+        Some(ranges) => redact_stream(stream, range.map_or(0, |range| range.start), ranges),
+        None => stream,
+    };
+
+    Ok(GetMediaStreamResult {
+        stream,
+        content_type: media_file.content_type,
+        total_size,
+        e_tag,
+        range,
+    })
+}
+
+/// Reads the headers of a file and finds the bytes holding its location
+async fn location_metadata(
+    media_storage: &dyn FileStorageService,
+    file_path: &str,
+    total_size: u64,
+) -> Result<Vec<ByteRange>, GetMediaStreamError> {
+    if total_size == 0 {
+        return Err(GetMediaStreamError::MetadataNotStrippable(
+            MetadataStrippingError::UnsupportedFormat,
+        ));
+    }
+    let head_range = ByteRange {
+        start: 0,
+        end: total_size.min(METADATA_MAX_READ_BYTES) - 1,
+    };
+    let head: Vec<Bytes> = media_storage
+        .get_file_range_stream(file_path, head_range)
+        .await?
+        .try_collect()
+        .await?;
+
+    location_metadata_ranges(&head.concat()).map_err(GetMediaStreamError::MetadataNotStrippable)
 }
@@ -0,0 +1,130 @@
+use crate::{
+    media::{
+        DEFAULT_RENDITION_NAME, FileStorageError, FileStorageService, FileStream, MediaId,
+        MediaRepository, MediaRepositoryError, MediaStatus, MediaStreamAccess, MediaUrlSigner,
+        RenditionFormat,
+    },
+    sharing::domain::{AuthorizationService, Permission},
+};
+
+pub struct GetMediaThumbnailQuery {
+    pub media_id: MediaId,
+    pub access: MediaStreamAccess,
+    /// Rendition name, defaults to [`DEFAULT_RENDITION_NAME`]
+    pub size: Option<String>,
+    /// Raw value of the `Accept` header, picks the format of the rendition
+    pub accept: Option<String>,
+}
+
+pub struct GetMediaThumbnailResult {
+    pub stream: FileStream,
+    pub content_type: String,
+    /// Unknown for renditions generated before their size was tracked
+    pub content_length: Option<u64>,
+    pub e_tag: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GetMediaThumbnailError {
+    #[error("Media not found")]
+    NotFound,
+    #[error("Thumbnail not found")]
+    ThumbnailNotFound,
+    #[error("Invalid or expired media URL")]
+    InvalidSignature,
+    #[error("Internal server error: {0}")]
+    InternalError(String),
+}
+
+pub async fn get_media_thumbnail_query_handler<
+    FS: FileStorageService + ?Sized,
+    MR: MediaRepository + ?Sized,
+    US: MediaUrlSigner + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    query: GetMediaThumbnailQuery,
+    media_storage: &FS,
+    media_repo: &MR,
+    url_signer: &US,
+    authorization_service: &AS,
+) -> Result<GetMediaThumbnailResult, GetMediaThumbnailError> {
+    if let MediaStreamAccess::SignedUrl {
+        expires_at,
+        signature,
+    } = &query.access
+        && !url_signer.verify(query.media_id, *expires_at, signature)
+    {
+        return Err(GetMediaThumbnailError::InvalidSignature);
+    }
+
+    let media_file = media_repo
+        .get_media_file_by_id(query.media_id)
+        .await
+        .map_err(|e| match e {
+            MediaRepositoryError::MediaFileNotFound => GetMediaThumbnailError::NotFound,
+            _ => GetMediaThumbnailError::InternalError("Failed to retrieve media file".to_string()),
+        })?
+        .filter(|media_file| media_file.status == MediaStatus::Ready)
+        .ok_or(GetMediaThumbnailError::NotFound)?;
+
+    // Do not reveal whether media owned by someone else exists
+    if let MediaStreamAccess::User(user_id) = query.access
+        && !authorization_service
+            .can_access_media(user_id, &media_file, Permission::View)
+            .await
+            .map_err(|e| GetMediaThumbnailError::InternalError(e.to_string()))?
+    {
+        return Err(GetMediaThumbnailError::NotFound);
+    }
+
+    let size = query.size.as_deref().unwrap_or(DEFAULT_RENDITION_NAME);
+    // Renditions are generated in the background, they may not exist yet
+    let renditions: Vec<_> = media_repo
+        .get_media_renditions_by_media_ids(vec![media_file.id])
+        .await
+        .map_err(|_| {
+            GetMediaThumbnailError::InternalError("Failed to retrieve renditions".to_string())
+        })?
+        .into_iter()
+        .filter(|rendition| rendition.name == size)
+        .collect();
+    let formats: Vec<_> = renditions
+        .iter()
+        .filter_map(|rendition| RenditionFormat::from_content_type(&rendition.content_type))
+        .collect();
+    let format = RenditionFormat::negotiate(query.accept.as_deref(), &formats);
+    let rendition = renditions
+        .into_iter()
+        .find(|rendition| {
+            format.is_none_or(|format| {
+                RenditionFormat::from_content_type(&rendition.content_type) == Some(format)
+            })
+        })
+        .ok_or(GetMediaThumbnailError::ThumbnailNotFound)?;
+
+    let stream = media_storage
+        .get_file_stream(&rendition.file_path)
+        .await
+        .map_err(|e| match e {
+            FileStorageError::NotFound => GetMediaThumbnailError::ThumbnailNotFound,
+            e => GetMediaThumbnailError::InternalError(e.to_string()),
+        })?;
+
+    // Regenerating a rendition at another size changes its dimensions, and so its validator.
+    // Each format of a rendition is a different file and gets its own validator.
+    let e_tag = format!(
+        "\"{}-{}-{}x{}-{}\"",
+        media_file.id,
+        rendition.name,
+        rendition.width.unwrap_or_default(),
+        rendition.height.unwrap_or_default(),
+        format.map_or("jpg", |format| format.extension())
+    );
+
+    Ok(GetMediaThumbnailResult {
+        stream,
+        content_type: rendition.content_type,
+        content_length: rendition.file_size.map(|size| size.max(0) as u64),
+        e_tag,
+    })
+}
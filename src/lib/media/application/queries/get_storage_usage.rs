@@ -0,0 +1,58 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    media::domain::MediaRepository,
+    users::domain::{QuotaConfig, UserRepository},
+};
+
+pub struct GetStorageUsageQuery {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct StorageUsageResult {
+    /// Bytes taken up by the originals of the media files of the user
+    pub used_bytes: i64,
+    pub used_items: i64,
+    pub quota_bytes: i64,
+    pub quota_items: i64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetStorageUsageError {
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
+
+pub async fn get_storage_usage_query_handler<
+    MR: MediaRepository + ?Sized,
+    UR: UserRepository + ?Sized,
+>(
+    query: GetStorageUsageQuery,
+    media_repository: &MR,
+    user_repository: &UR,
+    quota_config: &QuotaConfig,
+) -> Result<StorageUsageResult, GetStorageUsageError> {
+    let user = user_repository
+        .get_by_id(query.user_id)
+        .await
+        .map_err(|e| GetStorageUsageError::InternalServerError(e.to_string()))?
+        .ok_or(GetStorageUsageError::UserNotFound)?;
+    let quota = quota_config.quota_for(&user);
+
+    let usage = media_repository
+        .get_storage_usage(query.user_id)
+        .await
+        .map_err(|e| GetStorageUsageError::InternalServerError(e.to_string()))?;
+
+    Ok(StorageUsageResult {
+        used_bytes: usage.bytes,
+        used_items: usage.items,
+        quota_bytes: quota.max_bytes,
+        quota_items: quota.max_items,
+    })
+}
@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use crate::media::{
+    application::commands::create_upload_session::UploadSessionResult,
+    domain::{UploadSessionError, UploadSessionId, UploadSessionRepository},
+};
+
+pub struct GetUploadSessionQuery {
+    pub upload_session_id: UploadSessionId,
+    pub user_id: Uuid,
+}
+
+pub async fn get_upload_session_query_handler<SR: UploadSessionRepository + ?Sized>(
+    query: GetUploadSessionQuery,
+    upload_session_repository: &SR,
+) -> Result<UploadSessionResult, UploadSessionError> {
+    upload_session_repository
+        .get_upload_session_by_id(query.upload_session_id)
+        .await
+        .map_err(|e| UploadSessionError::InternalServerError(e.to_string()))?
+        .filter(|session| session.user_id == query.user_id && !session.is_expired())
+        .map(|session| session.into())
+        .ok_or(UploadSessionError::NotFound)
+}
@@ -1,5 +1,17 @@
+pub mod check_media_checksums;
+pub mod get_media_file;
 pub mod get_media_files;
+pub mod get_media_signed_url;
 pub mod get_media_stream;
+pub mod get_media_thumbnail;
+pub mod get_storage_usage;
+pub mod get_upload_session;
 
+pub use check_media_checksums::*;
+pub use get_media_file::*;
 pub use get_media_files::*;
+pub use get_media_signed_url::*;
 pub use get_media_stream::*;
+pub use get_media_thumbnail::*;
+pub use get_storage_usage::*;
+pub use get_upload_session::*;
@@ -0,0 +1,74 @@
+/// Inclusive byte range of a stored file, as requested through an HTTP `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ByteRangeError {
+    #[error("Range not satisfiable")]
+    NotSatisfiable,
+}
+
+impl ByteRange {
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Parses a `Range` header value against a file of `total_size` bytes.
+    ///
+    /// Returns `Ok(None)` when the header should be ignored and the whole file served
+    /// (unknown unit, malformed value or multiple ranges), and
+    /// `Err(ByteRangeError::NotSatisfiable)` when the range lies outside the file.
+    pub fn parse(header: &str, total_size: u64) -> Result<Option<ByteRange>, ByteRangeError> {
+        let Some(spec) = header.trim().strip_prefix("bytes=") else {
+            return Ok(None);
+        };
+
+        // Multipart/byteranges responses are not supported, serve the full file instead
+        if spec.contains(',') {
+            return Ok(None);
+        }
+
+        let Some((start, end)) = spec.trim().split_once('-') else {
+            return Ok(None);
+        };
+
+        let range = match (start.trim(), end.trim()) {
+            ("", "") => return Ok(None),
+            // Suffix range: the last N bytes of the file
+            ("", suffix) => {
+                let Ok(suffix) = suffix.parse::<u64>() else {
+                    return Ok(None);
+                };
+                if suffix == 0 || total_size == 0 {
+                    return Err(ByteRangeError::NotSatisfiable);
+                }
+                ByteRange {
+                    start: total_size.saturating_sub(suffix),
+                    end: total_size - 1,
+                }
+            }
+            (start, end) => {
+                let Ok(start) = start.parse::<u64>() else {
+                    return Ok(None);
+                };
+                let end = if end.is_empty() {
+                    total_size.saturating_sub(1)
+                } else {
+                    match end.parse::<u64>() {
+                        Ok(end) if end >= start => end.min(total_size.saturating_sub(1)),
+                        _ => return Ok(None),
+                    }
+                };
+                if start >= total_size {
+                    return Err(ByteRangeError::NotSatisfiable);
+                }
+                ByteRange { start, end }
+            }
+        };
+
+        Ok(Some(range))
+    }
+}
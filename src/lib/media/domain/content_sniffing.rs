@@ -0,0 +1,212 @@
+use std::env;
+
+/// Bytes read from the start of an upload to recognize its content type
+pub const CONTENT_SNIFF_BYTES: usize = 64;
+
+/// Content types accepted when `MEDIA_ALLOWED_CONTENT_TYPES` is not set, all of them are
+/// recognized from their magic bytes
+pub const DEFAULT_ALLOWED_CONTENT_TYPES: [&str; 30] = [
+    "image/jpeg",
+    "image/jpg",
+    "image/pjpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "image/bmp",
+    "image/tiff",
+    "image/heic",
+    "image/heif",
+    "image/avif",
+    "image/x-canon-cr2",
+    "image/x-canon-cr3",
+    "image/x-nikon-nef",
+    "image/x-sony-arw",
+    "image/x-adobe-dng",
+    "image/x-fuji-raf",
+    "image/x-olympus-orf",
+    "image/x-panasonic-rw2",
+    "image/x-pentax-pef",
+    "image/x-dcraw",
+    "video/mp4",
+    "video/x-m4v",
+    "video/quicktime",
+    "video/3gpp",
+    "video/webm",
+    "video/x-matroska",
+    "video/x-msvideo",
+    "video/avi",
+    "video/mpeg",
+];
+
+/// Content types the magic bytes cannot tell apart, content recognized as one of a group may
+/// be uploaded as any other of it. Every type `sniff_content_type` returns is listed.
+const CONTENT_TYPE_GROUPS: [&[&str]; 16] = [
+    &["image/jpeg", "image/jpg", "image/pjpeg"],
+    &["image/png"],
+    &["image/gif"],
+    &["image/webp"],
+    &["image/bmp", "image/x-ms-bmp"],
+    &["image/avif"],
+    &[
+        "image/heic",
+        "image/heif",
+        "image/heic-sequence",
+        "image/heif-sequence",
+    ],
+    // Camera RAW formats built on TIFF, `image/x-dcraw` stands for any RAW format
+    &[
+        "image/tiff",
+        "image/x-canon-cr2",
+        "image/x-nikon-nef",
+        "image/x-sony-arw",
+        "image/x-adobe-dng",
+        "image/x-pentax-pef",
+        "image/x-dcraw",
+    ],
+    &["image/x-canon-cr3", "image/x-dcraw"],
+    &["image/x-fuji-raf", "image/x-dcraw"],
+    &["image/x-olympus-orf", "image/x-dcraw"],
+    &["image/x-panasonic-rw2", "image/x-dcraw"],
+    // ISO base media files, the brand only hints at the application that wrote them
+    &["video/mp4", "video/x-m4v", "video/quicktime", "video/3gpp"],
+    &["video/webm", "video/x-matroska"],
+    &["video/x-msvideo", "video/avi"],
+    &["video/mpeg"],
+];
+
+/// Content types uploads may declare
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaTypeAllowlist {
+    /// Lowercase content types without parameters
+    pub content_types: Vec<String>,
+}
+
+impl MediaTypeAllowlist {
+    /// Reads `MEDIA_ALLOWED_CONTENT_TYPES`, a comma separated list of content types, and falls
+    /// back to the defaults when it is missing or empty
+    pub fn new() -> Self {
+        env::var("MEDIA_ALLOWED_CONTENT_TYPES")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Parses a list like `image/jpeg,video/mp4`, returns `None` when it holds no content type
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut content_types: Vec<String> = Vec::new();
+        for content_type in value
+            .split(',')
+            .map(content_type_essence)
+            .filter(|content_type| !content_type.is_empty())
+        {
+            if !content_types.contains(&content_type) {
+                content_types.push(content_type);
+            }
+        }
+        (!content_types.is_empty()).then_some(Self { content_types })
+    }
+
+    pub fn is_allowed(&self, content_type: &str) -> bool {
+        self.content_types
+            .contains(&content_type_essence(content_type))
+    }
+}
+
+impl Default for MediaTypeAllowlist {
+    fn default() -> Self {
+        Self {
+            content_types: DEFAULT_ALLOWED_CONTENT_TYPES
+                .iter()
+                .map(|content_type| content_type.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Lowercase content type without parameters, e.g. `image/jpeg` for `Image/JPEG; q=1`
+pub(crate) fn content_type_essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Recognizes the content type of a file from its first bytes, at least `CONTENT_SNIFF_BYTES`
+/// of them unless the file is shorter
+pub fn sniff_content_type(head: &[u8]) -> Option<&'static str> {
+    let content_type = match head {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => "image/png",
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', ..] => match head.get(8..12)? {
+            b"WEBP" => "image/webp",
+            b"AVI " => "video/x-msvideo",
+            _ => return None,
+        },
+        // The reserved header fields are zero
+        [b'B', b'M', _, _, _, _, 0, 0, 0, 0, ..] => "image/bmp",
+        [b'I', b'I', 0x2A, 0x00, _, _, _, _, b'C', b'R', ..] => "image/x-canon-cr2",
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => "image/tiff",
+        [b'I', b'I', b'R', b'O' | b'S', ..] | [b'M', b'M', b'O', b'R', ..] => "image/x-olympus-orf",
+        [b'I', b'I', b'U', 0x00, ..] => "image/x-panasonic-rw2",
+        _ if head.starts_with(b"FUJIFILMCCD-RAW") => "image/x-fuji-raf",
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => iso_media_content_type(head)?,
+        // EBML header of Matroska, WebM declares its document type right after
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => match head.windows(4).any(|bytes| bytes == b"webm") {
+            true => "video/webm",
+            false => "video/x-matroska",
+        },
+        [0x00, 0x00, 0x01, 0xBA, ..] => "video/mpeg",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// Content type of an ISO base media file from the brands of its `ftyp` box
+fn iso_media_content_type(head: &[u8]) -> Option<&'static str> {
+    let box_size = u32::from_be_bytes(head.get(..4)?.try_into().ok()?) as usize;
+    let major_brand = head.get(8..12)?;
+    // Compatible brands follow the minor version, as far as the head holds them
+    let compatible_brands: Vec<&[u8]> = head
+        .get(16..box_size.min(head.len()))
+        .unwrap_or_default()
+        .chunks_exact(4)
+        .collect();
+
+    let content_type = match major_brand {
+        b"avif" | b"avis" => "image/avif",
+        b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" => "image/heic",
+        // Generic image brands, the compatible brands name the codec
+        b"mif1" | b"msf1" => {
+            if compatible_brands.contains(&&b"avif"[..]) {
+                "image/avif"
+            } else if compatible_brands.contains(&&b"heic"[..]) {
+                "image/heic"
+            } else {
+                "image/heif"
+            }
+        }
+        b"crx " => "image/x-canon-cr3",
+        b"qt  " => "video/quicktime",
+        b"M4V " | b"M4VH" | b"M4VP" => "video/x-m4v",
+        [b'3', b'g', b'p' | b'2', _] => "video/3gpp",
+        _ => "video/mp4",
+    };
+    Some(content_type)
+}
+
+/// Whether content recognized as `detected` may be stored as `claimed`. Content types the
+/// magic bytes are unknown for are only checked against the allowlist.
+pub fn content_type_matches(claimed: &str, detected: Option<&str>) -> bool {
+    let claimed = content_type_essence(claimed);
+    let mut groups = CONTENT_TYPE_GROUPS
+        .iter()
+        .filter(|group| group.contains(&claimed.as_str()))
+        .peekable();
+    if groups.peek().is_none() {
+        return true;
+    }
+    detected.is_some_and(|detected| groups.any(|group| group.contains(&detected)))
+}
@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use futures_core::Stream;
 
+use super::ByteRange;
+
 #[derive(Debug, thiserror::Error)]
 pub enum FileStorageError {
     #[error("File does not exist")]
@@ -19,6 +21,36 @@ pub struct UploadedFileMetadata {
     pub file_size: u64,
 }
 
+/// Part of a multipart upload that has been stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedPart {
+    pub part_number: i32,
+    pub e_tag: String,
+}
+
+/// URL a client can upload a file to without going through the API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresignedUpload {
+    pub url: String,
+    /// HTTP method the URL was signed for
+    pub method: String,
+}
+
+/// Metadata of an object as reported by the storage service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredFileMetadata {
+    pub file_size: u64,
+    pub content_type: Option<String>,
+}
+
+/// Multipart upload that was started but neither completed nor aborted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompleteMultipartUpload {
+    pub file_path: String,
+    pub upload_id: String,
+    pub initiated_at: Option<chrono::NaiveDateTime>,
+}
+
 pub type FileStream = Pin<Box<dyn Stream<Item = Result<Bytes, FileStorageError>> + Send>>;
 
 #[async_trait]
@@ -35,4 +67,51 @@ pub trait FileStorageService: Send + Sync {
     async fn delete_file(&self, file_path: &str) -> Result<(), FileStorageError>;
     async fn get_file_url(&self, file_path: &str) -> Result<String, FileStorageError>;
     async fn get_file_stream(&self, file_path: &str) -> Result<FileStream, FileStorageError>;
+    async fn get_file_range_stream(
+        &self,
+        file_path: &str,
+        range: ByteRange,
+    ) -> Result<FileStream, FileStorageError>;
+    /// Starts a multipart upload whose parts are sent separately, returns its upload id
+    async fn create_multipart_upload(
+        &self,
+        file_path: &str,
+        content_type: &str,
+    ) -> Result<String, FileStorageError>;
+    /// Stores one part of a multipart upload, returns the ETag of the stored part
+    async fn upload_part(
+        &self,
+        file_path: &str,
+        upload_id: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<String, FileStorageError>;
+    async fn complete_multipart_upload(
+        &self,
+        file_path: &str,
+        upload_id: &str,
+        parts: Vec<UploadedPart>,
+    ) -> Result<(), FileStorageError>;
+    async fn abort_multipart_upload(
+        &self,
+        file_path: &str,
+        upload_id: &str,
+    ) -> Result<(), FileStorageError>;
+    /// Lists the multipart uploads that were neither completed nor aborted
+    async fn list_incomplete_multipart_uploads(
+        &self,
+    ) -> Result<Vec<IncompleteMultipartUpload>, FileStorageError>;
+    /// Signs a PUT URL that stores an object of exactly `file_size` bytes at `file_path`
+    async fn create_presigned_upload(
+        &self,
+        file_path: &str,
+        content_type: &str,
+        file_size: u64,
+        expires_in: std::time::Duration,
+    ) -> Result<PresignedUpload, FileStorageError>;
+    /// Reads the metadata of a stored object without downloading it
+    async fn get_file_metadata(
+        &self,
+        file_path: &str,
+    ) -> Result<StoredFileMetadata, FileStorageError>;
 }
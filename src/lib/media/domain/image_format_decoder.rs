@@ -0,0 +1,12 @@
+use super::{SpooledFile, ThumbnailError};
+
+/// Reads originals the image crate cannot decode, e.g. HEIC photos or camera RAW files, and
+/// turns them into an image it can. The result goes through the same size checks and memory
+/// budget as any other upload.
+pub trait ImageFormatDecoder: Send + Sync {
+    /// Whether the decoder reads originals of this content type
+    fn supports(&self, content_type: &str) -> bool;
+
+    /// Blocking, it is run outside of the async runtime
+    fn decode(&self, original: &SpooledFile) -> Result<SpooledFile, ThumbnailError>;
+}
@@ -2,6 +2,26 @@ use uuid::Uuid;
 
 pub type MediaId = Uuid;
 
+/// Time a client has to upload a file through a presigned URL
+pub const PRESIGNED_UPLOAD_TTL_SECONDS: u64 = 15 * 60;
+
+/// Time a pending media file is kept before it is cleaned up. It outlives the presigned URL, so
+/// an upload sent right before the URL expired can still be confirmed.
+pub const PENDING_MEDIA_FILE_TTL_SECONDS: u64 = PRESIGNED_UPLOAD_TTL_SECONDS + 60 * 60;
+
+/// Pending media files created before this time were abandoned and no longer reserve quota
+pub fn pending_media_files_expired_before() -> chrono::NaiveDateTime {
+    chrono::Utc::now().naive_utc()
+        - chrono::Duration::seconds(PENDING_MEDIA_FILE_TTL_SECONDS as i64)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MediaStatus {
+    /// Created through a presigned upload whose object was not confirmed yet
+    Pending,
+    Ready,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MediaFile {
     pub id: MediaId,
@@ -11,11 +31,26 @@ pub struct MediaFile {
     pub file_size: i64,
     pub content_type: String,
     pub file_path: String,
-    pub thumbnail_path: Option<String>,
+    pub status: MediaStatus,
+    /// Hex encoded SHA-256 of the content, missing for files uploaded before it was tracked
+    pub checksum: Option<String>,
+    /// Content type recognized from the magic bytes of the content, missing for files that were
+    /// not streamed through the server or uploaded before it was tracked
+    pub detected_content_type: Option<String>,
     pub uploaded_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
 }
 
+impl MediaFile {
+    /// Whether the presigned upload was never confirmed and is waiting to be cleaned up
+    pub fn is_expired_pending(&self) -> bool {
+        self.status == MediaStatus::Pending
+            && self
+                .uploaded_at
+                .is_some_and(|uploaded_at| uploaded_at < pending_media_files_expired_before())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct NewMediaFile {
     pub user_id: Uuid,
@@ -24,15 +59,30 @@ pub struct NewMediaFile {
     pub file_size: i64,
     pub content_type: String,
     pub file_path: String,
-    pub thumbnail_path: Option<String>,
+    pub status: MediaStatus,
+    pub checksum: Option<String>,
+    pub detected_content_type: Option<String>,
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum MediaUploadError {
     #[error("Invalid file type")]
     InvalidFileType,
-    #[error("File too large")]
-    FileTooLarge,
+    /// The content does not match the content type the client declared
+    #[error("Content is not {claimed}")]
+    ContentTypeMismatch {
+        claimed: String,
+        detected: Option<String>,
+    },
+    /// The file is larger than uploads of its content type may be
+    #[error("File too large, the maximum size is {max_bytes} bytes")]
+    FileTooLarge { max_bytes: u64 },
+    /// The received content is not as large as the client declared
+    #[error("Declared file size {declared} does not match the {received} bytes received")]
+    FileSizeMismatch { declared: u64, received: u64 },
+    /// The file does not fit into the storage quota of the user
+    #[error("Storage quota exceeded")]
+    QuotaExceeded,
     #[error("Storage error: {0}")]
     StorageError(String),
     #[error("Internal server error")]
@@ -48,3 +98,26 @@ pub enum MediaDeleteError {
     #[error("Internal server error")]
     InternalServerError(String),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum MediaConfirmError {
+    #[error("Media file not found")]
+    MediaFileNotFound,
+    #[error("Uploaded file not found in storage")]
+    FileNotUploaded,
+    #[error("Uploaded file does not match the requested upload")]
+    FileMismatch,
+    /// The uploaded content does not match the content type the client declared
+    #[error("Content is not {claimed}")]
+    ContentTypeMismatch {
+        claimed: String,
+        detected: Option<String>,
+    },
+    /// The file does not fit into the storage quota of the user
+    #[error("Storage quota exceeded")]
+    QuotaExceeded,
+    #[error("Storage error: {0}")]
+    StorageError(String),
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
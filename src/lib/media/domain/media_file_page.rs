@@ -0,0 +1,79 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, NaiveDateTime};
+use uuid::Uuid;
+
+use super::media_file::MediaFile;
+
+pub const MEDIA_FILES_DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MEDIA_FILES_MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum MediaFileSort {
+    #[default]
+    UploadedAtDesc,
+    UploadedAtAsc,
+}
+
+/// Restricts a media listing, every filter that is set must match
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct MediaFileFilter {
+    /// e.g. `image/` or `video/mp4`
+    pub content_type_prefix: Option<String>,
+    /// Inclusive lower bound of the upload date
+    pub uploaded_from: Option<NaiveDateTime>,
+    /// Exclusive upper bound of the upload date
+    pub uploaded_to: Option<NaiveDateTime>,
+    /// Case insensitive substring of the original filename
+    pub filename_contains: Option<String>,
+}
+
+/// Position in a listing, the `(uploaded_at, id)` key of the last returned media file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MediaFileCursor {
+    pub uploaded_at: NaiveDateTime,
+    pub id: Uuid,
+}
+
+impl MediaFileCursor {
+    pub fn from_media_file(media_file: &MediaFile) -> Option<Self> {
+        Some(MediaFileCursor {
+            uploaded_at: media_file.uploaded_at?,
+            id: media_file.id,
+        })
+    }
+
+    /// Encodes the cursor as an opaque URL safe token
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}:{}",
+            self.uploaded_at.and_utc().timestamp_micros(),
+            self.id
+        ))
+    }
+
+    /// Decodes a token returned by [`MediaFileCursor::encode`], `None` when it is malformed
+    pub fn decode(token: &str) -> Option<Self> {
+        let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(token).ok()?).ok()?;
+        let (micros, id) = decoded.split_once(':')?;
+        Some(MediaFileCursor {
+            uploaded_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc(),
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MediaFilePageRequest {
+    pub filter: MediaFileFilter,
+    pub sort: MediaFileSort,
+    /// Only media files after this position are returned, `None` for the first page
+    pub cursor: Option<MediaFileCursor>,
+    pub limit: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MediaFilePage {
+    pub media_files: Vec<MediaFile>,
+    /// Position to continue from, `None` when this is the last page
+    pub next_cursor: Option<MediaFileCursor>,
+}
@@ -0,0 +1,48 @@
+use super::MediaId;
+
+/// Metadata read from the file itself, mostly from its EXIF data or from the video container
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaMetadata {
+    pub media_id: MediaId,
+    /// Local time the photo was taken at, as recorded by the camera
+    pub taken_at: Option<chrono::NaiveDateTime>,
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    /// EXIF orientation, 1 to 8
+    pub orientation: Option<i16>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Exposure time in seconds, formatted the way cameras show it, e.g. `1/125`
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    pub iso: Option<i32>,
+    /// Focal length in millimeters
+    pub focal_length: Option<f64>,
+    pub duration_seconds: Option<f64>,
+    /// Codec of the video stream as named by ffmpeg, e.g. `h264`
+    pub video_codec: Option<String>,
+    /// Average frames per second of the video stream
+    pub frame_rate: Option<f64>,
+}
+
+impl MediaMetadata {
+    /// Whether anything besides the media id is known
+    pub fn is_empty(&self) -> bool {
+        *self
+            == MediaMetadata {
+                media_id: self.media_id,
+                ..MediaMetadata::default()
+            }
+    }
+
+    /// The metadata without where the photo was taken
+    pub fn without_location(self) -> Self {
+        MediaMetadata {
+            latitude: None,
+            longitude: None,
+            ..self
+        }
+    }
+}
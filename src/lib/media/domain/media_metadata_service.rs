@@ -0,0 +1,194 @@
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use exif::{Exif, In, Tag, Value};
+
+use crate::media::MediaId;
+
+use super::{MediaMetadata, MediaRepository, MediaRepositoryError};
+
+/// Metadata is read from the start of the file, EXIF data and image headers come before the
+/// pixels in the supported formats
+pub const METADATA_MAX_READ_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MediaMetadataError {
+    #[error("Metadata extraction error: {0}")]
+    ExtractionError(String),
+    #[error("Repository error: {0}")]
+    RepositoryError(#[from] MediaRepositoryError),
+}
+
+#[async_trait]
+pub trait MediaMetadataService: Send + Sync {
+    async fn extract_metadata(
+        &self,
+        media_id: MediaId,
+        image_data: &[u8],
+        content_type: &str,
+    ) -> Result<(), MediaMetadataError>;
+}
+
+pub struct ExifMetadataService<MR> {
+    media_repository: MR,
+}
+
+impl<MR> ExifMetadataService<MR>
+where
+    MR: MediaRepository,
+{
+    pub fn new(media_repository: MR) -> Self {
+        Self { media_repository }
+    }
+}
+
+#[async_trait]
+impl<MR> MediaMetadataService for ExifMetadataService<MR>
+where
+    MR: MediaRepository + 'static,
+{
+    async fn extract_metadata(
+        &self,
+        media_id: MediaId,
+        image_data: &[u8],
+        content_type: &str,
+    ) -> Result<(), MediaMetadataError> {
+        if !content_type.starts_with("image/") {
+            return Ok(());
+        }
+
+        let metadata = extract_image_metadata(media_id, image_data);
+
+        // Nothing worth storing, e.g. an image format the decoder does not know
+        if metadata.is_empty() {
+            return Ok(());
+        }
+
+        self.media_repository.save_media_metadata(metadata).await?;
+
+        Ok(())
+    }
+}
+
+/// Reads the EXIF data of JPEG, HEIF, TIFF, PNG and WebP images. Dimensions are taken from
+/// the image header when the EXIF data does not have them.
+pub fn extract_image_metadata(media_id: MediaId, image_data: &[u8]) -> MediaMetadata {
+    let mut metadata = exif::Reader::new()
+        .read_from_container(&mut Cursor::new(image_data))
+        .map(|exif| metadata_from_exif(media_id, &exif))
+        .unwrap_or(MediaMetadata {
+            media_id,
+            ..MediaMetadata::default()
+        });
+
+    if (metadata.width.is_none() || metadata.height.is_none())
+        && let Ok((width, height)) = image::ImageReader::new(Cursor::new(image_data))
+            .with_guessed_format()
+            .map_err(|e| e.to_string())
+            .and_then(|reader| reader.into_dimensions().map_err(|e| e.to_string()))
+    {
+        metadata.width = i32::try_from(width).ok();
+        metadata.height = i32::try_from(height).ok();
+    }
+
+    metadata
+}
+
+fn metadata_from_exif(media_id: MediaId, exif: &Exif) -> MediaMetadata {
+    let field = |tag: Tag| exif.get_field(tag, In::PRIMARY).map(|field| &field.value);
+
+    let uint = |tag: Tag| field(tag).and_then(|value| value.get_uint(0));
+
+    let text = |tag: Tag| match field(tag) {
+        Some(Value::Ascii(values)) => values
+            .first()
+            .map(|value| String::from_utf8_lossy(value).trim().to_string())
+            .filter(|value| !value.is_empty()),
+        _ => None,
+    };
+
+    let rational = |tag: Tag| match field(tag) {
+        Some(Value::Rational(values)) => values
+            .first()
+            .filter(|value| value.denom != 0)
+            .map(|value| value.to_f64()),
+        _ => None,
+    };
+
+    let taken_at = [Tag::DateTimeOriginal, Tag::DateTimeDigitized, Tag::DateTime]
+        .into_iter()
+        .find_map(|tag| match field(tag) {
+            Some(Value::Ascii(values)) => values.first().and_then(|value| parse_date_time(value)),
+            _ => None,
+        });
+
+    let exposure_time = match field(Tag::ExposureTime) {
+        Some(Value::Rational(values)) => values
+            .first()
+            .filter(|value| value.denom != 0)
+            .map(|value| match value.num {
+                1 => format!("1/{}", value.denom),
+                _ => format!("{}", value.to_f64()),
+            }),
+        _ => None,
+    };
+
+    MediaMetadata {
+        media_id,
+        taken_at,
+        camera_make: text(Tag::Make),
+        camera_model: text(Tag::Model),
+        orientation: uint(Tag::Orientation)
+            .filter(|orientation| (1..=8).contains(orientation))
+            .map(|orientation| orientation as i16),
+        width: uint(Tag::PixelXDimension)
+            .or_else(|| uint(Tag::ImageWidth))
+            .and_then(|width| i32::try_from(width).ok()),
+        height: uint(Tag::PixelYDimension)
+            .or_else(|| uint(Tag::ImageLength))
+            .and_then(|height| i32::try_from(height).ok()),
+        latitude: gps_coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, b'S'),
+        longitude: gps_coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, b'W'),
+        exposure_time,
+        f_number: rational(Tag::FNumber),
+        iso: uint(Tag::PhotographicSensitivity).and_then(|iso| i32::try_from(iso).ok()),
+        focal_length: rational(Tag::FocalLength),
+        ..MediaMetadata::default()
+    }
+}
+
+fn parse_date_time(value: &[u8]) -> Option<chrono::NaiveDateTime> {
+    let date_time = exif::DateTime::from_ascii(value).ok()?;
+    chrono::NaiveDate::from_ymd_opt(
+        date_time.year.into(),
+        date_time.month.into(),
+        date_time.day.into(),
+    )?
+    .and_hms_opt(
+        date_time.hour.into(),
+        date_time.minute.into(),
+        date_time.second.into(),
+    )
+}
+
+/// Converts degrees, minutes and seconds to signed decimal degrees
+fn gps_coordinate(exif: &Exif, tag: Tag, reference_tag: Tag, negative_reference: u8) -> Option<f64> {
+    let Value::Rational(values) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    if values.len() < 3 || values.iter().any(|value| value.denom == 0) {
+        return None;
+    }
+
+    let degrees = values[0].to_f64() + values[1].to_f64() / 60.0 + values[2].to_f64() / 3600.0;
+
+    let is_negative = match &exif.get_field(reference_tag, In::PRIMARY)?.value {
+        Value::Ascii(references) => references
+            .first()
+            .and_then(|reference| reference.first())
+            .is_some_and(|reference| reference.eq_ignore_ascii_case(&negative_reference)),
+        _ => false,
+    };
+
+    Some(if is_negative { -degrees } else { degrees })
+}
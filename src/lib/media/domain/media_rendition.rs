@@ -0,0 +1,245 @@
+use std::env;
+
+use super::MediaId;
+
+/// Rendition served when the client does not ask for a size
+pub const DEFAULT_RENDITION_NAME: &str = "medium";
+
+/// Largest rendition, served in place of the original where the original must not be handed out
+pub const PREVIEW_RENDITION_NAME: &str = "preview";
+
+/// A smaller copy of an image, e.g. a thumbnail or a preview
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRendition {
+    pub media_id: MediaId,
+    /// Name of the rendition size, e.g. `small`
+    pub name: String,
+    pub file_path: String,
+    pub content_type: String,
+    /// Unknown for renditions generated before the sizes were tracked
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub file_size: Option<i64>,
+}
+
+/// Size a rendition is generated at, the image is scaled down to fit in a square of
+/// `max_dimension` pixels and never scaled up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenditionSize {
+    pub name: String,
+    pub max_dimension: u32,
+}
+
+impl RenditionSize {
+    pub fn new(name: &str, max_dimension: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            max_dimension,
+        }
+    }
+}
+
+/// Image format renditions are encoded in, every size is stored once per configured format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenditionFormat {
+    Jpeg,
+    WebP,
+    Avif,
+}
+
+impl RenditionFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            RenditionFormat::Jpeg => "image/jpeg",
+            RenditionFormat::WebP => "image/webp",
+            RenditionFormat::Avif => "image/avif",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RenditionFormat::Jpeg => "jpg",
+            RenditionFormat::WebP => "webp",
+            RenditionFormat::Avif => "avif",
+        }
+    }
+
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        [
+            RenditionFormat::Jpeg,
+            RenditionFormat::WebP,
+            RenditionFormat::Avif,
+        ]
+        .into_iter()
+        .find(|format| {
+            format
+                .content_type()
+                .eq_ignore_ascii_case(content_type.trim())
+        })
+    }
+
+    /// Whether this build can encode the format, WebP and AVIF are cargo features
+    pub fn is_supported(&self) -> bool {
+        match self {
+            RenditionFormat::Jpeg => true,
+            RenditionFormat::WebP => cfg!(feature = "webp"),
+            RenditionFormat::Avif => cfg!(feature = "avif"),
+        }
+    }
+
+    /// Picks the format to serve for the raw value of an `Accept` header among the `available`
+    /// ones. Formats the client names explicitly win over wildcards, and among those the
+    /// smallest files win. Wildcards alone get JPEG, as clients sending only `*/*` may not
+    /// decode newer formats. Falls back to JPEG when nothing matches, an image the client may
+    /// not display beats an error for an `<img>` tag.
+    pub fn negotiate(accept: Option<&str>, available: &[RenditionFormat]) -> Option<Self> {
+        let ranges: Vec<(&str, f32)> = accept
+            .unwrap_or("")
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.split(';');
+                let media_range = parts.next()?.trim();
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (!media_range.is_empty()).then_some((media_range, quality))
+            })
+            .collect();
+
+        // The most specific matching range decides the quality of a format
+        let score = |format: &RenditionFormat| {
+            let quality_of = |media_range: &str| {
+                ranges
+                    .iter()
+                    .find(|(range, _)| range.eq_ignore_ascii_case(media_range))
+                    .map(|(_, quality)| *quality)
+            };
+            let (quality, explicit) = match quality_of(format.content_type()) {
+                Some(quality) => (quality, true),
+                None => (quality_of("image/*").or(quality_of("*/*"))?, false),
+            };
+            let preference = match (explicit, format) {
+                (true, RenditionFormat::Avif) => 4,
+                (true, RenditionFormat::WebP) => 3,
+                (true, RenditionFormat::Jpeg) => 2,
+                (false, RenditionFormat::Jpeg) => 1,
+                (false, _) => 0,
+            };
+            (quality > 0.0).then_some(((quality * 1000.0) as u32, preference))
+        };
+
+        available
+            .iter()
+            .filter_map(|format| score(format).map(|score| (score, *format)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, format)| format)
+            .or_else(|| {
+                available
+                    .iter()
+                    .find(|format| **format == RenditionFormat::Jpeg)
+                    .or(available.first())
+                    .copied()
+            })
+    }
+}
+
+/// Rendition sizes and formats generated for every uploaded image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenditionConfig {
+    /// Ordered from the smallest to the largest
+    pub sizes: Vec<RenditionSize>,
+    /// Always starts with JPEG, the format every client can display
+    pub formats: Vec<RenditionFormat>,
+}
+
+impl RenditionConfig {
+    /// Reads `MEDIA_RENDITIONS`, a comma separated list of `name:max_dimension` pairs, and
+    /// `MEDIA_RENDITION_FORMATS`, a comma separated list of formats, and falls back to the
+    /// defaults when either is missing or invalid
+    pub fn new() -> Self {
+        let config = env::var("MEDIA_RENDITIONS")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default();
+        let formats = env::var("MEDIA_RENDITION_FORMATS")
+            .ok()
+            .and_then(|value| Self::parse_formats(&value))
+            .unwrap_or(config.formats);
+
+        Self { formats, ..config }
+    }
+
+    /// Parses a list like `webp,avif`, returns `None` when a format is unknown or not compiled
+    /// in. JPEG is added when missing so there is always a rendition every client can display.
+    pub fn parse_formats(value: &str) -> Option<Vec<RenditionFormat>> {
+        let mut formats = vec![RenditionFormat::Jpeg];
+        for entry in value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let format = match entry.to_ascii_lowercase().as_str() {
+                "jpeg" | "jpg" => RenditionFormat::Jpeg,
+                "webp" => RenditionFormat::WebP,
+                "avif" => RenditionFormat::Avif,
+                _ => return None,
+            };
+            if !format.is_supported() {
+                return None;
+            }
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        Some(formats)
+    }
+
+    /// Parses a list like `small:64,medium:300`, returns `None` when any entry is invalid
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut sizes = value
+            .split(',')
+            .map(|entry| {
+                let (name, max_dimension) = entry.trim().split_once(':')?;
+                let name = name.trim();
+                let max_dimension = max_dimension.trim().parse::<u32>().ok()?;
+                let valid_name = !name.is_empty()
+                    && name.len() <= 32
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                (valid_name && max_dimension > 0).then(|| RenditionSize::new(name, max_dimension))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        sizes.sort_by_key(|size| size.max_dimension);
+        let has_duplicates = sizes
+            .iter()
+            .enumerate()
+            .any(|(i, size)| sizes[..i].iter().any(|other| other.name == size.name));
+        if has_duplicates {
+            return None;
+        }
+
+        Some(Self {
+            sizes,
+            formats: RenditionConfig::default().formats,
+        })
+    }
+}
+
+impl Default for RenditionConfig {
+    fn default() -> Self {
+        Self {
+            sizes: vec![
+                RenditionSize::new("small", 64),
+                RenditionSize::new(DEFAULT_RENDITION_NAME, 300),
+                RenditionSize::new(PREVIEW_RENDITION_NAME, 1080),
+            ],
+            formats: [RenditionFormat::Jpeg, RenditionFormat::WebP]
+                .into_iter()
+                .filter(RenditionFormat::is_supported)
+                .collect(),
+        }
+    }
+}
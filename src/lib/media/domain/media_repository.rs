@@ -3,7 +3,13 @@ use uuid::Uuid;
 
 use crate::media::MediaId;
 
-use super::media_file::{MediaFile, NewMediaFile};
+use super::{
+    media_file::{MediaFile, NewMediaFile},
+    media_file_page::{MediaFilePage, MediaFilePageRequest},
+    media_metadata::MediaMetadata,
+    media_rendition::MediaRendition,
+    storage_usage::StorageUsage,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum MediaRepositoryError {
@@ -11,6 +17,9 @@ pub enum MediaRepositoryError {
     InternalServerError,
     #[error("Media file not found")]
     MediaFileNotFound,
+    /// The user already has a ready media file with the same checksum
+    #[error("Media file with the same checksum already exists")]
+    DuplicateChecksum,
 }
 
 #[async_trait]
@@ -23,15 +32,50 @@ pub trait MediaRepository: Send + Sync {
         &self,
         id: MediaId,
     ) -> Result<Option<MediaFile>, MediaRepositoryError>;
+    /// Returns one page of the ready media files of the user, pending presigned uploads are
+    /// left out
     async fn get_media_files_by_user_id(
         &self,
         user_id: Uuid,
-    ) -> Result<Vec<MediaFile>, MediaRepositoryError>;
+        page: MediaFilePageRequest,
+    ) -> Result<MediaFilePage, MediaRepositoryError>;
     async fn get_media_path_by_id(&self, id: MediaId) -> Result<String, MediaRepositoryError>;
     async fn delete_media_file(&self, id: Uuid) -> Result<(), MediaRepositoryError>;
-    async fn update_thumbnail_path(
+    /// Returns the ready media files of the user whose checksum is one of `checksums`
+    async fn get_media_files_by_checksums(
         &self,
-        id: MediaId,
-        thumbnail_path: Option<String>,
+        user_id: Uuid,
+        checksums: Vec<String>,
+    ) -> Result<Vec<MediaFile>, MediaRepositoryError>;
+    /// Stores the metadata of a media file, replacing what was stored before
+    async fn save_media_metadata(&self, metadata: MediaMetadata) -> Result<(), MediaRepositoryError>;
+    async fn get_media_metadata_by_media_ids(
+        &self,
+        media_ids: Vec<MediaId>,
+    ) -> Result<Vec<MediaMetadata>, MediaRepositoryError>;
+    /// Stores a rendition of a media file, replacing the stored rendition with the same name
+    async fn save_media_rendition(
+        &self,
+        rendition: MediaRendition,
     ) -> Result<(), MediaRepositoryError>;
+    async fn get_media_renditions_by_media_ids(
+        &self,
+        media_ids: Vec<MediaId>,
+    ) -> Result<Vec<MediaRendition>, MediaRepositoryError>;
+    /// Marks a pending media file as ready once its object has been verified, along with the
+    /// checksum and the content type recognized from the object
+    async fn mark_media_file_ready(
+        &self,
+        id: MediaId,
+        checksum: String,
+        detected_content_type: Option<String>,
+    ) -> Result<MediaFile, MediaRepositoryError>;
+    /// Returns the pending media files created before `uploaded_before`
+    async fn get_pending_media_files_uploaded_before(
+        &self,
+        uploaded_before: chrono::NaiveDateTime,
+    ) -> Result<Vec<MediaFile>, MediaRepositoryError>;
+    /// Sums up the media files of the user, pending presigned uploads included as their size
+    /// is reserved until they expire
+    async fn get_storage_usage(&self, user_id: Uuid) -> Result<StorageUsage, MediaRepositoryError>;
 }
@@ -0,0 +1,15 @@
+use crate::media::MediaId;
+
+/// Signature that grants temporary access to a single media file without a bearer token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedMediaUrl {
+    pub media_id: MediaId,
+    /// Unix timestamp (seconds) after which the signature is no longer valid
+    pub expires_at: u64,
+    pub signature: String,
+}
+
+pub trait MediaUrlSigner: Send + Sync {
+    fn sign(&self, media_id: MediaId) -> SignedMediaUrl;
+    fn verify(&self, media_id: MediaId, expires_at: u64, signature: &str) -> bool;
+}
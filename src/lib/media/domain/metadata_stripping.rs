@@ -0,0 +1,224 @@
+use futures_util::TryStreamExt;
+
+use super::{ByteRange, FileStream, content_type_essence};
+
+/// EXIF tag of IFD0 pointing to the GPS directory
+const GPS_INFO_TAG: u16 = 0x8825;
+/// TIFF tag of IFD0 holding an XMP packet
+const XMP_TAG: u16 = 0x02BC;
+const IFD_ENTRY_SIZE: usize = 12;
+
+/// Identifiers of the JPEG APP1 segments holding XMP, which may repeat the location
+const XMP_SEGMENT_PREFIXES: [&[u8]; 2] = [
+    b"http://ns.adobe.com/xap/1.0/\0",
+    b"http://ns.adobe.com/xmp/extension/\0",
+];
+
+/// JPEG images and the TIFF based formats, the location can only be stripped from these
+const STRIPPABLE_CONTENT_TYPES: [&str; 7] = [
+    "image/jpeg",
+    "image/tiff",
+    "image/x-canon-cr2",
+    "image/x-nikon-nef",
+    "image/x-sony-arw",
+    "image/x-adobe-dng",
+    "image/x-pentax-pef",
+];
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MetadataStrippingError {
+    #[error("Metadata can only be stripped from JPEG and TIFF based images")]
+    UnsupportedFormat,
+    #[error("Metadata could not be read: {0}")]
+    InvalidMetadata(String),
+}
+
+/// Whether the location can be stripped from files of `content_type`
+pub fn supports_metadata_stripping(content_type: &str) -> bool {
+    STRIPPABLE_CONTENT_TYPES.contains(&content_type_essence(content_type).as_str())
+}
+
+/// Byte ranges of an image holding the location it was taken at, the EXIF GPS directory and
+/// XMP packets. `head` is the start of the file and has to hold all of its metadata.
+///
+/// Overwriting the ranges with zeros leaves empty directories behind, so the file keeps its size
+/// and every other offset in it stays valid.
+pub fn location_metadata_ranges(head: &[u8]) -> Result<Vec<ByteRange>, MetadataStrippingError> {
+    match head {
+        [0xFF, 0xD8, ..] => jpeg_location_ranges(head),
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => {
+            tiff_location_ranges(head, 0)
+        }
+        _ => Err(MetadataStrippingError::UnsupportedFormat),
+    }
+}
+
+/// Overwrites the bytes of `ranges` with zeros as the file streams through. `offset` is the
+/// position in the file of the first byte of the stream, e.g. the start of a requested range.
+pub fn redact_stream(stream: FileStream, offset: u64, ranges: Vec<ByteRange>) -> FileStream {
+    let mut position = offset;
+    Box::pin(stream.map_ok(move |chunk| {
+        let chunk_start = position;
+        let chunk_end = position + chunk.len() as u64;
+        position = chunk_end;
+
+        let overlaps: Vec<_> = ranges
+            .iter()
+            .filter(|range| range.start < chunk_end && range.end >= chunk_start)
+            .collect();
+        if overlaps.is_empty() {
+            return chunk;
+        }
+
+        let mut data = chunk.to_vec();
+        for range in overlaps {
+            let start = range.start.max(chunk_start) - chunk_start;
+            let end = range.end.min(chunk_end - 1) - chunk_start;
+            data[start as usize..=end as usize].fill(0);
+        }
+        data.into()
+    }))
+}
+
+fn invalid(message: &str) -> MetadataStrippingError {
+    MetadataStrippingError::InvalidMetadata(message.to_string())
+}
+
+/// Walks the segments before the image data, EXIF and XMP live in APP1 segments
+fn jpeg_location_ranges(head: &[u8]) -> Result<Vec<ByteRange>, MetadataStrippingError> {
+    let truncated = || invalid("JPEG headers are truncated");
+
+    let mut ranges = Vec::new();
+    let mut position = 2;
+    loop {
+        let marker = match head.get(position..position + 2) {
+            Some([0xFF, marker]) => *marker,
+            _ => return Err(truncated()),
+        };
+        // Markers may be preceded by fill bytes
+        if marker == 0xFF {
+            position += 1;
+            continue;
+        }
+        position += 2;
+
+        match marker {
+            0x01 | 0xD0..=0xD8 => continue,
+            // Start of scan or end of image, metadata comes before both
+            0xDA | 0xD9 => return Ok(ranges),
+            _ => {}
+        }
+
+        let length = head
+            .get(position..position + 2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]) as usize)
+            .ok_or_else(truncated)?;
+        let payload_start = position + 2;
+        let segment_end = position + length;
+        let payload = head
+            .get(payload_start..segment_end)
+            .filter(|_| length >= 2)
+            .ok_or_else(truncated)?;
+
+        if marker == 0xE1 {
+            if let Some(tiff) = payload.strip_prefix(b"Exif\0\0") {
+                ranges.extend(tiff_location_ranges(tiff, segment_end - tiff.len())?);
+            } else if let Some(prefix) = XMP_SEGMENT_PREFIXES
+                .iter()
+                .find(|prefix| payload.starts_with(prefix))
+                && payload.len() > prefix.len()
+            {
+                ranges.push(ByteRange {
+                    start: (payload_start + prefix.len()) as u64,
+                    end: (segment_end - 1) as u64,
+                });
+            }
+        }
+        position = segment_end;
+    }
+}
+
+/// Finds the GPS directory and the XMP packet of a TIFF structure, which starts at `tiff_start`
+/// in the file. Offsets pointing outside of `tiff` are rejected.
+fn tiff_location_ranges(
+    tiff: &[u8],
+    tiff_start: usize,
+) -> Result<Vec<ByteRange>, MetadataStrippingError> {
+    let big_endian = match tiff.get(..4) {
+        Some([b'I', b'I', 0x2A, 0x00]) => false,
+        Some([b'M', b'M', 0x00, 0x2A]) => true,
+        _ => return Err(invalid("Invalid TIFF header")),
+    };
+    let beyond = || invalid("Metadata lies beyond the headers of the file");
+    let u16_at = |offset: usize| {
+        tiff.get(offset..offset + 2)
+            .map(|bytes| match big_endian {
+                true => u16::from_be_bytes([bytes[0], bytes[1]]),
+                false => u16::from_le_bytes([bytes[0], bytes[1]]),
+            })
+            .ok_or_else(beyond)
+    };
+    let u32_at = |offset: usize| {
+        tiff.get(offset..offset + 4)
+            .map(|bytes| {
+                let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+                match big_endian {
+                    true => u32::from_be_bytes(bytes),
+                    false => u32::from_le_bytes(bytes),
+                }
+            })
+            .ok_or_else(beyond)
+    };
+    // Values larger than 4 bytes are stored elsewhere and the entry holds their offset
+    let value_range = |entry: usize| -> Result<Option<(usize, usize)>, MetadataStrippingError> {
+        let value_size = match u16_at(entry + 2)? {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => return Err(invalid("Unknown TIFF field type")),
+        };
+        let size = value_size * u32_at(entry + 4)? as usize;
+        if size <= 4 {
+            return Ok(None);
+        }
+        let offset = u32_at(entry + 8)? as usize;
+        if offset + size > tiff.len() {
+            return Err(beyond());
+        }
+        Ok(Some((offset, offset + size)))
+    };
+
+    let mut ranges = Vec::new();
+    let to_file_range = |(start, end): (usize, usize)| ByteRange {
+        start: (tiff_start + start) as u64,
+        end: (tiff_start + end - 1) as u64,
+    };
+
+    let ifd0 = u32_at(4)? as usize;
+    let mut gps_ifd = None;
+    for index in 0..u16_at(ifd0)? as usize {
+        let entry = ifd0 + 2 + index * IFD_ENTRY_SIZE;
+        match u16_at(entry)? {
+            GPS_INFO_TAG => gps_ifd = Some(u32_at(entry + 8)? as usize),
+            XMP_TAG => ranges.extend(value_range(entry)?.map(to_file_range)),
+            _ => {}
+        }
+    }
+
+    if let Some(gps_ifd) = gps_ifd {
+        let count = u16_at(gps_ifd)? as usize;
+        // Entry count, entries and the offset of the next directory
+        let directory_end = gps_ifd + 2 + count * IFD_ENTRY_SIZE + 4;
+        if directory_end > tiff.len() {
+            return Err(beyond());
+        }
+        for index in 0..count {
+            let entry = gps_ifd + 2 + index * IFD_ENTRY_SIZE;
+            ranges.extend(value_range(entry)?.map(to_file_range));
+        }
+        ranges.push(to_file_range((gps_ifd, directory_end)));
+    }
+
+    Ok(ranges)
+}
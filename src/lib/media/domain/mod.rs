@@ -1,9 +1,41 @@
+pub mod byte_range;
+pub mod content_sniffing;
 pub mod file_storage_service;
+pub mod image_format_decoder;
 pub mod media_file;
+pub mod media_file_page;
+pub mod media_metadata;
+pub mod media_metadata_service;
+pub mod media_rendition;
 pub mod media_repository;
+pub mod media_url_signer;
+pub mod metadata_stripping;
+pub mod spooled_file;
+pub mod storage_usage;
 pub mod thumbnail_service;
+pub mod upload_session;
+pub mod upload_session_repository;
+pub mod upload_size_limits;
+pub mod video_frame_extractor;
+pub mod video_thumbnail_service;
 
+pub use byte_range::*;
+pub use content_sniffing::*;
 pub use file_storage_service::*;
+pub use image_format_decoder::*;
 pub use media_file::*;
+pub use media_file_page::*;
+pub use media_metadata::*;
+pub use media_metadata_service::*;
+pub use media_rendition::*;
 pub use media_repository::*;
+pub use media_url_signer::*;
+pub use metadata_stripping::*;
+pub use spooled_file::*;
+pub use storage_usage::*;
 pub use thumbnail_service::*;
+pub use upload_session::*;
+pub use upload_session_repository::*;
+pub use upload_size_limits::*;
+pub use video_frame_extractor::*;
+pub use video_thumbnail_service::*;
@@ -0,0 +1,102 @@
+use std::{
+    env,
+    io::{BufRead, BufReader, Cursor, Seek},
+    path::{Path, PathBuf},
+};
+
+use futures_util::TryStreamExt;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use super::{FileStorageError, FileStream};
+
+/// File in the system temporary directory, removed when dropped, even on errors
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Reserves a unique path, the file itself is created by whoever writes to it
+    pub fn new(prefix: &str) -> Self {
+        TempFile(env::temp_dir().join(format!("{}_{}", prefix, Uuid::new_v4())))
+    }
+
+    /// For tools that pick the file format from the extension
+    pub fn with_extension(prefix: &str, extension: &str) -> Self {
+        TempFile(env::temp_dir().join(format!("{}_{}.{}", prefix, Uuid::new_v4(), extension)))
+    }
+
+    /// Writes the whole stream to a new temporary file
+    pub async fn from_stream(prefix: &str, stream: FileStream) -> Result<Self, FileStorageError> {
+        let temp_file = TempFile::new(prefix);
+        write_to_file(temp_file.path(), &[], stream).await?;
+        Ok(temp_file)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Both readers of a spooled file, image decoders need to seek
+pub trait BufReadSeek: BufRead + Seek {}
+
+impl<T: BufRead + Seek> BufReadSeek for T {}
+
+/// Local copy of a stored file, small files are kept in memory and larger ones are written to
+/// a temporary file so their size does not count against the memory of the server
+pub enum SpooledFile {
+    Memory(Vec<u8>),
+    Disk(TempFile),
+}
+
+impl SpooledFile {
+    /// Reads the whole stream, switching to a temporary file as soon as more than
+    /// `max_in_memory_bytes` have been read
+    pub async fn from_stream(
+        mut stream: FileStream,
+        max_in_memory_bytes: u64,
+    ) -> Result<Self, FileStorageError> {
+        let mut buffer = Vec::new();
+        while let Some(chunk) = stream.try_next().await? {
+            if (buffer.len() + chunk.len()) as u64 > max_in_memory_bytes {
+                let temp_file = TempFile::new("spool");
+                buffer.extend_from_slice(&chunk);
+                write_to_file(temp_file.path(), &buffer, stream).await?;
+                return Ok(SpooledFile::Disk(temp_file));
+            }
+            buffer.extend_from_slice(&chunk);
+        }
+        Ok(SpooledFile::Memory(buffer))
+    }
+
+    pub fn reader(&self) -> std::io::Result<Box<dyn BufReadSeek + '_>> {
+        Ok(match self {
+            SpooledFile::Memory(data) => Box::new(Cursor::new(data.as_slice())),
+            SpooledFile::Disk(temp_file) => {
+                Box::new(BufReader::new(std::fs::File::open(temp_file.path())?))
+            }
+        })
+    }
+}
+
+async fn write_to_file(
+    path: &Path,
+    head: &[u8],
+    mut stream: FileStream,
+) -> Result<(), FileStorageError> {
+    let io_error = |e: std::io::Error| FileStorageError::InternalError(e.to_string());
+
+    let mut file = tokio::fs::File::create(path).await.map_err(io_error)?;
+    file.write_all(head).await.map_err(io_error)?;
+    while let Some(chunk) = stream.try_next().await? {
+        file.write_all(&chunk).await.map_err(io_error)?;
+    }
+    file.flush().await.map_err(io_error)?;
+
+    Ok(())
+}
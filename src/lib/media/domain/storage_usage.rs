@@ -0,0 +1,6 @@
+/// Storage taken up by the originals of the media files of a user, renditions are not counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StorageUsage {
+    pub bytes: i64,
+    pub items: i64,
+}
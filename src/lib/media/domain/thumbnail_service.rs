@@ -1,16 +1,26 @@
-use std::io::Cursor;
+use std::{env, io::Cursor, sync::Arc};
 
 use async_trait::async_trait;
-use image::{DynamicImage, GenericImageView, ImageFormat, imageops::FilterType};
+use image::{
+    DynamicImage, GenericImageView, ImageBuffer, ImageDecoder, ImageFormat, ImageReader, Limits,
+    imageops::FilterType, metadata::Orientation,
+};
+use tokio::sync::Semaphore;
 use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 
 use crate::media::MediaId;
 
-use super::{FileStorageService, MediaRepository, MediaRepositoryError};
+use super::{
+    FileStorageService, FileStream, ImageFormatDecoder, MediaRendition, MediaRepository,
+    MediaRepositoryError, RenditionConfig, RenditionFormat, RenditionSize, SpooledFile,
+};
 
-const THUMBNAIL_WIDTH: u32 = 300;
-const THUMBNAIL_HEIGHT: u32 = 300;
+/// The decode budget is counted in KiB so it fits in the permits of a semaphore
+const DECODE_BUDGET_UNIT_BYTES: u64 = 1024;
+
+/// Scales JPEGs can be decoded at, as the denominator of the full size
+const JPEG_SCALE_DENOMINATORS: [u32; 3] = [2, 4, 8];
 
 #[derive(Debug, thiserror::Error)]
 pub enum ThumbnailError {
@@ -24,18 +34,74 @@ pub enum ThumbnailError {
 
 #[async_trait]
 pub trait ThumbnailService: Send + Sync {
-    async fn generate_thumbnail(
+    /// Generates and stores every configured rendition of an image, other content types are
+    /// ignored. The original is read from the stream and is not held in memory as a whole.
+    async fn generate_renditions(
         &self,
         media_id: Uuid,
         original_path: &str,
-        image_data: Vec<u8>,
+        file_stream: FileStream,
         content_type: &str,
     ) -> Result<(), ThumbnailError>;
 }
 
+/// Memory the image decoder is allowed to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThumbnailDecodeConfig {
+    /// Originals up to this size are decoded from memory, larger ones from a temporary file
+    pub max_in_memory_bytes: u64,
+    /// Images whose decoded pixels would take more memory are decoded at a reduced size when
+    /// their format allows it, and rejected without being decoded otherwise
+    pub max_decoded_bytes: u64,
+    /// Decoded pixels of the images processed at the same time share this budget, decodes wait
+    /// until enough of it is free
+    pub decode_memory_budget_bytes: u64,
+}
+
+impl ThumbnailDecodeConfig {
+    pub fn new() -> Self {
+        let env_u64 = |name: &str| env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        let default = ThumbnailDecodeConfig::default();
+
+        ThumbnailDecodeConfig {
+            max_in_memory_bytes: env_u64("THUMBNAIL_MAX_IN_MEMORY_BYTES")
+                .unwrap_or(default.max_in_memory_bytes),
+            max_decoded_bytes: env_u64("THUMBNAIL_MAX_DECODED_BYTES")
+                .unwrap_or(default.max_decoded_bytes),
+            decode_memory_budget_bytes: env_u64("THUMBNAIL_DECODE_MEMORY_BUDGET_BYTES")
+                .unwrap_or(default.decode_memory_budget_bytes),
+        }
+    }
+}
+
+impl Default for ThumbnailDecodeConfig {
+    fn default() -> Self {
+        Self {
+            max_in_memory_bytes: 16 * 1024 * 1024,
+            // A 64 megapixel RGBA image
+            max_decoded_bytes: 256 * 1024 * 1024,
+            decode_memory_budget_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// Rendition ready to be stored, `files` holds one file per configured format and is empty
+/// when the rendition has the same size as the previous one and shares its files
+struct EncodedRendition {
+    size: RenditionSize,
+    width: u32,
+    height: u32,
+    files: Vec<(RenditionFormat, Vec<u8>)>,
+}
+
 pub struct ImageThumbnailService<MR, FS> {
     media_repository: MR,
     storage_service: FS,
+    config: RenditionConfig,
+    decode_config: ThumbnailDecodeConfig,
+    decode_budget: Arc<Semaphore>,
+    decode_budget_units: u32,
+    decoders: Vec<Arc<dyn ImageFormatDecoder>>,
 }
 
 impl<MR, FS> ImageThumbnailService<MR, FS>
@@ -43,99 +109,399 @@ where
     MR: MediaRepository,
     FS: FileStorageService,
 {
-    pub fn new(media_repository: MR, storage_service: FS) -> Self {
+    pub fn new(
+        media_repository: MR,
+        storage_service: FS,
+        config: RenditionConfig,
+        decode_config: ThumbnailDecodeConfig,
+    ) -> Self {
+        let decode_budget_units = (decode_config.decode_memory_budget_bytes
+            / DECODE_BUDGET_UNIT_BYTES)
+            .clamp(1, u32::MAX as u64) as u32;
         Self {
             media_repository,
             storage_service,
+            config,
+            decode_config,
+            decode_budget: Arc::new(Semaphore::new(decode_budget_units as usize)),
+            decode_budget_units,
+            decoders: Vec::new(),
         }
     }
 
-    fn should_create_thumbnail(&self, image: &DynamicImage) -> bool {
-        let (width, height) = image.dimensions();
-        width > THUMBNAIL_WIDTH || height > THUMBNAIL_HEIGHT
+    /// Decoders for originals the image crate cannot read, the first one supporting the content
+    /// type of an original is used
+    pub fn with_decoders(mut self, decoders: Vec<Arc<dyn ImageFormatDecoder>>) -> Self {
+        self.decoders = decoders;
+        self
     }
 
-    fn create_thumbnail_data(&self, image_data: &[u8]) -> Result<Vec<u8>, ThumbnailError> {
-        let image = image::load_from_memory(image_data)
+    /// Size of the decoded pixels, read from the image header without decoding the image
+    fn decoded_size(original: &SpooledFile) -> Result<u64, ThumbnailError> {
+        let reader = original
+            .reader()
+            .map_err(|e| ThumbnailError::StorageError(e.to_string()))?;
+        let decoder = ImageReader::new(reader)
+            .with_guessed_format()
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?
+            .into_decoder()
             .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
 
-        if !self.should_create_thumbnail(&image) {
-            return Err(ThumbnailError::ImageProcessingError(
-                "Image is already smaller than thumbnail size".to_string(),
-            ));
+        Ok(decoder.total_bytes())
+    }
+
+    fn decode(
+        original: &SpooledFile,
+        max_decoded_bytes: u64,
+    ) -> Result<DynamicImage, ThumbnailError> {
+        let reader = original
+            .reader()
+            .map_err(|e| ThumbnailError::StorageError(e.to_string()))?;
+        let mut image_reader = ImageReader::new(reader)
+            .with_guessed_format()
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+
+        // Decoders refuse to allocate more than the cap instead of running out of memory
+        let mut limits = Limits::default();
+        limits.max_alloc = Some(max_decoded_bytes);
+        image_reader.limits(limits);
+
+        let mut decoder = image_reader
+            .into_decoder()
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        // Cameras store portrait photos sideways and record how to turn them in EXIF, renditions
+        // carry no EXIF so the pixels are turned instead
+        let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+        let mut image = DynamicImage::from_decoder(decoder)
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        image.apply_orientation(orientation);
+
+        Ok(image)
+    }
+
+    /// Decodes a JPEG at the largest of a half, a quarter or an eighth of its size whose pixels
+    /// fit in `max_decoded_bytes`. Returns `None` for other formats and for JPEGs that do not
+    /// fit even at an eighth.
+    fn decode_scaled_jpeg(
+        original: &SpooledFile,
+        max_decoded_bytes: u64,
+    ) -> Result<Option<DynamicImage>, ThumbnailError> {
+        let reader = original
+            .reader()
+            .map_err(|e| ThumbnailError::StorageError(e.to_string()))?;
+        let image_reader = ImageReader::new(reader)
+            .with_guessed_format()
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        if image_reader.format() != Some(ImageFormat::Jpeg) {
+            return Ok(None);
         }
+        let mut decoder = image_reader
+            .into_decoder()
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+        let (width, height) = decoder.dimensions();
+        let bytes_per_pixel = decoder.color_type().bytes_per_pixel() as u64;
+
+        let Some((scaled_width, scaled_height)) = JPEG_SCALE_DENOMINATORS
+            .iter()
+            .map(|denominator| (width.div_ceil(*denominator), height.div_ceil(*denominator)))
+            .find(|(scaled_width, scaled_height)| {
+                *scaled_width as u64 * *scaled_height as u64 * bytes_per_pixel <= max_decoded_bytes
+            })
+        else {
+            return Ok(None);
+        };
 
-        let thumbnail = image.resize(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT, FilterType::Lanczos3);
+        // The IDCT is run at the reduced size, the full size pixels are never held in memory
+        let reader = original
+            .reader()
+            .map_err(|e| ThumbnailError::StorageError(e.to_string()))?;
+        let mut jpeg_decoder = jpeg_decoder::Decoder::new(reader);
+        let (scaled_width, scaled_height) = jpeg_decoder
+            .scale(scaled_width as u16, scaled_height as u16)
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        let pixels = jpeg_decoder
+            .decode()
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        let (scaled_width, scaled_height) = (scaled_width as u32, scaled_height as u32);
 
-        // Convert to RGB if needed
-        let rgb_thumbnail = match thumbnail.color() {
-            image::ColorType::Rgba8 | image::ColorType::La8 => {
-                DynamicImage::ImageRgb8(thumbnail.to_rgb8())
+        let image = match jpeg_decoder.info().map(|info| info.pixel_format) {
+            Some(jpeg_decoder::PixelFormat::L8) => {
+                ImageBuffer::from_raw(scaled_width, scaled_height, pixels)
+                    .map(DynamicImage::ImageLuma8)
+            }
+            Some(jpeg_decoder::PixelFormat::RGB24) => {
+                ImageBuffer::from_raw(scaled_width, scaled_height, pixels)
+                    .map(DynamicImage::ImageRgb8)
             }
-            _ => thumbnail,
+            // CMYK and 16 bit JPEGs are rare enough to be rejected instead
+            _ => None,
         };
+        let Some(mut image) = image else {
+            return Ok(None);
+        };
+        image.apply_orientation(orientation);
 
+        Ok(Some(image))
+    }
+
+    /// Decodes the original at full size, or at a reduced size when its pixels would take more
+    /// than `max_decoded_bytes`
+    fn decode_within_cap(
+        original: &SpooledFile,
+        max_decoded_bytes: u64,
+    ) -> Result<DynamicImage, ThumbnailError> {
+        let decoded_bytes = Self::decoded_size(original)?;
+        if decoded_bytes <= max_decoded_bytes {
+            return Self::decode(original, max_decoded_bytes);
+        }
+
+        Self::decode_scaled_jpeg(original, max_decoded_bytes)?.ok_or_else(|| {
+            ThumbnailError::ImageProcessingError(format!(
+                "Image too large to decode, it needs {} bytes and at most {} are allowed",
+                decoded_bytes, max_decoded_bytes
+            ))
+        })
+    }
+
+    /// Decodes the original once and scales it down to the largest rendition first, so the full
+    /// size image is dropped early and every smaller rendition is made from the one above it
+    fn create_renditions(
+        original: &SpooledFile,
+        config: &RenditionConfig,
+        max_decoded_bytes: u64,
+    ) -> Result<Vec<EncodedRendition>, ThumbnailError> {
+        let mut image = Self::decode_within_cap(original, max_decoded_bytes)?;
+
+        let mut images = Vec::with_capacity(config.sizes.len());
+        for size in config.sizes.iter().rev() {
+            image = Self::create_rendition_image(image, size.max_dimension);
+            images.push((size.clone(), image.clone()));
+        }
+        drop(image);
+        images.reverse();
+
+        // Sizes larger than the image would all be the same copy, they share one stored file
+        let mut previous: Option<(u32, u32)> = None;
+        images
+            .into_iter()
+            .map(|(size, image)| {
+                let (width, height) = image.dimensions();
+                let files = match previous.replace((width, height)) {
+                    Some(dimensions) if dimensions == (width, height) => Vec::new(),
+                    _ => config
+                        .formats
+                        .iter()
+                        .map(|format| Ok((*format, Self::encode(&image, *format)?)))
+                        .collect::<Result<_, ThumbnailError>>()?,
+                };
+                Ok(EncodedRendition {
+                    size,
+                    width,
+                    height,
+                    files,
+                })
+            })
+            .collect()
+    }
+
+    /// Scales the image down to fit in a square of `max_dimension`, smaller images are kept as is
+    fn create_rendition_image(image: DynamicImage, max_dimension: u32) -> DynamicImage {
+        let (width, height) = image.dimensions();
+        if width <= max_dimension && height <= max_dimension {
+            return image;
+        }
+        image.resize(max_dimension, max_dimension, FilterType::Lanczos3)
+    }
+
+    fn encode(image: &DynamicImage, format: RenditionFormat) -> Result<Vec<u8>, ThumbnailError> {
         let mut output = Vec::new();
         let mut cursor = std::io::Cursor::new(&mut output);
 
-        rgb_thumbnail
-            .write_to(&mut cursor, ImageFormat::Jpeg)
-            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
+        let result = match format {
+            RenditionFormat::Jpeg => {
+                // JPEG has no alpha channel and no 16 bit support
+                let rgb_image = match image {
+                    DynamicImage::ImageRgb8(_) | DynamicImage::ImageLuma8(_) => image.clone(),
+                    _ => DynamicImage::ImageRgb8(image.to_rgb8()),
+                };
+                rgb_image.write_to(&mut cursor, ImageFormat::Jpeg)
+            }
+            // The WebP encoder of the image crate is lossless
+            #[cfg(feature = "webp")]
+            RenditionFormat::WebP => Self::to_8_bit(image).write_to(&mut cursor, ImageFormat::WebP),
+            #[cfg(feature = "avif")]
+            RenditionFormat::Avif => {
+                // Fast enough to encode every rendition of an upload in a few seconds
+                let encoder =
+                    image::codecs::avif::AvifEncoder::new_with_speed_quality(&mut cursor, 8, 70);
+                Self::to_8_bit(image).write_with_encoder(encoder)
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return Err(ThumbnailError::ImageProcessingError(format!(
+                    "{} renditions are not supported by this build",
+                    format.content_type()
+                )));
+            }
+        };
+        result.map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
 
         Ok(output)
     }
 
-    fn generate_thumbnail_path(&self, original_path: &str, media_id: MediaId) -> String {
+    /// WebP and AVIF encoders only take 8 bit RGB images, with or without alpha
+    #[cfg(any(feature = "webp", feature = "avif"))]
+    fn to_8_bit(image: &DynamicImage) -> DynamicImage {
+        match image {
+            DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgba8(_) => image.clone(),
+            _ if image.color().has_alpha() => DynamicImage::ImageRgba8(image.to_rgba8()),
+            _ => DynamicImage::ImageRgb8(image.to_rgb8()),
+        }
+    }
+
+    fn generate_rendition_path(
+        original_path: &str,
+        media_id: MediaId,
+        name: &str,
+        format: RenditionFormat,
+    ) -> String {
         let path_parts: Vec<&str> = original_path.split('/').collect();
         let dir = &path_parts[..path_parts.len() - 1].join("/");
-        format!("{}/thumb_{}.jpg", dir, media_id)
+        format!("{}/thumb_{}_{}.{}", dir, media_id, name, format.extension())
     }
 }
 
+/// Runs CPU heavy image work outside of the async runtime
+async fn run_blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, ThumbnailError> + Send + 'static,
+) -> Result<T, ThumbnailError> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?
+}
+
 #[async_trait]
 impl<MR, FS> ThumbnailService for ImageThumbnailService<MR, FS>
 where
     MR: MediaRepository + 'static,
     FS: FileStorageService + 'static,
 {
-    async fn generate_thumbnail(
+    async fn generate_renditions(
         &self,
         media_id: MediaId,
         original_path: &str,
-        image_data: Vec<u8>,
+        file_stream: FileStream,
         content_type: &str,
     ) -> Result<(), ThumbnailError> {
         if !content_type.starts_with("image/") {
             return Ok(());
         }
 
-        let thumbnail_data = match self.create_thumbnail_data(&image_data) {
-            Ok(data) => data,
-            Err(ThumbnailError::ImageProcessingError(msg)) if msg.contains("already smaller") => {
-                return Ok(());
+        let original =
+            SpooledFile::from_stream(file_stream, self.decode_config.max_in_memory_bytes)
+                .await
+                .map_err(|e| ThumbnailError::StorageError(e.to_string()))?;
+        let format_decoder = self
+            .decoders
+            .iter()
+            .find(|decoder| decoder.supports(content_type))
+            .cloned();
+
+        // Larger images are decoded at a reduced size that fits the cap. The size of a converted
+        // original is only known once it is converted, so it is counted with the most an image
+        // may take.
+        let max_decoded_bytes = self.decode_config.max_decoded_bytes;
+        let (original, decoded_bytes) = match format_decoder {
+            Some(_) => (original, max_decoded_bytes),
+            None => {
+                let (original, decoded_bytes) = run_blocking(move || {
+                    Self::decoded_size(&original).map(|decoded_bytes| (original, decoded_bytes))
+                })
+                .await?;
+                (original, decoded_bytes.min(max_decoded_bytes))
             }
-            Err(e) => return Err(e),
         };
 
-        let thumbnail_path = self.generate_thumbnail_path(original_path, media_id);
+        // Capped at the whole budget so a single large image cannot wait forever
+        let units = decoded_bytes
+            .div_ceil(DECODE_BUDGET_UNIT_BYTES)
+            .clamp(1, self.decode_budget_units as u64) as u32;
+        let permit = self
+            .decode_budget
+            .clone()
+            .acquire_many_owned(units)
+            .await
+            .map_err(|e| ThumbnailError::ImageProcessingError(e.to_string()))?;
 
-        let thumbnail_stream = Box::pin(ReaderStream::new(Cursor::new(thumbnail_data)));
+        let config = self.config.clone();
+        let renditions = run_blocking(move || {
+            let original = match format_decoder {
+                Some(format_decoder) => format_decoder.decode(&original)?,
+                None => original,
+            };
+            Self::create_renditions(&original, &config, max_decoded_bytes)
+        })
+        .await?;
+        drop(permit);
 
-        self.storage_service
-            .store_file(
-                &thumbnail_path,
-                "image/jpeg",
-                Some(image_data.len() as u64),
-                thumbnail_stream,
-            )
-            .await
-            .map_err(|_| {
-                ThumbnailError::StorageError("An error saving the image occurred".to_string())
-            })?;
+        // The first rendition is always encoded, later ones may share its files
+        let mut previous: Vec<MediaRendition> = Vec::new();
+        for encoded in renditions {
+            let renditions = if encoded.files.is_empty() {
+                previous
+                    .into_iter()
+                    .map(|existing| MediaRendition {
+                        name: encoded.size.name.clone(),
+                        ..existing
+                    })
+                    .collect()
+            } else {
+                let mut stored = Vec::with_capacity(encoded.files.len());
+                for (format, rendition_data) in encoded.files {
+                    let file_size = rendition_data.len() as u64;
+                    let file_path = Self::generate_rendition_path(
+                        original_path,
+                        media_id,
+                        &encoded.size.name,
+                        format,
+                    );
+                    let rendition_stream = Box::pin(ReaderStream::new(Cursor::new(rendition_data)));
 
-        self.media_repository
-            .update_thumbnail_path(media_id, Some(thumbnail_path))
-            .await?;
+                    self.storage_service
+                        .store_file(
+                            &file_path,
+                            format.content_type(),
+                            Some(file_size),
+                            rendition_stream,
+                        )
+                        .await
+                        .map_err(|_| {
+                            ThumbnailError::StorageError(
+                                "An error saving the image occurred".to_string(),
+                            )
+                        })?;
+
+                    stored.push(MediaRendition {
+                        media_id,
+                        name: encoded.size.name.clone(),
+                        file_path,
+                        content_type: format.content_type().to_string(),
+                        width: Some(encoded.width as i32),
+                        height: Some(encoded.height as i32),
+                        file_size: Some(file_size as i64),
+                    });
+                }
+                stored
+            };
+
+            for rendition in &renditions {
+                self.media_repository
+                    .save_media_rendition(rendition.clone())
+                    .await?;
+            }
+            previous = renditions;
+        }
 
         Ok(())
     }
@@ -0,0 +1,86 @@
+use uuid::Uuid;
+
+pub type UploadSessionId = Uuid;
+
+/// Size of every chunk but the last one. Each chunk is stored as one part of a multipart
+/// upload, so it has to stay above the 5 MiB minimum part size of S3.
+pub const UPLOAD_SESSION_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Time a client has to finish an upload before the session is cleaned up
+pub const UPLOAD_SESSION_TTL_HOURS: i64 = 24;
+
+/// Age after which a multipart upload is considered abandoned. Resumable uploads expire
+/// before, so only uploads nothing will complete any more are aborted.
+pub const STALE_MULTIPART_UPLOAD_HOURS: i64 = UPLOAD_SESSION_TTL_HOURS + 1;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UploadSession {
+    pub id: UploadSessionId,
+    pub user_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub file_path: String,
+    /// Identifier of the multipart upload in the storage service
+    pub storage_upload_id: String,
+    /// Number of bytes received and stored so far
+    pub upload_offset: i64,
+    /// ETags of the stored parts, part `n` is at index `n - 1`
+    pub part_etags: Vec<String>,
+    pub expires_at: chrono::NaiveDateTime,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl UploadSession {
+    pub fn is_complete(&self) -> bool {
+        self.upload_offset == self.file_size
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= chrono::Utc::now().naive_utc()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewUploadSession {
+    pub user_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub file_path: String,
+    pub storage_upload_id: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UploadSessionError {
+    #[error("Upload session not found")]
+    NotFound,
+    #[error("Invalid file type")]
+    InvalidFileType,
+    #[error("Upload offset mismatch, current offset is {current_offset}")]
+    OffsetMismatch { current_offset: u64 },
+    #[error("Invalid chunk size")]
+    InvalidChunkSize,
+    #[error("Upload is not complete")]
+    Incomplete,
+    /// The assembled content does not match the content type the client declared
+    #[error("Content is not {claimed}")]
+    ContentTypeMismatch {
+        claimed: String,
+        detected: Option<String>,
+    },
+    /// The file is larger than uploads of its content type may be
+    #[error("File too large, maximum size is {max_bytes} bytes")]
+    FileTooLarge { max_bytes: u64 },
+    /// The file does not fit into the storage quota of the user
+    #[error("Storage quota exceeded")]
+    QuotaExceeded,
+    #[error("Storage error: {0}")]
+    StorageError(String),
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
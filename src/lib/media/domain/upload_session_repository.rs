@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use super::upload_session::{NewUploadSession, UploadSession, UploadSessionId};
+
+#[derive(Debug, thiserror::Error)]
+pub enum UploadSessionRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+    #[error("Upload session not found")]
+    UploadSessionNotFound,
+    #[error("Upload session was modified concurrently")]
+    Conflict,
+}
+
+#[async_trait]
+pub trait UploadSessionRepository: Send + Sync {
+    async fn create_upload_session(
+        &self,
+        upload_session: NewUploadSession,
+    ) -> Result<UploadSession, UploadSessionRepositoryError>;
+    async fn get_upload_session_by_id(
+        &self,
+        id: UploadSessionId,
+    ) -> Result<Option<UploadSession>, UploadSessionRepositoryError>;
+    /// Records a stored part, only if the session is still at `expected_offset`
+    async fn append_uploaded_part(
+        &self,
+        id: UploadSessionId,
+        expected_offset: i64,
+        part_size: i64,
+        part_etag: String,
+    ) -> Result<UploadSession, UploadSessionRepositoryError>;
+    async fn get_expired_upload_sessions(
+        &self,
+    ) -> Result<Vec<UploadSession>, UploadSessionRepositoryError>;
+    async fn delete_upload_session(
+        &self,
+        id: UploadSessionId,
+    ) -> Result<(), UploadSessionRepositoryError>;
+}
@@ -0,0 +1,88 @@
+use std::env;
+
+/// Largest upload accepted for content types without a limit of their own
+pub const DEFAULT_MAX_UPLOAD_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Limits applying when `MEDIA_MAX_UPLOAD_BYTES_BY_TYPE` is not set, videos tend to be a lot
+/// larger than photos
+pub const DEFAULT_CONTENT_TYPE_MAX_UPLOAD_BYTES: [(&str, u64); 1] =
+    [("video/*", 4 * 1024 * 1024 * 1024)];
+
+/// Largest uploads accepted per content type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaSizeLimits {
+    pub default_max_bytes: u64,
+    /// Lowercase content types without parameters, `image/*` stands for every image type
+    pub content_type_max_bytes: Vec<(String, u64)>,
+}
+
+impl MediaSizeLimits {
+    /// Reads `MEDIA_MAX_UPLOAD_BYTES` and `MEDIA_MAX_UPLOAD_BYTES_BY_TYPE`, a comma separated
+    /// list of `content_type:max_bytes` pairs, and falls back to the defaults when either is
+    /// missing or invalid
+    pub fn new() -> Self {
+        let defaults = Self::default();
+        Self {
+            default_max_bytes: env::var("MEDIA_MAX_UPLOAD_BYTES")
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(defaults.default_max_bytes),
+            content_type_max_bytes: env::var("MEDIA_MAX_UPLOAD_BYTES_BY_TYPE")
+                .ok()
+                .and_then(|value| Self::parse_content_type_limits(&value))
+                .unwrap_or(defaults.content_type_max_bytes),
+        }
+    }
+
+    /// Parses a list like `video/*:4294967296,image/gif:20971520`, returns `None` when any
+    /// pair is invalid or the list holds none
+    pub fn parse_content_type_limits(value: &str) -> Option<Vec<(String, u64)>> {
+        let limits = value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (content_type, max_bytes) = pair.rsplit_once(':')?;
+                let content_type = content_type.trim().to_ascii_lowercase();
+                let max_bytes = max_bytes.trim().parse::<u64>().ok()?;
+                (!content_type.is_empty()).then_some((content_type, max_bytes))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (!limits.is_empty()).then_some(limits)
+    }
+
+    /// Largest upload accepted for the content type, an exact match wins over a `type/*` one
+    pub fn max_bytes_for(&self, content_type: &str) -> u64 {
+        let content_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let wildcard = content_type
+            .split_once('/')
+            .map(|(top_level, _)| format!("{}/*", top_level));
+
+        let find = |wanted: &str| {
+            self.content_type_max_bytes
+                .iter()
+                .find(|(content_type, _)| content_type == wanted)
+                .map(|(_, max_bytes)| *max_bytes)
+        };
+        find(&content_type)
+            .or_else(|| wildcard.as_deref().and_then(find))
+            .unwrap_or(self.default_max_bytes)
+    }
+}
+
+impl Default for MediaSizeLimits {
+    fn default() -> Self {
+        Self {
+            default_max_bytes: DEFAULT_MAX_UPLOAD_BYTES,
+            content_type_max_bytes: DEFAULT_CONTENT_TYPE_MAX_UPLOAD_BYTES
+                .iter()
+                .map(|(content_type, max_bytes)| (content_type.to_string(), *max_bytes))
+                .collect(),
+        }
+    }
+}
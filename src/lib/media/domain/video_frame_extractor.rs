@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use super::FileStream;
+
+/// Video containers a poster frame can be extracted from
+pub const VIDEO_CONTENT_TYPES: [&str; 3] = ["video/mp4", "video/quicktime", "video/webm"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum VideoFrameExtractorError {
+    #[error("No video stream found")]
+    NoVideoStream,
+    #[error("Frame extraction error: {0}")]
+    ExtractionError(String),
+    /// Reading the video or starting the extraction failed, trying again may succeed
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+/// What was read from a video container
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExtractedVideo {
+    /// PNG image of the frame shown before the video plays
+    pub poster_frame: Vec<u8>,
+    pub duration_seconds: Option<f64>,
+    pub video_codec: Option<String>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub frame_rate: Option<f64>,
+}
+
+#[async_trait]
+pub trait VideoFrameExtractor: Send + Sync {
+    /// Videos can be large, implementations should not buffer the whole stream in memory
+    async fn extract(&self, video: FileStream) -> Result<ExtractedVideo, VideoFrameExtractorError>;
+}
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use uuid::Uuid;
+
+use super::{
+    FileStream, MediaMetadata, MediaRepository, ThumbnailError, ThumbnailService,
+    VIDEO_CONTENT_TYPES, VideoFrameExtractor, VideoFrameExtractorError,
+};
+
+/// Generates the renditions of videos from their poster frame and stores their metadata,
+/// everything else is passed on to the image service it wraps
+pub struct VideoThumbnailService<TS, VE, MR> {
+    image_thumbnail_service: TS,
+    frame_extractor: VE,
+    media_repository: MR,
+}
+
+impl<TS, VE, MR> VideoThumbnailService<TS, VE, MR>
+where
+    TS: ThumbnailService,
+    VE: VideoFrameExtractor,
+    MR: MediaRepository,
+{
+    pub fn new(image_thumbnail_service: TS, frame_extractor: VE, media_repository: MR) -> Self {
+        Self {
+            image_thumbnail_service,
+            frame_extractor,
+            media_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl<TS, VE, MR> ThumbnailService for VideoThumbnailService<TS, VE, MR>
+where
+    TS: ThumbnailService + 'static,
+    VE: VideoFrameExtractor + 'static,
+    MR: MediaRepository + 'static,
+{
+    async fn generate_renditions(
+        &self,
+        media_id: Uuid,
+        original_path: &str,
+        file_stream: FileStream,
+        content_type: &str,
+    ) -> Result<(), ThumbnailError> {
+        if !VIDEO_CONTENT_TYPES.contains(&content_type) {
+            return self
+                .image_thumbnail_service
+                .generate_renditions(media_id, original_path, file_stream, content_type)
+                .await;
+        }
+
+        let video = self
+            .frame_extractor
+            .extract(file_stream)
+            .await
+            .map_err(|e| match e {
+                VideoFrameExtractorError::IoError(_) => ThumbnailError::StorageError(e.to_string()),
+                e => ThumbnailError::ImageProcessingError(e.to_string()),
+            })?;
+
+        self.media_repository
+            .save_media_metadata(MediaMetadata {
+                media_id,
+                width: video.width,
+                height: video.height,
+                duration_seconds: video.duration_seconds,
+                video_codec: video.video_codec,
+                frame_rate: video.frame_rate,
+                ..MediaMetadata::default()
+            })
+            .await?;
+
+        // The image service only looks at the content type to tell images apart
+        let poster_frame = Bytes::from(video.poster_frame);
+        self.image_thumbnail_service
+            .generate_renditions(
+                media_id,
+                original_path,
+                Box::pin(futures_util::stream::once(async move { Ok(poster_frame) })),
+                "image/png",
+            )
+            .await
+    }
+}
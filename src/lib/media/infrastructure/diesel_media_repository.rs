@@ -3,9 +3,19 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use uuid::Uuid;
 
-use super::models::{MediaFileModel, NewMediaFileModel};
+use super::models::{
+    MediaFileModel, MediaMetadataModel, MediaRenditionModel, NewMediaFileModel,
+    NewMediaMetadataModel, NewMediaRenditionModel, RowMediaStatus,
+};
 use crate::media::MediaId;
-use crate::media::domain::{MediaFile, MediaRepository, MediaRepositoryError, NewMediaFile};
+use crate::media::domain::{
+    MediaFile, MediaFileCursor, MediaFilePage, MediaFilePageRequest, MediaFileSort, MediaMetadata,
+    MediaRendition, MediaRepository, MediaRepositoryError, NewMediaFile, StorageUsage,
+    pending_media_files_expired_before,
+};
+
+/// Partial unique index keeping one ready media file per user and checksum
+const CHECKSUM_UNIQUE_INDEX: &str = "idx_media_files_user_id_checksum_ready";
 
 pub struct DieselMediaRepository {
     connection_pool: Pool<ConnectionManager<PgConnection>>,
@@ -35,7 +45,10 @@ impl MediaRepository for DieselMediaRepository {
             .values(&new_media_model)
             .returning(MediaFileModel::as_returning())
             .get_result(&mut conn)
-            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+            .map_err(|e| match e {
+                e if is_duplicate_checksum(&e) => MediaRepositoryError::DuplicateChecksum,
+                _ => MediaRepositoryError::InternalServerError,
+            })?;
 
         Ok(created_media.into())
     }
@@ -64,7 +77,8 @@ impl MediaRepository for DieselMediaRepository {
     async fn get_media_files_by_user_id(
         &self,
         user_uuid: Uuid,
-    ) -> Result<Vec<MediaFile>, MediaRepositoryError> {
+        page: MediaFilePageRequest,
+    ) -> Result<MediaFilePage, MediaRepositoryError> {
         use crate::schema::media_files::dsl::*;
 
         let mut conn = self
@@ -72,14 +86,64 @@ impl MediaRepository for DieselMediaRepository {
             .get()
             .map_err(|_| MediaRepositoryError::InternalServerError)?;
 
-        let results = media_files
+        let mut query = media_files
             .filter(user_id.eq(user_uuid))
-            .order(uploaded_at.desc())
+            .filter(status.eq(RowMediaStatus::Ready))
+            .into_boxed();
+
+        if let Some(prefix) = page.filter.content_type_prefix {
+            query = query.filter(content_type.like(format!("{}%", escape_like(&prefix))));
+        }
+        if let Some(from) = page.filter.uploaded_from {
+            query = query.filter(uploaded_at.ge(from));
+        }
+        if let Some(to) = page.filter.uploaded_to {
+            query = query.filter(uploaded_at.lt(to));
+        }
+        if let Some(substring) = page.filter.filename_contains {
+            query = query.filter(original_filename.ilike(format!("%{}%", escape_like(&substring))));
+        }
+
+        // Keyset pagination on (uploaded_at, id) so deep pages stay as cheap as the first one
+        query = match (page.sort, page.cursor) {
+            (MediaFileSort::UploadedAtDesc, Some(cursor)) => query.filter(
+                uploaded_at
+                    .lt(cursor.uploaded_at)
+                    .or(uploaded_at.eq(cursor.uploaded_at).and(id.lt(cursor.id))),
+            ),
+            (MediaFileSort::UploadedAtAsc, Some(cursor)) => query.filter(
+                uploaded_at
+                    .gt(cursor.uploaded_at)
+                    .or(uploaded_at.eq(cursor.uploaded_at).and(id.gt(cursor.id))),
+            ),
+            (_, None) => query,
+        };
+        query = match page.sort {
+            MediaFileSort::UploadedAtDesc => query.order((uploaded_at.desc(), id.desc())),
+            MediaFileSort::UploadedAtAsc => query.order((uploaded_at.asc(), id.asc())),
+        };
+
+        // One extra row tells whether there is a next page
+        let mut results: Vec<MediaFile> = query
+            .limit(page.limit + 1)
             .select(MediaFileModel::as_select())
             .load::<MediaFileModel>(&mut conn)
-            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+            .map_err(|_| MediaRepositoryError::InternalServerError)?
+            .into_iter()
+            .map(|model| model.into())
+            .collect();
 
-        Ok(results.into_iter().map(|model| model.into()).collect())
+        let next_cursor = if results.len() as i64 > page.limit {
+            results.truncate(page.limit as usize);
+            results.last().and_then(MediaFileCursor::from_media_file)
+        } else {
+            None
+        };
+
+        Ok(MediaFilePage {
+            media_files: results,
+            next_cursor,
+        })
     }
 
     async fn delete_media_file(&self, media_id: Uuid) -> Result<(), MediaRepositoryError> {
@@ -101,11 +165,10 @@ impl MediaRepository for DieselMediaRepository {
         }
     }
 
-    async fn update_thumbnail_path(
+    async fn get_media_path_by_id(
         &self,
         media_id: MediaId,
-        thumbnail_path_value: Option<String>,
-    ) -> Result<(), MediaRepositoryError> {
+    ) -> Result<String, MediaRepositoryError> {
         use crate::schema::media_files::dsl::*;
 
         let mut conn = self
@@ -113,22 +176,22 @@ impl MediaRepository for DieselMediaRepository {
             .get()
             .map_err(|_| MediaRepositoryError::InternalServerError)?;
 
-        let updated_rows = diesel::update(media_files.filter(id.eq(media_id)))
-            .set(thumbnail_path.eq(thumbnail_path_value))
-            .execute(&mut conn)
-            .map_err(|_| MediaRepositoryError::InternalServerError)?;
-
-        if updated_rows == 0 {
-            Err(MediaRepositoryError::MediaFileNotFound)
-        } else {
-            Ok(())
-        }
+        media_files
+            .filter(id.eq(media_id))
+            .select(file_path)
+            .first::<String>(&mut conn)
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => MediaRepositoryError::MediaFileNotFound,
+                _ => MediaRepositoryError::InternalServerError,
+            })
     }
 
-    async fn get_media_path_by_id(
+    async fn mark_media_file_ready(
         &self,
         media_id: MediaId,
-    ) -> Result<String, MediaRepositoryError> {
+        file_checksum: String,
+        detected: Option<String>,
+    ) -> Result<MediaFile, MediaRepositoryError> {
         use crate::schema::media_files::dsl::*;
 
         let mut conn = self
@@ -136,13 +199,213 @@ impl MediaRepository for DieselMediaRepository {
             .get()
             .map_err(|_| MediaRepositoryError::InternalServerError)?;
 
-        media_files
-            .filter(id.eq(media_id))
-            .select(file_path)
-            .first::<String>(&mut conn)
+        diesel::update(media_files.filter(id.eq(media_id)))
+            .set((
+                status.eq(RowMediaStatus::Ready),
+                checksum.eq(file_checksum),
+                detected_content_type.eq(detected),
+            ))
+            .returning(MediaFileModel::as_returning())
+            .get_result(&mut conn)
+            .map(|model| model.into())
             .map_err(|e| match e {
                 diesel::result::Error::NotFound => MediaRepositoryError::MediaFileNotFound,
+                e if is_duplicate_checksum(&e) => MediaRepositoryError::DuplicateChecksum,
                 _ => MediaRepositoryError::InternalServerError,
             })
     }
+
+    async fn get_media_files_by_checksums(
+        &self,
+        user_uuid: Uuid,
+        checksums: Vec<String>,
+    ) -> Result<Vec<MediaFile>, MediaRepositoryError> {
+        use crate::schema::media_files::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        let results = media_files
+            .filter(user_id.eq(user_uuid))
+            .filter(status.eq(RowMediaStatus::Ready))
+            .filter(checksum.eq_any(checksums))
+            .order(uploaded_at.asc())
+            .select(MediaFileModel::as_select())
+            .load::<MediaFileModel>(&mut conn)
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn save_media_metadata(
+        &self,
+        metadata: MediaMetadata,
+    ) -> Result<(), MediaRepositoryError> {
+        use crate::schema::media_metadata::dsl::*;
+
+        let new_metadata_model: NewMediaMetadataModel = metadata.into();
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        diesel::insert_into(media_metadata)
+            .values(&new_metadata_model)
+            .on_conflict(media_id)
+            .do_update()
+            .set(&new_metadata_model)
+            .execute(&mut conn)
+            .map_err(|e| match e {
+                // The media file was deleted while its metadata was being extracted
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => MediaRepositoryError::MediaFileNotFound,
+                _ => MediaRepositoryError::InternalServerError,
+            })?;
+
+        Ok(())
+    }
+
+    async fn get_media_metadata_by_media_ids(
+        &self,
+        media_ids: Vec<MediaId>,
+    ) -> Result<Vec<MediaMetadata>, MediaRepositoryError> {
+        use crate::schema::media_metadata::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        let results = media_metadata
+            .filter(media_id.eq_any(media_ids))
+            .select(MediaMetadataModel::as_select())
+            .load::<MediaMetadataModel>(&mut conn)
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn save_media_rendition(
+        &self,
+        rendition: MediaRendition,
+    ) -> Result<(), MediaRepositoryError> {
+        use crate::schema::media_renditions::dsl::*;
+
+        let new_rendition_model: NewMediaRenditionModel = rendition.into();
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        diesel::insert_into(media_renditions)
+            .values(&new_rendition_model)
+            .on_conflict((media_id, name, content_type))
+            .do_update()
+            .set(&new_rendition_model)
+            .execute(&mut conn)
+            .map_err(|e| match e {
+                // The media file was deleted while its renditions were being generated
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => MediaRepositoryError::MediaFileNotFound,
+                _ => MediaRepositoryError::InternalServerError,
+            })?;
+
+        Ok(())
+    }
+
+    async fn get_media_renditions_by_media_ids(
+        &self,
+        media_ids: Vec<MediaId>,
+    ) -> Result<Vec<MediaRendition>, MediaRepositoryError> {
+        use crate::schema::media_renditions::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        let results = media_renditions
+            .filter(media_id.eq_any(media_ids))
+            .select(MediaRenditionModel::as_select())
+            .load::<MediaRenditionModel>(&mut conn)
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn get_pending_media_files_uploaded_before(
+        &self,
+        uploaded_before: chrono::NaiveDateTime,
+    ) -> Result<Vec<MediaFile>, MediaRepositoryError> {
+        use crate::schema::media_files::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        let results = media_files
+            .filter(status.eq(RowMediaStatus::Pending))
+            .filter(uploaded_at.lt(uploaded_before))
+            .select(MediaFileModel::as_select())
+            .load::<MediaFileModel>(&mut conn)
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn get_storage_usage(
+        &self,
+        user_uuid: Uuid,
+    ) -> Result<StorageUsage, MediaRepositoryError> {
+        use crate::schema::media_files::dsl::*;
+        use diesel::{dsl::sql, sql_types::BigInt};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        // SUM of BIGINT is NUMERIC in PostgreSQL
+        let (bytes, items) = media_files
+            .filter(user_id.eq(user_uuid))
+            .filter(
+                status
+                    .eq(RowMediaStatus::Ready)
+                    .or(uploaded_at.ge(pending_media_files_expired_before())),
+            )
+            .select((
+                sql::<BigInt>("COALESCE(SUM(file_size), 0)::BIGINT"),
+                diesel::dsl::count_star(),
+            ))
+            .first::<(i64, i64)>(&mut conn)
+            .map_err(|_| MediaRepositoryError::InternalServerError)?;
+
+        Ok(StorageUsage { bytes, items })
+    }
+}
+
+/// Escapes the `LIKE` wildcards of user input so it is matched literally
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Whether storing a ready media file ran into another one of the same user and content
+fn is_duplicate_checksum(error: &diesel::result::Error) -> bool {
+    matches!(
+        error,
+        diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::UniqueViolation,
+            info,
+        ) if info.constraint_name() == Some(CHECKSUM_UNIQUE_INDEX)
+    )
 }
@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use super::models::{NewUploadSessionModel, UploadSessionModel};
+use crate::media::domain::{
+    NewUploadSession, UploadSession, UploadSessionId, UploadSessionRepository,
+    UploadSessionRepositoryError,
+};
+
+pub struct DieselUploadSessionRepository {
+    connection_pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselUploadSessionRepository {
+    pub fn new(connection_pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+#[async_trait]
+impl UploadSessionRepository for DieselUploadSessionRepository {
+    async fn create_upload_session(
+        &self,
+        upload_session: NewUploadSession,
+    ) -> Result<UploadSession, UploadSessionRepositoryError> {
+        use crate::schema::upload_sessions::dsl::*;
+
+        let new_session_model: NewUploadSessionModel = upload_session.into();
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        let created_session = diesel::insert_into(upload_sessions)
+            .values(&new_session_model)
+            .returning(UploadSessionModel::as_returning())
+            .get_result(&mut conn)
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        Ok(created_session.into())
+    }
+
+    async fn get_upload_session_by_id(
+        &self,
+        session_id: UploadSessionId,
+    ) -> Result<Option<UploadSession>, UploadSessionRepositoryError> {
+        use crate::schema::upload_sessions::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        let result = upload_sessions
+            .filter(id.eq(session_id))
+            .select(UploadSessionModel::as_select())
+            .first::<UploadSessionModel>(&mut conn)
+            .optional()
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        Ok(result.map(|model| model.into()))
+    }
+
+    async fn append_uploaded_part(
+        &self,
+        session_id: UploadSessionId,
+        expected_offset: i64,
+        part_size: i64,
+        part_etag: String,
+    ) -> Result<UploadSession, UploadSessionRepositoryError> {
+        use crate::schema::upload_sessions::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        conn.transaction(|conn| {
+            let session = upload_sessions
+                .filter(id.eq(session_id))
+                .select(UploadSessionModel::as_select())
+                .for_update()
+                .first::<UploadSessionModel>(conn)
+                .optional()?
+                .ok_or(UploadSessionRepositoryError::UploadSessionNotFound)?;
+
+            // Another request stored this part first
+            if session.upload_offset != expected_offset {
+                return Err(UploadSessionRepositoryError::Conflict);
+            }
+
+            let mut etags = session.part_etags;
+            etags.push(Some(part_etag));
+
+            diesel::update(upload_sessions.filter(id.eq(session_id)))
+                .set((
+                    upload_offset.eq(expected_offset + part_size),
+                    part_etags.eq(etags),
+                ))
+                .returning(UploadSessionModel::as_returning())
+                .get_result(conn)
+                .map_err(UploadSessionRepositoryError::from)
+        })
+        .map(|model| model.into())
+    }
+
+    async fn get_expired_upload_sessions(
+        &self,
+    ) -> Result<Vec<UploadSession>, UploadSessionRepositoryError> {
+        use crate::schema::upload_sessions::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        let results = upload_sessions
+            .filter(expires_at.le(chrono::Utc::now().naive_utc()))
+            .select(UploadSessionModel::as_select())
+            .load::<UploadSessionModel>(&mut conn)
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn delete_upload_session(
+        &self,
+        session_id: UploadSessionId,
+    ) -> Result<(), UploadSessionRepositoryError> {
+        use crate::schema::upload_sessions::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        let deleted_rows = diesel::delete(upload_sessions.filter(id.eq(session_id)))
+            .execute(&mut conn)
+            .map_err(|_| UploadSessionRepositoryError::InternalServerError)?;
+
+        if deleted_rows == 0 {
+            Err(UploadSessionRepositoryError::UploadSessionNotFound)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<diesel::result::Error> for UploadSessionRepositoryError {
+    fn from(_: diesel::result::Error) -> Self {
+        UploadSessionRepositoryError::InternalServerError
+    }
+}
@@ -0,0 +1,213 @@
+use std::{env, process::Stdio, time::Duration};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::process::Command;
+
+use crate::media::domain::{
+    ExtractedVideo, FileStream, TempFile, VideoFrameExtractor, VideoFrameExtractorError,
+};
+
+const DEFAULT_POSTER_FRAME_OFFSET_SECONDS: f64 = 1.0;
+const DEFAULT_FFMPEG_TIMEOUT_SECONDS: u64 = 120;
+
+#[derive(Clone)]
+pub struct FfmpegFrameExtractorConfig {
+    pub ffmpeg_path: String,
+    pub ffprobe_path: String,
+    /// Position of the poster frame, moved to the start for shorter videos
+    pub poster_frame_offset_seconds: f64,
+    /// ffprobe and ffmpeg runs taking longer are killed, a malformed container may stall them
+    pub timeout: Duration,
+}
+
+impl FfmpegFrameExtractorConfig {
+    pub fn new() -> Self {
+        FfmpegFrameExtractorConfig {
+            ffmpeg_path: env::var("FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string()),
+            ffprobe_path: env::var("FFPROBE_PATH").unwrap_or_else(|_| "ffprobe".to_string()),
+            poster_frame_offset_seconds: env::var("POSTER_FRAME_OFFSET_SECONDS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|offset| *offset >= 0.0)
+                .unwrap_or(DEFAULT_POSTER_FRAME_OFFSET_SECONDS),
+            timeout: Duration::from_secs(
+                env::var("FFMPEG_TIMEOUT_SECONDS")
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(DEFAULT_FFMPEG_TIMEOUT_SECONDS),
+            ),
+        }
+    }
+}
+
+impl Default for FfmpegFrameExtractorConfig {
+    fn default() -> Self {
+        FfmpegFrameExtractorConfig::new()
+    }
+}
+
+/// Runs `ffprobe` and `ffmpeg` as subprocesses, both have to be installed on the host
+#[derive(Clone)]
+pub struct FfmpegFrameExtractor {
+    config: FfmpegFrameExtractorConfig,
+}
+
+#[derive(Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+    format: Option<ProbeFormat>,
+}
+
+#[derive(Deserialize)]
+struct ProbeStream {
+    codec_name: Option<String>,
+    width: Option<i32>,
+    height: Option<i32>,
+    avg_frame_rate: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProbeFormat {
+    duration: Option<String>,
+}
+
+impl FfmpegFrameExtractor {
+    pub fn new(config: FfmpegFrameExtractorConfig) -> Self {
+        FfmpegFrameExtractor { config }
+    }
+
+    async fn run(&self, program: &str, args: &[&str]) -> Result<Vec<u8>, VideoFrameExtractorError> {
+        let mut command = Command::new(program);
+        command.args(args).stdin(Stdio::null()).kill_on_drop(true);
+
+        // Dropping the output future kills the process
+        let output = tokio::time::timeout(self.config.timeout, command.output())
+            .await
+            .map_err(|_| {
+                VideoFrameExtractorError::ExtractionError(format!(
+                    "{} timed out after {} seconds",
+                    program,
+                    self.config.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| {
+                VideoFrameExtractorError::IoError(format!("Failed to run {}: {}", program, e))
+            })?;
+
+        if !output.status.success() {
+            return Err(VideoFrameExtractorError::ExtractionError(format!(
+                "{} failed: {}",
+                program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(output.stdout)
+    }
+
+    async fn probe(&self, path: &str) -> Result<ProbeOutput, VideoFrameExtractorError> {
+        let stdout = self
+            .run(
+                &self.config.ffprobe_path,
+                &[
+                    "-v",
+                    "error",
+                    "-select_streams",
+                    "v:0",
+                    "-show_entries",
+                    "stream=codec_name,width,height,avg_frame_rate:format=duration",
+                    "-of",
+                    "json",
+                    path,
+                ],
+            )
+            .await?;
+
+        serde_json::from_slice(&stdout)
+            .map_err(|e| VideoFrameExtractorError::ExtractionError(e.to_string()))
+    }
+
+    async fn extract_frame(
+        &self,
+        path: &str,
+        offset_seconds: f64,
+    ) -> Result<Vec<u8>, VideoFrameExtractorError> {
+        let offset = format!("{:.3}", offset_seconds);
+        self.run(
+            &self.config.ffmpeg_path,
+            &[
+                "-v",
+                "error",
+                "-ss",
+                &offset,
+                "-i",
+                path,
+                "-frames:v",
+                "1",
+                "-f",
+                "image2pipe",
+                "-vcodec",
+                "png",
+                "-",
+            ],
+        )
+        .await
+    }
+}
+
+/// Parses frame rates the way ffprobe reports them, e.g. `30000/1001`
+fn parse_frame_rate(value: &str) -> Option<f64> {
+    let (numerator, denominator) = value.split_once('/')?;
+    let numerator = numerator.parse::<f64>().ok()?;
+    let denominator = denominator.parse::<f64>().ok()?;
+    (denominator > 0.0 && numerator > 0.0).then(|| numerator / denominator)
+}
+
+#[async_trait]
+impl VideoFrameExtractor for FfmpegFrameExtractor {
+    async fn extract(&self, video: FileStream) -> Result<ExtractedVideo, VideoFrameExtractorError> {
+        // MP4 and MOV files may keep their index at the end, ffmpeg needs to seek so it cannot
+        // read them from a pipe
+        let temp_file = TempFile::from_stream("video", video)
+            .await
+            .map_err(|e| VideoFrameExtractorError::IoError(e.to_string()))?;
+        let path = temp_file.path().to_string_lossy().to_string();
+
+        let probe = self.probe(&path).await?;
+        let stream = probe
+            .streams
+            .into_iter()
+            .next()
+            .ok_or(VideoFrameExtractorError::NoVideoStream)?;
+        let duration_seconds = probe
+            .format
+            .and_then(|format| format.duration)
+            .and_then(|duration| duration.parse::<f64>().ok());
+
+        // Very short videos have no frame at the configured offset
+        let offset_seconds = match duration_seconds {
+            Some(duration) if duration <= self.config.poster_frame_offset_seconds => duration / 2.0,
+            _ => self.config.poster_frame_offset_seconds,
+        };
+        let mut poster_frame = self.extract_frame(&path, offset_seconds).await?;
+        if poster_frame.is_empty() {
+            poster_frame = self.extract_frame(&path, 0.0).await?;
+        }
+        if poster_frame.is_empty() {
+            return Err(VideoFrameExtractorError::ExtractionError(
+                "No frame could be decoded".to_string(),
+            ));
+        }
+
+        Ok(ExtractedVideo {
+            poster_frame,
+            duration_seconds,
+            video_codec: stream.codec_name,
+            width: stream.width,
+            height: stream.height,
+            frame_rate: stream.avg_frame_rate.as_deref().and_then(parse_frame_rate),
+        })
+    }
+}
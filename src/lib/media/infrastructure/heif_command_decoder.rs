@@ -0,0 +1,120 @@
+use std::{env, process::Stdio, time::Duration};
+
+use tokio::process::Command;
+
+use crate::media::domain::{ImageFormatDecoder, SpooledFile, TempFile, ThumbnailError};
+
+/// HEIC is what iPhones upload, AVIF is only read here as the image crate cannot decode it
+/// without native libraries
+const HEIF_CONTENT_TYPES: [&str; 5] = [
+    "image/heic",
+    "image/heif",
+    "image/heic-sequence",
+    "image/heif-sequence",
+    "image/avif",
+];
+
+const DEFAULT_HEIF_CONVERT_TIMEOUT_SECONDS: u64 = 60;
+
+#[derive(Clone)]
+pub struct HeifCommandDecoderConfig {
+    pub heif_convert_path: String,
+    /// Conversions running longer are killed, a malformed file may make heif-convert hang
+    pub timeout: Duration,
+}
+
+impl HeifCommandDecoderConfig {
+    pub fn new() -> Self {
+        HeifCommandDecoderConfig {
+            heif_convert_path: env::var("HEIF_CONVERT_PATH")
+                .unwrap_or_else(|_| "heif-convert".to_string()),
+            timeout: Duration::from_secs(
+                env::var("HEIF_CONVERT_TIMEOUT_SECONDS")
+                    .ok()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(DEFAULT_HEIF_CONVERT_TIMEOUT_SECONDS),
+            ),
+        }
+    }
+}
+
+impl Default for HeifCommandDecoderConfig {
+    fn default() -> Self {
+        HeifCommandDecoderConfig::new()
+    }
+}
+
+/// Runs `heif-convert` from libheif as a subprocess, which has to be installed on the host, and
+/// converts the primary image to a JPEG. Unlike a PNG, a JPEG over the decode cap can still be
+/// decoded at a reduced size, transparency is lost but phones do not record any.
+#[derive(Clone)]
+pub struct HeifCommandDecoder {
+    config: HeifCommandDecoderConfig,
+}
+
+impl HeifCommandDecoder {
+    pub fn new(config: HeifCommandDecoderConfig) -> Self {
+        HeifCommandDecoder { config }
+    }
+}
+
+impl ImageFormatDecoder for HeifCommandDecoder {
+    fn supports(&self, content_type: &str) -> bool {
+        HEIF_CONTENT_TYPES
+            .iter()
+            .any(|heif_content_type| heif_content_type.eq_ignore_ascii_case(content_type))
+    }
+
+    fn decode(&self, original: &SpooledFile) -> Result<SpooledFile, ThumbnailError> {
+        let io_error = |e: std::io::Error| ThumbnailError::StorageError(e.to_string());
+
+        // heif-convert only reads files
+        let input_file;
+        let input_path = match original {
+            SpooledFile::Disk(temp_file) => temp_file.path(),
+            SpooledFile::Memory(data) => {
+                input_file = TempFile::new("heif");
+                std::fs::write(input_file.path(), data).map_err(io_error)?;
+                input_file.path()
+            }
+        };
+        // The output format is picked from the extension
+        let output_file = TempFile::with_extension("heif", "jpg");
+
+        let mut command = Command::new(&self.config.heif_convert_path);
+        command
+            .args(["-q", "95"])
+            .arg(input_path)
+            .arg(output_file.path())
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+
+        // Decoding runs on a blocking thread, the subprocess is awaited on the runtime so it can
+        // be killed once it times out
+        let output = tokio::runtime::Handle::current()
+            .block_on(tokio::time::timeout(self.config.timeout, command.output()))
+            .map_err(|_| {
+                ThumbnailError::ImageProcessingError(format!(
+                    "{} timed out after {} seconds",
+                    self.config.heif_convert_path,
+                    self.config.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| {
+                ThumbnailError::ImageProcessingError(format!(
+                    "Failed to run {}: {}",
+                    self.config.heif_convert_path, e
+                ))
+            })?;
+
+        if !output.status.success() || !output_file.path().exists() {
+            return Err(ThumbnailError::ImageProcessingError(format!(
+                "{} failed: {}",
+                self.config.heif_convert_path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(SpooledFile::Disk(output_file))
+    }
+}
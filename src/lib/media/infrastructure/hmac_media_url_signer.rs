@@ -0,0 +1,102 @@
+use std::env;
+
+use hmac::{Hmac, Mac};
+use ring::hkdf;
+use sha2::Sha256;
+
+use crate::media::{
+    MediaId,
+    domain::{MediaUrlSigner, SignedMediaUrl},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const DEFAULT_SIGNED_URL_TTL_SECONDS: u64 = 300;
+
+/// HKDF info separating the media URL key from the tokens signed with `JWT_SECRET_KEY`
+const MEDIA_URL_KEY_INFO: &[u8] = b"media-url-signing";
+
+#[derive(Debug, thiserror::Error)]
+pub enum HmacMediaUrlSignerConfigError {
+    /// Servers signing tokens with the keys of `JWT_KEY_DIR` have no secret to fall back to
+    #[error("MEDIA_URL_SECRET_KEY has to be set when JWT_SECRET_KEY is not")]
+    MissingSecretKey,
+}
+
+#[derive(Clone)]
+pub struct HmacMediaUrlSignerConfig {
+    pub secret_key: String,
+    pub ttl_seconds: u64,
+}
+
+impl HmacMediaUrlSignerConfig {
+    pub fn new() -> Result<Self, HmacMediaUrlSignerConfigError> {
+        Ok(HmacMediaUrlSignerConfig {
+            // Existing deployments keep working with a key derived from the JWT secret
+            secret_key: env::var("MEDIA_URL_SECRET_KEY")
+                .or_else(|_| env::var("JWT_SECRET_KEY").map(|jwt| derive_media_url_secret(&jwt)))
+                .map_err(|_| HmacMediaUrlSignerConfigError::MissingSecretKey)?,
+            ttl_seconds: env::var("MEDIA_URL_TTL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_SIGNED_URL_TTL_SECONDS),
+        })
+    }
+}
+
+/// Hex encoded HKDF-SHA256 key for media URLs, so the JWT secret itself never signs them
+pub fn derive_media_url_secret(jwt_secret_key: &str) -> String {
+    let mut key = [0u8; 32];
+    hkdf::Salt::new(hkdf::HKDF_SHA256, &[])
+        .extract(jwt_secret_key.as_bytes())
+        .expand(&[MEDIA_URL_KEY_INFO], hkdf::HKDF_SHA256)
+        .and_then(|okm| okm.fill(&mut key))
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    hex::encode(key)
+}
+
+#[derive(Clone)]
+pub struct HmacMediaUrlSigner {
+    config: HmacMediaUrlSignerConfig,
+}
+
+impl HmacMediaUrlSigner {
+    pub fn new(config: HmacMediaUrlSignerConfig) -> Self {
+        HmacMediaUrlSigner { config }
+    }
+
+    fn mac(&self, media_id: MediaId, expires_at: u64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.config.secret_key.as_bytes())
+            .expect("HMAC accepts keys of any size");
+        mac.update(format!("{}:{}", media_id, expires_at).as_bytes());
+        mac
+    }
+}
+
+impl MediaUrlSigner for HmacMediaUrlSigner {
+    fn sign(&self, media_id: MediaId) -> SignedMediaUrl {
+        let expires_at = chrono::Utc::now().timestamp() as u64 + self.config.ttl_seconds;
+        let signature = hex::encode(self.mac(media_id, expires_at).finalize().into_bytes());
+
+        SignedMediaUrl {
+            media_id,
+            expires_at,
+            signature,
+        }
+    }
+
+    fn verify(&self, media_id: MediaId, expires_at: u64, signature: &str) -> bool {
+        if expires_at < chrono::Utc::now().timestamp() as u64 {
+            return false;
+        }
+
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        // verify_slice compares in constant time
+        self.mac(media_id, expires_at)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+}
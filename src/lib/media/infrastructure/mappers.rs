@@ -1,5 +1,10 @@
-use super::models::{MediaFileModel, NewMediaFileModel};
-use crate::media::domain::{MediaFile, NewMediaFile};
+use super::models::{
+    MediaFileModel, MediaMetadataModel, MediaRenditionModel, NewMediaFileModel,
+    NewMediaMetadataModel, NewMediaRenditionModel, NewUploadSessionModel, UploadSessionModel,
+};
+use crate::media::domain::{
+    MediaFile, MediaMetadata, MediaRendition, NewMediaFile, NewUploadSession, UploadSession,
+};
 
 impl From<MediaFileModel> for MediaFile {
     fn from(model: MediaFileModel) -> Self {
@@ -11,7 +16,9 @@ impl From<MediaFileModel> for MediaFile {
             file_size: model.file_size,
             content_type: model.content_type,
             file_path: model.file_path,
-            thumbnail_path: model.thumbnail_path,
+            status: model.status.into(),
+            checksum: model.checksum,
+            detected_content_type: model.detected_content_type,
             uploaded_at: model.uploaded_at,
             updated_at: model.updated_at,
         }
@@ -27,7 +34,118 @@ impl From<NewMediaFile> for NewMediaFileModel {
             file_size: new_media.file_size,
             content_type: new_media.content_type,
             file_path: new_media.file_path,
-            thumbnail_path: new_media.thumbnail_path,
+            status: new_media.status.into(),
+            checksum: new_media.checksum,
+            detected_content_type: new_media.detected_content_type,
+        }
+    }
+}
+
+impl From<UploadSessionModel> for UploadSession {
+    fn from(model: UploadSessionModel) -> Self {
+        UploadSession {
+            id: model.id,
+            user_id: model.user_id,
+            filename: model.filename,
+            original_filename: model.original_filename,
+            file_size: model.file_size,
+            content_type: model.content_type,
+            file_path: model.file_path,
+            storage_upload_id: model.storage_upload_id,
+            upload_offset: model.upload_offset,
+            part_etags: model.part_etags.into_iter().flatten().collect(),
+            expires_at: model.expires_at,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<NewUploadSession> for NewUploadSessionModel {
+    fn from(new_session: NewUploadSession) -> Self {
+        NewUploadSessionModel {
+            user_id: new_session.user_id,
+            filename: new_session.filename,
+            original_filename: new_session.original_filename,
+            file_size: new_session.file_size,
+            content_type: new_session.content_type,
+            file_path: new_session.file_path,
+            storage_upload_id: new_session.storage_upload_id,
+            expires_at: new_session.expires_at,
+        }
+    }
+}
+
+impl From<MediaMetadataModel> for MediaMetadata {
+    fn from(model: MediaMetadataModel) -> Self {
+        MediaMetadata {
+            media_id: model.media_id,
+            taken_at: model.taken_at,
+            camera_make: model.camera_make,
+            camera_model: model.camera_model,
+            orientation: model.orientation,
+            width: model.width,
+            height: model.height,
+            latitude: model.latitude,
+            longitude: model.longitude,
+            exposure_time: model.exposure_time,
+            f_number: model.f_number,
+            iso: model.iso,
+            focal_length: model.focal_length,
+            duration_seconds: model.duration_seconds,
+            video_codec: model.video_codec,
+            frame_rate: model.frame_rate,
+        }
+    }
+}
+
+impl From<MediaMetadata> for NewMediaMetadataModel {
+    fn from(metadata: MediaMetadata) -> Self {
+        NewMediaMetadataModel {
+            media_id: metadata.media_id,
+            taken_at: metadata.taken_at,
+            camera_make: metadata.camera_make,
+            camera_model: metadata.camera_model,
+            orientation: metadata.orientation,
+            width: metadata.width,
+            height: metadata.height,
+            latitude: metadata.latitude,
+            longitude: metadata.longitude,
+            exposure_time: metadata.exposure_time,
+            f_number: metadata.f_number,
+            iso: metadata.iso,
+            focal_length: metadata.focal_length,
+            duration_seconds: metadata.duration_seconds,
+            video_codec: metadata.video_codec,
+            frame_rate: metadata.frame_rate,
+        }
+    }
+}
+
+impl From<MediaRenditionModel> for MediaRendition {
+    fn from(model: MediaRenditionModel) -> Self {
+        MediaRendition {
+            media_id: model.media_id,
+            name: model.name,
+            file_path: model.file_path,
+            content_type: model.content_type,
+            width: model.width,
+            height: model.height,
+            file_size: model.file_size,
+        }
+    }
+}
+
+impl From<MediaRendition> for NewMediaRenditionModel {
+    fn from(rendition: MediaRendition) -> Self {
+        NewMediaRenditionModel {
+            media_id: rendition.media_id,
+            name: rendition.name,
+            file_path: rendition.file_path,
+            content_type: rendition.content_type,
+            width: rendition.width,
+            height: rendition.height,
+            file_size: rendition.file_size,
         }
     }
 }
@@ -1,7 +1,9 @@
 use async_trait::async_trait;
 use aws_config::{BehaviorVersion, Region};
 use aws_sdk_s3::config::{RequestChecksumCalculation, ResponseChecksumValidation};
-use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::error::{DisplayErrorContext, SdkError};
+use aws_sdk_s3::operation::get_object::{GetObjectError, GetObjectOutput};
+use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
 use aws_sdk_s3::{Client, Error as S3Error};
@@ -11,11 +13,15 @@ use futures_util::StreamExt;
 use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
-use tokio::task::JoinSet;
+use tokio::task::{JoinError, JoinSet};
 use tokio_util::io::ReaderStream;
 
-use crate::media::domain::file_storage_service::{
-    FileStorageError, FileStorageService, FileStream, UploadedFileMetadata,
+use crate::media::domain::{
+    ByteRange,
+    file_storage_service::{
+        FileStorageError, FileStorageService, FileStream, IncompleteMultipartUpload,
+        PresignedUpload, StoredFileMetadata, UploadedFileMetadata, UploadedPart,
+    },
 };
 
 pub struct MinioStorageService {
@@ -119,56 +125,25 @@ impl MinioStorageService {
             file_size: total_size,
         })
     }
-}
 
-#[async_trait]
-impl FileStorageService for MinioStorageService {
-    async fn store_file(
+    /// Streams the file into the parts of a multipart upload and completes it, returns the
+    /// number of bytes stored. Part uploads still running on failure are left in `upload_tasks`.
+    async fn upload_parts(
         &self,
         file_path: &str,
-        content_type: &str,
-        file_size: Option<u64>, // file_size is unused, can be removed if not needed elsewhere
+        upload_id: &str,
         mut file_data: Pin<
             Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync + 'static>,
         >,
-    ) -> Result<UploadedFileMetadata, FileStorageError> {
-        // Check if the file is smaller than the size of a chunk and upload it directly if so
-        if let Some(size) = file_size {
-            if size <= CHUNK_SIZE {
-                return self
-                    .store_small_file(file_path, content_type, file_data)
-                    .await;
-            }
-        }
-
-        // 1. Create multipart upload
-        let multipart_upload_res = self
-            .client
-            .create_multipart_upload()
-            .bucket(&self.bucket)
-            .key(file_path)
-            .content_type(content_type)
-            .send()
-            .await
-            .map_err(|e| {
-                FileStorageError::InternalError(format!(
-                    "Failed to create multipart upload: {}",
-                    DisplayErrorContext(e)
-                ))
-            })?;
-
-        let upload_id = multipart_upload_res.upload_id().ok_or_else(|| {
-            FileStorageError::InternalError("Failed to get upload ID".to_string())
-        })?;
-
-        // 2. Set up for concurrent part uploads
+        upload_tasks: &mut JoinSet<Result<CompletedPart, FileStorageError>>,
+    ) -> Result<u64, FileStorageError> {
+        // Set up for concurrent part uploads
         let mut part_number = 1i32;
         let mut total_size = 0u64;
         let mut current_chunk = Vec::with_capacity(CHUNK_SIZE as usize);
-        let mut upload_tasks = JoinSet::new();
         let mut completed_parts = Vec::new();
 
-        // 3. Process the stream and spawn upload tasks
+        // Process the stream and spawn upload tasks
         while let Some(chunk_result) = file_data.next().await {
             let chunk = chunk_result.map_err(|e| {
                 FileStorageError::InternalError(format!("Failed to read file data: {}", e))
@@ -220,9 +195,14 @@ impl FileStorageService for MinioStorageService {
 
                 part_number += 1;
             }
+
+            // Stop reading as soon as a part failed instead of at the end of the stream
+            while let Some(join_result) = upload_tasks.try_join_next() {
+                completed_parts.push(Self::completed_part(join_result)?);
+            }
         }
 
-        // 4. Handle the final chunk (if any)
+        // Handle the final chunk (if any)
         if !current_chunk.is_empty() {
             let permit = self
                 .concurrent_upload_semaphore
@@ -261,15 +241,12 @@ impl FileStorageService for MinioStorageService {
             });
         }
 
-        // 5. Collect results from all tasks
+        // Collect results from all tasks
         while let Some(join_result) = upload_tasks.join_next().await {
-            let part_result = join_result.map_err(|e| {
-                FileStorageError::InternalError(format!("Upload task panicked: {}", e))
-            })?;
-            completed_parts.push(part_result?);
+            completed_parts.push(Self::completed_part(join_result)?);
         }
 
-        // 6. Sort and complete the upload
+        // Sort and complete the upload
         completed_parts.sort_by_key(|part| part.part_number());
 
         let completed_multipart_upload = CompletedMultipartUpload::builder()
@@ -291,6 +268,111 @@ impl FileStorageService for MinioStorageService {
                 ))
             })?;
 
+        Ok(total_size)
+    }
+
+    fn completed_part(
+        join_result: Result<Result<CompletedPart, FileStorageError>, JoinError>,
+    ) -> Result<CompletedPart, FileStorageError> {
+        join_result
+            .map_err(|e| FileStorageError::InternalError(format!("Upload task panicked: {}", e)))?
+    }
+
+    fn into_file_stream(
+        response: Result<GetObjectOutput, SdkError<GetObjectError>>,
+    ) -> Result<FileStream, FileStorageError> {
+        match response {
+            Ok(output) => {
+                // Convert ByteStream to a proper Stream that returns Result<Bytes, FileStorageError>
+                let async_read = output.body.into_async_read();
+                let reader_stream = ReaderStream::new(async_read);
+
+                let stream = reader_stream.map(|result| match result {
+                    Ok(bytes) => Ok(bytes),
+                    Err(e) => Err(FileStorageError::InternalError(format!(
+                        "Stream error: {}",
+                        e
+                    ))),
+                });
+
+                Ok(Box::pin(stream))
+            }
+            Err(err) => {
+                if err.to_string().contains("NoSuchKey") || err.to_string().contains("404") {
+                    Err(FileStorageError::NotFound)
+                } else {
+                    Err(FileStorageError::InternalError(format!(
+                        "Failed to get file stream: {}",
+                        err
+                    )))
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl FileStorageService for MinioStorageService {
+    async fn store_file(
+        &self,
+        file_path: &str,
+        content_type: &str,
+        file_size: Option<u64>, // file_size is unused, can be removed if not needed elsewhere
+        file_data: Pin<
+            Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync + 'static>,
+        >,
+    ) -> Result<UploadedFileMetadata, FileStorageError> {
+        // Check if the file is smaller than the size of a chunk and upload it directly if so
+        if let Some(size) = file_size
+            && size <= CHUNK_SIZE
+        {
+            return self
+                .store_small_file(file_path, content_type, file_data)
+                .await;
+        }
+
+        // 1. Create multipart upload
+        let multipart_upload_res = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| {
+                FileStorageError::InternalError(format!(
+                    "Failed to create multipart upload: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+
+        let upload_id = multipart_upload_res.upload_id().ok_or_else(|| {
+            FileStorageError::InternalError("Failed to get upload ID".to_string())
+        })?;
+
+        // 2. Upload the parts, a failed upload would keep its parts in the bucket forever
+        let mut upload_tasks = JoinSet::new();
+        let total_size = match self
+            .upload_parts(file_path, upload_id, file_data, &mut upload_tasks)
+            .await
+        {
+            Ok(total_size) => total_size,
+            Err(e) => {
+                // Parts still being uploaded must not land after the upload was aborted
+                upload_tasks.shutdown().await;
+                if let Err(abort_error) = self.abort_multipart_upload(file_path, upload_id).await {
+                    tracing::warn!(
+                        "Failed to abort multipart upload {} of {}: {}",
+                        upload_id,
+                        file_path,
+                        abort_error
+                    );
+                }
+                return Err(e);
+            }
+        };
+
         Ok(UploadedFileMetadata {
             file_path: file_path.to_string(),
             file_size: total_size,
@@ -326,32 +408,262 @@ impl FileStorageService for MinioStorageService {
             .send()
             .await;
 
-        match response {
-            Ok(output) => {
-                // Convert ByteStream to a proper Stream that returns Result<Bytes, FileStorageError>
-                let async_read = output.body.into_async_read();
-                let reader_stream = ReaderStream::new(async_read);
+        Self::into_file_stream(response)
+    }
 
-                let stream = reader_stream.map(|result| match result {
-                    Ok(bytes) => Ok(bytes),
-                    Err(e) => Err(FileStorageError::InternalError(format!(
-                        "Stream error: {}",
-                        e
-                    ))),
-                });
+    async fn get_file_range_stream(
+        &self,
+        file_path: &str,
+        range: ByteRange,
+    ) -> Result<FileStream, FileStorageError> {
+        let response = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .range(format!("bytes={}-{}", range.start, range.end))
+            .send()
+            .await;
 
-                Ok(Box::pin(stream))
-            }
-            Err(err) => {
-                if err.to_string().contains("NoSuchKey") || err.to_string().contains("404") {
-                    Err(FileStorageError::NotFound)
+        Self::into_file_stream(response)
+    }
+
+    async fn create_multipart_upload(
+        &self,
+        file_path: &str,
+        content_type: &str,
+    ) -> Result<String, FileStorageError> {
+        let multipart_upload_res = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| {
+                FileStorageError::InternalError(format!(
+                    "Failed to create multipart upload: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+
+        multipart_upload_res
+            .upload_id()
+            .map(|upload_id| upload_id.to_string())
+            .ok_or_else(|| FileStorageError::InternalError("Failed to get upload ID".to_string()))
+    }
+
+    async fn upload_part(
+        &self,
+        file_path: &str,
+        upload_id: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<String, FileStorageError> {
+        let _permit = self
+            .concurrent_upload_semaphore
+            .acquire()
+            .await
+            .map_err(|e| FileStorageError::InternalError(e.to_string()))?;
+
+        let part_output = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| {
+                FileStorageError::InternalError(format!(
+                    "Failed to upload part {}: {}",
+                    part_number,
+                    DisplayErrorContext(e)
+                ))
+            })?;
+
+        Ok(part_output.e_tag().unwrap_or_default().to_string())
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        file_path: &str,
+        upload_id: &str,
+        parts: Vec<UploadedPart>,
+    ) -> Result<(), FileStorageError> {
+        let completed_parts = parts
+            .into_iter()
+            .map(|part| {
+                CompletedPart::builder()
+                    .part_number(part.part_number)
+                    .e_tag(part.e_tag)
+                    .build()
+            })
+            .collect();
+
+        let completed_multipart_upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(completed_parts))
+            .build();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .upload_id(upload_id)
+            .multipart_upload(completed_multipart_upload)
+            .send()
+            .await
+            .map_err(|e| {
+                FileStorageError::InternalError(format!(
+                    "Failed to complete multipart upload: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        file_path: &str,
+        upload_id: &str,
+    ) -> Result<(), FileStorageError> {
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error()
+                    .is_some_and(|service_error| service_error.is_no_such_upload())
+                {
+                    FileStorageError::NotFound
                 } else {
-                    Err(FileStorageError::InternalError(format!(
-                        "Failed to get file stream: {}",
-                        err
-                    )))
+                    FileStorageError::InternalError(format!(
+                        "Failed to abort multipart upload: {}",
+                        DisplayErrorContext(e)
+                    ))
                 }
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_incomplete_multipart_uploads(
+        &self,
+    ) -> Result<Vec<IncompleteMultipartUpload>, FileStorageError> {
+        let mut uploads = Vec::new();
+        let mut key_marker: Option<String> = None;
+        let mut upload_id_marker: Option<String> = None;
+
+        // The listing is paginated, each page continues after the last upload of the previous
+        loop {
+            let response = self
+                .client
+                .list_multipart_uploads()
+                .bucket(&self.bucket)
+                .set_key_marker(key_marker.take())
+                .set_upload_id_marker(upload_id_marker.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    FileStorageError::InternalError(format!(
+                        "Failed to list multipart uploads: {}",
+                        DisplayErrorContext(e)
+                    ))
+                })?;
+
+            uploads.extend(response.uploads().iter().filter_map(|upload| {
+                Some(IncompleteMultipartUpload {
+                    file_path: upload.key()?.to_string(),
+                    upload_id: upload.upload_id()?.to_string(),
+                    initiated_at: upload.initiated().and_then(|initiated| {
+                        chrono::DateTime::from_timestamp(initiated.secs(), 0)
+                            .map(|initiated| initiated.naive_utc())
+                    }),
+                })
+            }));
+
+            if !response.is_truncated().unwrap_or(false) {
+                break;
+            }
+            key_marker = response.next_key_marker().map(str::to_string);
+            upload_id_marker = response.next_upload_id_marker().map(str::to_string);
+            if key_marker.is_none() && upload_id_marker.is_none() {
+                break;
             }
         }
+
+        Ok(uploads)
+    }
+
+    async fn create_presigned_upload(
+        &self,
+        file_path: &str,
+        content_type: &str,
+        file_size: u64,
+        expires_in: std::time::Duration,
+    ) -> Result<PresignedUpload, FileStorageError> {
+        let presigning_config = PresigningConfig::expires_in(expires_in).map_err(|e| {
+            FileStorageError::InternalError(format!("Invalid presigning configuration: {}", e))
+        })?;
+
+        // Content type and length are part of the signature, so the client cannot store
+        // anything else than what was requested
+        let presigned_request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .content_type(content_type)
+            .content_length(file_size as i64)
+            .presigned(presigning_config)
+            .await
+            .map_err(|e| {
+                FileStorageError::InternalError(format!(
+                    "Failed to presign upload: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+
+        Ok(PresignedUpload {
+            url: presigned_request.uri().to_string(),
+            method: presigned_request.method().to_string(),
+        })
+    }
+
+    async fn get_file_metadata(
+        &self,
+        file_path: &str,
+    ) -> Result<StoredFileMetadata, FileStorageError> {
+        let response = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(file_path)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.as_service_error()
+                    .is_some_and(|service_error| service_error.is_not_found())
+                {
+                    FileStorageError::NotFound
+                } else {
+                    FileStorageError::InternalError(format!(
+                        "Failed to read file metadata: {}",
+                        DisplayErrorContext(e)
+                    ))
+                }
+            })?;
+
+        Ok(StoredFileMetadata {
+            file_size: response.content_length().unwrap_or(0).max(0) as u64,
+            content_type: response.content_type().map(|value| value.to_string()),
+        })
     }
 }
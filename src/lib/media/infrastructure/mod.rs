@@ -1,7 +1,21 @@
 pub mod diesel_media_repository;
+pub mod diesel_upload_session_repository;
+pub mod ffmpeg_frame_extractor;
+#[cfg(feature = "heif")]
+pub mod heif_command_decoder;
+pub mod hmac_media_url_signer;
 pub mod mappers;
 pub mod minio_storage_service;
 pub mod models;
+#[cfg(feature = "raw")]
+pub mod raw_preview_decoder;
 
 pub use diesel_media_repository::*;
+pub use diesel_upload_session_repository::*;
+pub use ffmpeg_frame_extractor::*;
+#[cfg(feature = "heif")]
+pub use heif_command_decoder::*;
+pub use hmac_media_url_signer::*;
 pub use minio_storage_service::*;
+#[cfg(feature = "raw")]
+pub use raw_preview_decoder::*;
@@ -1,7 +1,11 @@
+use std::io::Write;
+
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::{media::domain::MediaStatus, persistence::domain::schema::sql_types};
+
 #[derive(Queryable, Selectable, Identifiable, Serialize, Deserialize, Debug)]
 #[diesel(table_name = crate::schema::media_files)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -13,9 +17,11 @@ pub struct MediaFileModel {
     pub file_size: i64,
     pub content_type: String,
     pub file_path: String,
-    pub thumbnail_path: Option<String>,
     pub uploaded_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
+    pub status: RowMediaStatus,
+    pub checksum: Option<String>,
+    pub detected_content_type: Option<String>,
 }
 
 #[derive(Insertable, Debug)]
@@ -27,5 +33,174 @@ pub struct NewMediaFileModel {
     pub file_size: i64,
     pub content_type: String,
     pub file_path: String,
-    pub thumbnail_path: Option<String>,
+    pub status: RowMediaStatus,
+    pub checksum: Option<String>,
+    pub detected_content_type: Option<String>,
+}
+
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    diesel::FromSqlRow,
+    diesel::AsExpression,
+)]
+#[diesel(sql_type = sql_types::MediaStatus)]
+pub enum RowMediaStatus {
+    Pending,
+    Ready,
+}
+
+impl From<MediaStatus> for RowMediaStatus {
+    fn from(status: MediaStatus) -> Self {
+        match status {
+            MediaStatus::Pending => RowMediaStatus::Pending,
+            MediaStatus::Ready => RowMediaStatus::Ready,
+        }
+    }
+}
+
+impl From<RowMediaStatus> for MediaStatus {
+    fn from(row_status: RowMediaStatus) -> Self {
+        match row_status {
+            RowMediaStatus::Pending => MediaStatus::Pending,
+            RowMediaStatus::Ready => MediaStatus::Ready,
+        }
+    }
+}
+
+impl diesel::serialize::ToSql<sql_types::MediaStatus, diesel::pg::Pg> for RowMediaStatus {
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        match *self {
+            RowMediaStatus::Pending => out.write_all(b"PENDING")?,
+            RowMediaStatus::Ready => out.write_all(b"READY")?,
+        }
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+impl diesel::deserialize::FromSql<sql_types::MediaStatus, diesel::pg::Pg> for RowMediaStatus {
+    fn from_sql(bytes: diesel::pg::PgValue) -> diesel::deserialize::Result<Self> {
+        match std::str::from_utf8(bytes.as_bytes())? {
+            "PENDING" => Ok(RowMediaStatus::Pending),
+            "READY" => Ok(RowMediaStatus::Ready),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::media_metadata)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MediaMetadataModel {
+    pub media_id: Uuid,
+    pub taken_at: Option<chrono::NaiveDateTime>,
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub orientation: Option<i16>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    pub iso: Option<i32>,
+    pub focal_length: Option<f64>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+    pub duration_seconds: Option<f64>,
+    pub video_codec: Option<String>,
+    pub frame_rate: Option<f64>,
+}
+
+/// Used for inserts and for replacing the metadata of a media file, fields that are no longer
+/// known are cleared
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::media_metadata)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewMediaMetadataModel {
+    pub media_id: Uuid,
+    pub taken_at: Option<chrono::NaiveDateTime>,
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub orientation: Option<i16>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    pub iso: Option<i32>,
+    pub focal_length: Option<f64>,
+    pub duration_seconds: Option<f64>,
+    pub video_codec: Option<String>,
+    pub frame_rate: Option<f64>,
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::media_renditions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MediaRenditionModel {
+    pub media_id: Uuid,
+    pub name: String,
+    pub file_path: String,
+    pub content_type: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub file_size: Option<i64>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+/// Used for inserts and for replacing a rendition that was generated again
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = crate::schema::media_renditions)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewMediaRenditionModel {
+    pub media_id: Uuid,
+    pub name: String,
+    pub file_path: String,
+    pub content_type: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub file_size: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Debug)]
+#[diesel(table_name = crate::schema::upload_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UploadSessionModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub file_path: String,
+    pub storage_upload_id: String,
+    pub upload_offset: i64,
+    pub part_etags: Vec<Option<String>>,
+    pub expires_at: chrono::NaiveDateTime,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::upload_sessions)]
+pub struct NewUploadSessionModel {
+    pub user_id: Uuid,
+    pub filename: String,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub file_path: String,
+    pub storage_upload_id: String,
+    pub expires_at: chrono::NaiveDateTime,
 }
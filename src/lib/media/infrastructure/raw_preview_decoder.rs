@@ -0,0 +1,227 @@
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+
+use crate::media::domain::{BufReadSeek, ImageFormatDecoder, SpooledFile, ThumbnailError};
+
+/// Camera RAW formats, all of them embed a JPEG preview rendered by the camera
+const RAW_CONTENT_TYPES: [&str; 10] = [
+    "image/x-canon-cr2",
+    "image/x-canon-cr3",
+    "image/x-nikon-nef",
+    "image/x-sony-arw",
+    "image/x-adobe-dng",
+    "image/x-fuji-raf",
+    "image/x-olympus-orf",
+    "image/x-panasonic-rw2",
+    "image/x-pentax-pef",
+    "image/x-dcraw",
+];
+
+/// Start of image marker followed by the first byte of the next marker
+const JPEG_START: [u8; 3] = [0xFF, 0xD8, 0xFF];
+
+/// JPEG stream found inside a RAW file
+#[derive(Debug, Clone, Copy)]
+struct EmbeddedJpeg {
+    offset: u64,
+    length: u64,
+    pixels: u64,
+}
+
+/// Makes thumbnails of camera RAW files from their embedded JPEG previews instead of
+/// developing the sensor data. Files are scanned for JPEG streams and the largest one is used,
+/// which works across vendors without parsing each container format.
+#[derive(Clone, Default)]
+pub struct RawPreviewDecoder;
+
+impl RawPreviewDecoder {
+    pub fn new() -> Self {
+        RawPreviewDecoder
+    }
+
+    fn find_largest_preview(reader: &mut dyn BufReadSeek) -> io::Result<Option<EmbeddedJpeg>> {
+        let mut largest: Option<EmbeddedJpeg> = None;
+        let mut position = 0;
+        while let Some(offset) = find_jpeg_start(reader, position)? {
+            reader.seek(SeekFrom::Start(offset))?;
+            position = match parse_jpeg(reader)? {
+                Some((length, pixels)) => {
+                    if largest.is_none_or(|largest| pixels > largest.pixels) {
+                        largest = Some(EmbeddedJpeg {
+                            offset,
+                            length,
+                            pixels,
+                        });
+                    }
+                    // Thumbnails nested in the metadata of this preview are smaller
+                    offset + length
+                }
+                // Sensor data may contain the start marker by chance
+                None => offset + 1,
+            };
+        }
+        Ok(largest)
+    }
+}
+
+impl ImageFormatDecoder for RawPreviewDecoder {
+    fn supports(&self, content_type: &str) -> bool {
+        RAW_CONTENT_TYPES
+            .iter()
+            .any(|raw_content_type| raw_content_type.eq_ignore_ascii_case(content_type))
+    }
+
+    fn decode(&self, original: &SpooledFile) -> Result<SpooledFile, ThumbnailError> {
+        let io_error = |e: io::Error| ThumbnailError::StorageError(e.to_string());
+
+        let mut reader = original.reader().map_err(io_error)?;
+        let preview = Self::find_largest_preview(reader.as_mut())
+            .map_err(io_error)?
+            .ok_or_else(|| {
+                ThumbnailError::ImageProcessingError("No embedded preview found".to_string())
+            })?;
+
+        let mut data = Vec::with_capacity(preview.length as usize);
+        reader
+            .seek(SeekFrom::Start(preview.offset))
+            .map_err(io_error)?;
+        reader
+            .take(preview.length)
+            .read_to_end(&mut data)
+            .map_err(io_error)?;
+
+        Ok(SpooledFile::Memory(data))
+    }
+}
+
+/// Offset of the next JPEG start marker at or after `position`
+fn find_jpeg_start(reader: &mut dyn BufReadSeek, position: u64) -> io::Result<Option<u64>> {
+    reader.seek(SeekFrom::Start(position))?;
+    let mut offset = position;
+    let mut matched = 0;
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(None);
+        }
+        let length = buffer.len();
+        for (i, byte) in buffer.iter().enumerate() {
+            matched = match (matched, *byte) {
+                (2, 0xFF) => return Ok(Some(offset + i as u64 + 1 - JPEG_START.len() as u64)),
+                (1, 0xD8) => 2,
+                (_, 0xFF) => 1,
+                _ => 0,
+            };
+        }
+        reader.consume(length);
+        offset += length as u64;
+    }
+}
+
+/// Walks the markers of a JPEG stream starting at the current position, returns its length and
+/// pixel count, or `None` when it is not a complete JPEG stream
+fn parse_jpeg(reader: &mut dyn BufReadSeek) -> io::Result<Option<(u64, u64)>> {
+    match read_jpeg(reader) {
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof || e.kind() == ErrorKind::InvalidData => {
+            Ok(None)
+        }
+        result => result.map(Some),
+    }
+}
+
+fn read_jpeg(reader: &mut dyn BufReadSeek) -> io::Result<(u64, u64)> {
+    let invalid = || io::Error::from(ErrorKind::InvalidData);
+
+    if read_u8(reader)? != 0xFF || read_u8(reader)? != 0xD8 {
+        return Err(invalid());
+    }
+    let mut length = 2u64;
+    let mut pixels = None;
+    // Marker found while reading entropy coded data
+    let mut pending_marker = None;
+
+    loop {
+        let marker = match pending_marker.take() {
+            Some(marker) => marker,
+            None => {
+                if read_u8(reader)? != 0xFF {
+                    return Err(invalid());
+                }
+                length += 1;
+                let mut marker = read_u8(reader)?;
+                length += 1;
+                // Markers may be preceded by fill bytes
+                while marker == 0xFF {
+                    marker = read_u8(reader)?;
+                    length += 1;
+                }
+                marker
+            }
+        };
+
+        match marker {
+            // End of image
+            0xD9 => return pixels.map(|pixels| (length, pixels)).ok_or_else(invalid),
+            // Markers without a segment
+            0x01 | 0xD0..=0xD7 => continue,
+            0x00 => return Err(invalid()),
+            _ => {}
+        }
+
+        let segment_length = read_u16(reader)? as u64;
+        if segment_length < 2 {
+            return Err(invalid());
+        }
+        length += segment_length;
+
+        // Start of frame markers, except for DHT, JPG and DAC which share the range
+        if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            if segment_length < 7 {
+                return Err(invalid());
+            }
+            let _precision = read_u8(reader)?;
+            let height = read_u16(reader)? as u64;
+            let width = read_u16(reader)? as u64;
+            if width == 0 || height == 0 {
+                return Err(invalid());
+            }
+            pixels = Some(width * height);
+            reader.seek_relative(segment_length as i64 - 7)?;
+        } else {
+            reader.seek_relative(segment_length as i64 - 2)?;
+        }
+
+        // Start of scan, the entropy coded data runs until the next marker
+        if marker == 0xDA {
+            loop {
+                let byte = read_u8(reader)?;
+                length += 1;
+                if byte != 0xFF {
+                    continue;
+                }
+                let mut next = read_u8(reader)?;
+                length += 1;
+                while next == 0xFF {
+                    next = read_u8(reader)?;
+                    length += 1;
+                }
+                // Stuffed zero bytes and restart markers are part of the data
+                if next != 0x00 && !(0xD0..=0xD7).contains(&next) {
+                    pending_marker = Some(next);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn read_u8(reader: &mut dyn BufReadSeek) -> io::Result<u8> {
+    let mut buffer = [0; 1];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer[0])
+}
+
+fn read_u16(reader: &mut dyn BufReadSeek) -> io::Result<u16> {
+    let mut buffer = [0; 2];
+    reader.read_exact(&mut buffer)?;
+    Ok(u16::from_be_bytes(buffer))
+}
@@ -3,12 +3,12 @@ use std::str::FromStr;
 use axum::{
     Extension, Json,
     body::Body,
-    extract::{Path, Request, State},
-    http::StatusCode,
-    routing::{delete, get, post},
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, head, post},
 };
-use bytes::Bytes;
-use futures_util::{StreamExt, TryStreamExt};
+use futures_util::StreamExt;
 use multer::Multipart;
 use serde;
 use utoipa::OpenApi;
@@ -23,25 +23,70 @@ use crate::{
         },
         http_server::AppState,
     },
+    jobs::{
+        application::commands::enqueue_job::{EnqueueJobCommand, enqueue_job_command_handler},
+        domain::JobPayload,
+    },
     media::{
-        GetMediaStreamError, GetMediaStreamQuery,
+        GetMediaStreamError, GetMediaStreamQuery, MediaStreamAccess,
         application::{
             commands::{
+                abort_upload_session::{
+                    AbortUploadSessionCommand, abort_upload_session_command_handler,
+                },
+                confirm_upload::{ConfirmUploadCommand, confirm_upload_command_handler},
+                create_upload_session::{
+                    CreateUploadSessionCommand, UploadSessionResult,
+                    create_upload_session_command_handler,
+                },
                 delete_media::{
                     DeleteMediaCommand, DeleteMediaResult, delete_media_command_handler,
                 },
+                finalize_upload_session::{
+                    FinalizeUploadSessionCommand, finalize_upload_session_command_handler,
+                },
+                request_upload::{
+                    RequestUploadCommand, RequestUploadResult, request_upload_command_handler,
+                },
+                upload_chunk::{UploadChunkCommand, upload_chunk_command_handler},
                 upload_media::{
                     UploadMediaCommand, UploadMediaResult, upload_media_command_handler,
                 },
             },
-            queries::get_media_files::{
-                GetMediaFilesQuery, GetMediaFilesResult, get_media_files_query_handler,
+            queries::{
+                check_media_checksums::{
+                    CheckMediaChecksumsQuery, ExistingMediaChecksumResult,
+                    check_media_checksums_query_handler,
+                },
+                get_media_file::{GetMediaFileQuery, GetMediaFileResult, get_media_file_query_handler},
+                get_media_files::{
+                    GetMediaFilesError, GetMediaFilesPageResult, GetMediaFilesQuery,
+                    get_media_files_query_handler,
+                },
+                get_media_signed_url::{
+                    GetMediaSignedUrlQuery, GetMediaSignedUrlResult,
+                    get_media_signed_url_query_handler,
+                },
+                get_media_thumbnail::{
+                    GetMediaThumbnailError, GetMediaThumbnailQuery,
+                    get_media_thumbnail_query_handler,
+                },
+                get_storage_usage::{
+                    GetStorageUsageError, GetStorageUsageQuery, StorageUsageResult,
+                    get_storage_usage_query_handler,
+                },
+                get_upload_session::{GetUploadSessionQuery, get_upload_session_query_handler},
             },
         },
-        domain::{MediaDeleteError, MediaUploadError},
+        domain::{
+            MediaConfirmError, MediaDeleteError, MediaFileFilter, MediaFileSort, MediaId,
+            MediaRepositoryError, MediaUploadError,
+            UPLOAD_SESSION_CHUNK_SIZE, UploadSessionError,
+        },
         get_media_stream_query_handler,
     },
     protected,
+    shared::interface::http::{ValidatedJson, authenticate_bearer_token},
     users::domain::Claims,
 };
 
@@ -62,6 +107,38 @@ struct UploadMediaRequestBody {
     file: u8,
 }
 
+/// Stores every upload under a generated name so uploads with the same filename never collide
+fn generate_unique_filename(original_filename: &str) -> String {
+    let file_extension = original_filename
+        .split('.')
+        .next_back()
+        .unwrap_or("unknown");
+    format!(
+        "{}_{}.{}",
+        Uuid::new_v4(),
+        chrono::Utc::now().timestamp(),
+        file_extension
+    )
+}
+
+/// Queues the metadata extraction and rendition generation so the upload response is not
+/// delayed by them, the job survives restarts and is retried when it fails
+async fn enqueue_media_processing(state: &AppState, media_id: MediaId, user_id: Uuid) {
+    let command = EnqueueJobCommand {
+        user_id: Some(user_id),
+        payload: JobPayload::GenerateThumbnail { media_id },
+    };
+
+    match enqueue_job_command_handler(command, state.job_repository.as_ref()).await {
+        Ok(job) => tracing::info!("Queued job {} to process media {}", job.id, media_id),
+        Err(e) => tracing::error!(
+            "Failed to queue the processing of media {}: {}",
+            media_id,
+            e
+        ),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/upload",
@@ -72,7 +149,10 @@ struct UploadMediaRequestBody {
     ),
     responses(
         (status = 201, description = "Media uploaded successfully", body = ApiResponseBody<UploadMediaResult>),
-        (status = 400, description = "Invalid file or file too large", body = ApiErrorBody),
+        (status = 200, description = "Same content was already uploaded, the existing media file is returned with `duplicate` set", body = ApiResponseBody<UploadMediaResult>),
+        (status = 400, description = "Invalid file, a content type that is not allowed, content not matching its content type or not as large as `x-file-size`", body = ApiErrorBody),
+        (status = 403, description = "The file does not fit into the storage quota", body = ApiErrorBody),
+        (status = 413, description = "The file is larger than uploads of its content type may be", body = ApiErrorBody),
         (status = 500, description = "Internal server error", body = ApiErrorBody)
     ),
     security(("bearer_auth" = [])),
@@ -106,7 +186,7 @@ pub async fn upload_media(
     let body_stream = req
         .into_body()
         .into_data_stream()
-        .map(|result| result.map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err)));
+        .map(|result| result.map_err(std::io::Error::other));
 
     // Create multer multipart
     let mut multipart = Multipart::new(body_stream, boundary);
@@ -122,13 +202,13 @@ pub async fn upload_media(
         .await
         .map_err(|_| ApiError::BadRequestError("Invalid multipart data".to_string()))?
     {
-        if let Some(field_name) = field.name() {
-            if field_name == "file" {
-                filename = field.file_name().map(|name| name.to_string());
-                field_content_type = field.content_type().map(|ct| ct.to_string());
-                file_field = Some(field);
-                break; // Exit loop once we find the file field
-            }
+        if let Some(field_name) = field.name()
+            && field_name == "file"
+        {
+            filename = field.file_name().map(|name| name.to_string());
+            field_content_type = field.content_type().map(|ct| ct.to_string());
+            file_field = Some(field);
+            break; // Exit loop once we find the file field
         }
     }
 
@@ -142,23 +222,12 @@ pub async fn upload_media(
     let content_type = field_content_type
         .ok_or_else(|| ApiError::BadRequestError("No content type provided".to_string()))?;
 
-    // Generate unique filename
-    let file_extension = filename.split('.').next_back().unwrap_or("unknown");
-    let unique_filename = format!(
-        "{}_{}.{}",
-        Uuid::new_v4(),
-        chrono::Utc::now().timestamp(),
-        file_extension
-    );
+    let unique_filename = generate_unique_filename(&filename);
 
     // Create stream from field
     let file_stream = file_field.map(|chunk_result| {
-        chunk_result.map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to read file data: {}", e),
-            )
-        })
+        chunk_result
+            .map_err(|e| std::io::Error::other(format!("Failed to read file data: {}", e)))
     });
 
     let boxed_file_data = Box::pin(file_stream);
@@ -175,54 +244,18 @@ pub async fn upload_media(
     match upload_media_command_handler(
         state.media_repository.as_ref(),
         state.storage_service.as_ref(),
+        state.user_repository.as_ref(),
+        state.media_type_allowlist.as_ref(),
+        state.media_size_limits.as_ref(),
+        state.quota_config.as_ref(),
         command,
     )
     .await
     {
+        // Nothing new was stored, the existing media file already has its renditions
+        Ok(result) if result.duplicate => Ok((StatusCode::OK, ApiResponseBody::new(result).into())),
         Ok(result) => {
-            let media_id = result.id;
-            let file_path = format!("media/{}/{}", claims.sub, unique_filename);
-            let thumbnail_service = state.thumbnail_service.clone();
-            let storage_service = state.storage_service.clone();
-
-            tokio::spawn(async move {
-                tracing::info!("Generating thumbnail for media {}", media_id);
-                match storage_service.get_file_stream(&file_path).await {
-                    Ok(file_stream) => match file_stream.try_collect::<Vec<Bytes>>().await {
-                        Ok(chunks) => {
-                            let total_len = chunks.iter().map(|b| b.len()).sum::<usize>();
-                            let mut image_data = Vec::with_capacity(total_len);
-                            for chunk in chunks {
-                                image_data.extend_from_slice(&chunk);
-                            }
-                            if let Err(e) = thumbnail_service
-                                .generate_thumbnail(media_id, &file_path, image_data, &content_type)
-                                .await
-                            {
-                                tracing::warn!(
-                                    "Failed to generate thumbnail for media {}: {}",
-                                    media_id,
-                                    e
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to read file stream for media {} when generating thumbnail: {}",
-                                media_id,
-                                e
-                            );
-                        }
-                    },
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to get file stream for media {} when generating thumbnail: {}",
-                            media_id,
-                            e
-                        );
-                    }
-                };
-            });
+            enqueue_media_processing(&state, result.id, claims.sub).await;
 
             Ok((StatusCode::CREATED, ApiResponseBody::new(result).into()))
         }
@@ -230,8 +263,20 @@ pub async fn upload_media(
             MediaUploadError::InvalidFileType => Err(ApiError::BadRequestError(
                 "Invalid file type. Only images and videos are allowed".to_string(),
             )),
-            MediaUploadError::FileTooLarge => Err(ApiError::BadRequestError(
-                "File too large. Maximum size is 100MB".to_string(),
+            MediaUploadError::ContentTypeMismatch { claimed, detected } => Err(
+                ApiError::BadRequestError(content_type_mismatch_message(&claimed, detected)),
+            ),
+            MediaUploadError::FileTooLarge { max_bytes } => Err(ApiError::PayloadTooLargeError(
+                format!("File too large. Maximum size is {} bytes", max_bytes),
+            )),
+            MediaUploadError::FileSizeMismatch { declared, received } => {
+                Err(ApiError::BadRequestError(format!(
+                    "Declared file size {} does not match the {} bytes received",
+                    declared, received
+                )))
+            }
+            MediaUploadError::QuotaExceeded => Err(ApiError::ForbiddenError(
+                "Storage quota exceeded".to_string(),
             )),
             MediaUploadError::StorageError(msg) => {
                 tracing::event!(target: "server_error", 
@@ -251,13 +296,198 @@ pub async fn upload_media(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/upload/request",
+    description = "Request a presigned URL to upload a file directly to the storage. The upload has to be confirmed afterwards, until then the media file stays pending",
+    tag = "media",
+    request_body = RequestUploadRequestBody,
+    responses(
+        (status = 201, description = "Presigned upload created", body = ApiResponseBody<RequestUploadResult>),
+        (status = 400, description = "Invalid request or file type", body = ApiErrorBody),
+        (status = 403, description = "The file does not fit into the storage quota", body = ApiErrorBody),
+        (status = 413, description = "The file is larger than uploads of its content type may be", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn request_upload(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedJson(body): ValidatedJson<RequestUploadRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<RequestUploadResult>>), ApiError> {
+    let command = RequestUploadCommand {
+        user_id: claims.sub,
+        filename: generate_unique_filename(&body.filename),
+        original_filename: body.filename,
+        file_size: body.file_size,
+        content_type: body.content_type,
+    };
+
+    match request_upload_command_handler(
+        state.media_repository.as_ref(),
+        state.storage_service.as_ref(),
+        state.user_repository.as_ref(),
+        state.media_type_allowlist.as_ref(),
+        state.media_size_limits.as_ref(),
+        state.quota_config.as_ref(),
+        command,
+    )
+    .await
+    {
+        Ok(result) => Ok((StatusCode::CREATED, ApiResponseBody::new(result).into())),
+        Err(MediaUploadError::InvalidFileType) => Err(ApiError::BadRequestError(
+            "Invalid file type. Only images and videos are allowed".to_string(),
+        )),
+        Err(MediaUploadError::FileTooLarge { max_bytes }) => Err(ApiError::PayloadTooLargeError(
+            format!("File too large. Maximum size is {} bytes", max_bytes),
+        )),
+        // Presigned uploads are sniffed once they are confirmed
+        Err(MediaUploadError::ContentTypeMismatch { .. }) => Err(ApiError::BadRequestError(
+            "File content does not match its content type".to_string(),
+        )),
+        Err(MediaUploadError::FileSizeMismatch { .. }) => Err(ApiError::BadRequestError(
+            "File size does not match the uploaded content".to_string(),
+        )),
+        Err(MediaUploadError::QuotaExceeded) => Err(ApiError::ForbiddenError(
+            "Storage quota exceeded".to_string(),
+        )),
+        Err(MediaUploadError::StorageError(msg)) => {
+            tracing::event!(target: "server_error",
+                tracing::Level::ERROR,
+                "Failed to presign upload, {}", msg);
+            Err(ApiError::InternalServerError(
+                "Internal server error".to_string(),
+            ))
+        }
+        Err(MediaUploadError::InternalServerError(msg)) => {
+            tracing::error!("Internal server error, {}", msg);
+            Err(ApiError::InternalServerError(
+                "Internal server error".to_string(),
+            ))
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/{media_id}/confirm",
+    description = "Confirm a presigned upload. The stored file is checked against the requested size and content type before the media file becomes ready",
+    tag = "media",
+    params(
+        ("media_id" = String, Path, description = "ID of the pending media file")
+    ),
+    responses(
+        (status = 200, description = "Upload confirmed. When the same content was already uploaded, the file is discarded and the existing media file is returned with `duplicate` set", body = ApiResponseBody<UploadMediaResult>),
+        (status = 400, description = "Invalid media ID format, or content not matching its content type, it was discarded", body = ApiErrorBody),
+        (status = 403, description = "The file does not fit into the storage quota, it was discarded", body = ApiErrorBody),
+        (status = 404, description = "Media file not found", body = ApiErrorBody),
+        (status = 409, description = "File was not uploaded or does not match the request", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn confirm_upload(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(media_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<UploadMediaResult>>), ApiError> {
+    let command = ConfirmUploadCommand {
+        media_id: Uuid::parse_str(&media_id)
+            .map_err(|_| ApiError::BadRequestError("Invalid media ID format".to_string()))?,
+        user_id: claims.sub,
+    };
+
+    match confirm_upload_command_handler(
+        state.media_repository.as_ref(),
+        state.storage_service.as_ref(),
+        state.user_repository.as_ref(),
+        state.quota_config.as_ref(),
+        command,
+    )
+    .await
+    {
+        // Nothing new was stored, the existing media file already has its renditions
+        Ok(result) if result.duplicate => Ok((StatusCode::OK, ApiResponseBody::new(result).into())),
+        Ok(result) => {
+            enqueue_media_processing(&state, result.id, claims.sub).await;
+            Ok((StatusCode::OK, ApiResponseBody::new(result).into()))
+        }
+        Err(err) => match err {
+            MediaConfirmError::MediaFileNotFound => {
+                Err(ApiError::NotFoundError("Media file not found".to_string()))
+            }
+            MediaConfirmError::FileNotUploaded | MediaConfirmError::FileMismatch => {
+                Err(ApiError::ConflictError(err.to_string()))
+            }
+            MediaConfirmError::ContentTypeMismatch { claimed, detected } => Err(
+                ApiError::BadRequestError(content_type_mismatch_message(&claimed, detected)),
+            ),
+            MediaConfirmError::QuotaExceeded => Err(ApiError::ForbiddenError(
+                "Storage quota exceeded".to_string(),
+            )),
+            MediaConfirmError::StorageError(msg) => {
+                tracing::event!(target: "server_error",
+                    tracing::Level::ERROR,
+                    "Failed to verify uploaded file, {}", msg);
+                Err(ApiError::InternalServerError(
+                    "Internal server error".to_string(),
+                ))
+            }
+            MediaConfirmError::InternalServerError(msg) => {
+                tracing::error!("Internal server error, {}", msg);
+                Err(ApiError::InternalServerError(
+                    "Internal server error".to_string(),
+                ))
+            }
+        },
+    }
+}
+
+#[derive(serde::Deserialize, utoipa::ToSchema, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaFilesSortParam {
+    #[default]
+    UploadedAtDesc,
+    UploadedAtAsc,
+}
+
+impl From<MediaFilesSortParam> for MediaFileSort {
+    fn from(sort: MediaFilesSortParam) -> Self {
+        match sort {
+            MediaFilesSortParam::UploadedAtDesc => MediaFileSort::UploadedAtDesc,
+            MediaFilesSortParam::UploadedAtAsc => MediaFileSort::UploadedAtAsc,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetMediaFilesParams {
+    /// `next_cursor` of the previous page
+    cursor: Option<String>,
+    /// Number of media files per page, between 1 and 200 (default 50)
+    limit: Option<i64>,
+    sort: Option<MediaFilesSortParam>,
+    /// Only media files whose content type starts with it, e.g. `image/`
+    content_type: Option<String>,
+    /// Only media files uploaded at or after this date (RFC 3339)
+    uploaded_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only media files uploaded before this date (RFC 3339)
+    uploaded_to: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only media files whose original filename contains it, case insensitive
+    filename: Option<String>,
+}
+
 #[utoipa::path(
     get,
     path = "",
-    description = "Get user's media files",
+    description = "Get a page of the user's media files. Follow `next_cursor` to get the next page",
     tag = "media",
+    params(GetMediaFilesParams),
     responses(
-        (status = 200, description = "Media files retrieved successfully", body = ApiResponseBody<Vec<GetMediaFilesResult>>),
+        (status = 200, description = "Media files retrieved successfully", body = ApiResponseBody<GetMediaFilesPageResult>),
+        (status = 400, description = "Invalid cursor", body = ApiErrorBody),
         (status = 500, description = "Internal server error", body = ApiErrorBody)
     ),
     security(("bearer_auth" = [])),
@@ -265,19 +495,159 @@ pub async fn upload_media(
 pub async fn get_media_files(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
-) -> Result<(StatusCode, Json<ApiResponseBody<Vec<GetMediaFilesResult>>>), ApiError> {
+    Query(params): Query<GetMediaFilesParams>,
+) -> Result<(StatusCode, Json<ApiResponseBody<GetMediaFilesPageResult>>), ApiError> {
     let query = GetMediaFilesQuery {
         user_id: claims.sub,
+        filter: MediaFileFilter {
+            content_type_prefix: params.content_type,
+            uploaded_from: params.uploaded_from.map(|date| date.naive_utc()),
+            uploaded_to: params.uploaded_to.map(|date| date.naive_utc()),
+            filename_contains: params.filename,
+        },
+        sort: params.sort.unwrap_or_default().into(),
+        cursor: params.cursor,
+        limit: params.limit,
     };
 
     match get_media_files_query_handler(query, state.media_repository.as_ref()).await {
-        Ok(media_files) => Ok((StatusCode::OK, ApiResponseBody::new(media_files).into())),
-        Err(_) => Err(ApiError::InternalServerError(
+        Ok(page) => Ok((StatusCode::OK, ApiResponseBody::new(page).into())),
+        Err(GetMediaFilesError::InvalidCursor) => {
+            Err(ApiError::BadRequestError("Invalid cursor".to_string()))
+        }
+        Err(GetMediaFilesError::RepositoryError(_)) => Err(ApiError::InternalServerError(
             "Failed to retrieve media files".to_string(),
         )),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/usage",
+    description = "Get the storage taken up by the user's media files and their quota",
+    tag = "media",
+    responses(
+        (status = 200, description = "Storage usage retrieved successfully", body = ApiResponseBody<StorageUsageResult>),
+        (status = 404, description = "User not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_storage_usage(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<(StatusCode, Json<ApiResponseBody<StorageUsageResult>>), ApiError> {
+    let query = GetStorageUsageQuery {
+        user_id: claims.sub,
+    };
+
+    match get_storage_usage_query_handler(
+        query,
+        state.media_repository.as_ref(),
+        state.user_repository.as_ref(),
+        state.quota_config.as_ref(),
+    )
+    .await
+    {
+        Ok(usage) => Ok((StatusCode::OK, ApiResponseBody::new(usage).into())),
+        Err(GetStorageUsageError::UserNotFound) => {
+            Err(ApiError::NotFoundError("User not found".to_string()))
+        }
+        Err(GetStorageUsageError::InternalServerError(msg)) => {
+            tracing::error!("Internal server error, {}", msg);
+            Err(ApiError::InternalServerError(
+                "Failed to retrieve storage usage".to_string(),
+            ))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/{media_id}",
+    description = "Get a media file, owned by or shared with the user, with its metadata",
+    tag = "media",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file")
+    ),
+    responses(
+        (status = 200, description = "Media file retrieved successfully", body = ApiResponseBody<GetMediaFileResult>),
+        (status = 400, description = "Invalid media ID format", body = ApiErrorBody),
+        (status = 404, description = "Media file not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_media_file(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(media_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<GetMediaFileResult>>), ApiError> {
+    let query = GetMediaFileQuery {
+        media_id: Uuid::parse_str(&media_id)
+            .map_err(|_| ApiError::BadRequestError("Invalid media ID format".to_string()))?,
+        user_id: claims.sub,
+    };
+
+    match get_media_file_query_handler(
+        query,
+        state.media_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    {
+        Ok(media_file) => Ok((StatusCode::OK, ApiResponseBody::new(media_file).into())),
+        Err(MediaRepositoryError::MediaFileNotFound) => {
+            Err(ApiError::NotFoundError("Media file not found".to_string()))
+        }
+        Err(_) => Err(ApiError::InternalServerError(
+            "Failed to retrieve media file".to_string(),
+        )),
+    }
+}
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct CheckMediaChecksumsRequestBody {
+    /// Hex encoded SHA-256 checksums of the files to upload
+    #[validate(length(
+        min = 1,
+        max = 1000,
+        message = "Between 1 and 1000 checksums must be sent"
+    ))]
+    checksums: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/checksums",
+    description = "Check which files were already uploaded, so clients can skip uploading duplicates",
+    tag = "media",
+    request_body = CheckMediaChecksumsRequestBody,
+    responses(
+        (status = 200, description = "Checksums the user already has media files for", body = ApiResponseBody<Vec<ExistingMediaChecksumResult>>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn check_media_checksums(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedJson(body): ValidatedJson<CheckMediaChecksumsRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<ExistingMediaChecksumResult>>>), ApiError> {
+    let query = CheckMediaChecksumsQuery {
+        user_id: claims.sub,
+        checksums: body.checksums,
+    };
+
+    match check_media_checksums_query_handler(query, state.media_repository.as_ref()).await {
+        Ok(existing) => Ok((StatusCode::OK, ApiResponseBody::new(existing).into())),
+        Err(_) => Err(ApiError::InternalServerError(
+            "Failed to check media checksums".to_string(),
+        )),
+    }
+}
+
 #[utoipa::path(
     delete,
     path = "/{media_id}",
@@ -338,61 +708,705 @@ pub async fn delete_media(
     }
 }
 
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StreamMediaParams {
+    /// Expiration of a signed URL, as returned by the signed URL endpoint
+    expires: Option<u64>,
+    /// Signature of a signed URL, as returned by the signed URL endpoint
+    signature: Option<String>,
+    /// Remove the location the photo was taken at (EXIF GPS data and XMP) from JPEG and TIFF
+    /// based images, other formats are rejected
+    strip_metadata: Option<bool>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema, Debug, Clone, PartialEq, Eq)]
+pub struct SignedMediaUrlResponseBody {
+    /// Relative URL that streams the media file without an Authorization header
+    pub url: String,
+    pub expires_at: u64,
+}
+
+impl From<GetMediaSignedUrlResult> for SignedMediaUrlResponseBody {
+    fn from(result: GetMediaSignedUrlResult) -> Self {
+        SignedMediaUrlResponseBody {
+            url: format!(
+                "/api/media/stream/{}?expires={}&signature={}",
+                result.media_id, result.expires_at, result.signature
+            ),
+            expires_at: result.expires_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/{media_id}/signed-url",
+    description = "Issue a short-lived URL that streams a media file without an Authorization header, for use in <img>/<video> tags",
+    tag = "media",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file")
+    ),
+    responses(
+        (status = 200, description = "Signed URL issued", body = ApiResponseBody<SignedMediaUrlResponseBody>),
+        (status = 400, description = "Invalid media ID format", body = ApiErrorBody),
+        (status = 404, description = "Media file not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn create_media_signed_url(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(media_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<SignedMediaUrlResponseBody>>), ApiError> {
+    let query = GetMediaSignedUrlQuery {
+        media_id: Uuid::parse_str(&media_id)
+            .map_err(|_| ApiError::BadRequestError("Invalid media ID format".to_string()))?,
+        user_id: claims.sub,
+    };
+
+    match get_media_signed_url_query_handler(
+        query,
+        state.media_repository.as_ref(),
+        state.media_url_signer.as_ref(),
+    )
+    .await
+    {
+        Ok(result) => Ok((
+            StatusCode::OK,
+            ApiResponseBody::new(SignedMediaUrlResponseBody::from(result)).into(),
+        )),
+        Err(MediaRepositoryError::MediaFileNotFound) => {
+            Err(ApiError::NotFoundError("Media file not found".to_string()))
+        }
+        Err(_) => Err(ApiError::InternalServerError(
+            "Internal server error".to_string(),
+        )),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/stream/{media_id}",
-    description = "Stream media file",
+    description = "Stream media file. Requires either a bearer token of the owner or of a user the media file is shared with, or the `expires`/`signature` pair of a signed URL. Supports single `Range` requests (with `If-Range`) so clients can seek. With `strip_metadata=true` the location is removed from the original, the file keeps its size",
     tag = "media",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file to stream"),
+        StreamMediaParams,
+        ("Range" = Option<String>, Header, description = "Byte range to return, e.g. `bytes=0-1023`"),
+        ("If-Range" = Option<String>, Header, description = "ETag the range request is conditional on"),
+    ),
     responses(
         (status = 200, description = "Media file streamed correctly", body = [u8]),
-        (status = 400, description = "Invalid media ID format", body = ApiErrorBody),
+        (status = 206, description = "Requested range of the media file", body = [u8]),
+        (status = 400, description = "Invalid media ID format, or metadata cannot be stripped from the media file", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Invalid or expired signed URL", body = ApiErrorBody),
         (status = 404, description = "Media file not found", body = ApiErrorBody),
+        (status = 416, description = "Requested range not satisfiable"),
         (status = 500, description = "Internal server error", body = ApiErrorBody)
     ),
+    security((), ("bearer_auth" = [])),
 )]
 pub async fn get_media_stream(
     State(state): State<AppState>,
     Path(media_id): Path<String>,
-) -> Result<Body, ApiError> {
+    Query(params): Query<StreamMediaParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let header_value = |name: header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+
+    let strip_metadata = params.strip_metadata.unwrap_or(false);
     let query = GetMediaStreamQuery {
         media_id: Uuid::from_str(&media_id)
             .map_err(|_| ApiError::BadRequestError("Invalid media ID format".to_string()))?,
+        access: media_stream_access(&state, &headers, params).await?,
+        range: header_value(header::RANGE),
+        if_range: header_value(header::IF_RANGE),
+        strip_metadata,
+    };
+
+    media_stream_response(&state, query).await
+}
+
+/// Resolves who is reading a media file, from a bearer token or from a signed URL
+async fn media_stream_access(
+    state: &AppState,
+    headers: &HeaderMap,
+    params: StreamMediaParams,
+) -> Result<MediaStreamAccess, ApiError> {
+    // A bearer token takes precedence, signed URLs are meant for clients that cannot set headers
+    let bearer_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match (bearer_token, params.expires, params.signature) {
+        (Some(token), _, _) => {
+            let claims = authenticate_bearer_token(state, token).await?;
+            Ok(MediaStreamAccess::User(claims.sub))
+        }
+        (None, Some(expires_at), Some(signature)) => Ok(MediaStreamAccess::SignedUrl {
+            expires_at,
+            signature,
+        }),
+        _ => Err(ApiError::UnauthorizedError("Unauthorized".to_string())),
+    }
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MediaThumbnailParams {
+    /// Rendition to return, one of the `renditions` of the media file, defaults to `medium`
+    size: Option<String>,
+    /// Expiration of a signed URL, as returned by the signed URL endpoint
+    expires: Option<u64>,
+    /// Signature of a signed URL, as returned by the signed URL endpoint
+    signature: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/{media_id}/thumbnail",
+    description = "Get a downscaled rendition of an image. Accepts the same credentials as the stream endpoint, a signed URL of the media file also works for its thumbnails. The format is picked from the `Accept` header among the configured rendition formats, clients that do not ask for WebP or AVIF get JPEG",
+    tag = "media",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file"),
+        MediaThumbnailParams,
+    ),
+    responses(
+        (status = 200, description = "Rendition of the media file", body = [u8]),
+        (status = 400, description = "Invalid media ID format", body = ApiErrorBody),
+        (status = 401, description = "Missing or invalid credentials", body = ApiErrorBody),
+        (status = 403, description = "Invalid or expired signed URL", body = ApiErrorBody),
+        (status = 404, description = "Media file or rendition not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security((), ("bearer_auth" = [])),
+)]
+pub async fn get_media_thumbnail(
+    State(state): State<AppState>,
+    Path(media_id): Path<String>,
+    Query(params): Query<MediaThumbnailParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let media_id = Uuid::from_str(&media_id)
+        .map_err(|_| ApiError::BadRequestError("Invalid media ID format".to_string()))?;
+    let access = media_stream_access(
+        &state,
+        &headers,
+        StreamMediaParams {
+            expires: params.expires,
+            signature: params.signature,
+            strip_metadata: None,
+        },
+    )
+    .await?;
+
+    let query = GetMediaThumbnailQuery {
+        media_id,
+        access,
+        size: params.size,
+        accept: headers
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string()),
+    };
+
+    media_thumbnail_response(&state, query).await
+}
+
+/// Runs the thumbnail query and turns its result into a response
+pub(crate) async fn media_thumbnail_response(
+    state: &AppState,
+    query: GetMediaThumbnailQuery,
+) -> Result<Response, ApiError> {
+    let result = match get_media_thumbnail_query_handler(
+        query,
+        state.storage_service.as_ref(),
+        state.media_repository.as_ref(),
+        state.media_url_signer.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(GetMediaThumbnailError::NotFound) => {
+            return Err(ApiError::NotFoundError("Media file not found".to_string()));
+        }
+        Err(GetMediaThumbnailError::ThumbnailNotFound) => {
+            return Err(ApiError::NotFoundError("Thumbnail not found".to_string()));
+        }
+        Err(GetMediaThumbnailError::InvalidSignature) => {
+            return Err(ApiError::ForbiddenError(
+                "Invalid or expired media URL".to_string(),
+            ));
+        }
+        Err(GetMediaThumbnailError::InternalError(error)) => {
+            tracing::error!("Internal server error while getting thumbnail: {}", error);
+            return Err(ApiError::InternalServerError(
+                "Internal server error".to_string(),
+            ));
+        }
     };
 
-    let file_stream = get_media_stream_query_handler(
+    let mut response = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, result.content_type)
+        .header(header::ETAG, result.e_tag)
+        // The format depends on what the client accepts
+        .header(header::VARY, header::ACCEPT)
+        // Thumbnails may be shared, so only the client may keep them
+        .header(header::CACHE_CONTROL, "private, max-age=86400");
+
+    if let Some(content_length) = result.content_length {
+        response = response.header(header::CONTENT_LENGTH, content_length);
+    }
+
+    response
+        .body(Body::from_stream(result.stream))
+        .map_err(|e| {
+            tracing::error!("Failed to build thumbnail response: {}", e);
+            ApiError::InternalServerError("Internal server error".to_string())
+        })
+}
+
+/// Runs the stream query and turns its result into a full or partial content response
+pub(crate) async fn media_stream_response(
+    state: &AppState,
+    query: GetMediaStreamQuery,
+) -> Result<Response, ApiError> {
+    let result = match get_media_stream_query_handler(
         query,
         state.storage_service.as_ref(),
         state.media_repository.as_ref(),
+        state.media_url_signer.as_ref(),
+        state.authorization_service.as_ref(),
     )
     .await
-    .map_err(|e| match e {
-        GetMediaStreamError::NotFound => {
-            ApiError::NotFoundError("Media file not found".to_string())
+    {
+        Ok(result) => result,
+        Err(GetMediaStreamError::RangeNotSatisfiable { total_size }) => {
+            return Ok((
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [
+                    (header::CONTENT_RANGE, format!("bytes */{}", total_size)),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                ],
+            )
+                .into_response());
+        }
+        Err(GetMediaStreamError::NotFound) => {
+            return Err(ApiError::NotFoundError("Media file not found".to_string()));
         }
-        GetMediaStreamError::InternalError(error) => {
+        Err(GetMediaStreamError::InvalidSignature) => {
+            return Err(ApiError::ForbiddenError(
+                "Invalid or expired media URL".to_string(),
+            ));
+        }
+        Err(GetMediaStreamError::MetadataNotStrippable(error)) => {
+            return Err(ApiError::BadRequestError(error.to_string()));
+        }
+        Err(GetMediaStreamError::InternalError(error)) => {
             tracing::error!(
                 "Internal server error while streaming media file: {}",
                 error
             );
+            return Err(ApiError::InternalServerError(
+                "Internal server error".to_string(),
+            ));
+        }
+    };
+
+    let (status, content_length) = match result.range {
+        Some(range) => (StatusCode::PARTIAL_CONTENT, range.length()),
+        None => (StatusCode::OK, result.total_size),
+    };
+
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, result.content_type)
+        .header(header::CONTENT_LENGTH, content_length)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::ETAG, result.e_tag);
+
+    if let Some(range) = result.range {
+        response = response.header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", range.start, range.end, result.total_size),
+        );
+    }
+
+    response
+        .body(Body::from_stream(result.stream))
+        .map_err(|e| {
+            tracing::error!("Failed to build media stream response: {}", e);
+            ApiError::InternalServerError("Internal server error".to_string())
+        })
+}
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct CreateUploadSessionRequestBody {
+    #[validate(length(min = 1, message = "Filename cannot be empty"))]
+    filename: String,
+    #[validate(range(min = 1, message = "File size must be greater than 0"))]
+    file_size: u64,
+    #[validate(length(min = 1, message = "Content type cannot be empty"))]
+    content_type: String,
+}
+
+fn content_type_mismatch_message(claimed: &str, detected: Option<String>) -> String {
+    match detected {
+        Some(detected) => format!(
+            "File content is {}, which does not match its content type {}",
+            detected, claimed
+        ),
+        None => format!("File content does not match its content type {}", claimed),
+    }
+}
+
+const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+const UPLOAD_LENGTH_HEADER: &str = "upload-length";
+
+fn upload_session_error_to_api_error(err: UploadSessionError) -> ApiError {
+    match err {
+        UploadSessionError::NotFound => {
+            ApiError::NotFoundError("Upload session not found".to_string())
+        }
+        UploadSessionError::InvalidFileType => ApiError::BadRequestError(
+            "Invalid file type. Only images and videos are allowed".to_string(),
+        ),
+        UploadSessionError::OffsetMismatch { current_offset } => ApiError::ConflictError(format!(
+            "Upload offset mismatch, current offset is {}",
+            current_offset
+        )),
+        UploadSessionError::InvalidChunkSize => ApiError::BadRequestError(format!(
+            "Invalid chunk size. Every chunk but the last one must be {} bytes",
+            UPLOAD_SESSION_CHUNK_SIZE
+        )),
+        UploadSessionError::Incomplete => {
+            ApiError::ConflictError("Upload is not complete".to_string())
+        }
+        UploadSessionError::ContentTypeMismatch { claimed, detected } => {
+            ApiError::BadRequestError(content_type_mismatch_message(&claimed, detected))
+        }
+        UploadSessionError::FileTooLarge { max_bytes } => ApiError::PayloadTooLargeError(format!(
+            "File too large. Maximum size is {} bytes",
+            max_bytes
+        )),
+        UploadSessionError::QuotaExceeded => {
+            ApiError::ForbiddenError("Storage quota exceeded".to_string())
+        }
+        UploadSessionError::StorageError(msg) => {
+            tracing::event!(target: "server_error",
+                tracing::Level::ERROR,
+                "Upload session storage error, {}", msg);
+            ApiError::InternalServerError("Internal server error, Failed to store file".to_string())
+        }
+        UploadSessionError::InternalServerError(msg) => {
+            tracing::error!("Internal server error, {}", msg);
             ApiError::InternalServerError("Internal server error".to_string())
         }
-    })?;
+    }
+}
 
-    Ok(Body::from_stream(file_stream))
+fn parse_upload_session_id(upload_id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(upload_id)
+        .map_err(|_| ApiError::BadRequestError("Invalid upload session ID format".to_string()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/uploads",
+    description = "Start a resumable upload. The file is then sent in chunks of `chunk_size` bytes with PATCH requests and finalized once every byte was received",
+    tag = "media",
+    request_body = CreateUploadSessionRequestBody,
+    responses(
+        (status = 201, description = "Upload session created", body = ApiResponseBody<UploadSessionResult>),
+        (status = 400, description = "Invalid request or file type", body = ApiErrorBody),
+        (status = 403, description = "The file does not fit into the storage quota", body = ApiErrorBody),
+        (status = 413, description = "The file is larger than uploads of its content type may be", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn create_upload_session(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedJson(body): ValidatedJson<CreateUploadSessionRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<UploadSessionResult>>), ApiError> {
+    let command = CreateUploadSessionCommand {
+        user_id: claims.sub,
+        filename: generate_unique_filename(&body.filename),
+        original_filename: body.filename,
+        file_size: body.file_size,
+        content_type: body.content_type,
+    };
+
+    create_upload_session_command_handler(
+        state.upload_session_repository.as_ref(),
+        state.media_repository.as_ref(),
+        state.storage_service.as_ref(),
+        state.user_repository.as_ref(),
+        state.media_type_allowlist.as_ref(),
+        state.media_size_limits.as_ref(),
+        state.quota_config.as_ref(),
+        command,
+    )
+    .await
+    .map(|result| (StatusCode::CREATED, ApiResponseBody::new(result).into()))
+    .map_err(upload_session_error_to_api_error)
+}
+
+#[utoipa::path(
+    head,
+    path = "/uploads/{upload_id}",
+    description = "Get the offset a resumed upload has to continue from",
+    tag = "media",
+    params(
+        ("upload_id" = String, Path, description = "ID of the upload session")
+    ),
+    responses(
+        (status = 204, description = "Current offset in the `Upload-Offset` header and total size in the `Upload-Length` header"),
+        (status = 400, description = "Invalid upload session ID format"),
+        (status = 404, description = "Upload session not found or expired")
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_upload_session(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(upload_id): Path<String>,
+) -> Result<Response, ApiError> {
+    let query = GetUploadSessionQuery {
+        upload_session_id: parse_upload_session_id(&upload_id)?,
+        user_id: claims.sub,
+    };
+
+    let session = get_upload_session_query_handler(query, state.upload_session_repository.as_ref())
+        .await
+        .map_err(upload_session_error_to_api_error)?;
+
+    Ok((
+        StatusCode::NO_CONTENT,
+        [
+            (UPLOAD_OFFSET_HEADER, session.upload_offset.to_string()),
+            (UPLOAD_LENGTH_HEADER, session.file_size.to_string()),
+            (header::CACHE_CONTROL.as_str(), "no-store".to_string()),
+        ],
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/uploads/{upload_id}",
+    description = "Append a chunk to an upload session. The `Upload-Offset` header must match the current offset of the session",
+    tag = "media",
+    params(
+        ("upload_id" = String, Path, description = "ID of the upload session"),
+        ("Upload-Offset" = u64, Header, description = "Offset the chunk starts at"),
+    ),
+    request_body(content_type = "application/offset+octet-stream", content = [u8]),
+    responses(
+        (status = 204, description = "Chunk stored, new offset in the `Upload-Offset` header"),
+        (status = 400, description = "Invalid upload session ID, offset header or chunk size", body = ApiErrorBody),
+        (status = 404, description = "Upload session not found or expired", body = ApiErrorBody),
+        (status = 409, description = "Offset does not match the current offset of the session", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn upload_chunk(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(upload_id): Path<String>,
+    req: Request<Body>,
+) -> Result<Response, ApiError> {
+    let upload_session_id = parse_upload_session_id(&upload_id)?;
+
+    let offset = req
+        .headers()
+        .get(UPLOAD_OFFSET_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .ok_or_else(|| {
+            ApiError::BadRequestError("Missing or invalid Upload-Offset header".to_string())
+        })?;
+
+    // A chunk is never bigger than the chunk size, so that is the limit of what is buffered
+    let data = axum::body::to_bytes(req.into_body(), UPLOAD_SESSION_CHUNK_SIZE as usize)
+        .await
+        .map_err(|_| {
+            ApiError::BadRequestError(format!(
+                "Invalid chunk. Chunks cannot be larger than {} bytes",
+                UPLOAD_SESSION_CHUNK_SIZE
+            ))
+        })?;
+
+    let command = UploadChunkCommand {
+        upload_session_id,
+        user_id: claims.sub,
+        offset,
+        data,
+    };
+
+    let session = upload_chunk_command_handler(
+        state.upload_session_repository.as_ref(),
+        state.storage_service.as_ref(),
+        command,
+    )
+    .await
+    .map_err(upload_session_error_to_api_error)?;
+
+    Ok((
+        StatusCode::NO_CONTENT,
+        [(UPLOAD_OFFSET_HEADER, session.upload_offset.to_string())],
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/uploads/{upload_id}/finalize",
+    description = "Complete an upload session once every byte was received and create the media file",
+    tag = "media",
+    params(
+        ("upload_id" = String, Path, description = "ID of the upload session")
+    ),
+    responses(
+        (status = 201, description = "Media uploaded successfully", body = ApiResponseBody<UploadMediaResult>),
+        (status = 200, description = "Same content was already uploaded, the file is discarded and the existing media file is returned with `duplicate` set", body = ApiResponseBody<UploadMediaResult>),
+        (status = 400, description = "Invalid upload session ID format, or content not matching its content type, it was discarded", body = ApiErrorBody),
+        (status = 403, description = "The file does not fit into the storage quota, it was discarded", body = ApiErrorBody),
+        (status = 404, description = "Upload session not found or expired", body = ApiErrorBody),
+        (status = 409, description = "Upload is not complete", body = ApiErrorBody),
+        (status = 413, description = "The file is larger than uploads of its content type may be, it was discarded", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn finalize_upload_session(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(upload_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<UploadMediaResult>>), ApiError> {
+    let command = FinalizeUploadSessionCommand {
+        upload_session_id: parse_upload_session_id(&upload_id)?,
+        user_id: claims.sub,
+    };
+
+    let result = finalize_upload_session_command_handler(
+        state.upload_session_repository.as_ref(),
+        state.media_repository.as_ref(),
+        state.storage_service.as_ref(),
+        state.user_repository.as_ref(),
+        state.media_size_limits.as_ref(),
+        state.quota_config.as_ref(),
+        command,
+    )
+    .await
+    .map_err(upload_session_error_to_api_error)?;
+
+    // Nothing new was stored, the existing media file already has its renditions
+    if result.duplicate {
+        return Ok((StatusCode::OK, ApiResponseBody::new(result).into()));
+    }
+
+    enqueue_media_processing(&state, result.id, claims.sub).await;
+
+    Ok((StatusCode::CREATED, ApiResponseBody::new(result).into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/uploads/{upload_id}",
+    description = "Abort an upload session and discard the chunks received so far",
+    tag = "media",
+    params(
+        ("upload_id" = String, Path, description = "ID of the upload session")
+    ),
+    responses(
+        (status = 204, description = "Upload session aborted"),
+        (status = 400, description = "Invalid upload session ID format", body = ApiErrorBody),
+        (status = 404, description = "Upload session not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn abort_upload_session(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(upload_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let command = AbortUploadSessionCommand {
+        upload_session_id: parse_upload_session_id(&upload_id)?,
+        user_id: claims.sub,
+    };
+
+    abort_upload_session_command_handler(
+        state.upload_session_repository.as_ref(),
+        state.storage_service.as_ref(),
+        command,
+    )
+    .await
+    .map(|_| StatusCode::NO_CONTENT)
+    .map_err(upload_session_error_to_api_error)
 }
 
 pub fn api_routes(state: AppState) -> axum::Router<AppState> {
     axum::Router::new()
         .route("/upload", post(upload_media))
+        .route("/upload/request", post(request_upload))
+        .route("/{media_id}/confirm", post(confirm_upload))
         .route("/", get(get_media_files))
-        .route("/{media_id}", delete(delete_media))
+        .route("/checksums", post(check_media_checksums))
+        .route("/usage", get(get_storage_usage))
+        .route("/{media_id}", get(get_media_file).delete(delete_media))
+        .route("/{media_id}/signed-url", post(create_media_signed_url))
+        .route("/uploads", post(create_upload_session))
+        .route(
+            "/uploads/{upload_id}",
+            head(get_upload_session)
+                .patch(upload_chunk)
+                .delete(abort_upload_session),
+        )
+        .route(
+            "/uploads/{upload_id}/finalize",
+            post(finalize_upload_session),
+        )
         .route_layer(protected!(state.clone()))
-        .route("/stream/{media_path}", get(get_media_stream))
+        // Authenticates by itself, as it also accepts signed URLs
+        .route("/stream/{media_id}", get(get_media_stream))
+        .route("/{media_id}/thumbnail", get(get_media_thumbnail))
 }
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(upload_media, get_media_files, delete_media, get_media_stream),
+    paths(
+        upload_media,
+        request_upload,
+        confirm_upload,
+        get_media_files,
+        get_media_file,
+        check_media_checksums,
+        get_storage_usage,
+        delete_media,
+        create_media_signed_url,
+        get_media_stream,
+        get_media_thumbnail,
+        create_upload_session,
+        get_upload_session,
+        upload_chunk,
+        finalize_upload_session,
+        abort_upload_session
+    ),
     tags(
         (name = "media", description = "Media upload and management API")
     )
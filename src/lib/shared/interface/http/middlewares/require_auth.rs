@@ -24,12 +24,9 @@ pub async fn mw_require_auth(
         && let Ok(auth_str) = auth_value.to_str()
         && let Some(token) = auth_str.strip_prefix("Bearer ")
     {
-        // Validate the JWT token
-        let claims = state
-            .login_token_service
-            .as_ref()
-            .validate_token(token)
-            .map_err(|_| ApiError::UnauthorizedError("Unauthorized".to_string()).into_response())?;
+        let claims = authenticate_bearer_token(&state, token)
+            .await
+            .map_err(IntoResponse::into_response)?;
         // If the token is valid, attach the claims to the request
         req.extensions_mut().insert(claims);
         // Proceed to the next middleware or handler
@@ -39,6 +36,30 @@ pub async fn mw_require_auth(
     Err(ApiError::UnauthorizedError("Unauthorized".to_string()).into_response())
 }
 
+/// Validates an access token and checks its session was not revoked, for every endpoint taking
+/// bearer tokens, whether or not it sits behind `mw_require_auth`
+pub async fn authenticate_bearer_token(state: &AppState, token: &str) -> Result<Claims, ApiError> {
+    let claims = state
+        .login_token_service
+        .validate_token(token)
+        .map_err(|_| ApiError::UnauthorizedError("Unauthorized".to_string()))?;
+    // Reject access tokens of revoked sessions, sessions found active are cached for a while
+    if let Some(sid) = claims.sid
+        && !state.session_cache.is_active(sid)
+    {
+        let session = state
+            .session_repository
+            .touch_session(sid)
+            .await
+            .map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+        match session {
+            Some(session) if session.user_id == claims.sub => state.session_cache.mark_active(sid),
+            _ => return Err(ApiError::UnauthorizedError("Unauthorized".to_string())),
+        }
+    }
+    Ok(claims)
+}
+
 pub async fn mw_require_role(
     allowed_roles: &'static [Role],
     req: Request<Body>,
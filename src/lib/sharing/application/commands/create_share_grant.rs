@@ -0,0 +1,132 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::AlbumRepository,
+    media::domain::{MediaRepository, MediaStatus},
+    sharing::domain::{
+        AuthorizationService, NewShareGrant, Permission, ShareError, ShareGrant,
+        ShareGrantRepository, ShareRole, ShareTarget,
+    },
+    users::domain::UserRepository,
+};
+
+#[derive(Debug)]
+pub struct CreateShareGrantCommand {
+    pub target: ShareTarget,
+    pub owner_id: Uuid,
+    pub grantee_username: String,
+    pub role: ShareRole,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct ShareGrantResult {
+    pub grantee_id: Uuid,
+    pub grantee_username: String,
+    pub role: ShareRole,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl ShareGrantResult {
+    pub fn new(grant: ShareGrant, grantee_username: String) -> Self {
+        ShareGrantResult {
+            grantee_id: grant.grantee_id,
+            grantee_username,
+            role: grant.role,
+            created_at: grant.created_at,
+            updated_at: grant.updated_at,
+        }
+    }
+}
+
+/// Fails unless the shared media file or album exists and the user may manage it. Missing
+/// targets and targets of other users are reported the same way.
+pub(crate) async fn authorize_share_target<
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    target: ShareTarget,
+    user_id: Uuid,
+    media_repository: &MR,
+    album_repository: &AR,
+    authorization_service: &AS,
+) -> Result<(), ShareError> {
+    let authorized = match target {
+        ShareTarget::Media(media_id) => {
+            let media_file = media_repository
+                .get_media_file_by_id(media_id)
+                .await
+                .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?
+                .filter(|media_file| media_file.status == MediaStatus::Ready)
+                .ok_or(ShareError::MediaFileNotFound)?;
+            authorization_service
+                .can_access_media(user_id, &media_file, Permission::Manage)
+                .await
+        }
+        ShareTarget::Album(album_id) => {
+            let album = album_repository
+                .get_album_by_id(album_id)
+                .await
+                .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?
+                .ok_or(ShareError::AlbumNotFound)?;
+            authorization_service
+                .can_access_album(user_id, &album, Permission::Manage)
+                .await
+        }
+    }
+    .map_err(|e| ShareError::InternalServerError(e.to_string()))?;
+
+    match (authorized, target) {
+        (true, _) => Ok(()),
+        (false, ShareTarget::Media(_)) => Err(ShareError::MediaFileNotFound),
+        (false, ShareTarget::Album(_)) => Err(ShareError::AlbumNotFound),
+    }
+}
+
+/// Grants a role on a media file or an album, replacing the role the user had before
+pub async fn create_share_grant_command_handler<
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+    UR: UserRepository + ?Sized,
+    SR: ShareGrantRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    command: CreateShareGrantCommand,
+    media_repository: &MR,
+    album_repository: &AR,
+    user_repository: &UR,
+    share_grant_repository: &SR,
+    authorization_service: &AS,
+) -> Result<ShareGrantResult, ShareError> {
+    authorize_share_target(
+        command.target,
+        command.owner_id,
+        media_repository,
+        album_repository,
+        authorization_service,
+    )
+    .await?;
+
+    let grantee = user_repository
+        .get_by_username(command.grantee_username)
+        .await
+        .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?
+        .ok_or(ShareError::UserNotFound)?;
+
+    if grantee.id == command.owner_id {
+        return Err(ShareError::CannotShareWithOwner);
+    }
+
+    let grant = share_grant_repository
+        .save_share_grant(NewShareGrant {
+            target: command.target,
+            grantee_id: grantee.id,
+            role: command.role,
+        })
+        .await?;
+
+    Ok(ShareGrantResult::new(grant, grantee.username))
+}
@@ -0,0 +1,110 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::AlbumRepository,
+    media::domain::MediaRepository,
+    sharing::{
+        application::commands::create_share_grant::authorize_share_target,
+        domain::{
+            AuthorizationService, NewShareLink, ShareError, ShareLink, ShareLinkRepository,
+            ShareTarget,
+        },
+    },
+    users::domain::hash_password,
+};
+
+#[derive(Debug)]
+pub struct CreateShareLinkCommand {
+    pub target: ShareTarget,
+    pub owner_id: Uuid,
+    pub password: Option<String>,
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct ShareLinkResult {
+    pub id: Uuid,
+    /// Identifies the link in the public routes
+    pub slug: String,
+    pub media_id: Option<Uuid>,
+    pub album_id: Option<Uuid>,
+    pub has_password: bool,
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    pub view_count: i64,
+    pub last_viewed_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn create_share_link_command_handler<
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+    LR: ShareLinkRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    command: CreateShareLinkCommand,
+    media_repository: &MR,
+    album_repository: &AR,
+    share_link_repository: &LR,
+    authorization_service: &AS,
+) -> Result<ShareLinkResult, ShareError> {
+    authorize_share_target(
+        command.target,
+        command.owner_id,
+        media_repository,
+        album_repository,
+        authorization_service,
+    )
+    .await?;
+
+    if command
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= chrono::Utc::now().naive_utc())
+    {
+        return Err(ShareError::InvalidExpiry);
+    }
+
+    let password_hash = command
+        .password
+        .map(|password| hash_password(&password))
+        .transpose()
+        .map_err(|_| ShareError::InternalServerError("Failed to hash password".to_string()))?;
+
+    let link = share_link_repository
+        .create_share_link(NewShareLink {
+            slug: ShareLink::generate_slug(),
+            owner_id: command.owner_id,
+            target: command.target,
+            password_hash,
+            allow_download: command.allow_download,
+            expires_at: command.expires_at,
+        })
+        .await?;
+
+    Ok(link.into())
+}
+
+impl From<ShareLink> for ShareLinkResult {
+    fn from(link: ShareLink) -> Self {
+        let (media_id, album_id) = match link.target {
+            ShareTarget::Media(media_id) => (Some(media_id), None),
+            ShareTarget::Album(album_id) => (None, Some(album_id)),
+        };
+
+        ShareLinkResult {
+            id: link.id,
+            slug: link.slug,
+            media_id,
+            album_id,
+            has_password: link.password_hash.is_some(),
+            allow_download: link.allow_download,
+            expires_at: link.expires_at,
+            view_count: link.view_count,
+            last_viewed_at: link.last_viewed_at,
+            created_at: link.created_at,
+        }
+    }
+}
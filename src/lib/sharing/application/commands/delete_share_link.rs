@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use crate::sharing::domain::{ShareError, ShareLinkId, ShareLinkRepository};
+
+#[derive(Debug)]
+pub struct DeleteShareLinkCommand {
+    pub link_id: ShareLinkId,
+    pub owner_id: Uuid,
+}
+
+pub async fn delete_share_link_command_handler<LR: ShareLinkRepository + ?Sized>(
+    command: DeleteShareLinkCommand,
+    share_link_repository: &LR,
+) -> Result<(), ShareError> {
+    let link = share_link_repository
+        .get_share_link_by_id(command.link_id)
+        .await?
+        .filter(|link| link.owner_id == command.owner_id)
+        .ok_or(ShareError::ShareLinkNotFound)?;
+
+    share_link_repository.delete_share_link(link.id).await?;
+
+    Ok(())
+}
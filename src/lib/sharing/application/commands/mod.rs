@@ -0,0 +1,9 @@
+pub mod create_share_grant;
+pub mod create_share_link;
+pub mod delete_share_link;
+pub mod revoke_share_grant;
+
+pub use create_share_grant::*;
+pub use create_share_link::*;
+pub use delete_share_link::*;
+pub use revoke_share_grant::*;
@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::AlbumRepository,
+    media::domain::MediaRepository,
+    sharing::{
+        application::commands::create_share_grant::authorize_share_target,
+        domain::{AuthorizationService, ShareError, ShareGrantRepository, ShareTarget},
+    },
+};
+
+#[derive(Debug)]
+pub struct RevokeShareGrantCommand {
+    pub target: ShareTarget,
+    pub owner_id: Uuid,
+    pub grantee_id: Uuid,
+}
+
+pub async fn revoke_share_grant_command_handler<
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+    SR: ShareGrantRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    command: RevokeShareGrantCommand,
+    media_repository: &MR,
+    album_repository: &AR,
+    share_grant_repository: &SR,
+    authorization_service: &AS,
+) -> Result<(), ShareError> {
+    authorize_share_target(
+        command.target,
+        command.owner_id,
+        media_repository,
+        album_repository,
+        authorization_service,
+    )
+    .await?;
+
+    share_grant_repository
+        .delete_share_grant(command.target, command.grantee_id)
+        .await?;
+
+    Ok(())
+}
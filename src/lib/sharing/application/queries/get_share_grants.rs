@@ -0,0 +1,60 @@
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::AlbumRepository,
+    media::domain::MediaRepository,
+    sharing::{
+        application::commands::create_share_grant::{ShareGrantResult, authorize_share_target},
+        domain::{AuthorizationService, ShareError, ShareGrantRepository, ShareTarget},
+    },
+    users::domain::UserRepository,
+};
+
+#[derive(Debug)]
+pub struct GetShareGrantsQuery {
+    pub target: ShareTarget,
+    pub owner_id: Uuid,
+}
+
+/// Lists who a media file or an album is shared with, only the owner may see it
+pub async fn get_share_grants_query_handler<
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+    UR: UserRepository + ?Sized,
+    SR: ShareGrantRepository + ?Sized,
+    AS: AuthorizationService + ?Sized,
+>(
+    query: GetShareGrantsQuery,
+    media_repository: &MR,
+    album_repository: &AR,
+    user_repository: &UR,
+    share_grant_repository: &SR,
+    authorization_service: &AS,
+) -> Result<Vec<ShareGrantResult>, ShareError> {
+    authorize_share_target(
+        query.target,
+        query.owner_id,
+        media_repository,
+        album_repository,
+        authorization_service,
+    )
+    .await?;
+
+    let grants = share_grant_repository
+        .get_share_grants_by_target(query.target)
+        .await?;
+
+    let mut results = Vec::with_capacity(grants.len());
+    for grant in grants {
+        let grantee = user_repository
+            .get_by_id(grant.grantee_id)
+            .await
+            .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?;
+        // Grants are removed along with their user, a missing one was deleted meanwhile
+        if let Some(grantee) = grantee {
+            results.push(ShareGrantResult::new(grant, grantee.username));
+        }
+    }
+
+    Ok(results)
+}
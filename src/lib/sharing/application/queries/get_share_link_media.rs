@@ -0,0 +1,82 @@
+use crate::{
+    albums::domain::AlbumRepository,
+    media::domain::{MediaFile, MediaId, MediaRepository, MediaStatus},
+    sharing::{
+        application::queries::open_share_link::unlock_share_link,
+        domain::{ShareError, ShareLinkRepository, ShareTarget},
+    },
+};
+
+#[derive(Debug)]
+pub struct GetShareLinkMediaQuery {
+    pub slug: String,
+    pub password: Option<String>,
+    pub media_id: MediaId,
+    /// Whether the visitor asked for the original file as an attachment
+    pub download: bool,
+}
+
+/// Media file reachable through a share link
+#[derive(Debug)]
+pub struct ShareLinkMedia {
+    pub media_file: MediaFile,
+    /// Whether the original may be downloaded as an attachment
+    pub allow_download: bool,
+    /// Whether the original is streamed, links without downloads only show renditions of
+    /// images but still play videos inline
+    pub stream_original: bool,
+}
+
+/// Returns a media file reachable through a share link, for the caller to stream it
+pub async fn get_share_link_media_query_handler<
+    LR: ShareLinkRepository + ?Sized,
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+>(
+    query: GetShareLinkMediaQuery,
+    share_link_repository: &LR,
+    media_repository: &MR,
+    album_repository: &AR,
+) -> Result<ShareLinkMedia, ShareError> {
+    let link = unlock_share_link(
+        share_link_repository,
+        &query.slug,
+        query.password.as_deref(),
+    )
+    .await?;
+
+    if query.download && !link.allow_download {
+        return Err(ShareError::DownloadNotAllowed);
+    }
+
+    let media_file = match link.target {
+        ShareTarget::Media(media_id) if media_id == query.media_id => media_repository
+            .get_media_file_by_id(media_id)
+            .await
+            .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?,
+        ShareTarget::Media(_) => None,
+        ShareTarget::Album(album_id) => album_repository
+            .get_album_media(album_id)
+            .await
+            .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?
+            .into_iter()
+            .map(|album_media| album_media.media_file)
+            .find(|media_file| media_file.id == query.media_id),
+    };
+
+    media_file
+        .filter(|media_file| media_file.status == MediaStatus::Ready)
+        .map(|media_file| {
+            let content_type = media_file
+                .detected_content_type
+                .as_deref()
+                .unwrap_or(&media_file.content_type);
+            let stream_original = link.allow_download || content_type.starts_with("video/");
+            ShareLinkMedia {
+                media_file,
+                allow_download: link.allow_download,
+                stream_original,
+            }
+        })
+        .ok_or(ShareError::MediaFileNotFound)
+}
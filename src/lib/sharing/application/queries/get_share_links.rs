@@ -0,0 +1,22 @@
+use uuid::Uuid;
+
+use crate::sharing::{
+    application::commands::create_share_link::ShareLinkResult,
+    domain::{ShareError, ShareLinkRepository},
+};
+
+#[derive(Debug)]
+pub struct GetShareLinksQuery {
+    pub owner_id: Uuid,
+}
+
+pub async fn get_share_links_query_handler<LR: ShareLinkRepository + ?Sized>(
+    query: GetShareLinksQuery,
+    share_link_repository: &LR,
+) -> Result<Vec<ShareLinkResult>, ShareError> {
+    let links = share_link_repository
+        .get_share_links_by_owner_id(query.owner_id)
+        .await?;
+
+    Ok(links.into_iter().map(|link| link.into()).collect())
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::sharing::domain::{
+    ShareError, ShareGrantRepository, ShareRole, SharedAlbum, SharedMedia,
+};
+
+#[derive(Debug)]
+pub struct GetSharedWithMeQuery {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct SharedWithMeResult {
+    pub media: Vec<SharedMediaResult>,
+    pub albums: Vec<SharedAlbumResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct SharedMediaResult {
+    pub media_id: Uuid,
+    pub owner_id: Uuid,
+    pub role: ShareRole,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub uploaded_at: Option<chrono::NaiveDateTime>,
+    pub shared_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct SharedAlbumResult {
+    pub album_id: Uuid,
+    pub owner_id: Uuid,
+    pub role: ShareRole,
+    pub name: String,
+    pub description: Option<String>,
+    pub cover_media_id: Option<Uuid>,
+    pub shared_at: Option<chrono::NaiveDateTime>,
+}
+
+/// Lists the media files and albums other users shared with the user
+pub async fn get_shared_with_me_query_handler<SR: ShareGrantRepository + ?Sized>(
+    query: GetSharedWithMeQuery,
+    share_grant_repository: &SR,
+) -> Result<SharedWithMeResult, ShareError> {
+    let media = share_grant_repository
+        .get_shared_media_by_grantee_id(query.user_id)
+        .await?;
+    let albums = share_grant_repository
+        .get_shared_albums_by_grantee_id(query.user_id)
+        .await?;
+
+    Ok(SharedWithMeResult {
+        media: media.into_iter().map(|shared| shared.into()).collect(),
+        albums: albums.into_iter().map(|shared| shared.into()).collect(),
+    })
+}
+
+impl From<SharedMedia> for SharedMediaResult {
+    fn from(shared: SharedMedia) -> Self {
+        SharedMediaResult {
+            media_id: shared.media_file.id,
+            owner_id: shared.media_file.user_id,
+            role: shared.grant.role,
+            original_filename: shared.media_file.original_filename,
+            file_size: shared.media_file.file_size,
+            content_type: shared.media_file.content_type,
+            uploaded_at: shared.media_file.uploaded_at,
+            shared_at: shared.grant.created_at,
+        }
+    }
+}
+
+impl From<SharedAlbum> for SharedAlbumResult {
+    fn from(shared: SharedAlbum) -> Self {
+        SharedAlbumResult {
+            album_id: shared.album.id,
+            owner_id: shared.album.user_id,
+            role: shared.grant.role,
+            name: shared.album.name,
+            description: shared.album.description,
+            cover_media_id: shared.album.cover_media_id,
+            shared_at: shared.grant.created_at,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod get_share_grants;
+pub mod get_share_link_media;
+pub mod get_share_links;
+pub mod get_shared_with_me;
+pub mod open_share_link;
+
+pub use get_share_grants::*;
+pub use get_share_link_media::*;
+pub use get_share_links::*;
+pub use get_shared_with_me::*;
+pub use open_share_link::*;
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::AlbumRepository,
+    media::domain::{MediaFile, MediaRepository, MediaStatus},
+    sharing::domain::{ShareError, ShareLink, ShareLinkRepository, ShareTarget},
+    users::domain::verify_password,
+};
+
+#[derive(Debug)]
+pub struct OpenShareLinkQuery {
+    pub slug: String,
+    pub password: Option<String>,
+}
+
+/// What a visitor of a share link gets to see, nothing about the owner is exposed
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct ShareLinkContentResult {
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    /// Set when the link points to an album
+    pub album: Option<PublicAlbumResult>,
+    /// The shared media file, or the media files of the album in display order
+    pub media: Vec<PublicMediaResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct PublicAlbumResult {
+    pub name: String,
+    pub description: Option<String>,
+    pub cover_media_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct PublicMediaResult {
+    pub media_id: Uuid,
+    pub original_filename: String,
+    pub file_size: i64,
+    pub content_type: String,
+    pub uploaded_at: Option<chrono::NaiveDateTime>,
+}
+
+/// Loads a link by slug, failing when it expired or the password does not match
+pub(crate) async fn unlock_share_link<LR: ShareLinkRepository + ?Sized>(
+    share_link_repository: &LR,
+    slug: &str,
+    password: Option<&str>,
+) -> Result<ShareLink, ShareError> {
+    let link = share_link_repository
+        .get_share_link_by_slug(slug)
+        .await?
+        .ok_or(ShareError::ShareLinkNotFound)?;
+
+    if link.is_expired() {
+        return Err(ShareError::ShareLinkExpired);
+    }
+
+    if let Some(password_hash) = &link.password_hash
+        && !password.is_some_and(|password| verify_password(password, password_hash))
+    {
+        return Err(ShareError::InvalidSharePassword);
+    }
+
+    Ok(link)
+}
+
+/// Returns the content of a link and counts the visit
+pub async fn open_share_link_query_handler<
+    LR: ShareLinkRepository + ?Sized,
+    MR: MediaRepository + ?Sized,
+    AR: AlbumRepository + ?Sized,
+>(
+    query: OpenShareLinkQuery,
+    share_link_repository: &LR,
+    media_repository: &MR,
+    album_repository: &AR,
+) -> Result<ShareLinkContentResult, ShareError> {
+    let link = unlock_share_link(
+        share_link_repository,
+        &query.slug,
+        query.password.as_deref(),
+    )
+    .await?;
+
+    let (album, media_files) = match link.target {
+        ShareTarget::Media(media_id) => {
+            let media_file = media_repository
+                .get_media_file_by_id(media_id)
+                .await
+                .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?
+                .ok_or(ShareError::MediaFileNotFound)?;
+            (None, vec![media_file])
+        }
+        ShareTarget::Album(album_id) => {
+            let album = album_repository
+                .get_album_by_id(album_id)
+                .await
+                .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?
+                .ok_or(ShareError::AlbumNotFound)?;
+            let album_media = album_repository
+                .get_album_media(album_id)
+                .await
+                .map_err(|_| ShareError::InternalServerError("Database error".to_string()))?;
+            let album = PublicAlbumResult {
+                name: album.name,
+                description: album.description,
+                cover_media_id: album.cover_media_id,
+            };
+            (
+                Some(album),
+                album_media
+                    .into_iter()
+                    .map(|album_media| album_media.media_file)
+                    .collect(),
+            )
+        }
+    };
+
+    share_link_repository
+        .record_share_link_view(link.id)
+        .await?;
+
+    Ok(ShareLinkContentResult {
+        allow_download: link.allow_download,
+        expires_at: link.expires_at,
+        album,
+        media: media_files
+            .into_iter()
+            .filter(|media_file| media_file.status == MediaStatus::Ready)
+            .map(|media_file| media_file.into())
+            .collect(),
+    })
+}
+
+impl From<MediaFile> for PublicMediaResult {
+    fn from(media_file: MediaFile) -> Self {
+        PublicMediaResult {
+            media_id: media_file.id,
+            original_filename: media_file.original_filename,
+            file_size: media_file.file_size,
+            content_type: media_file.content_type,
+            uploaded_at: media_file.uploaded_at,
+        }
+    }
+}
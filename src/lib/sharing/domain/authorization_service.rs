@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{albums::domain::Album, media::domain::MediaFile};
+
+use super::{
+    share_grant::ShareRole,
+    share_grant_repository::{ShareGrantRepository, ShareGrantRepositoryError},
+};
+
+/// What a user wants to do with a media file or an album
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    View,
+    /// Add media files to an album
+    Contribute,
+    /// Modify, delete or share, reserved to the owner
+    Manage,
+}
+
+impl ShareRole {
+    pub fn allows(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::View => true,
+            Permission::Contribute => *self >= ShareRole::Contributor,
+            Permission::Manage => false,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthorizationError {
+    #[error("Repository error: {0}")]
+    RepositoryError(#[from] ShareGrantRepositoryError),
+}
+
+/// Single place deciding whether a user may access media files and albums of other users
+#[async_trait]
+pub trait AuthorizationService: Send + Sync {
+    async fn can_access_media(
+        &self,
+        user_id: Uuid,
+        media_file: &MediaFile,
+        permission: Permission,
+    ) -> Result<bool, AuthorizationError>;
+    async fn can_access_album(
+        &self,
+        user_id: Uuid,
+        album: &Album,
+        permission: Permission,
+    ) -> Result<bool, AuthorizationError>;
+}
+
+/// Owners may do anything, other users only what their share grants allow
+pub struct ShareGrantAuthorizationService<SR> {
+    share_grant_repository: SR,
+}
+
+impl<SR> ShareGrantAuthorizationService<SR>
+where
+    SR: ShareGrantRepository,
+{
+    pub fn new(share_grant_repository: SR) -> Self {
+        Self {
+            share_grant_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl<SR> AuthorizationService for ShareGrantAuthorizationService<SR>
+where
+    SR: ShareGrantRepository,
+{
+    async fn can_access_media(
+        &self,
+        user_id: Uuid,
+        media_file: &MediaFile,
+        permission: Permission,
+    ) -> Result<bool, AuthorizationError> {
+        if media_file.user_id == user_id {
+            return Ok(true);
+        }
+        if permission == Permission::Manage {
+            return Ok(false);
+        }
+
+        let role = self
+            .share_grant_repository
+            .get_media_role(media_file.id, user_id)
+            .await?;
+
+        Ok(role.is_some_and(|role| role.allows(permission)))
+    }
+
+    async fn can_access_album(
+        &self,
+        user_id: Uuid,
+        album: &Album,
+        permission: Permission,
+    ) -> Result<bool, AuthorizationError> {
+        if album.user_id == user_id {
+            return Ok(true);
+        }
+        if permission == Permission::Manage {
+            return Ok(false);
+        }
+
+        let role = self
+            .share_grant_repository
+            .get_album_role(album.id, user_id)
+            .await?;
+
+        Ok(role.is_some_and(|role| role.allows(permission)))
+    }
+}
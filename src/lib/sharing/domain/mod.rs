@@ -0,0 +1,11 @@
+pub mod authorization_service;
+pub mod share_grant;
+pub mod share_grant_repository;
+pub mod share_link;
+pub mod share_link_repository;
+
+pub use authorization_service::*;
+pub use share_grant::*;
+pub use share_grant_repository::*;
+pub use share_link::*;
+pub use share_link_repository::*;
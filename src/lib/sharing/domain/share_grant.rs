@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    albums::domain::{Album, AlbumId},
+    media::domain::{MediaFile, MediaId},
+};
+
+/// Role granted by an owner to another user, roles are ordered from least to most privileged
+#[derive(Debug, Clone, Copy, ToSchema, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum ShareRole {
+    /// Can see and stream the shared media
+    Viewer,
+    /// Can also add their own media files to a shared album
+    Contributor,
+}
+
+/// What is shared, sharing an album shares every media file in it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    Media(MediaId),
+    Album(AlbumId),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ShareGrant {
+    pub target: ShareTarget,
+    pub grantee_id: Uuid,
+    pub role: ShareRole,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewShareGrant {
+    pub target: ShareTarget,
+    pub grantee_id: Uuid,
+    pub role: ShareRole,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SharedMedia {
+    pub grant: ShareGrant,
+    pub media_file: MediaFile,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SharedAlbum {
+    pub grant: ShareGrant,
+    pub album: Album,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShareError {
+    #[error("Media file not found")]
+    MediaFileNotFound,
+    #[error("Album not found")]
+    AlbumNotFound,
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Share grant not found")]
+    ShareGrantNotFound,
+    #[error("Cannot share with the owner")]
+    CannotShareWithOwner,
+    #[error("Share link not found")]
+    ShareLinkNotFound,
+    #[error("Share link expired")]
+    ShareLinkExpired,
+    #[error("Invalid or missing share link password")]
+    InvalidSharePassword,
+    #[error("Downloads are not allowed through this share link")]
+    DownloadNotAllowed,
+    #[error("Expiry must be in the future")]
+    InvalidExpiry,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{albums::domain::AlbumId, media::domain::MediaId};
+
+use super::share_grant::{
+    NewShareGrant, ShareError, ShareGrant, ShareRole, ShareTarget, SharedAlbum, SharedMedia,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShareGrantRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+    #[error("Share grant not found")]
+    ShareGrantNotFound,
+}
+
+impl From<ShareGrantRepositoryError> for ShareError {
+    fn from(error: ShareGrantRepositoryError) -> Self {
+        match error {
+            ShareGrantRepositoryError::ShareGrantNotFound => ShareError::ShareGrantNotFound,
+            ShareGrantRepositoryError::InternalServerError => {
+                ShareError::InternalServerError("Database error".to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait ShareGrantRepository: Send + Sync {
+    /// Creates the grant, or replaces the role when the user already has one on the target
+    async fn save_share_grant(
+        &self,
+        grant: NewShareGrant,
+    ) -> Result<ShareGrant, ShareGrantRepositoryError>;
+    async fn delete_share_grant(
+        &self,
+        target: ShareTarget,
+        grantee_id: Uuid,
+    ) -> Result<(), ShareGrantRepositoryError>;
+    async fn get_share_grants_by_target(
+        &self,
+        target: ShareTarget,
+    ) -> Result<Vec<ShareGrant>, ShareGrantRepositoryError>;
+    /// Returns the ready media files shared directly with the user
+    async fn get_shared_media_by_grantee_id(
+        &self,
+        grantee_id: Uuid,
+    ) -> Result<Vec<SharedMedia>, ShareGrantRepositoryError>;
+    async fn get_shared_albums_by_grantee_id(
+        &self,
+        grantee_id: Uuid,
+    ) -> Result<Vec<SharedAlbum>, ShareGrantRepositoryError>;
+    /// Returns the highest role the user holds on a media file, either granted directly or
+    /// through an album containing it. Owners of an album see every media file added to it.
+    async fn get_media_role(
+        &self,
+        media_id: MediaId,
+        user_id: Uuid,
+    ) -> Result<Option<ShareRole>, ShareGrantRepositoryError>;
+    async fn get_album_role(
+        &self,
+        album_id: AlbumId,
+        user_id: Uuid,
+    ) -> Result<Option<ShareRole>, ShareGrantRepositoryError>;
+}
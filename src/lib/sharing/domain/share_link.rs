@@ -0,0 +1,52 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use password_hash::rand_core::{OsRng, RngCore};
+use uuid::Uuid;
+
+use super::share_grant::ShareTarget;
+
+pub type ShareLinkId = Uuid;
+
+/// Random bytes behind a slug, enough to make links impossible to guess
+const SLUG_BYTES: usize = 18;
+
+/// Public link giving people without an account access to a media file or an album
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ShareLink {
+    pub id: ShareLinkId,
+    pub slug: String,
+    pub owner_id: Uuid,
+    pub target: ShareTarget,
+    /// Argon2 hash of the password visitors have to provide, if any
+    pub password_hash: Option<String>,
+    /// Whether visitors may download the original files
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    pub view_count: i64,
+    pub last_viewed_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl ShareLink {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now().naive_utc())
+    }
+
+    /// Generates a random URL safe slug
+    pub fn generate_slug() -> String {
+        let mut bytes = [0u8; SLUG_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewShareLink {
+    pub slug: String,
+    pub owner_id: Uuid,
+    pub target: ShareTarget,
+    pub password_hash: Option<String>,
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+}
@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{
+    share_grant::ShareError,
+    share_link::{NewShareLink, ShareLink, ShareLinkId},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShareLinkRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+    #[error("Share link not found")]
+    ShareLinkNotFound,
+}
+
+impl From<ShareLinkRepositoryError> for ShareError {
+    fn from(error: ShareLinkRepositoryError) -> Self {
+        match error {
+            ShareLinkRepositoryError::ShareLinkNotFound => ShareError::ShareLinkNotFound,
+            ShareLinkRepositoryError::InternalServerError => {
+                ShareError::InternalServerError("Database error".to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait ShareLinkRepository: Send + Sync {
+    async fn create_share_link(
+        &self,
+        link: NewShareLink,
+    ) -> Result<ShareLink, ShareLinkRepositoryError>;
+    async fn get_share_link_by_id(
+        &self,
+        id: ShareLinkId,
+    ) -> Result<Option<ShareLink>, ShareLinkRepositoryError>;
+    async fn get_share_link_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<ShareLink>, ShareLinkRepositoryError>;
+    /// Returns the links of the user, newest first
+    async fn get_share_links_by_owner_id(
+        &self,
+        owner_id: Uuid,
+    ) -> Result<Vec<ShareLink>, ShareLinkRepositoryError>;
+    async fn delete_share_link(&self, id: ShareLinkId) -> Result<(), ShareLinkRepositoryError>;
+    /// Increments the view counter and sets the last view time
+    async fn record_share_link_view(&self, id: ShareLinkId)
+    -> Result<(), ShareLinkRepositoryError>;
+}
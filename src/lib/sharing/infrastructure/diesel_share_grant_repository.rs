@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+use super::models::{
+    AlbumShareModel, MediaShareModel, NewAlbumShareModel, NewMediaShareModel, RowShareRole,
+};
+use crate::{
+    albums::{domain::AlbumId, infrastructure::models::AlbumModel},
+    media::{
+        domain::MediaId,
+        infrastructure::models::{MediaFileModel, RowMediaStatus},
+    },
+    sharing::domain::{
+        NewShareGrant, ShareGrant, ShareGrantRepository, ShareGrantRepositoryError, ShareRole,
+        ShareTarget, SharedAlbum, SharedMedia,
+    },
+};
+
+pub struct DieselShareGrantRepository {
+    connection_pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselShareGrantRepository {
+    pub fn new(connection_pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+#[async_trait]
+impl ShareGrantRepository for DieselShareGrantRepository {
+    async fn save_share_grant(
+        &self,
+        grant: NewShareGrant,
+    ) -> Result<ShareGrant, ShareGrantRepositoryError> {
+        use crate::schema::{album_shares, media_shares};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let saved_grant = match grant.target {
+            ShareTarget::Media(media_id) => diesel::insert_into(media_shares::table)
+                .values(&NewMediaShareModel {
+                    media_id,
+                    grantee_id: grant.grantee_id,
+                    role: grant.role.into(),
+                })
+                .on_conflict((media_shares::media_id, media_shares::grantee_id))
+                .do_update()
+                .set(media_shares::role.eq(excluded(media_shares::role)))
+                .returning(MediaShareModel::as_returning())
+                .get_result(&mut conn)
+                .map(ShareGrant::from),
+            ShareTarget::Album(album_id) => diesel::insert_into(album_shares::table)
+                .values(&NewAlbumShareModel {
+                    album_id,
+                    grantee_id: grant.grantee_id,
+                    role: grant.role.into(),
+                })
+                .on_conflict((album_shares::album_id, album_shares::grantee_id))
+                .do_update()
+                .set(album_shares::role.eq(excluded(album_shares::role)))
+                .returning(AlbumShareModel::as_returning())
+                .get_result(&mut conn)
+                .map(ShareGrant::from),
+        }
+        .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        Ok(saved_grant)
+    }
+
+    async fn delete_share_grant(
+        &self,
+        target: ShareTarget,
+        grantee: Uuid,
+    ) -> Result<(), ShareGrantRepositoryError> {
+        use crate::schema::{album_shares, media_shares};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let deleted_rows = match target {
+            ShareTarget::Media(media_id) => diesel::delete(
+                media_shares::table
+                    .filter(media_shares::media_id.eq(media_id))
+                    .filter(media_shares::grantee_id.eq(grantee)),
+            )
+            .execute(&mut conn),
+            ShareTarget::Album(album_id) => diesel::delete(
+                album_shares::table
+                    .filter(album_shares::album_id.eq(album_id))
+                    .filter(album_shares::grantee_id.eq(grantee)),
+            )
+            .execute(&mut conn),
+        }
+        .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        if deleted_rows == 0 {
+            Err(ShareGrantRepositoryError::ShareGrantNotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn get_share_grants_by_target(
+        &self,
+        target: ShareTarget,
+    ) -> Result<Vec<ShareGrant>, ShareGrantRepositoryError> {
+        use crate::schema::{album_shares, media_shares};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let grants = match target {
+            ShareTarget::Media(media_id) => media_shares::table
+                .filter(media_shares::media_id.eq(media_id))
+                .order(media_shares::created_at.asc())
+                .select(MediaShareModel::as_select())
+                .load::<MediaShareModel>(&mut conn)
+                .map(|models| models.into_iter().map(ShareGrant::from).collect()),
+            ShareTarget::Album(album_id) => album_shares::table
+                .filter(album_shares::album_id.eq(album_id))
+                .order(album_shares::created_at.asc())
+                .select(AlbumShareModel::as_select())
+                .load::<AlbumShareModel>(&mut conn)
+                .map(|models| models.into_iter().map(ShareGrant::from).collect()),
+        }
+        .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        Ok(grants)
+    }
+
+    async fn get_shared_media_by_grantee_id(
+        &self,
+        grantee: Uuid,
+    ) -> Result<Vec<SharedMedia>, ShareGrantRepositoryError> {
+        use crate::schema::{media_files, media_shares};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let results = media_shares::table
+            .inner_join(media_files::table)
+            .filter(media_shares::grantee_id.eq(grantee))
+            .filter(media_files::status.eq(RowMediaStatus::Ready))
+            .order(media_shares::created_at.desc())
+            .select((MediaShareModel::as_select(), MediaFileModel::as_select()))
+            .load::<(MediaShareModel, MediaFileModel)>(&mut conn)
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|row| row.into()).collect())
+    }
+
+    async fn get_shared_albums_by_grantee_id(
+        &self,
+        grantee: Uuid,
+    ) -> Result<Vec<SharedAlbum>, ShareGrantRepositoryError> {
+        use crate::schema::{album_shares, albums};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let results = album_shares::table
+            .inner_join(albums::table)
+            .filter(album_shares::grantee_id.eq(grantee))
+            .order(album_shares::created_at.desc())
+            .select((AlbumShareModel::as_select(), AlbumModel::as_select()))
+            .load::<(AlbumShareModel, AlbumModel)>(&mut conn)
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        Ok(results.into_iter().map(|row| row.into()).collect())
+    }
+
+    async fn get_media_role(
+        &self,
+        media: MediaId,
+        user: Uuid,
+    ) -> Result<Option<ShareRole>, ShareGrantRepositoryError> {
+        use crate::schema::{album_media, album_shares, albums, media_shares};
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let direct_role = media_shares::table
+            .filter(media_shares::media_id.eq(media))
+            .filter(media_shares::grantee_id.eq(user))
+            .select(media_shares::role)
+            .first::<RowShareRole>(&mut conn)
+            .optional()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let album_roles = album_shares::table
+            .inner_join(album_media::table.on(album_media::album_id.eq(album_shares::album_id)))
+            .filter(album_media::media_id.eq(media))
+            .filter(album_shares::grantee_id.eq(user))
+            .select(album_shares::role)
+            .load::<RowShareRole>(&mut conn)
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        // Media files contributed to an album are visible to the album owner
+        let in_owned_album = diesel::select(diesel::dsl::exists(
+            album_media::table
+                .inner_join(albums::table)
+                .filter(album_media::media_id.eq(media))
+                .filter(albums::user_id.eq(user)),
+        ))
+        .get_result::<bool>(&mut conn)
+        .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        Ok(direct_role
+            .into_iter()
+            .chain(album_roles)
+            .map(ShareRole::from)
+            .chain(in_owned_album.then_some(ShareRole::Viewer))
+            .max())
+    }
+
+    async fn get_album_role(
+        &self,
+        album: AlbumId,
+        user: Uuid,
+    ) -> Result<Option<ShareRole>, ShareGrantRepositoryError> {
+        use crate::schema::album_shares::dsl::*;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        let result = album_shares
+            .filter(album_id.eq(album))
+            .filter(grantee_id.eq(user))
+            .select(role)
+            .first::<RowShareRole>(&mut conn)
+            .optional()
+            .map_err(|_| ShareGrantRepositoryError::InternalServerError)?;
+
+        Ok(result.map(ShareRole::from))
+    }
+}
@@ -0,0 +1,151 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use uuid::Uuid;
+
+use super::models::{NewShareLinkModel, ShareLinkModel};
+use crate::sharing::domain::{
+    NewShareLink, ShareLink, ShareLinkId, ShareLinkRepository, ShareLinkRepositoryError,
+};
+
+pub struct DieselShareLinkRepository {
+    connection_pool: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl DieselShareLinkRepository {
+    pub fn new(connection_pool: Pool<ConnectionManager<PgConnection>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+#[async_trait]
+impl ShareLinkRepository for DieselShareLinkRepository {
+    async fn create_share_link(
+        &self,
+        link: NewShareLink,
+    ) -> Result<ShareLink, ShareLinkRepositoryError> {
+        use crate::schema::share_links;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        diesel::insert_into(share_links::table)
+            .values(&NewShareLinkModel::from(link))
+            .returning(ShareLinkModel::as_returning())
+            .get_result(&mut conn)
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?
+            .try_into()
+    }
+
+    async fn get_share_link_by_id(
+        &self,
+        id: ShareLinkId,
+    ) -> Result<Option<ShareLink>, ShareLinkRepositoryError> {
+        use crate::schema::share_links;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        share_links::table
+            .find(id)
+            .select(ShareLinkModel::as_select())
+            .first::<ShareLinkModel>(&mut conn)
+            .optional()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?
+            .map(ShareLink::try_from)
+            .transpose()
+    }
+
+    async fn get_share_link_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<ShareLink>, ShareLinkRepositoryError> {
+        use crate::schema::share_links;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        share_links::table
+            .filter(share_links::slug.eq(slug))
+            .select(ShareLinkModel::as_select())
+            .first::<ShareLinkModel>(&mut conn)
+            .optional()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?
+            .map(ShareLink::try_from)
+            .transpose()
+    }
+
+    async fn get_share_links_by_owner_id(
+        &self,
+        owner: Uuid,
+    ) -> Result<Vec<ShareLink>, ShareLinkRepositoryError> {
+        use crate::schema::share_links;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        share_links::table
+            .filter(share_links::owner_id.eq(owner))
+            .order(share_links::created_at.desc())
+            .select(ShareLinkModel::as_select())
+            .load::<ShareLinkModel>(&mut conn)
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?
+            .into_iter()
+            .map(ShareLink::try_from)
+            .collect()
+    }
+
+    async fn delete_share_link(&self, id: ShareLinkId) -> Result<(), ShareLinkRepositoryError> {
+        use crate::schema::share_links;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        let deleted_rows = diesel::delete(share_links::table.find(id))
+            .execute(&mut conn)
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        if deleted_rows == 0 {
+            Err(ShareLinkRepositoryError::ShareLinkNotFound)
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn record_share_link_view(
+        &self,
+        id: ShareLinkId,
+    ) -> Result<(), ShareLinkRepositoryError> {
+        use crate::schema::share_links;
+
+        let mut conn = self
+            .connection_pool
+            .get()
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        // Incremented in SQL so concurrent views are all counted
+        let updated_rows = diesel::update(share_links::table.find(id))
+            .set((
+                share_links::view_count.eq(share_links::view_count + 1),
+                share_links::last_viewed_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .map_err(|_| ShareLinkRepositoryError::InternalServerError)?;
+
+        if updated_rows == 0 {
+            Err(ShareLinkRepositoryError::ShareLinkNotFound)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use super::models::{AlbumShareModel, MediaShareModel, NewShareLinkModel, ShareLinkModel};
+use crate::{
+    albums::infrastructure::models::AlbumModel,
+    media::infrastructure::models::MediaFileModel,
+    sharing::domain::{
+        NewShareLink, ShareGrant, ShareLink, ShareLinkRepositoryError, ShareTarget, SharedAlbum,
+        SharedMedia,
+    },
+};
+
+impl From<MediaShareModel> for ShareGrant {
+    fn from(model: MediaShareModel) -> Self {
+        ShareGrant {
+            target: ShareTarget::Media(model.media_id),
+            grantee_id: model.grantee_id,
+            role: model.role.into(),
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<AlbumShareModel> for ShareGrant {
+    fn from(model: AlbumShareModel) -> Self {
+        ShareGrant {
+            target: ShareTarget::Album(model.album_id),
+            grantee_id: model.grantee_id,
+            role: model.role.into(),
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<(MediaShareModel, MediaFileModel)> for SharedMedia {
+    fn from((share, media_file): (MediaShareModel, MediaFileModel)) -> Self {
+        SharedMedia {
+            grant: share.into(),
+            media_file: media_file.into(),
+        }
+    }
+}
+
+impl From<(AlbumShareModel, AlbumModel)> for SharedAlbum {
+    fn from((share, album): (AlbumShareModel, AlbumModel)) -> Self {
+        SharedAlbum {
+            grant: share.into(),
+            album: album.into(),
+        }
+    }
+}
+
+impl TryFrom<ShareLinkModel> for ShareLink {
+    type Error = ShareLinkRepositoryError;
+
+    fn try_from(model: ShareLinkModel) -> Result<Self, Self::Error> {
+        // The table constraint guarantees exactly one target is set
+        let target = match (model.media_id, model.album_id) {
+            (Some(media_id), None) => ShareTarget::Media(media_id),
+            (None, Some(album_id)) => ShareTarget::Album(album_id),
+            _ => return Err(ShareLinkRepositoryError::InternalServerError),
+        };
+
+        Ok(ShareLink {
+            id: model.id,
+            slug: model.slug,
+            owner_id: model.owner_id,
+            target,
+            password_hash: model.password_hash,
+            allow_download: model.allow_download,
+            expires_at: model.expires_at,
+            view_count: model.view_count,
+            last_viewed_at: model.last_viewed_at,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        })
+    }
+}
+
+impl From<NewShareLink> for NewShareLinkModel {
+    fn from(link: NewShareLink) -> Self {
+        let (media_id, album_id) = match link.target {
+            ShareTarget::Media(media_id) => (Some(media_id), None),
+            ShareTarget::Album(album_id) => (None, Some(album_id)),
+        };
+
+        NewShareLinkModel {
+            slug: link.slug,
+            owner_id: link.owner_id,
+            media_id,
+            album_id,
+            password_hash: link.password_hash,
+            allow_download: link.allow_download,
+            expires_at: link.expires_at,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod diesel_share_grant_repository;
+pub mod diesel_share_link_repository;
+pub mod mappers;
+pub mod models;
+
+pub use diesel_share_grant_repository::*;
+pub use diesel_share_link_repository::*;
@@ -0,0 +1,122 @@
+use std::io::Write;
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::{persistence::domain::schema::sql_types, sharing::domain::ShareRole};
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::media_shares)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MediaShareModel {
+    pub media_id: Uuid,
+    pub grantee_id: Uuid,
+    pub role: RowShareRole,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::media_shares)]
+pub struct NewMediaShareModel {
+    pub media_id: Uuid,
+    pub grantee_id: Uuid,
+    pub role: RowShareRole,
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::album_shares)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AlbumShareModel {
+    pub album_id: Uuid,
+    pub grantee_id: Uuid,
+    pub role: RowShareRole,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::album_shares)]
+pub struct NewAlbumShareModel {
+    pub album_id: Uuid,
+    pub grantee_id: Uuid,
+    pub role: RowShareRole,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, diesel::FromSqlRow, diesel::AsExpression)]
+#[diesel(sql_type = sql_types::ShareRole)]
+pub enum RowShareRole {
+    Viewer,
+    Contributor,
+}
+
+impl From<ShareRole> for RowShareRole {
+    fn from(role: ShareRole) -> Self {
+        match role {
+            ShareRole::Viewer => RowShareRole::Viewer,
+            ShareRole::Contributor => RowShareRole::Contributor,
+        }
+    }
+}
+
+impl From<RowShareRole> for ShareRole {
+    fn from(row_role: RowShareRole) -> Self {
+        match row_role {
+            RowShareRole::Viewer => ShareRole::Viewer,
+            RowShareRole::Contributor => ShareRole::Contributor,
+        }
+    }
+}
+
+impl diesel::serialize::ToSql<sql_types::ShareRole, diesel::pg::Pg> for RowShareRole {
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        match *self {
+            RowShareRole::Viewer => out.write_all(b"VIEWER")?,
+            RowShareRole::Contributor => out.write_all(b"CONTRIBUTOR")?,
+        }
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+impl diesel::deserialize::FromSql<sql_types::ShareRole, diesel::pg::Pg> for RowShareRole {
+    fn from_sql(bytes: diesel::pg::PgValue) -> diesel::deserialize::Result<Self> {
+        match std::str::from_utf8(bytes.as_bytes())? {
+            "VIEWER" => Ok(RowShareRole::Viewer),
+            "CONTRIBUTOR" => Ok(RowShareRole::Contributor),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::share_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ShareLinkModel {
+    pub id: Uuid,
+    pub slug: String,
+    pub owner_id: Uuid,
+    pub media_id: Option<Uuid>,
+    pub album_id: Option<Uuid>,
+    pub password_hash: Option<String>,
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    pub view_count: i64,
+    pub last_viewed_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::share_links)]
+pub struct NewShareLinkModel {
+    pub slug: String,
+    pub owner_id: Uuid,
+    pub media_id: Option<Uuid>,
+    pub album_id: Option<Uuid>,
+    pub password_hash: Option<String>,
+    pub allow_download: bool,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+}
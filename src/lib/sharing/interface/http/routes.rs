@@ -0,0 +1,686 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::Response,
+    routing::{delete, get},
+};
+use chrono::{DateTime, Utc};
+use utoipa::OpenApi;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    api::{
+        domain::{
+            errors::{ApiError, ApiErrorBody},
+            response_body::ApiResponseBody,
+        },
+        http_server::AppState,
+    },
+    media::{
+        PREVIEW_RENDITION_NAME,
+        application::queries::{
+            get_media_stream::{GetMediaStreamQuery, MediaStreamAccess},
+            get_media_thumbnail::GetMediaThumbnailQuery,
+        },
+        interface::http::routes::{media_stream_response, media_thumbnail_response},
+        supports_metadata_stripping,
+    },
+    protected,
+    shared::interface::http::ValidatedJson,
+    sharing::{
+        application::{
+            commands::{
+                create_share_grant::{
+                    CreateShareGrantCommand, ShareGrantResult, create_share_grant_command_handler,
+                },
+                create_share_link::{
+                    CreateShareLinkCommand, ShareLinkResult, create_share_link_command_handler,
+                },
+                delete_share_link::{DeleteShareLinkCommand, delete_share_link_command_handler},
+                revoke_share_grant::{RevokeShareGrantCommand, revoke_share_grant_command_handler},
+            },
+            queries::{
+                get_share_grants::{GetShareGrantsQuery, get_share_grants_query_handler},
+                get_share_link_media::{
+                    GetShareLinkMediaQuery, ShareLinkMedia, get_share_link_media_query_handler,
+                },
+                get_share_links::{GetShareLinksQuery, get_share_links_query_handler},
+                get_shared_with_me::{
+                    GetSharedWithMeQuery, SharedWithMeResult, get_shared_with_me_query_handler,
+                },
+                open_share_link::{
+                    OpenShareLinkQuery, ShareLinkContentResult, open_share_link_query_handler,
+                },
+            },
+        },
+        domain::{ShareError, ShareRole, ShareTarget},
+    },
+    users::domain::Claims,
+};
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct CreateShareGrantRequestBody {
+    /// Username of the user to share with
+    #[validate(length(min = 1, message = "Username cannot be empty"))]
+    username: String,
+    role: ShareRole,
+}
+
+fn parse_id(id: &str, name: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id)
+        .map_err(|_| ApiError::BadRequestError(format!("Invalid {} ID format", name)))
+}
+
+fn share_error_to_api_error(error: ShareError) -> ApiError {
+    match error {
+        ShareError::MediaFileNotFound
+        | ShareError::AlbumNotFound
+        | ShareError::UserNotFound
+        | ShareError::ShareGrantNotFound => ApiError::NotFoundError(error.to_string()),
+        ShareError::ShareLinkNotFound | ShareError::ShareLinkExpired => {
+            ApiError::NotFoundError(error.to_string())
+        }
+        ShareError::CannotShareWithOwner | ShareError::InvalidExpiry => {
+            ApiError::BadRequestError(error.to_string())
+        }
+        ShareError::InvalidSharePassword => ApiError::UnauthorizedError(error.to_string()),
+        ShareError::DownloadNotAllowed => ApiError::ForbiddenError(error.to_string()),
+        ShareError::InternalServerError(msg) => {
+            tracing::error!("Internal server error, {}", msg);
+            ApiError::InternalServerError("Internal server error".to_string())
+        }
+    }
+}
+
+async fn create_share_grant(
+    state: AppState,
+    claims: Claims,
+    target: ShareTarget,
+    body: CreateShareGrantRequestBody,
+) -> Result<(StatusCode, Json<ApiResponseBody<ShareGrantResult>>), ApiError> {
+    let command = CreateShareGrantCommand {
+        target,
+        owner_id: claims.sub,
+        grantee_username: body.username,
+        role: body.role,
+    };
+
+    create_share_grant_command_handler(
+        command,
+        state.media_repository.as_ref(),
+        state.album_repository.as_ref(),
+        state.user_repository.as_ref(),
+        state.share_grant_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    .map(|grant| (StatusCode::OK, ApiResponseBody::new(grant).into()))
+    .map_err(share_error_to_api_error)
+}
+
+async fn get_share_grants(
+    state: AppState,
+    claims: Claims,
+    target: ShareTarget,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<ShareGrantResult>>>), ApiError> {
+    let query = GetShareGrantsQuery {
+        target,
+        owner_id: claims.sub,
+    };
+
+    get_share_grants_query_handler(
+        query,
+        state.media_repository.as_ref(),
+        state.album_repository.as_ref(),
+        state.user_repository.as_ref(),
+        state.share_grant_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    .map(|grants| (StatusCode::OK, ApiResponseBody::new(grants).into()))
+    .map_err(share_error_to_api_error)
+}
+
+async fn revoke_share_grant(
+    state: AppState,
+    claims: Claims,
+    target: ShareTarget,
+    grantee_id: &str,
+) -> Result<StatusCode, ApiError> {
+    let command = RevokeShareGrantCommand {
+        target,
+        owner_id: claims.sub,
+        grantee_id: parse_id(grantee_id, "user")?,
+    };
+
+    revoke_share_grant_command_handler(
+        command,
+        state.media_repository.as_ref(),
+        state.album_repository.as_ref(),
+        state.share_grant_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    .map(|_| StatusCode::NO_CONTENT)
+    .map_err(share_error_to_api_error)
+}
+
+#[utoipa::path(
+    post,
+    path = "/media/{media_id}",
+    description = "Share a media file with another user, replacing the role they had before",
+    tag = "sharing",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file")
+    ),
+    request_body = CreateShareGrantRequestBody,
+    responses(
+        (status = 200, description = "Media file shared", body = ApiResponseBody<ShareGrantResult>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 404, description = "Media file or user not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn share_media(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(media_id): Path<String>,
+    ValidatedJson(body): ValidatedJson<CreateShareGrantRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<ShareGrantResult>>), ApiError> {
+    let target = ShareTarget::Media(parse_id(&media_id, "media")?);
+    create_share_grant(state, claims, target, body).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/media/{media_id}",
+    description = "List the users a media file is shared with",
+    tag = "sharing",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file")
+    ),
+    responses(
+        (status = 200, description = "Share grants of the media file", body = ApiResponseBody<Vec<ShareGrantResult>>),
+        (status = 400, description = "Invalid media ID format", body = ApiErrorBody),
+        (status = 404, description = "Media file not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_media_shares(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(media_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<ShareGrantResult>>>), ApiError> {
+    let target = ShareTarget::Media(parse_id(&media_id, "media")?);
+    get_share_grants(state, claims, target).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/media/{media_id}/{user_id}",
+    description = "Stop sharing a media file with a user",
+    tag = "sharing",
+    params(
+        ("media_id" = String, Path, description = "ID of the media file"),
+        ("user_id" = String, Path, description = "ID of the user the media file is shared with")
+    ),
+    responses(
+        (status = 204, description = "Share grant revoked"),
+        (status = 400, description = "Invalid media or user ID format", body = ApiErrorBody),
+        (status = 404, description = "Media file or share grant not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn unshare_media(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((media_id, user_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    let target = ShareTarget::Media(parse_id(&media_id, "media")?);
+    revoke_share_grant(state, claims, target, &user_id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/albums/{album_id}",
+    description = "Share an album and every media file in it with another user, replacing the role they had before. Contributors can also add their own media files to the album",
+    tag = "sharing",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    request_body = CreateShareGrantRequestBody,
+    responses(
+        (status = 200, description = "Album shared", body = ApiResponseBody<ShareGrantResult>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 404, description = "Album or user not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn share_album(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+    ValidatedJson(body): ValidatedJson<CreateShareGrantRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<ShareGrantResult>>), ApiError> {
+    let target = ShareTarget::Album(parse_id(&album_id, "album")?);
+    create_share_grant(state, claims, target, body).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/albums/{album_id}",
+    description = "List the users an album is shared with",
+    tag = "sharing",
+    params(
+        ("album_id" = String, Path, description = "ID of the album")
+    ),
+    responses(
+        (status = 200, description = "Share grants of the album", body = ApiResponseBody<Vec<ShareGrantResult>>),
+        (status = 400, description = "Invalid album ID format", body = ApiErrorBody),
+        (status = 404, description = "Album not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_album_shares(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(album_id): Path<String>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<ShareGrantResult>>>), ApiError> {
+    let target = ShareTarget::Album(parse_id(&album_id, "album")?);
+    get_share_grants(state, claims, target).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/albums/{album_id}/{user_id}",
+    description = "Stop sharing an album with a user",
+    tag = "sharing",
+    params(
+        ("album_id" = String, Path, description = "ID of the album"),
+        ("user_id" = String, Path, description = "ID of the user the album is shared with")
+    ),
+    responses(
+        (status = 204, description = "Share grant revoked"),
+        (status = 400, description = "Invalid album or user ID format", body = ApiErrorBody),
+        (status = 404, description = "Album or share grant not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn unshare_album(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path((album_id, user_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    let target = ShareTarget::Album(parse_id(&album_id, "album")?);
+    revoke_share_grant(state, claims, target, &user_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/with-me",
+    description = "List the media files and albums other users shared with the user",
+    tag = "sharing",
+    responses(
+        (status = 200, description = "Media files and albums shared with the user", body = ApiResponseBody<SharedWithMeResult>),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_shared_with_me(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<(StatusCode, Json<ApiResponseBody<SharedWithMeResult>>), ApiError> {
+    let query = GetSharedWithMeQuery {
+        user_id: claims.sub,
+    };
+
+    get_shared_with_me_query_handler(query, state.share_grant_repository.as_ref())
+        .await
+        .map(|shared| (StatusCode::OK, ApiResponseBody::new(shared).into()))
+        .map_err(share_error_to_api_error)
+}
+
+/// Header visitors send the password of a protected share link in
+const SHARE_PASSWORD_HEADER: &str = "x-share-password";
+
+#[derive(Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct CreateShareLinkRequestBody {
+    /// Media file to share, exclusive with `album_id`
+    media_id: Option<Uuid>,
+    /// Album to share, exclusive with `media_id`
+    album_id: Option<Uuid>,
+    /// Password visitors have to send in the `x-share-password` header
+    #[validate(length(min = 4, message = "Password must be at least 4 characters long"))]
+    password: Option<String>,
+    /// Whether visitors may download the original files, otherwise they only get previews
+    #[serde(default)]
+    allow_download: bool,
+    /// The link stops working after this time, never expires when omitted
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/links",
+    description = "Create a public link to a media file or an album, for people without an account",
+    tag = "sharing",
+    request_body = CreateShareLinkRequestBody,
+    responses(
+        (status = 201, description = "Share link created", body = ApiResponseBody<ShareLinkResult>),
+        (status = 400, description = "Invalid request", body = ApiErrorBody),
+        (status = 404, description = "Media file or album not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn create_share_link(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedJson(body): ValidatedJson<CreateShareLinkRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<ShareLinkResult>>), ApiError> {
+    let target = match (body.media_id, body.album_id) {
+        (Some(media_id), None) => ShareTarget::Media(media_id),
+        (None, Some(album_id)) => ShareTarget::Album(album_id),
+        _ => {
+            return Err(ApiError::BadRequestError(
+                "Exactly one of media_id and album_id must be set".to_string(),
+            ));
+        }
+    };
+
+    let command = CreateShareLinkCommand {
+        target,
+        owner_id: claims.sub,
+        password: body.password,
+        allow_download: body.allow_download,
+        expires_at: body.expires_at.map(|expires_at| expires_at.naive_utc()),
+    };
+
+    create_share_link_command_handler(
+        command,
+        state.media_repository.as_ref(),
+        state.album_repository.as_ref(),
+        state.share_link_repository.as_ref(),
+        state.authorization_service.as_ref(),
+    )
+    .await
+    .map(|link| (StatusCode::CREATED, ApiResponseBody::new(link).into()))
+    .map_err(share_error_to_api_error)
+}
+
+#[utoipa::path(
+    get,
+    path = "/links",
+    description = "List the share links of the user, newest first, with their view counters",
+    tag = "sharing",
+    responses(
+        (status = 200, description = "Share links of the user", body = ApiResponseBody<Vec<ShareLinkResult>>),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_share_links(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<ShareLinkResult>>>), ApiError> {
+    let query = GetShareLinksQuery {
+        owner_id: claims.sub,
+    };
+
+    get_share_links_query_handler(query, state.share_link_repository.as_ref())
+        .await
+        .map(|links| (StatusCode::OK, ApiResponseBody::new(links).into()))
+        .map_err(share_error_to_api_error)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/links/{link_id}",
+    description = "Delete a share link, it stops working immediately",
+    tag = "sharing",
+    params(
+        ("link_id" = String, Path, description = "ID of the share link")
+    ),
+    responses(
+        (status = 204, description = "Share link deleted"),
+        (status = 400, description = "Invalid share link ID format", body = ApiErrorBody),
+        (status = 404, description = "Share link not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn delete_share_link(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(link_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let command = DeleteShareLinkCommand {
+        link_id: parse_id(&link_id, "share link")?,
+        owner_id: claims.sub,
+    };
+
+    delete_share_link_command_handler(command, state.share_link_repository.as_ref())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(share_error_to_api_error)
+}
+
+fn share_password(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SHARE_PASSWORD_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+#[utoipa::path(
+    get,
+    path = "/links/{slug}",
+    description = "Open a share link, returns the shared media file or album and counts the visit. No account is needed",
+    tag = "public",
+    params(
+        ("slug" = String, Path, description = "Slug of the share link"),
+        ("x-share-password" = Option<String>, Header, description = "Password of the share link, if it has one")
+    ),
+    responses(
+        (status = 200, description = "Content of the share link", body = ApiResponseBody<ShareLinkContentResult>),
+        (status = 401, description = "Invalid or missing password", body = ApiErrorBody),
+        (status = 404, description = "Share link not found or expired", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+)]
+pub async fn open_share_link(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<ApiResponseBody<ShareLinkContentResult>>), ApiError> {
+    let query = OpenShareLinkQuery {
+        slug,
+        password: share_password(&headers),
+    };
+
+    open_share_link_query_handler(
+        query,
+        state.share_link_repository.as_ref(),
+        state.media_repository.as_ref(),
+        state.album_repository.as_ref(),
+    )
+    .await
+    .map(|content| (StatusCode::OK, ApiResponseBody::new(content).into()))
+    .map_err(share_error_to_api_error)
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ShareLinkMediaParams {
+    /// Serve the original file as an attachment, requires a link allowing downloads
+    download: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/links/{slug}/media/{media_id}",
+    description = "Stream a media file reachable through a share link. Supports single `Range` requests like the authenticated stream. Links that do not allow downloads serve the `preview` rendition of images instead of the original, in a format picked from the `Accept` header, videos are still streamed inline. The location is removed from JPEG and TIFF based originals",
+    tag = "public",
+    params(
+        ("slug" = String, Path, description = "Slug of the share link"),
+        ("media_id" = String, Path, description = "ID of the shared media file"),
+        ShareLinkMediaParams,
+        ("x-share-password" = Option<String>, Header, description = "Password of the share link, if it has one"),
+        ("Range" = Option<String>, Header, description = "Byte range to return, e.g. `bytes=0-1023`"),
+        ("If-Range" = Option<String>, Header, description = "ETag the range request is conditional on"),
+    ),
+    responses(
+        (status = 200, description = "Media file streamed correctly", body = [u8]),
+        (status = 206, description = "Requested range of the media file", body = [u8]),
+        (status = 400, description = "Invalid media ID format", body = ApiErrorBody),
+        (status = 401, description = "Invalid or missing password", body = ApiErrorBody),
+        (status = 403, description = "Downloads are not allowed through this share link", body = ApiErrorBody),
+        (status = 404, description = "Share link, media file or its preview not found", body = ApiErrorBody),
+        (status = 416, description = "Requested range not satisfiable"),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+)]
+pub async fn stream_share_link_media(
+    State(state): State<AppState>,
+    Path((slug, media_id)): Path<(String, String)>,
+    Query(params): Query<ShareLinkMediaParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let download = params.download.unwrap_or(false);
+    let query = GetShareLinkMediaQuery {
+        slug,
+        password: share_password(&headers),
+        media_id: parse_id(&media_id, "media")?,
+        download,
+    };
+
+    let ShareLinkMedia {
+        media_file,
+        stream_original,
+        ..
+    } = get_share_link_media_query_handler(
+        query,
+        state.share_link_repository.as_ref(),
+        state.media_repository.as_ref(),
+        state.album_repository.as_ref(),
+    )
+    .await
+    .map_err(share_error_to_api_error)?;
+
+    let header_value = |name: header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+
+    // The link already granted access, a freshly signed URL carries that to the media queries
+    let signed_url = state.media_url_signer.sign(media_file.id);
+    let access = MediaStreamAccess::SignedUrl {
+        expires_at: signed_url.expires_at,
+        signature: signed_url.signature,
+    };
+
+    // Without downloads original images never leave the server, not even to be shown inline
+    if !stream_original {
+        let query = GetMediaThumbnailQuery {
+            media_id: media_file.id,
+            access,
+            size: Some(PREVIEW_RENDITION_NAME.to_string()),
+            accept: header_value(header::ACCEPT),
+        };
+        return media_thumbnail_response(&state, query).await;
+    }
+
+    // Visitors are strangers, the location is removed from every image it can be removed from
+    let content_type = media_file
+        .detected_content_type
+        .as_deref()
+        .unwrap_or(&media_file.content_type);
+    let query = GetMediaStreamQuery {
+        media_id: media_file.id,
+        access,
+        range: header_value(header::RANGE),
+        if_range: header_value(header::IF_RANGE),
+        strip_metadata: supports_metadata_stripping(content_type),
+    };
+
+    let mut response = media_stream_response(&state, query).await?;
+
+    if download {
+        let filename = media_file.original_filename.replace(['"', '\\'], "_");
+        let disposition = HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+            .unwrap_or(HeaderValue::from_static("attachment"));
+        response
+            .headers_mut()
+            .insert(header::CONTENT_DISPOSITION, disposition);
+    }
+
+    Ok(response)
+}
+
+pub fn api_routes(state: AppState) -> axum::Router<AppState> {
+    axum::Router::new()
+        .route("/with-me", get(get_shared_with_me))
+        .route("/media/{media_id}", get(get_media_shares).post(share_media))
+        .route("/media/{media_id}/{user_id}", delete(unshare_media))
+        .route(
+            "/albums/{album_id}",
+            get(get_album_shares).post(share_album),
+        )
+        .route("/albums/{album_id}/{user_id}", delete(unshare_album))
+        .route("/links", get(get_share_links).post(create_share_link))
+        .route("/links/{link_id}", delete(delete_share_link))
+        .route_layer(protected!(state.clone()))
+}
+
+/// Routes for visitors without an account, access is granted by the share link alone
+pub fn public_routes() -> axum::Router<AppState> {
+    axum::Router::new()
+        .route("/links/{slug}", get(open_share_link))
+        .route(
+            "/links/{slug}/media/{media_id}",
+            get(stream_share_link_media),
+        )
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_shared_with_me,
+        share_media,
+        get_media_shares,
+        unshare_media,
+        share_album,
+        get_album_shares,
+        unshare_album,
+        create_share_link,
+        get_share_links,
+        delete_share_link
+    ),
+    tags(
+        (name = "sharing", description = "Sharing media files and albums with other users")
+    )
+)]
+pub struct ApiDoc;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(open_share_link, stream_share_link_media),
+    tags(
+        (name = "public", description = "Share links opened without an account")
+    )
+)]
+pub struct PublicApiDoc;
+
+pub fn combine_openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
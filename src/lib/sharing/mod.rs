@@ -0,0 +1,9 @@
+pub mod application;
+pub mod domain;
+pub mod infrastructure;
+pub mod interface;
+
+pub use application::*;
+pub use domain::*;
+pub use infrastructure::*;
+pub use interface::*;
@@ -0,0 +1,138 @@
+use uuid::Uuid;
+
+use crate::users::{
+    application::commands::revoke_session::{
+        RevokeOtherSessionsCommand, RevokeSessionError, revoke_other_sessions_command_handler,
+    },
+    domain::{
+        RefreshTokenRepository, SessionCache, SessionId, SessionRepository, UserRepository,
+        UserRepositoryError, hash_password, verify_password,
+    },
+};
+
+pub struct ChangePasswordCommand {
+    pub user_id: Uuid,
+    /// Session of the request, it stays logged in while every other session is revoked
+    pub current_session_id: Option<SessionId>,
+    pub current_password: String,
+    pub new_password: String,
+}
+
+pub struct ResetPasswordCommand {
+    pub user_id: Uuid,
+    /// Temporary password the user has to replace at the next login
+    pub new_password: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChangePasswordError {
+    #[error("Current password is incorrect")]
+    InvalidCurrentPassword,
+    #[error("New password must be different from the current one")]
+    SamePassword,
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
+
+pub async fn change_password_command_handler(
+    command: ChangePasswordCommand,
+    user_repository: &dyn UserRepository,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), ChangePasswordError> {
+    let user = user_repository
+        .get_by_id(command.user_id)
+        .await
+        .map_err(user_repository_error_to_change_password_error)?
+        .ok_or(ChangePasswordError::UserNotFound)?;
+
+    if !verify_password(&command.current_password, &user.password) {
+        return Err(ChangePasswordError::InvalidCurrentPassword);
+    }
+    if command.new_password == command.current_password {
+        return Err(ChangePasswordError::SamePassword);
+    }
+
+    update_password(
+        user.id,
+        &command.new_password,
+        false,
+        command.current_session_id,
+        user_repository,
+        session_repository,
+        refresh_token_repository,
+        session_cache,
+    )
+    .await
+}
+
+/// Sets a temporary password chosen by an admin and logs the user out everywhere, the user has
+/// to pick a new password at the next login
+pub async fn reset_password_command_handler(
+    command: ResetPasswordCommand,
+    user_repository: &dyn UserRepository,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), ChangePasswordError> {
+    update_password(
+        command.user_id,
+        &command.new_password,
+        true,
+        None,
+        user_repository,
+        session_repository,
+        refresh_token_repository,
+        session_cache,
+    )
+    .await
+}
+
+/// Stores the hash of the new password and revokes every session but the one given, so tokens
+/// issued with the old password stop working
+#[allow(clippy::too_many_arguments)]
+pub async fn update_password(
+    user_id: Uuid,
+    new_password: &str,
+    must_change_password: bool,
+    keep_session_id: Option<SessionId>,
+    user_repository: &dyn UserRepository,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), ChangePasswordError> {
+    let password_hash = hash_password(new_password)
+        .map_err(|e| ChangePasswordError::InternalServerError(e.to_string()))?;
+    user_repository
+        .update_password(user_id, password_hash, must_change_password)
+        .await
+        .map_err(user_repository_error_to_change_password_error)?;
+
+    revoke_other_sessions_command_handler(
+        RevokeOtherSessionsCommand {
+            user_id,
+            current_session_id: keep_session_id,
+        },
+        session_repository,
+        refresh_token_repository,
+        session_cache,
+    )
+    .await
+    .map_err(|e| match e {
+        RevokeSessionError::InternalServerError(msg) => {
+            ChangePasswordError::InternalServerError(msg)
+        }
+        RevokeSessionError::NotFound => ChangePasswordError::InternalServerError(e.to_string()),
+    })?;
+    Ok(())
+}
+
+fn user_repository_error_to_change_password_error(err: UserRepositoryError) -> ChangePasswordError {
+    match err {
+        UserRepositoryError::UserNotFound => ChangePasswordError::UserNotFound,
+        _ => ChangePasswordError::InternalServerError(err.to_string()),
+    }
+}
@@ -1,24 +1,31 @@
-use argon2::{Argon2, PasswordHasher};
-use password_hash::{SaltString, rand_core::OsRng};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-use crate::users::domain::{Role, User, UserRepository, UserRepositoryError, user::NewUser};
+use crate::users::domain::{
+    Role, User, UserRepository, UserRepositoryError, hash_password, user::NewUser,
+};
 
 #[derive(Debug, Validate, Deserialize, ToSchema)]
 pub struct CreateUserCommand {
     #[validate(length(min = 1, message = "Username cannot be empty"))]
     pub username: String,
+    /// Address password reset links are sent to, unique among users
+    #[validate(email(message = "Email is invalid"))]
+    pub email: Option<String>,
     #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
     pub password: String,
     pub role: Option<Role>,
+    /// Makes the user pick a new password at the first login
+    #[serde(default)]
+    pub must_change_password: bool,
 }
 
 #[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
 pub struct CreateUserResult {
     pub id: uuid::Uuid,
     pub username: String,
+    pub email: Option<String>,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
 }
@@ -36,15 +43,20 @@ pub async fn create_user_command_handler(
         return Err(UserRepositoryError::UserAlreadyExists);
     }
 
-    // Hash user password
-    let salt = SaltString::generate(&mut OsRng);
-
-    let argon2 = Argon2::default();
+    // Addresses are compared case-insensitively
+    command.email = command
+        .email
+        .map(|email| email.trim().to_lowercase())
+        .filter(|email| !email.is_empty());
+    if let Some(email) = &command.email
+        && user_repository.get_by_email(email).await?.is_some()
+    {
+        return Err(UserRepositoryError::EmailAlreadyExists);
+    }
 
-    command.password = argon2
-        .hash_password(command.password.as_bytes(), &salt)
-        .map_err(|_| UserRepositoryError::InternalServerError)?
-        .to_string();
+    // Hash user password
+    command.password =
+        hash_password(&command.password).map_err(|_| UserRepositoryError::InternalServerError)?;
 
     Ok(user_repository.create_user(command.into()).await?.into())
 }
@@ -53,8 +65,10 @@ impl From<CreateUserCommand> for NewUser {
     fn from(command: CreateUserCommand) -> Self {
         NewUser {
             username: command.username,
+            email: command.email,
             password: command.password,
             role: Some(command.role.unwrap_or(Role::User)),
+            must_change_password: command.must_change_password,
         }
     }
 }
@@ -64,6 +78,7 @@ impl From<User> for CreateUserResult {
         CreateUserResult {
             id: user.id,
             username: user.username,
+            email: user.email,
             created_at: user.created_at,
             updated_at: user.updated_at,
         }
@@ -4,7 +4,9 @@ use utoipa::ToSchema;
 use validator::Validate;
 
 use crate::users::domain::{
-    Claims, LoginTokenService, Token, UserRepository,
+    AuthTokenConfig, AuthTokens, Claims, LoginTokenService, NewRefreshToken, NewSession,
+    RefreshToken, RefreshTokenRepository, SessionClient, SessionId, SessionRepository, Token, User,
+    UserRepository, hash_password,
     user::{UserLogin, UserLoginError},
 };
 
@@ -14,6 +16,17 @@ pub struct LoginCommand {
     pub username: String,
     #[validate(length(min = 1, message = "Password cannot be empty"))]
     pub password: String,
+    /// Name the session is listed under, like "Alice's phone"
+    #[validate(length(
+        max = 100,
+        message = "Device name cannot be longer than 100 characters"
+    ))]
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// Replaces the password when it has to be changed, ignored otherwise
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    #[serde(default)]
+    pub new_password: Option<String>,
 }
 
 impl From<LoginCommand> for UserLogin {
@@ -27,9 +40,15 @@ impl From<LoginCommand> for UserLogin {
 
 pub async fn login_command_handler(
     command: LoginCommand,
+    client: SessionClient,
     user_repository: &dyn UserRepository,
     login_token_service: &dyn LoginTokenService,
-) -> Result<Token, UserLoginError> {
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    auth_token_config: &AuthTokenConfig,
+) -> Result<AuthTokens, UserLoginError> {
+    let device_name = command.device_name;
+    let new_password = command.new_password;
     let user = (user_repository.get_by_username(command.username).await).unwrap_or_default();
 
     // Always perform password hashing to prevent timing attacks
@@ -57,11 +76,41 @@ pub async fn login_command_handler(
     // Check if user exists and password is valid
     if let Some(user) = user {
         if password_valid {
-            login_token_service.create_token(Claims {
-                sub: user.id,
-                username: user.username,
-                role: user.role,
-                exp: (chrono::Utc::now() + chrono::Duration::days(1)).timestamp() as u64,
+            if user.must_change_password {
+                change_required_password(&user, &command.password, new_password, user_repository)
+                    .await?;
+            }
+
+            // Every login starts a new session, its id is the family of its refresh tokens
+            let session = session_repository
+                .create_session(NewSession {
+                    user_id: user.id,
+                    device_name,
+                    user_agent: client.user_agent,
+                    ip_address: client.ip_address,
+                })
+                .await
+                .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?;
+            let refresh_token = RefreshToken::generate();
+            refresh_token_repository
+                .create_refresh_token(NewRefreshToken {
+                    user_id: user.id,
+                    family_id: session.id,
+                    token_hash: RefreshToken::hash(&refresh_token),
+                    expires_at: auth_token_config.refresh_token_expires_at(),
+                })
+                .await
+                .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?;
+
+            Ok(AuthTokens {
+                access_token: create_access_token(
+                    user,
+                    session.id,
+                    login_token_service,
+                    auth_token_config,
+                )?,
+                refresh_token,
+                expires_in: auth_token_config.access_token_ttl_seconds,
             })
         } else {
             Err(UserLoginError::InvalidCredentials)
@@ -70,3 +119,38 @@ pub async fn login_command_handler(
         Err(UserLoginError::InvalidCredentials)
     }
 }
+
+/// Replaces a password reset by an admin, the new one has to differ from the temporary one
+async fn change_required_password(
+    user: &User,
+    current_password: &str,
+    new_password: Option<String>,
+    user_repository: &dyn UserRepository,
+) -> Result<(), UserLoginError> {
+    let new_password = new_password
+        .filter(|new_password| new_password != current_password)
+        .ok_or(UserLoginError::PasswordChangeRequired)?;
+    let password_hash = hash_password(&new_password)
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?;
+    user_repository
+        .update_password(user.id, password_hash, false)
+        .await
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?;
+    Ok(())
+}
+
+/// Short-lived access token of the user, tied to the session it was issued for
+pub fn create_access_token(
+    user: User,
+    session_id: SessionId,
+    login_token_service: &dyn LoginTokenService,
+    auth_token_config: &AuthTokenConfig,
+) -> Result<Token, UserLoginError> {
+    login_token_service.create_token(Claims {
+        sub: user.id,
+        username: user.username,
+        role: user.role,
+        exp: auth_token_config.access_token_expires_at(),
+        sid: Some(session_id),
+    })
+}
@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::users::{
+    application::commands::revoke_session::revoke_session,
+    domain::{
+        RefreshToken, RefreshTokenRepository, SessionCache, SessionRepository, user::UserLoginError,
+    },
+};
+
+#[derive(Debug, ToSchema, Deserialize, Validate)]
+pub struct LogoutCommand {
+    #[validate(length(min = 1, message = "Refresh token cannot be empty"))]
+    pub refresh_token: String,
+}
+
+/// Revokes the session the refresh token belongs to, its access tokens stop working as well.
+/// Unknown tokens are ignored so logging out twice succeeds.
+pub async fn logout_command_handler(
+    command: LogoutCommand,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), UserLoginError> {
+    let token = refresh_token_repository
+        .get_refresh_token_by_hash(&RefreshToken::hash(&command.refresh_token))
+        .await
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?;
+
+    if let Some(token) = token {
+        revoke_session(
+            token.user_id,
+            token.family_id,
+            session_repository,
+            refresh_token_repository,
+            session_cache,
+        )
+        .await
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?;
+    }
+    Ok(())
+}
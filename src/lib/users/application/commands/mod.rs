@@ -1,2 +1,8 @@
+pub mod change_password;
 pub mod create_user;
 pub mod login;
+pub mod logout;
+pub mod password_reset;
+pub mod refresh_token;
+pub mod revoke_session;
+pub mod update_user_quota;
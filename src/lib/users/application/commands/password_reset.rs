@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::users::{
+    application::commands::change_password::{ChangePasswordError, update_password},
+    domain::{
+        Email, Mailer, NewPasswordResetToken, PasswordResetConfig, PasswordResetToken,
+        PasswordResetTokenRepository, RefreshTokenRepository, SessionCache, SessionRepository,
+        UserRepository,
+    },
+};
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct RequestPasswordResetCommand {
+    #[validate(email(message = "Email is invalid"))]
+    pub email: String,
+}
+
+#[derive(Debug, Validate, Deserialize, ToSchema)]
+pub struct ConfirmPasswordResetCommand {
+    /// Token from the password reset email
+    #[validate(length(min = 1, message = "Token cannot be empty"))]
+    pub token: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub new_password: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordResetError {
+    #[error("Invalid or expired password reset token")]
+    InvalidToken,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
+
+/// Emails a reset token to the user with the address. Succeeds whether or not the address
+/// belongs to a user, so the response does not tell which addresses are registered. The token
+/// is stored and sent in the background, so the response takes as long either way.
+pub async fn request_password_reset_command_handler(
+    command: RequestPasswordResetCommand,
+    user_repository: &dyn UserRepository,
+    password_reset_token_repository: Arc<dyn PasswordResetTokenRepository>,
+    mailer: Arc<dyn Mailer>,
+    password_reset_config: &PasswordResetConfig,
+) -> Result<(), PasswordResetError> {
+    let email = command.email.trim().to_lowercase();
+    let Some(user) = user_repository
+        .get_by_email(&email)
+        .await
+        .map_err(|e| PasswordResetError::InternalServerError(e.to_string()))?
+    else {
+        return Ok(());
+    };
+
+    let token = PasswordResetToken::generate();
+    let new_token = NewPasswordResetToken {
+        user_id: user.id,
+        token_hash: PasswordResetToken::hash(&token),
+        expires_at: password_reset_config.token_expires_at(),
+    };
+    let email = Email {
+        to: email,
+        subject: "Reset your password".to_string(),
+        body: format!(
+            "Hello {},\n\nUse this to choose a new password within the next {} minutes:\n\n{}\n\nIf you did not ask for a password reset, you can ignore this email.\n",
+            user.username,
+            password_reset_config.token_ttl_minutes,
+            password_reset_config.reset_link(&token)
+        ),
+    };
+    // Failing here would tell the address is registered, the user can ask again instead
+    tokio::spawn(async move {
+        if let Err(e) = password_reset_token_repository
+            .create_password_reset_token(new_token)
+            .await
+        {
+            tracing::error!(
+                "Failed to store password reset token of user {}: {}",
+                user.id,
+                e
+            );
+            return;
+        }
+        if let Err(e) = mailer.send(email).await {
+            tracing::error!(
+                "Failed to send password reset email to user {}: {}",
+                user.id,
+                e
+            );
+        }
+    });
+    Ok(())
+}
+
+/// Sets the new password if the token was neither used nor expired, and logs the user out
+/// everywhere
+pub async fn confirm_password_reset_command_handler(
+    command: ConfirmPasswordResetCommand,
+    user_repository: &dyn UserRepository,
+    password_reset_token_repository: &dyn PasswordResetTokenRepository,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), PasswordResetError> {
+    let token = password_reset_token_repository
+        .consume_password_reset_token(&PasswordResetToken::hash(&command.token))
+        .await
+        .map_err(|e| PasswordResetError::InternalServerError(e.to_string()))?
+        .ok_or(PasswordResetError::InvalidToken)?;
+
+    update_password(
+        token.user_id,
+        &command.new_password,
+        false,
+        None,
+        user_repository,
+        session_repository,
+        refresh_token_repository,
+        session_cache,
+    )
+    .await
+    .map_err(|e| match e {
+        // Deleted after the token was sent
+        ChangePasswordError::UserNotFound => PasswordResetError::InvalidToken,
+        ChangePasswordError::InternalServerError(msg) => {
+            PasswordResetError::InternalServerError(msg)
+        }
+        _ => PasswordResetError::InternalServerError(e.to_string()),
+    })
+}
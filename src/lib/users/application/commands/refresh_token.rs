@@ -0,0 +1,124 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::users::{
+    application::commands::{login::create_access_token, revoke_session::revoke_session},
+    domain::{
+        AuthTokenConfig, AuthTokens, LoginTokenService, NewRefreshToken, RefreshToken,
+        RefreshTokenRepository, RefreshTokenRepositoryError, SessionCache, SessionRepository,
+        UserRepository, user::UserLoginError,
+    },
+};
+
+#[derive(Debug, ToSchema, Deserialize, Validate)]
+pub struct RefreshTokenCommand {
+    #[validate(length(min = 1, message = "Refresh token cannot be empty"))]
+    pub refresh_token: String,
+}
+
+/// Exchanges a refresh token for a new access token and a new refresh token. A token presented
+/// again after it was exchanged has leaked, so its whole session is revoked.
+pub async fn refresh_token_command_handler(
+    command: RefreshTokenCommand,
+    user_repository: &dyn UserRepository,
+    login_token_service: &dyn LoginTokenService,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+    auth_token_config: &AuthTokenConfig,
+) -> Result<AuthTokens, UserLoginError> {
+    let token = refresh_token_repository
+        .get_refresh_token_by_hash(&RefreshToken::hash(&command.refresh_token))
+        .await
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?
+        .ok_or(UserLoginError::InvalidToken)?;
+
+    if token.revoked_at.is_some() || token.is_expired() {
+        return Err(UserLoginError::InvalidToken);
+    }
+    if token.used_at.is_some() {
+        return Err(revoke_reused_session(
+            &token,
+            session_repository,
+            refresh_token_repository,
+            session_cache,
+        )
+        .await);
+    }
+    // The session may have been revoked from another device
+    session_repository
+        .touch_session(token.family_id)
+        .await
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?
+        .ok_or(UserLoginError::InvalidToken)?;
+
+    // The user may have been deleted or changed since the last refresh
+    let user = user_repository
+        .get_by_id(token.user_id)
+        .await
+        .map_err(|e| UserLoginError::InternalServerError(e.to_string()))?
+        .ok_or(UserLoginError::InvalidToken)?;
+
+    let refresh_token = RefreshToken::generate();
+    let replacement = NewRefreshToken {
+        user_id: token.user_id,
+        family_id: token.family_id,
+        token_hash: RefreshToken::hash(&refresh_token),
+        expires_at: auth_token_config.refresh_token_expires_at(),
+    };
+    match refresh_token_repository
+        .rotate_refresh_token(token.id, replacement)
+        .await
+    {
+        Ok(_) => {}
+        // Another request exchanged the token first
+        Err(RefreshTokenRepositoryError::AlreadyUsed) => {
+            return Err(revoke_reused_session(
+                &token,
+                session_repository,
+                refresh_token_repository,
+                session_cache,
+            )
+            .await);
+        }
+        Err(e) => return Err(UserLoginError::InternalServerError(e.to_string())),
+    }
+
+    Ok(AuthTokens {
+        access_token: create_access_token(
+            user,
+            token.family_id,
+            login_token_service,
+            auth_token_config,
+        )?,
+        refresh_token,
+        expires_in: auth_token_config.access_token_ttl_seconds,
+    })
+}
+
+async fn revoke_reused_session(
+    token: &RefreshToken,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> UserLoginError {
+    tracing::warn!(
+        "Refresh token {} of user {} was reused, revoking its session {}",
+        token.id,
+        token.user_id,
+        token.family_id
+    );
+    match revoke_session(
+        token.user_id,
+        token.family_id,
+        session_repository,
+        refresh_token_repository,
+        session_cache,
+    )
+    .await
+    {
+        Ok(_) => UserLoginError::RefreshTokenReused,
+        Err(e) => UserLoginError::InternalServerError(e.to_string()),
+    }
+}
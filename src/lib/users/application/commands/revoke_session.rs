@@ -0,0 +1,100 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::users::domain::{RefreshTokenRepository, SessionCache, SessionId, SessionRepository};
+
+pub struct RevokeSessionCommand {
+    pub user_id: Uuid,
+    pub session_id: SessionId,
+}
+
+pub struct RevokeOtherSessionsCommand {
+    pub user_id: Uuid,
+    /// Session of the request, `None` revokes every session of the user
+    pub current_session_id: Option<SessionId>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct RevokeOtherSessionsResult {
+    pub revoked_sessions: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RevokeSessionError {
+    #[error("Session not found")]
+    NotFound,
+    #[error("Internal server error")]
+    InternalServerError(String),
+}
+
+pub async fn revoke_session_command_handler(
+    command: RevokeSessionCommand,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), RevokeSessionError> {
+    let revoked = revoke_session(
+        command.user_id,
+        command.session_id,
+        session_repository,
+        refresh_token_repository,
+        session_cache,
+    )
+    .await?;
+    if revoked {
+        Ok(())
+    } else {
+        Err(RevokeSessionError::NotFound)
+    }
+}
+
+pub async fn revoke_other_sessions_command_handler(
+    command: RevokeOtherSessionsCommand,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<RevokeOtherSessionsResult, RevokeSessionError> {
+    let session_ids = session_repository
+        .revoke_other_sessions(command.user_id, command.current_session_id)
+        .await
+        .map_err(|e| RevokeSessionError::InternalServerError(e.to_string()))?;
+
+    for session_id in &session_ids {
+        revoke_session_tokens(*session_id, refresh_token_repository, session_cache).await?;
+    }
+    Ok(RevokeOtherSessionsResult {
+        revoked_sessions: session_ids.len(),
+    })
+}
+
+/// Revokes the session of the user along with its refresh tokens, the access tokens issued for
+/// it are rejected from then on. Returns `false` when the user has no such active session.
+pub async fn revoke_session(
+    user_id: Uuid,
+    session_id: SessionId,
+    session_repository: &dyn SessionRepository,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<bool, RevokeSessionError> {
+    let revoked = session_repository
+        .revoke_session(user_id, session_id)
+        .await
+        .map_err(|e| RevokeSessionError::InternalServerError(e.to_string()))?;
+    if revoked {
+        revoke_session_tokens(session_id, refresh_token_repository, session_cache).await?;
+    }
+    Ok(revoked)
+}
+
+async fn revoke_session_tokens(
+    session_id: SessionId,
+    refresh_token_repository: &dyn RefreshTokenRepository,
+    session_cache: &SessionCache,
+) -> Result<(), RevokeSessionError> {
+    session_cache.invalidate(session_id);
+    refresh_token_repository
+        .revoke_refresh_token_family(session_id)
+        .await
+        .map_err(|e| RevokeSessionError::InternalServerError(e.to_string()))
+}
@@ -0,0 +1,41 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::users::domain::{User, UserRepository, UserRepositoryError};
+
+pub struct UpdateUserQuotaCommand {
+    pub id: uuid::Uuid,
+    /// `None` falls back to the default quota
+    pub quota_bytes: Option<i64>,
+    /// `None` falls back to the default quota
+    pub quota_items: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct UpdateUserQuotaResult {
+    pub id: uuid::Uuid,
+    pub quota_bytes: Option<i64>,
+    pub quota_items: Option<i64>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+pub async fn update_user_quota_command_handler(
+    command: UpdateUserQuotaCommand,
+    user_repository: &dyn UserRepository,
+) -> Result<UpdateUserQuotaResult, UserRepositoryError> {
+    Ok(user_repository
+        .update_quota(command.id, command.quota_bytes, command.quota_items)
+        .await?
+        .into())
+}
+
+impl From<User> for UpdateUserQuotaResult {
+    fn from(user: User) -> Self {
+        UpdateUserQuotaResult {
+            id: user.id,
+            quota_bytes: user.quota_bytes,
+            quota_items: user.quota_items,
+            updated_at: user.updated_at,
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::users::domain::{Role, User, UserRepository, UserRepositoryError};
 pub struct GetAllUsersResult {
     pub id: uuid::Uuid,
     pub username: String,
+    pub email: Option<String>,
     pub role: Role,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
@@ -24,6 +25,7 @@ impl From<User> for GetAllUsersResult {
         GetAllUsersResult {
             id: user.id,
             username: user.username,
+            email: user.email,
             role: user.role,
             created_at: user.created_at,
             updated_at: user.updated_at,
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::users::domain::{Session, SessionId, SessionRepository, SessionRepositoryError};
+
+#[derive(Debug)]
+pub struct GetSessionsQuery {
+    pub user_id: Uuid,
+    /// Session of the request, it is flagged in the results
+    pub current_session_id: Option<SessionId>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct GetSessionsResult {
+    pub id: Uuid,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub last_seen_at: Option<chrono::NaiveDateTime>,
+    /// Whether this is the session the request was made with
+    pub current: bool,
+}
+
+pub async fn get_sessions_query_handler(
+    query: GetSessionsQuery,
+    session_repository: &dyn SessionRepository,
+) -> Result<Vec<GetSessionsResult>, SessionRepositoryError> {
+    let sessions = session_repository
+        .get_active_sessions_by_user(query.user_id)
+        .await?;
+    Ok(sessions
+        .into_iter()
+        .map(|session| {
+            let current = query.current_session_id == Some(session.id);
+            GetSessionsResult::new(session, current)
+        })
+        .collect())
+}
+
+impl GetSessionsResult {
+    fn new(session: Session, current: bool) -> Self {
+        GetSessionsResult {
+            id: session.id,
+            device_name: session.device_name,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            current,
+        }
+    }
+}
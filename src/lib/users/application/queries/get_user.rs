@@ -12,6 +12,7 @@ pub struct GetUserQuery {
 pub struct GetUserResult {
     pub id: uuid::Uuid,
     pub username: String,
+    pub email: Option<String>,
     pub role: Role,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
@@ -30,6 +31,7 @@ impl From<User> for GetUserResult {
         GetUserResult {
             id: user.id,
             username: user.username,
+            email: user.email,
             role: user.role,
             created_at: user.created_at,
             updated_at: user.updated_at,
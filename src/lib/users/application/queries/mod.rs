@@ -1,5 +1,7 @@
 pub mod get_all_users;
+pub mod get_sessions;
 pub mod get_user;
 
 pub use get_all_users::*;
+pub use get_sessions::*;
 pub use get_user::*;
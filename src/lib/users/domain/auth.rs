@@ -1,21 +1,119 @@
+use std::env;
+
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::users::domain::{Role, user::UserLoginError};
 
+/// Lifetime of access tokens when `ACCESS_TOKEN_TTL_SECONDS` is not set
+pub const DEFAULT_ACCESS_TOKEN_TTL_SECONDS: i64 = 15 * 60;
+/// Lifetime of refresh tokens when `REFRESH_TOKEN_TTL_DAYS` is not set
+pub const DEFAULT_REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct Claims {
     pub sub: Uuid,
     pub username: String,
     pub role: Role,
     pub exp: u64,
+    /// Session the token was issued for, revoking the session rejects the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, ToSchema, Deserialize, Clone, PartialEq, Eq)]
 pub struct Token(pub String);
 
+/// Access token along with the refresh token to renew it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthTokens {
+    pub access_token: Token,
+    pub refresh_token: String,
+    /// Seconds until the access token expires
+    pub expires_in: i64,
+}
+
+/// Lifetimes of the tokens handed out at login and refresh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthTokenConfig {
+    pub access_token_ttl_seconds: i64,
+    pub refresh_token_ttl_days: i64,
+}
+
+impl AuthTokenConfig {
+    /// Reads `ACCESS_TOKEN_TTL_SECONDS` and `REFRESH_TOKEN_TTL_DAYS` and falls back to the
+    /// defaults when either is missing or invalid
+    pub fn new() -> Self {
+        let read = |name: &str, default: i64| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            access_token_ttl_seconds: read(
+                "ACCESS_TOKEN_TTL_SECONDS",
+                DEFAULT_ACCESS_TOKEN_TTL_SECONDS,
+            ),
+            refresh_token_ttl_days: read("REFRESH_TOKEN_TTL_DAYS", DEFAULT_REFRESH_TOKEN_TTL_DAYS),
+        }
+    }
+
+    pub fn access_token_expires_at(&self) -> u64 {
+        (chrono::Utc::now() + chrono::Duration::seconds(self.access_token_ttl_seconds)).timestamp()
+            as u64
+    }
+
+    pub fn refresh_token_expires_at(&self) -> chrono::NaiveDateTime {
+        (chrono::Utc::now() + chrono::Duration::days(self.refresh_token_ttl_days)).naive_utc()
+    }
+}
+
+impl Default for AuthTokenConfig {
+    fn default() -> Self {
+        Self {
+            access_token_ttl_seconds: DEFAULT_ACCESS_TOKEN_TTL_SECONDS,
+            refresh_token_ttl_days: DEFAULT_REFRESH_TOKEN_TTL_DAYS,
+        }
+    }
+}
+
+/// Public key tokens can be verified with, in the JSON Web Key format (RFC 7517)
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct JsonWebKey {
+    /// `RSA` or `OKP` for Ed25519 keys
+    pub kty: String,
+    /// Matches the `kid` header of the tokens signed with the key
+    pub kid: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    /// RSA modulus, base64url encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// RSA public exponent, base64url encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// Ed25519 public key, base64url encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone, PartialEq, Eq)]
+pub struct JsonWebKeySet {
+    pub keys: Vec<JsonWebKey>,
+}
+
 pub trait LoginTokenService: Send + Sync {
     fn create_token(&self, claims: Claims) -> Result<Token, UserLoginError>;
     fn validate_token(&self, token: &str) -> Result<Claims, UserLoginError>;
+    /// Keys other services can verify tokens with, none when tokens are signed with a shared
+    /// secret
+    fn public_keys(&self) -> Vec<JsonWebKey> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+/// Plain text email
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MailerError {
+    #[error("Invalid email: {0}")]
+    InvalidEmail(String),
+    #[error("Failed to send email: {0}")]
+    SendFailed(String),
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: Email) -> Result<(), MailerError>;
+}
@@ -1,10 +1,30 @@
 pub mod auth;
+pub mod mailer;
+pub mod password;
+pub mod password_reset_token;
+pub mod password_reset_token_repository;
+pub mod quota;
+pub mod refresh_token;
+pub mod refresh_token_repository;
 pub mod roles;
+pub mod session;
+pub mod session_cache;
+pub mod session_repository;
 pub mod user;
 pub mod user_repository;
 
 pub use auth::*;
+pub use mailer::*;
+pub use password::{hash_password, verify_password};
+pub use password_reset_token::*;
+pub use password_reset_token_repository::*;
+pub use quota::*;
+pub use refresh_token::*;
+pub use refresh_token_repository::*;
 pub use roles::*;
+pub use session::*;
+pub use session_cache::*;
+pub use session_repository::*;
 pub use user::User;
 pub use user_repository::UserRepository;
 pub use user_repository::UserRepositoryError;
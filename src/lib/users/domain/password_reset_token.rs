@@ -0,0 +1,90 @@
+use std::env;
+
+use uuid::Uuid;
+
+use crate::users::domain::RefreshToken;
+
+pub type PasswordResetTokenId = Uuid;
+
+/// How long an emailed reset token can be used for
+pub const DEFAULT_PASSWORD_RESET_TOKEN_TTL_MINUTES: i64 = 60;
+
+/// Opaque single-use token emailed to reset a forgotten password, only its hash is stored
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PasswordResetToken {
+    pub id: PasswordResetTokenId,
+    pub user_id: Uuid,
+    /// SHA-256 hash of the token, hex encoded
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+    pub used_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+impl PasswordResetToken {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= chrono::Utc::now().naive_utc()
+    }
+
+    /// Generates a random URL safe token, in the same format as refresh tokens
+    pub fn generate() -> String {
+        RefreshToken::generate()
+    }
+
+    pub fn hash(token: &str) -> String {
+        RefreshToken::hash(token)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NewPasswordResetToken {
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordResetConfig {
+    pub token_ttl_minutes: i64,
+    /// Link sent in the email, `{token}` is replaced with the token. Without it the email holds
+    /// the bare token.
+    pub reset_url: Option<String>,
+}
+
+impl PasswordResetConfig {
+    /// Reads `PASSWORD_RESET_TOKEN_TTL_MINUTES` and `PASSWORD_RESET_URL` and falls back to the
+    /// defaults when either is missing or invalid
+    pub fn new() -> Self {
+        Self {
+            token_ttl_minutes: env::var("PASSWORD_RESET_TOKEN_TTL_MINUTES")
+                .ok()
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(DEFAULT_PASSWORD_RESET_TOKEN_TTL_MINUTES),
+            reset_url: env::var("PASSWORD_RESET_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty()),
+        }
+    }
+
+    pub fn token_expires_at(&self) -> chrono::NaiveDateTime {
+        (chrono::Utc::now() + chrono::Duration::minutes(self.token_ttl_minutes)).naive_utc()
+    }
+
+    /// What the user follows or pastes to reset their password
+    pub fn reset_link(&self, token: &str) -> String {
+        match &self.reset_url {
+            Some(url) => url.replace("{token}", token),
+            None => token.to_string(),
+        }
+    }
+}
+
+impl Default for PasswordResetConfig {
+    fn default() -> Self {
+        Self {
+            token_ttl_minutes: DEFAULT_PASSWORD_RESET_TOKEN_TTL_MINUTES,
+            reset_url: None,
+        }
+    }
+}
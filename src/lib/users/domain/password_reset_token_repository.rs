@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use super::password_reset_token::{NewPasswordResetToken, PasswordResetToken};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordResetTokenRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+}
+
+#[async_trait]
+pub trait PasswordResetTokenRepository: Send + Sync {
+    async fn create_password_reset_token(
+        &self,
+        token: NewPasswordResetToken,
+    ) -> Result<PasswordResetToken, PasswordResetTokenRepositoryError>;
+    /// Marks the token as used if it is neither used nor expired, along with every other unused
+    /// token of its user. Returns `None` when the token cannot be used.
+    async fn consume_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, PasswordResetTokenRepositoryError>;
+}
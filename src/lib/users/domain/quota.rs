@@ -0,0 +1,60 @@
+use std::env;
+
+use crate::users::domain::User;
+
+/// Storage a user may take up when no quota was set for them
+pub const DEFAULT_QUOTA_BYTES: i64 = 10 * 1024 * 1024 * 1024;
+/// Media files a user may own when no quota was set for them
+pub const DEFAULT_QUOTA_ITEMS: i64 = 100_000;
+
+/// Limits applying to the media a user owns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageQuota {
+    pub max_bytes: i64,
+    pub max_items: i64,
+}
+
+/// Quota of users without one of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaConfig {
+    pub default_quota: StorageQuota,
+}
+
+impl QuotaConfig {
+    /// Reads `DEFAULT_QUOTA_BYTES` and `DEFAULT_QUOTA_ITEMS` and falls back to the defaults when
+    /// either is missing or invalid
+    pub fn new() -> Self {
+        let read = |name: &str, default: i64| {
+            env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|value| *value >= 0)
+                .unwrap_or(default)
+        };
+        Self {
+            default_quota: StorageQuota {
+                max_bytes: read("DEFAULT_QUOTA_BYTES", DEFAULT_QUOTA_BYTES),
+                max_items: read("DEFAULT_QUOTA_ITEMS", DEFAULT_QUOTA_ITEMS),
+            },
+        }
+    }
+
+    /// Quota of the user, limits they have no own value for come from the defaults
+    pub fn quota_for(&self, user: &User) -> StorageQuota {
+        StorageQuota {
+            max_bytes: user.quota_bytes.unwrap_or(self.default_quota.max_bytes),
+            max_items: user.quota_items.unwrap_or(self.default_quota.max_items),
+        }
+    }
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            default_quota: StorageQuota {
+                max_bytes: DEFAULT_QUOTA_BYTES,
+                max_items: DEFAULT_QUOTA_ITEMS,
+            },
+        }
+    }
+}
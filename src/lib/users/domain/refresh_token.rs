@@ -0,0 +1,51 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub type RefreshTokenId = Uuid;
+
+/// Random bytes behind a refresh token, enough to make it impossible to guess
+const REFRESH_TOKEN_BYTES: usize = 32;
+
+/// Opaque token exchanged for a new access token, only its hash is stored
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RefreshToken {
+    pub id: RefreshTokenId,
+    pub user_id: Uuid,
+    /// Tokens replacing each other on refresh share their family, it stands for one login
+    pub family_id: Uuid,
+    /// SHA-256 hash of the token, hex encoded
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+    /// Set once the token was exchanged for a new one
+    pub used_at: Option<chrono::NaiveDateTime>,
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+impl RefreshToken {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= chrono::Utc::now().naive_utc()
+    }
+
+    /// Generates a random URL safe token
+    pub fn generate() -> String {
+        let mut bytes = [0u8; REFRESH_TOKEN_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Hash the token is stored and looked up by. The token is random, so a fast hash is enough.
+    pub fn hash(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NewRefreshToken {
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::refresh_token::{NewRefreshToken, RefreshToken, RefreshTokenId};
+
+#[derive(Debug, thiserror::Error)]
+pub enum RefreshTokenRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+    /// The token was exchanged or revoked in the meantime
+    #[error("Refresh token was already used")]
+    AlreadyUsed,
+}
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn create_refresh_token(
+        &self,
+        token: NewRefreshToken,
+    ) -> Result<RefreshToken, RefreshTokenRepositoryError>;
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, RefreshTokenRepositoryError>;
+    /// Marks the token as used and stores its replacement, only if it was neither used nor
+    /// revoked before
+    async fn rotate_refresh_token(
+        &self,
+        id: RefreshTokenId,
+        replacement: NewRefreshToken,
+    ) -> Result<RefreshToken, RefreshTokenRepositoryError>;
+    /// Revokes every token of the family, none of them can be exchanged afterwards
+    async fn revoke_refresh_token_family(
+        &self,
+        family_id: Uuid,
+    ) -> Result<(), RefreshTokenRepositoryError>;
+}
@@ -0,0 +1,33 @@
+use uuid::Uuid;
+
+/// Also the family id of the session's refresh tokens and the `sid` claim of its access tokens
+pub type SessionId = Uuid;
+
+/// A device the user is logged in from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Session {
+    pub id: SessionId,
+    pub user_id: Uuid,
+    /// Name the client gave itself at login, like "Alice's phone"
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub last_seen_at: Option<chrono::NaiveDateTime>,
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NewSession {
+    pub user_id: Uuid,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// Where a login comes from, read from the request rather than its body
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
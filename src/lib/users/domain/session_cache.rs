@@ -0,0 +1,74 @@
+use std::{
+    collections::HashMap,
+    env,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::session::SessionId;
+
+/// How long a session checked against the database is trusted when
+/// `SESSION_CACHE_TTL_SECONDS` is not set
+pub const DEFAULT_SESSION_CACHE_TTL_SECONDS: u64 = 30;
+
+/// Sessions beyond this count make the cache drop the stale ones
+const SESSION_CACHE_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Remembers sessions recently found active, so authenticating a request does not need a
+/// database query every time. Revocations on this instance take effect right away, revocations
+/// on other instances once the entry went stale.
+#[derive(Debug)]
+pub struct SessionCache {
+    ttl: Duration,
+    checked_at: Mutex<HashMap<SessionId, Instant>>,
+}
+
+impl SessionCache {
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            checked_at: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads `SESSION_CACHE_TTL_SECONDS` and falls back to the default when it is missing or
+    /// invalid, `0` disables the cache
+    pub fn new() -> Self {
+        Self::with_ttl(Duration::from_secs(
+            env::var("SESSION_CACHE_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_SESSION_CACHE_TTL_SECONDS),
+        ))
+    }
+
+    /// Whether the session was found active recently enough to skip the database
+    pub fn is_active(&self, id: SessionId) -> bool {
+        self.checked_at
+            .lock()
+            .unwrap()
+            .get(&id)
+            .is_some_and(|checked_at| checked_at.elapsed() < self.ttl)
+    }
+
+    pub fn mark_active(&self, id: SessionId) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut checked_at = self.checked_at.lock().unwrap();
+        if checked_at.len() >= SESSION_CACHE_PRUNE_THRESHOLD {
+            checked_at.retain(|_, checked_at| checked_at.elapsed() < self.ttl);
+        }
+        checked_at.insert(id, Instant::now());
+    }
+
+    pub fn invalidate(&self, id: SessionId) {
+        self.checked_at.lock().unwrap().remove(&id);
+    }
+}
+
+impl Default for SessionCache {
+    fn default() -> Self {
+        Self::with_ttl(Duration::from_secs(DEFAULT_SESSION_CACHE_TTL_SECONDS))
+    }
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::session::{NewSession, Session, SessionId};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionRepositoryError {
+    #[error("Internal server error")]
+    InternalServerError,
+}
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create_session(&self, session: NewSession) -> Result<Session, SessionRepositoryError>;
+    /// Sessions of the user that were not revoked, most recently seen first
+    async fn get_active_sessions_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<Session>, SessionRepositoryError>;
+    /// Records that the session was just used, returns `None` when it does not exist or was
+    /// revoked
+    async fn touch_session(&self, id: SessionId)
+    -> Result<Option<Session>, SessionRepositoryError>;
+    /// Revokes the session if it belongs to the user and is active, returns whether it did
+    async fn revoke_session(
+        &self,
+        user_id: Uuid,
+        id: SessionId,
+    ) -> Result<bool, SessionRepositoryError>;
+    /// Revokes every active session of the user except the one given, returns the revoked ids
+    async fn revoke_other_sessions(
+        &self,
+        user_id: Uuid,
+        keep_id: Option<SessionId>,
+    ) -> Result<Vec<SessionId>, SessionRepositoryError>;
+}
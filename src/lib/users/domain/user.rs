@@ -6,8 +6,16 @@ use crate::users::domain::Role;
 pub struct User {
     pub id: Uuid,
     pub username: String,
+    /// Lowercase address password reset links are sent to
+    pub email: Option<String>,
     pub password: String,
     pub role: Role,
+    /// Storage the user may take up in bytes, `None` falls back to the default quota
+    pub quota_bytes: Option<i64>,
+    /// Media files the user may own, `None` falls back to the default quota
+    pub quota_items: Option<i64>,
+    /// Set when an admin reset the password, logging in requires picking a new one
+    pub must_change_password: bool,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
 }
@@ -15,8 +23,10 @@ pub struct User {
 #[derive(Debug, PartialEq, Eq)]
 pub struct NewUser {
     pub username: String,
+    pub email: Option<String>,
     pub password: String,
     pub role: Option<Role>,
+    pub must_change_password: bool,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -33,4 +43,10 @@ pub enum UserLoginError {
     InternalServerError(String),
     #[error("Invalid token")]
     InvalidToken,
+    /// A refresh token was presented again after it had been exchanged, the family is revoked
+    #[error("Refresh token was already used")]
+    RefreshTokenReused,
+    /// The password was reset by an admin, logging in requires a new one
+    #[error("Password has to be changed, log in again with a new password")]
+    PasswordChangeRequired,
 }
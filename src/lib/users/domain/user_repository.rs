@@ -6,14 +6,33 @@ use crate::users::domain::{User, user::NewUser};
 pub trait UserRepository: Send + Sync {
     async fn get_by_username(&self, username: String) -> Result<Option<User>, UserRepositoryError>;
     async fn get_by_id(&self, id: uuid::Uuid) -> Result<Option<User>, UserRepositoryError>;
+    /// Looks the user up by a lowercase email address
+    async fn get_by_email(&self, email: &str) -> Result<Option<User>, UserRepositoryError>;
     async fn get_all_users(&self) -> Result<Vec<User>, UserRepositoryError>;
     async fn create_user(&self, user: NewUser) -> Result<User, UserRepositoryError>;
+    /// Sets the quota of the user, `None` limits fall back to the default quota
+    async fn update_quota(
+        &self,
+        id: uuid::Uuid,
+        quota_bytes: Option<i64>,
+        quota_items: Option<i64>,
+    ) -> Result<User, UserRepositoryError>;
+    /// Replaces the password hash of the user and sets whether it has to be changed at the
+    /// next login
+    async fn update_password(
+        &self,
+        id: uuid::Uuid,
+        password_hash: String,
+        must_change_password: bool,
+    ) -> Result<User, UserRepositoryError>;
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum UserRepositoryError {
     #[error("User already exists")]
     UserAlreadyExists,
+    #[error("Email is already in use")]
+    EmailAlreadyExists,
     #[error("User not found")]
     UserNotFound,
     #[error("Unexpected error")]
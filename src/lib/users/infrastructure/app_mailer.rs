@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use crate::users::{
+    domain::{Email, Mailer, MailerError},
+    infrastructure::{FileMailer, SmtpMailer},
+};
+
+/// Mailer picked at startup, SMTP when it is configured and files otherwise
+pub enum AppMailer {
+    Smtp(Box<SmtpMailer>),
+    File(FileMailer),
+}
+
+#[async_trait]
+impl Mailer for AppMailer {
+    async fn send(&self, email: Email) -> Result<(), MailerError> {
+        match self {
+            AppMailer::Smtp(mailer) => mailer.send(email).await,
+            AppMailer::File(mailer) => mailer.send(email).await,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+
+use diesel::{
+    PgConnection,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::users::infrastructure::{CreatePasswordResetTokenRow, PasswordResetTokenRow};
+use crate::{
+    persistence::domain::schema,
+    users::domain::{
+        NewPasswordResetToken, PasswordResetToken, PasswordResetTokenRepository,
+        PasswordResetTokenRepositoryError,
+    },
+};
+
+#[derive(Clone)]
+pub struct DieselPasswordResetTokenRepository {
+    pool: Arc<Pool<ConnectionManager<PgConnection>>>,
+}
+
+impl DieselPasswordResetTokenRepository {
+    pub fn new(connection: Arc<Pool<ConnectionManager<PgConnection>>>) -> Self {
+        DieselPasswordResetTokenRepository { pool: connection }
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for DieselPasswordResetTokenRepository {
+    async fn create_password_reset_token(
+        &self,
+        token: NewPasswordResetToken,
+    ) -> Result<PasswordResetToken, PasswordResetTokenRepositoryError> {
+        use schema::password_reset_tokens::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| PasswordResetTokenRepositoryError::InternalServerError)?;
+
+        let token_row = diesel::insert_into(password_reset_tokens)
+            .values(CreatePasswordResetTokenRow::from(token))
+            .returning(PasswordResetTokenRow::as_returning())
+            .get_result::<PasswordResetTokenRow>(&mut *conn)
+            .map_err(|_| PasswordResetTokenRepositoryError::InternalServerError)?;
+
+        Ok(token_row.into())
+    }
+
+    async fn consume_password_reset_token(
+        &self,
+        hash: &str,
+    ) -> Result<Option<PasswordResetToken>, PasswordResetTokenRepositoryError> {
+        use schema::password_reset_tokens::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| PasswordResetTokenRepositoryError::InternalServerError)?;
+
+        conn.transaction(|conn| {
+            // Only one of two concurrent resets with the same token gets to mark it as used
+            let token_row = diesel::update(
+                password_reset_tokens
+                    .filter(token_hash.eq(hash))
+                    .filter(used_at.is_null())
+                    .filter(expires_at.gt(diesel::dsl::now)),
+            )
+            .set(used_at.eq(diesel::dsl::now))
+            .returning(PasswordResetTokenRow::as_returning())
+            .get_result::<PasswordResetTokenRow>(conn)
+            .optional()?;
+
+            // Other links sent to the user stop working once the password was reset
+            if let Some(token_row) = &token_row {
+                diesel::update(
+                    password_reset_tokens
+                        .filter(user_id.eq(token_row.user_id))
+                        .filter(used_at.is_null()),
+                )
+                .set(used_at.eq(diesel::dsl::now))
+                .execute(conn)?;
+            }
+            Ok(token_row.map(PasswordResetToken::from))
+        })
+    }
+}
+
+impl From<diesel::result::Error> for PasswordResetTokenRepositoryError {
+    fn from(_: diesel::result::Error) -> Self {
+        PasswordResetTokenRepositoryError::InternalServerError
+    }
+}
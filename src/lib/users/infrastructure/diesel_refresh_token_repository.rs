@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use diesel::{
+    PgConnection,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::users::infrastructure::{CreateRefreshTokenRow, RefreshTokenRow};
+use crate::{
+    persistence::domain::schema,
+    users::domain::{
+        NewRefreshToken, RefreshToken, RefreshTokenId, RefreshTokenRepository,
+        RefreshTokenRepositoryError,
+    },
+};
+
+#[derive(Clone)]
+pub struct DieselRefreshTokenRepository {
+    pool: Arc<Pool<ConnectionManager<PgConnection>>>,
+}
+
+impl DieselRefreshTokenRepository {
+    pub fn new(connection: Arc<Pool<ConnectionManager<PgConnection>>>) -> Self {
+        DieselRefreshTokenRepository { pool: connection }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for DieselRefreshTokenRepository {
+    async fn create_refresh_token(
+        &self,
+        token: NewRefreshToken,
+    ) -> Result<RefreshToken, RefreshTokenRepositoryError> {
+        use schema::refresh_tokens::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        let token_row = diesel::insert_into(refresh_tokens)
+            .values(CreateRefreshTokenRow::from(token))
+            .returning(RefreshTokenRow::as_returning())
+            .get_result::<RefreshTokenRow>(&mut *conn)
+            .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        Ok(token_row.into())
+    }
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        hash: &str,
+    ) -> Result<Option<RefreshToken>, RefreshTokenRepositoryError> {
+        use schema::refresh_tokens::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        let token_row = refresh_tokens
+            .filter(token_hash.eq(hash))
+            .select(RefreshTokenRow::as_select())
+            .first::<RefreshTokenRow>(&mut *conn)
+            .optional()
+            .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        Ok(token_row.map(RefreshToken::from))
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        token_id: RefreshTokenId,
+        replacement: NewRefreshToken,
+    ) -> Result<RefreshToken, RefreshTokenRepositoryError> {
+        use schema::refresh_tokens::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        conn.transaction(|conn| {
+            // Only one of two concurrent refreshes with the same token gets to mark it as used
+            let updated_rows = diesel::update(
+                refresh_tokens
+                    .filter(id.eq(token_id))
+                    .filter(used_at.is_null())
+                    .filter(revoked_at.is_null()),
+            )
+            .set(used_at.eq(diesel::dsl::now))
+            .execute(conn)?;
+            if updated_rows == 0 {
+                return Err(RefreshTokenRepositoryError::AlreadyUsed);
+            }
+
+            diesel::insert_into(refresh_tokens)
+                .values(CreateRefreshTokenRow::from(replacement))
+                .returning(RefreshTokenRow::as_returning())
+                .get_result::<RefreshTokenRow>(conn)
+                .map_err(RefreshTokenRepositoryError::from)
+        })
+        .map(RefreshToken::from)
+    }
+
+    async fn revoke_refresh_token_family(
+        &self,
+        token_family_id: Uuid,
+    ) -> Result<(), RefreshTokenRepositoryError> {
+        use schema::refresh_tokens::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        diesel::update(
+            refresh_tokens
+                .filter(family_id.eq(token_family_id))
+                .filter(revoked_at.is_null()),
+        )
+        .set(revoked_at.eq(diesel::dsl::now))
+        .execute(&mut *conn)
+        .map_err(|_| RefreshTokenRepositoryError::InternalServerError)?;
+
+        Ok(())
+    }
+}
+
+impl From<diesel::result::Error> for RefreshTokenRepositoryError {
+    fn from(_: diesel::result::Error) -> Self {
+        RefreshTokenRepositoryError::InternalServerError
+    }
+}
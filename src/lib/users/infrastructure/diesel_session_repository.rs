@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use diesel::{
+    PgConnection,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::users::infrastructure::{CreateSessionRow, SessionRow};
+use crate::{
+    persistence::domain::schema,
+    users::domain::{NewSession, Session, SessionId, SessionRepository, SessionRepositoryError},
+};
+
+#[derive(Clone)]
+pub struct DieselSessionRepository {
+    pool: Arc<Pool<ConnectionManager<PgConnection>>>,
+}
+
+impl DieselSessionRepository {
+    pub fn new(connection: Arc<Pool<ConnectionManager<PgConnection>>>) -> Self {
+        DieselSessionRepository { pool: connection }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for DieselSessionRepository {
+    async fn create_session(&self, session: NewSession) -> Result<Session, SessionRepositoryError> {
+        use schema::sessions::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        let session_row = diesel::insert_into(sessions)
+            .values(CreateSessionRow::from(session))
+            .returning(SessionRow::as_returning())
+            .get_result::<SessionRow>(&mut *conn)
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        Ok(session_row.into())
+    }
+
+    async fn get_active_sessions_by_user(
+        &self,
+        session_user_id: Uuid,
+    ) -> Result<Vec<Session>, SessionRepositoryError> {
+        use schema::sessions::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        let session_rows = sessions
+            .filter(user_id.eq(session_user_id))
+            .filter(revoked_at.is_null())
+            .order(last_seen_at.desc())
+            .select(SessionRow::as_select())
+            .load::<SessionRow>(&mut *conn)
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        Ok(session_rows.into_iter().map(Session::from).collect())
+    }
+
+    async fn touch_session(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Option<Session>, SessionRepositoryError> {
+        use schema::sessions::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        let session_row = diesel::update(
+            sessions
+                .filter(id.eq(session_id))
+                .filter(revoked_at.is_null()),
+        )
+        .set(last_seen_at.eq(diesel::dsl::now))
+        .returning(SessionRow::as_returning())
+        .get_result::<SessionRow>(&mut *conn)
+        .optional()
+        .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        Ok(session_row.map(Session::from))
+    }
+
+    async fn revoke_session(
+        &self,
+        session_user_id: Uuid,
+        session_id: SessionId,
+    ) -> Result<bool, SessionRepositoryError> {
+        use schema::sessions::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        let updated_rows = diesel::update(
+            sessions
+                .filter(id.eq(session_id))
+                .filter(user_id.eq(session_user_id))
+                .filter(revoked_at.is_null()),
+        )
+        .set(revoked_at.eq(diesel::dsl::now))
+        .execute(&mut *conn)
+        .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        Ok(updated_rows > 0)
+    }
+
+    async fn revoke_other_sessions(
+        &self,
+        session_user_id: Uuid,
+        keep_id: Option<SessionId>,
+    ) -> Result<Vec<SessionId>, SessionRepositoryError> {
+        use schema::sessions::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| SessionRepositoryError::InternalServerError)?;
+
+        let mut query = diesel::update(sessions)
+            .filter(user_id.eq(session_user_id))
+            .filter(revoked_at.is_null())
+            .into_boxed();
+        if let Some(keep_id) = keep_id {
+            query = query.filter(id.ne(keep_id));
+        }
+
+        query
+            .set(revoked_at.eq(diesel::dsl::now))
+            .returning(id)
+            .get_results::<Uuid>(&mut *conn)
+            .map_err(|_| SessionRepositoryError::InternalServerError)
+    }
+}
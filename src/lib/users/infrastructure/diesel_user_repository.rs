@@ -16,6 +16,9 @@ use crate::{
     users::domain::{User, UserRepository},
 };
 
+/// Unique constraint on `users.email`, violating it means the address is taken
+const EMAIL_UNIQUE_CONSTRAINT: &str = "users_email_key";
+
 #[derive(Clone)]
 pub struct DieselUserRepository {
     pool: Arc<Pool<ConnectionManager<PgConnection>>>,
@@ -43,6 +46,12 @@ impl UserRepository for DieselUserRepository {
             .returning(UserRow::as_returning())
             .get_result::<UserRow>(&mut *conn)
             .map_err(|e| match e {
+                DieselError::DatabaseError(
+                    diesel::result::DatabaseErrorKind::UniqueViolation,
+                    info,
+                ) if info.constraint_name() == Some(EMAIL_UNIQUE_CONSTRAINT) => {
+                    UserRepositoryError::EmailAlreadyExists
+                }
                 DieselError::DatabaseError(
                     diesel::result::DatabaseErrorKind::UniqueViolation,
                     _,
@@ -92,6 +101,24 @@ impl UserRepository for DieselUserRepository {
         Ok(user_row.map(User::from))
     }
 
+    async fn get_by_email(&self, user_email: &str) -> Result<Option<User>, UserRepositoryError> {
+        use schema::users::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| UserRepositoryError::InternalServerError)?;
+
+        let user_row = users
+            .filter(email.eq(user_email))
+            .select(UserRow::as_select())
+            .first::<UserRow>(&mut *conn)
+            .optional()
+            .map_err(|_| UserRepositoryError::InternalServerError)?;
+
+        Ok(user_row.map(User::from))
+    }
+
     async fn get_all_users(&self) -> Result<Vec<User>, UserRepositoryError> {
         use schema::users::dsl::*;
         // Get a connection from the pool
@@ -107,4 +134,62 @@ impl UserRepository for DieselUserRepository {
 
         Ok(user_rows.into_iter().map(User::from).collect())
     }
+
+    async fn update_quota(
+        &self,
+        user_id: uuid::Uuid,
+        new_quota_bytes: Option<i64>,
+        new_quota_items: Option<i64>,
+    ) -> Result<User, UserRepositoryError> {
+        use schema::users::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| UserRepositoryError::InternalServerError)?;
+
+        let user_row = diesel::update(users.filter(id.eq(user_id)))
+            .set((
+                quota_bytes.eq(new_quota_bytes),
+                quota_items.eq(new_quota_items),
+                updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(UserRow::as_returning())
+            .get_result::<UserRow>(&mut *conn)
+            .optional()
+            .map_err(|_| UserRepositoryError::InternalServerError)?;
+
+        user_row
+            .map(User::from)
+            .ok_or(UserRepositoryError::UserNotFound)
+    }
+
+    async fn update_password(
+        &self,
+        user_id: uuid::Uuid,
+        password_hash: String,
+        new_must_change_password: bool,
+    ) -> Result<User, UserRepositoryError> {
+        use schema::users::dsl::*;
+        // Get a connection from the pool
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|_| UserRepositoryError::InternalServerError)?;
+
+        let user_row = diesel::update(users.filter(id.eq(user_id)))
+            .set((
+                password.eq(password_hash),
+                must_change_password.eq(new_must_change_password),
+                updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(UserRow::as_returning())
+            .get_result::<UserRow>(&mut *conn)
+            .optional()
+            .map_err(|_| UserRepositoryError::InternalServerError)?;
+
+        user_row
+            .map(User::from)
+            .ok_or(UserRepositoryError::UserNotFound)
+    }
 }
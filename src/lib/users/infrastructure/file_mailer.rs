@@ -0,0 +1,64 @@
+use std::{env, path::PathBuf};
+
+use async_trait::async_trait;
+
+use crate::users::domain::{Email, Mailer, MailerError};
+
+#[derive(Clone)]
+pub struct FileMailerConfig {
+    pub output_dir: PathBuf,
+}
+
+impl FileMailerConfig {
+    pub fn new() -> Self {
+        FileMailerConfig {
+            output_dir: env::var("MAIL_OUTPUT_DIR")
+                .unwrap_or_else(|_| "mail".to_string())
+                .into(),
+        }
+    }
+}
+
+impl Default for FileMailerConfig {
+    fn default() -> Self {
+        FileMailerConfig::new()
+    }
+}
+
+/// Writes every email to a file of its own and logs where, for development and tests where
+/// nothing should leave the machine
+#[derive(Clone)]
+pub struct FileMailer {
+    config: FileMailerConfig,
+}
+
+impl FileMailer {
+    pub fn new(config: FileMailerConfig) -> Self {
+        FileMailer { config }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, email: Email) -> Result<(), MailerError> {
+        tokio::fs::create_dir_all(&self.config.output_dir)
+            .await
+            .map_err(|e| MailerError::SendFailed(e.to_string()))?;
+
+        let path = self.config.output_dir.join(format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            uuid::Uuid::new_v4()
+        ));
+        let content = format!(
+            "To: {}\r\nSubject: {}\r\n\r\n{}",
+            email.to, email.subject, email.body
+        );
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|e| MailerError::SendFailed(e.to_string()))?;
+
+        tracing::info!("Email to {} written to {}", email.to, path.display());
+        Ok(())
+    }
+}
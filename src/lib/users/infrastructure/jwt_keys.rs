@@ -0,0 +1,257 @@
+use std::{
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use ring::{
+    rand::SystemRandom,
+    rsa::PublicKeyComponents,
+    signature::{Ed25519KeyPair, KeyPair, RsaKeyPair},
+};
+
+use crate::users::domain::JsonWebKey;
+
+/// Extension of the private key files in the key directory, the file stem is the key id
+pub const JWT_KEY_FILE_EXTENSION: &str = "pem";
+
+/// Size of the RSA keys generated for RS256
+const RSA_KEY_BITS: u32 = 2048;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtKeyAlgorithm {
+    Rs256,
+    EdDsa,
+}
+
+impl fmt::Display for JwtKeyAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwtKeyAlgorithm::Rs256 => write!(f, "RS256"),
+            JwtKeyAlgorithm::EdDsa => write!(f, "EdDSA"),
+        }
+    }
+}
+
+impl FromStr for JwtKeyAlgorithm {
+    type Err = JwtKeyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "rs256" => Ok(JwtKeyAlgorithm::Rs256),
+            "eddsa" => Ok(JwtKeyAlgorithm::EdDsa),
+            _ => Err(JwtKeyError::UnsupportedAlgorithm(value.to_string())),
+        }
+    }
+}
+
+impl From<JwtKeyAlgorithm> for Algorithm {
+    fn from(algorithm: JwtKeyAlgorithm) -> Self {
+        match algorithm {
+            JwtKeyAlgorithm::Rs256 => Algorithm::RS256,
+            JwtKeyAlgorithm::EdDsa => Algorithm::EdDSA,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JwtKeyError {
+    #[error("Failed to access {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Invalid key {0}: {1}")]
+    InvalidKey(String, String),
+    #[error("Unsupported algorithm {0}, use RS256 or EdDSA")]
+    UnsupportedAlgorithm(String),
+    #[error("Failed to generate key: {0}")]
+    KeyGeneration(String),
+}
+
+/// Private key tokens are signed with, along with the public key they are verified with
+#[derive(Clone)]
+pub struct JwtKey {
+    pub kid: String,
+    pub algorithm: JwtKeyAlgorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    public_key: JsonWebKey,
+}
+
+impl JwtKey {
+    /// Reads a PKCS#8 encoded Ed25519 or RSA private key, or a PKCS#1 encoded RSA private key
+    pub fn from_pem(kid: &str, pem: &[u8]) -> Result<Self, JwtKeyError> {
+        let invalid = |message: &str| JwtKeyError::InvalidKey(kid.to_string(), message.to_string());
+        let pem = pem::parse(pem).map_err(|e| invalid(&e.to_string()))?;
+        let der = pem.contents();
+
+        match pem.tag() {
+            "PRIVATE KEY" => {
+                if let Ok(key_pair) = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der) {
+                    Ok(Self::ed25519(kid, der, &key_pair))
+                } else {
+                    let key_pair = RsaKeyPair::from_pkcs8(der)
+                        .map_err(|e| invalid(&format!("not an Ed25519 or RSA key, {}", e)))?;
+                    let encoding_key = EncodingKey::from_rsa_pem(pem::encode(&pem).as_bytes())
+                        .map_err(|e| invalid(&e.to_string()))?;
+                    Ok(Self::rsa(kid, encoding_key, &key_pair))
+                }
+            }
+            "RSA PRIVATE KEY" => {
+                let key_pair = RsaKeyPair::from_der(der).map_err(|e| invalid(&e.to_string()))?;
+                Ok(Self::rsa(kid, EncodingKey::from_rsa_der(der), &key_pair))
+            }
+            tag => Err(invalid(&format!("expected a private key, found {}", tag))),
+        }
+    }
+
+    fn ed25519(kid: &str, pkcs8: &[u8], key_pair: &Ed25519KeyPair) -> Self {
+        let public_key = key_pair.public_key().as_ref();
+        JwtKey {
+            kid: kid.to_string(),
+            algorithm: JwtKeyAlgorithm::EdDsa,
+            encoding_key: EncodingKey::from_ed_der(pkcs8),
+            decoding_key: DecodingKey::from_ed_der(public_key),
+            public_key: JsonWebKey {
+                kty: "OKP".to_string(),
+                kid: kid.to_string(),
+                alg: JwtKeyAlgorithm::EdDsa.to_string(),
+                key_use: "sig".to_string(),
+                n: None,
+                e: None,
+                crv: Some("Ed25519".to_string()),
+                x: Some(URL_SAFE_NO_PAD.encode(public_key)),
+            },
+        }
+    }
+
+    fn rsa(kid: &str, encoding_key: EncodingKey, key_pair: &RsaKeyPair) -> Self {
+        let components = PublicKeyComponents::<Vec<u8>>::from(key_pair.public());
+        JwtKey {
+            kid: kid.to_string(),
+            algorithm: JwtKeyAlgorithm::Rs256,
+            encoding_key,
+            decoding_key: DecodingKey::from_rsa_raw_components(&components.n, &components.e),
+            public_key: JsonWebKey {
+                kty: "RSA".to_string(),
+                kid: kid.to_string(),
+                alg: JwtKeyAlgorithm::Rs256.to_string(),
+                key_use: "sig".to_string(),
+                n: Some(URL_SAFE_NO_PAD.encode(&components.n)),
+                e: Some(URL_SAFE_NO_PAD.encode(&components.e)),
+                crv: None,
+                x: None,
+            },
+        }
+    }
+
+    pub fn encoding_key(&self) -> &EncodingKey {
+        &self.encoding_key
+    }
+
+    pub fn decoding_key(&self) -> &DecodingKey {
+        &self.decoding_key
+    }
+
+    pub fn public_key(&self) -> &JsonWebKey {
+        &self.public_key
+    }
+}
+
+/// Loads every key of the directory, sorted by key id so the newest generated key comes last
+pub fn load_jwt_keys(dir: &Path) -> Result<Vec<JwtKey>, JwtKeyError> {
+    let mut keys = jwt_key_files(dir)?
+        .into_iter()
+        .map(|(kid, path)| {
+            let pem = fs::read(&path).map_err(|e| JwtKeyError::Io(path, e))?;
+            JwtKey::from_pem(&kid, &pem)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    keys.sort_by(|a, b| a.kid.cmp(&b.kid));
+    Ok(keys)
+}
+
+/// Writes a new private key to the directory and returns its id. Ids start with the creation
+/// time, so the new key sorts last and becomes the signing key.
+pub fn generate_jwt_key(dir: &Path, algorithm: JwtKeyAlgorithm) -> Result<String, JwtKeyError> {
+    let pem = match algorithm {
+        JwtKeyAlgorithm::EdDsa => {
+            let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+                .map_err(|e| JwtKeyError::KeyGeneration(e.to_string()))?;
+            pem::encode(&pem::Pem::new("PRIVATE KEY", pkcs8.as_ref()))
+        }
+        // ring cannot generate RSA keys, so this needs the openssl binary
+        JwtKeyAlgorithm::Rs256 => {
+            let output = Command::new("openssl")
+                .args(["genpkey", "-algorithm", "RSA", "-pkeyopt"])
+                .arg(format!("rsa_keygen_bits:{}", RSA_KEY_BITS))
+                .output()
+                .map_err(|e| JwtKeyError::KeyGeneration(format!("failed to run openssl, {}", e)))?;
+            if !output.status.success() {
+                return Err(JwtKeyError::KeyGeneration(
+                    String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                ));
+            }
+            String::from_utf8(output.stdout)
+                .map_err(|e| JwtKeyError::KeyGeneration(e.to_string()))?
+        }
+    };
+
+    let kid = format!(
+        "{}-{}",
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        algorithm.to_string().to_ascii_lowercase()
+    );
+    // Refuses keys that do not load before writing them
+    JwtKey::from_pem(&kid, pem.as_bytes())?;
+
+    fs::create_dir_all(dir).map_err(|e| JwtKeyError::Io(dir.to_path_buf(), e))?;
+    let path = dir.join(format!("{}.{}", kid, JWT_KEY_FILE_EXTENSION));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(&path)
+        .and_then(|mut file| file.write_all(pem.as_bytes()))
+        .map_err(|e| JwtKeyError::Io(path, e))?;
+    Ok(kid)
+}
+
+/// Deletes all but the newest `keep` keys of the directory, returns the ids of the deleted keys
+pub fn remove_old_jwt_keys(dir: &Path, keep: usize) -> Result<Vec<String>, JwtKeyError> {
+    let mut files = jwt_key_files(dir)?;
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let remove_count = files.len().saturating_sub(keep);
+
+    files
+        .into_iter()
+        .take(remove_count)
+        .map(|(kid, path)| {
+            fs::remove_file(&path).map_err(|e| JwtKeyError::Io(path, e))?;
+            Ok(kid)
+        })
+        .collect()
+}
+
+fn jwt_key_files(dir: &Path) -> Result<Vec<(String, PathBuf)>, JwtKeyError> {
+    let entries = fs::read_dir(dir).map_err(|e| JwtKeyError::Io(dir.to_path_buf(), e))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| JwtKeyError::Io(dir.to_path_buf(), e))?
+            .path();
+        if !path.is_file()
+            || path.extension().and_then(|extension| extension.to_str())
+                != Some(JWT_KEY_FILE_EXTENSION)
+        {
+            continue;
+        }
+        if let Some(kid) = path.file_stem().and_then(|stem| stem.to_str()) {
+            files.push((kid.to_string(), path.clone()));
+        }
+    }
+    Ok(files)
+}
@@ -1,21 +1,76 @@
-use std::env;
+use std::{env, path::Path};
 
-use jsonwebtoken::{EncodingKey, Header, Validation, encode};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode_header, encode};
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use crate::users::domain::{Claims, LoginTokenService, Token, user::UserLoginError};
+use crate::users::{
+    domain::{Claims, JsonWebKey, LoginTokenService, Token, user::UserLoginError},
+    infrastructure::jwt_keys::{JwtKey, load_jwt_keys},
+};
 
 #[derive(Clone)]
 pub struct JwtTokenConfig {
-    pub secret_key: String,
+    /// Shared HS256 secret, only used to sign tokens when there are no keys. With keys it still
+    /// verifies tokens without a `kid` header, so switching to keys does not log everyone out.
+    pub secret_key: Option<String>,
+    /// Keys tokens are verified with, by the `kid` header of the token
+    pub keys: Vec<JwtKey>,
+    /// Key new tokens are signed with, the newest key when not set
+    pub signing_key_id: Option<String>,
 }
 
 impl JwtTokenConfig {
+    /// Reads the keys of `JWT_KEY_DIR`, `JWT_SIGNING_KEY_ID` and `JWT_SECRET_KEY`, either keys
+    /// or the secret have to be set
     pub fn new() -> Self {
-        JwtTokenConfig {
+        let keys = env::var("JWT_KEY_DIR")
+            .ok()
+            .filter(|dir| !dir.trim().is_empty())
+            .map(|dir| {
+                load_jwt_keys(Path::new(&dir))
+                    .unwrap_or_else(|e| panic!("Failed to load the keys of JWT_KEY_DIR: {}", e))
+            })
+            .unwrap_or_default();
+        let config = JwtTokenConfig {
             secret_key: env::var("JWT_SECRET_KEY")
-                .expect("JWT_SECRET_KEY environment variable has to be set"),
+                .ok()
+                .filter(|secret| !secret.is_empty()),
+            keys,
+            signing_key_id: env::var("JWT_SIGNING_KEY_ID")
+                .ok()
+                .filter(|kid| !kid.trim().is_empty()),
+        };
+
+        if config.keys.is_empty() && config.secret_key.is_none() {
+            panic!("JWT_KEY_DIR holding at least one key or JWT_SECRET_KEY has to be set");
+        }
+        if let Some(kid) = &config.signing_key_id
+            && config.key(kid).is_none()
+        {
+            panic!("JWT_SIGNING_KEY_ID {} is not a key of JWT_KEY_DIR", kid);
+        }
+        config
+    }
+
+    /// Config signing and verifying tokens with a shared HS256 secret
+    pub fn from_secret(secret_key: &str) -> Self {
+        JwtTokenConfig {
+            secret_key: Some(secret_key.to_string()),
+            keys: Vec::new(),
+            signing_key_id: None,
+        }
+    }
+
+    pub fn key(&self, kid: &str) -> Option<&JwtKey> {
+        self.keys.iter().find(|key| key.kid == kid)
+    }
+
+    /// Key new tokens are signed with, `None` when tokens are signed with the secret
+    pub fn signing_key(&self) -> Option<&JwtKey> {
+        match &self.signing_key_id {
+            Some(kid) => self.key(kid),
+            None => self.keys.iter().max_by(|a, b| a.kid.cmp(&b.kid)),
         }
     }
 }
@@ -42,11 +97,25 @@ pub struct JWT(pub String);
 
 impl JWT {
     pub fn new(logged_user: Claims, config: &JwtTokenConfig) -> Result<Self, UserLoginError> {
-        match encode(
-            &Header::default(),
-            &logged_user,
-            &EncodingKey::from_secret(config.secret_key.as_ref()),
-        ) {
+        let encoded = match (config.signing_key(), &config.secret_key) {
+            (Some(key), _) => {
+                let mut header = Header::new(key.algorithm.into());
+                header.kid = Some(key.kid.clone());
+                encode(&header, &logged_user, key.encoding_key())
+            }
+            (None, Some(secret_key)) => encode(
+                &Header::default(),
+                &logged_user,
+                &EncodingKey::from_secret(secret_key.as_ref()),
+            ),
+            (None, None) => {
+                return Err(UserLoginError::InternalServerError(
+                    "No key to sign JWT tokens with".to_string(),
+                ));
+            }
+        };
+
+        match encoded {
             Ok(token) => Ok(JWT(token)),
             Err(_) => Err(UserLoginError::InternalServerError(
                 "Failed to generate JWT token".to_string(),
@@ -59,13 +128,34 @@ impl JWT {
     }
 
     pub fn validate(token: &str, config: &JwtTokenConfig) -> Result<Claims, UserLoginError> {
-        jsonwebtoken::decode::<Claims>(
-            token,
-            &jsonwebtoken::DecodingKey::from_secret(config.secret_key.as_ref()),
-            &Validation::default(),
-        )
-        .map_err(|_| UserLoginError::InvalidToken)
-        .map(|data| data.claims)
+        let header = decode_header(token).map_err(|_| UserLoginError::InvalidToken)?;
+
+        // The algorithm comes from the key, never from the token, so a token cannot pick one
+        let result = match header.kid {
+            Some(kid) => {
+                let key = config.key(&kid).ok_or(UserLoginError::InvalidToken)?;
+                jsonwebtoken::decode::<Claims>(
+                    token,
+                    key.decoding_key(),
+                    &Validation::new(key.algorithm.into()),
+                )
+            }
+            None => {
+                let secret_key = config
+                    .secret_key
+                    .as_ref()
+                    .ok_or(UserLoginError::InvalidToken)?;
+                jsonwebtoken::decode::<Claims>(
+                    token,
+                    &DecodingKey::from_secret(secret_key.as_ref()),
+                    &Validation::default(),
+                )
+            }
+        };
+
+        result
+            .map_err(|_| UserLoginError::InvalidToken)
+            .map(|data| data.claims)
     }
 }
 
@@ -80,4 +170,12 @@ impl LoginTokenService for JwtTokenService {
     fn validate_token(&self, token: &str) -> Result<Claims, UserLoginError> {
         JWT::validate(token, &self.config)
     }
+
+    fn public_keys(&self) -> Vec<JsonWebKey> {
+        self.config
+            .keys
+            .iter()
+            .map(|key| key.public_key().clone())
+            .collect()
+    }
 }
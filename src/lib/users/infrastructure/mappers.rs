@@ -1,14 +1,64 @@
-use crate::users::{domain::User, infrastructure::UserRow};
+use crate::users::{
+    domain::{PasswordResetToken, RefreshToken, Session, User},
+    infrastructure::{PasswordResetTokenRow, RefreshTokenRow, SessionRow, UserRow},
+};
 
 impl From<UserRow> for User {
     fn from(row: UserRow) -> Self {
         User {
             id: row.id,
             username: row.username,
+            email: row.email,
             password: row.password,
             role: row.role.into(),
+            quota_bytes: row.quota_bytes,
+            quota_items: row.quota_items,
+            must_change_password: row.must_change_password,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
     }
 }
+
+impl From<PasswordResetTokenRow> for PasswordResetToken {
+    fn from(row: PasswordResetTokenRow) -> Self {
+        PasswordResetToken {
+            id: row.id,
+            user_id: row.user_id,
+            token_hash: row.token_hash,
+            expires_at: row.expires_at,
+            used_at: row.used_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+impl From<RefreshTokenRow> for RefreshToken {
+    fn from(row: RefreshTokenRow) -> Self {
+        RefreshToken {
+            id: row.id,
+            user_id: row.user_id,
+            family_id: row.family_id,
+            token_hash: row.token_hash,
+            expires_at: row.expires_at,
+            used_at: row.used_at,
+            revoked_at: row.revoked_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+impl From<SessionRow> for Session {
+    fn from(row: SessionRow) -> Self {
+        Session {
+            id: row.id,
+            user_id: row.user_id,
+            device_name: row.device_name,
+            user_agent: row.user_agent,
+            ip_address: row.ip_address,
+            created_at: row.created_at,
+            last_seen_at: row.last_seen_at,
+            revoked_at: row.revoked_at,
+        }
+    }
+}
@@ -1,7 +1,24 @@
+pub mod app_mailer;
+pub mod diesel_password_reset_token_repository;
+pub mod diesel_refresh_token_repository;
+pub mod diesel_session_repository;
 pub mod diesel_user_repository;
+pub mod file_mailer;
+pub mod jwt_keys;
 pub mod jwt_token_service;
 pub mod mappers;
 pub mod models;
+pub mod smtp_mailer;
 
+pub use app_mailer::AppMailer;
+pub use diesel_password_reset_token_repository::DieselPasswordResetTokenRepository;
+pub use diesel_refresh_token_repository::DieselRefreshTokenRepository;
+pub use diesel_session_repository::DieselSessionRepository;
 pub use diesel_user_repository::DieselUserRepository;
-pub use models::{CreateUserRow, UserRow};
+pub use file_mailer::{FileMailer, FileMailerConfig};
+pub use jwt_keys::{JwtKey, JwtKeyAlgorithm, JwtKeyError};
+pub use models::{
+    CreatePasswordResetTokenRow, CreateRefreshTokenRow, CreateSessionRow, CreateUserRow,
+    PasswordResetTokenRow, RefreshTokenRow, SessionRow, UserRow,
+};
+pub use smtp_mailer::{SmtpMailer, SmtpMailerConfig};
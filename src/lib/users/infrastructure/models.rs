@@ -1,8 +1,10 @@
 use std::io::Write;
 
 use crate::{
-    persistence::domain::schema::{sql_types, users},
-    users::domain::user::NewUser,
+    persistence::domain::schema::{
+        password_reset_tokens, refresh_tokens, sessions, sql_types, users,
+    },
+    users::domain::{NewPasswordResetToken, NewRefreshToken, NewSession, user::NewUser},
 };
 use diesel::prelude::*;
 use serde::Deserialize;
@@ -19,6 +21,10 @@ pub struct UserRow {
     pub role: RowRole,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
+    pub quota_bytes: Option<i64>,
+    pub quota_items: Option<i64>,
+    pub must_change_password: bool,
+    pub email: Option<String>,
 }
 
 #[derive(Insertable, ToSchema, Deserialize)]
@@ -27,6 +33,8 @@ pub struct CreateUserRow {
     pub username: String,
     pub password: String,
     pub role: Option<RowRole>,
+    pub must_change_password: bool,
+    pub email: Option<String>,
 }
 
 impl From<NewUser> for CreateUserRow {
@@ -35,6 +43,106 @@ impl From<NewUser> for CreateUserRow {
             username: command.username,
             password: command.password,
             role: command.role.map(RowRole::from),
+            must_change_password: command.must_change_password,
+            email: command.email,
+        }
+    }
+}
+
+#[derive(Queryable, Debug, Selectable)]
+#[diesel(table_name = password_reset_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PasswordResetTokenRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+    pub used_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = password_reset_tokens)]
+pub struct CreatePasswordResetTokenRow {
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+impl From<NewPasswordResetToken> for CreatePasswordResetTokenRow {
+    fn from(token: NewPasswordResetToken) -> Self {
+        CreatePasswordResetTokenRow {
+            user_id: token.user_id,
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+        }
+    }
+}
+
+#[derive(Queryable, Debug, Selectable)]
+#[diesel(table_name = refresh_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RefreshTokenRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+    pub used_at: Option<chrono::NaiveDateTime>,
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct CreateRefreshTokenRow {
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: chrono::NaiveDateTime,
+}
+
+impl From<NewRefreshToken> for CreateRefreshTokenRow {
+    fn from(token: NewRefreshToken) -> Self {
+        CreateRefreshTokenRow {
+            user_id: token.user_id,
+            family_id: token.family_id,
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+        }
+    }
+}
+
+#[derive(Queryable, Debug, Selectable)]
+#[diesel(table_name = sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SessionRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub last_seen_at: Option<chrono::NaiveDateTime>,
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = sessions)]
+pub struct CreateSessionRow {
+    pub user_id: Uuid,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl From<NewSession> for CreateSessionRow {
+    fn from(session: NewSession) -> Self {
+        CreateSessionRow {
+            user_id: session.user_id,
+            device_name: session.device_name,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
         }
     }
 }
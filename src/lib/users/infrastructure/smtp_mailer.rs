@@ -0,0 +1,103 @@
+use std::env;
+
+use async_trait::async_trait;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+
+use crate::users::domain::{Email, Mailer, MailerError};
+
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+#[derive(Clone)]
+pub struct SmtpMailerConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender of every email, like `Photos <noreply@example.com>`
+    pub from: String,
+    /// Upgrades the connection with STARTTLS, only disable it for local test servers
+    pub starttls: bool,
+}
+
+impl SmtpMailerConfig {
+    pub fn new() -> Self {
+        SmtpMailerConfig {
+            host: env::var("SMTP_HOST").expect("SMTP_HOST environment variable has to be set"),
+            port: env::var("SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse::<u16>().ok())
+                .unwrap_or(DEFAULT_SMTP_PORT),
+            username: env::var("SMTP_USERNAME").ok(),
+            password: env::var("SMTP_PASSWORD").ok(),
+            from: env::var("SMTP_FROM").expect("SMTP_FROM environment variable has to be set"),
+            starttls: env::var("SMTP_STARTTLS")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+        }
+    }
+}
+
+impl Default for SmtpMailerConfig {
+    fn default() -> Self {
+        SmtpMailerConfig::new()
+    }
+}
+
+/// Sends emails through an SMTP relay
+#[derive(Clone)]
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: SmtpMailerConfig) -> Result<Self, MailerError> {
+        let from = config
+            .from
+            .parse::<Mailbox>()
+            .map_err(|e| MailerError::InvalidEmail(format!("{}: {}", config.from, e)))?;
+
+        let mut builder = if config.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| MailerError::SendFailed(e.to_string()))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+        };
+        builder = builder.port(config.port);
+        if let (Some(username), Some(password)) = (config.username, config.password) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(SmtpMailer {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: Email) -> Result<(), MailerError> {
+        let to = email
+            .to
+            .parse::<Mailbox>()
+            .map_err(|e| MailerError::InvalidEmail(format!("{}: {}", email.to, e)))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(email.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(email.body)
+            .map_err(|e| MailerError::InvalidEmail(e.to_string()))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| MailerError::SendFailed(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
+use std::net::SocketAddr;
+
 use crate::shared::interface::http::mw_require_role;
 use axum::{
-    Json,
-    extract::{Path, State},
-    http::StatusCode,
-    routing::{get, post},
+    Extension, Json,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode, header},
+    routing::{delete, get, post, put},
 };
 use utoipa::OpenApi;
+use validator::Validate;
 
 use crate::{
     api::{
@@ -19,16 +22,38 @@ use crate::{
     shared::interface::{http::ValidatedJson, openapi::security::SecurityAddon},
     users::{
         application::{
-            commands::create_user::{
-                CreateUserCommand, CreateUserResult, create_user_command_handler,
+            commands::{
+                change_password::{
+                    ChangePasswordCommand, ChangePasswordError, ResetPasswordCommand,
+                    change_password_command_handler, reset_password_command_handler,
+                },
+                create_user::{CreateUserCommand, CreateUserResult, create_user_command_handler},
+                logout::{LogoutCommand, logout_command_handler},
+                password_reset::{
+                    ConfirmPasswordResetCommand, PasswordResetError, RequestPasswordResetCommand,
+                    confirm_password_reset_command_handler, request_password_reset_command_handler,
+                },
+                refresh_token::{RefreshTokenCommand, refresh_token_command_handler},
+                revoke_session::{
+                    RevokeOtherSessionsCommand, RevokeOtherSessionsResult, RevokeSessionCommand,
+                    RevokeSessionError, revoke_other_sessions_command_handler,
+                    revoke_session_command_handler,
+                },
+                update_user_quota::{
+                    UpdateUserQuotaCommand, UpdateUserQuotaResult,
+                    update_user_quota_command_handler,
+                },
             },
             login::{LoginCommand, login_command_handler},
             queries::{
                 get_all_users::{GetAllUsersResult, get_all_users_query_handler},
+                get_sessions::{GetSessionsQuery, GetSessionsResult, get_sessions_query_handler},
                 get_user::{GetUserQuery, GetUserResult, get_user_query_handler},
             },
         },
-        domain::{Role, UserRepositoryError, user::UserLoginError},
+        domain::{
+            Claims, JsonWebKeySet, Role, SessionClient, UserRepositoryError, user::UserLoginError,
+        },
     },
 };
 
@@ -40,7 +65,8 @@ use crate::{
     request_body = CreateUserCommand,
     responses(
         (status = 201, description = "User created correctly", body = ApiResponseBody<CreateUserResult>),
-        (status = 409, description = "Failed to create user, user already exists", body = ApiErrorBody,
+        (status = 400, description = "Invalid username, email or password", body = ApiErrorBody),
+        (status = 409, description = "Failed to create user, username or email already in use", body = ApiErrorBody,
             example = json!({
             "message": "Failed to create user, user already exists"
         })),
@@ -54,7 +80,9 @@ pub async fn create_user(
     match create_user_command_handler(body, state.user_repository.as_ref()).await {
         Ok(user) => Ok((StatusCode::CREATED, ApiResponseBody::new(user).into())),
         Err(err) => match err {
-            UserRepositoryError::UserAlreadyExists => Err(ApiError::ConflictError(err.to_string())),
+            UserRepositoryError::UserAlreadyExists | UserRepositoryError::EmailAlreadyExists => {
+                Err(ApiError::ConflictError(err.to_string()))
+            }
             UserRepositoryError::InternalServerError => {
                 Err(ApiError::InternalServerError(err.to_string()))
             }
@@ -78,6 +106,10 @@ pub async fn create_user(
             example = json!({
             "message": "Invalid username or password"
         })),
+        (status = 403, description = "Password was reset by an admin, log in again with `new_password`", body = ApiErrorBody,
+            example = json!({
+            "message": "Password has to be changed, log in again with a new password"
+        })),
         (status = 500, description = "Internal server error", body = ApiErrorBody,
             example = json!({
             "message": "Internal server error"
@@ -86,21 +118,231 @@ pub async fn create_user(
 )]
 pub async fn login_user(
     State(state): State<AppState>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
     ValidatedJson(body): ValidatedJson<LoginCommand>,
 ) -> Result<(StatusCode, Json<ApiResponseBody<TokenResponseBody>>), ApiError> {
+    let peer_address = connect_info.map(|Extension(ConnectInfo(address))| address);
     match login_command_handler(
         body,
+        session_client(&headers, peer_address),
         state.user_repository.as_ref(),
         state.login_token_service.as_ref(),
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.auth_token_config.as_ref(),
     )
     .await
     {
-        Ok(result) => Ok((StatusCode::OK, Json(ApiResponseBody::new(TokenResponseBody::new(result))))),
-        Err(err) => match err {
-            UserLoginError::InvalidCredentials => Err(ApiError::BadRequestError(err.to_string())),
-            UserLoginError::InternalServerError(msg) => Err(ApiError::InternalServerError(msg)),
-            UserLoginError::InvalidToken => Err(ApiError::UnauthorizedError(err.to_string())),
-        },
+        Ok(result) => Ok((
+            StatusCode::OK,
+            Json(ApiResponseBody::new(TokenResponseBody::new(result))),
+        )),
+        Err(err) => Err(login_error_to_api_error(err)),
+    }
+}
+
+/// Longest user agent stored with a session
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Client a login comes from. The address is only shown to the user, so the one a reverse proxy
+/// put in `X-Forwarded-For` is taken over the peer address when present.
+fn session_client(headers: &HeaderMap, peer_address: Option<SocketAddr>) -> SessionClient {
+    let header_value = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let forwarded_address = header_value(header::HeaderName::from_static("x-forwarded-for"))
+        .and_then(|value| value.split(',').next())
+        .and_then(|address| address.trim().parse::<std::net::IpAddr>().ok());
+
+    SessionClient {
+        user_agent: header_value(header::USER_AGENT)
+            .map(|user_agent| user_agent.chars().take(MAX_USER_AGENT_LENGTH).collect()),
+        ip_address: forwarded_address
+            .or(peer_address.map(|address| address.ip()))
+            .map(|address| address.to_string()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    description = "Exchange a refresh token for a new access token and refresh token. Presenting a refresh token that was already exchanged revokes the whole login.",
+    tag = "auth",
+    request_body = RefreshTokenCommand,
+    responses(
+        (status = 200, description = "Tokens refreshed successfully", body = ApiResponseBody<TokenResponseBody>),
+        (status = 401, description = "Refresh token is invalid, expired, revoked or was already used", body = ApiErrorBody,
+            example = json!({
+            "message": "Invalid token"
+        })),
+        (status = 500, description = "Internal server error", body = ApiErrorBody,
+            example = json!({
+            "message": "Internal server error"
+        }))
+    )
+)]
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    ValidatedJson(body): ValidatedJson<RefreshTokenCommand>,
+) -> Result<(StatusCode, Json<ApiResponseBody<TokenResponseBody>>), ApiError> {
+    match refresh_token_command_handler(
+        body,
+        state.user_repository.as_ref(),
+        state.login_token_service.as_ref(),
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+        state.auth_token_config.as_ref(),
+    )
+    .await
+    {
+        Ok(result) => Ok((
+            StatusCode::OK,
+            Json(ApiResponseBody::new(TokenResponseBody::new(result))),
+        )),
+        Err(err) => Err(login_error_to_api_error(err)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    description = "Revoke the login the refresh token belongs to, its access tokens are rejected afterwards",
+    tag = "auth",
+    request_body = LogoutCommand,
+    responses(
+        (status = 204, description = "Logged out successfully"),
+        (status = 500, description = "Internal server error", body = ApiErrorBody,
+            example = json!({
+            "message": "Internal server error"
+        }))
+    )
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    ValidatedJson(body): ValidatedJson<LogoutCommand>,
+) -> Result<StatusCode, ApiError> {
+    match logout_command_handler(
+        body,
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(err) => Err(login_error_to_api_error(err)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password-reset",
+    description = "Email a password reset token to the user with the address. The response is the same whether or not the address is registered",
+    tag = "auth",
+    request_body = RequestPasswordResetCommand,
+    responses(
+        (status = 202, description = "Reset token sent if the address belongs to a user"),
+        (status = 400, description = "Invalid email", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody,
+            example = json!({
+            "message": "Internal server error"
+        }))
+    )
+)]
+pub async fn request_password_reset(
+    State(state): State<AppState>,
+    ValidatedJson(body): ValidatedJson<RequestPasswordResetCommand>,
+) -> Result<StatusCode, ApiError> {
+    match request_password_reset_command_handler(
+        body,
+        state.user_repository.as_ref(),
+        state.password_reset_token_repository.clone(),
+        state.mailer.clone(),
+        state.password_reset_config.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => Ok(StatusCode::ACCEPTED),
+        Err(err) => Err(password_reset_error_to_api_error(err)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/password-reset/confirm",
+    description = "Set a new password with an emailed reset token, the user is logged out everywhere",
+    tag = "auth",
+    request_body = ConfirmPasswordResetCommand,
+    responses(
+        (status = 204, description = "Password reset successfully"),
+        (status = 400, description = "Invalid or expired token, or invalid password", body = ApiErrorBody,
+            example = json!({
+            "message": "Invalid or expired password reset token"
+        })),
+        (status = 500, description = "Internal server error", body = ApiErrorBody,
+            example = json!({
+            "message": "Internal server error"
+        }))
+    )
+)]
+pub async fn confirm_password_reset(
+    State(state): State<AppState>,
+    ValidatedJson(body): ValidatedJson<ConfirmPasswordResetCommand>,
+) -> Result<StatusCode, ApiError> {
+    match confirm_password_reset_command_handler(
+        body,
+        state.user_repository.as_ref(),
+        state.password_reset_token_repository.as_ref(),
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(err) => Err(password_reset_error_to_api_error(err)),
+    }
+}
+
+fn password_reset_error_to_api_error(err: PasswordResetError) -> ApiError {
+    match err {
+        PasswordResetError::InvalidToken => ApiError::BadRequestError(err.to_string()),
+        PasswordResetError::InternalServerError(msg) => ApiError::InternalServerError(msg),
+    }
+}
+
+/// Public keys other services verify access tokens with, served at `/.well-known/jwks.json`.
+/// Rotated keys only show up here after a restart, so clients may cache the set for a while.
+pub async fn get_jwks(
+    State(state): State<AppState>,
+) -> (
+    StatusCode,
+    [(header::HeaderName, &'static str); 1],
+    Json<JsonWebKeySet>,
+) {
+    (
+        StatusCode::OK,
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(JsonWebKeySet {
+            keys: state.login_token_service.public_keys(),
+        }),
+    )
+}
+
+fn login_error_to_api_error(err: UserLoginError) -> ApiError {
+    match err {
+        UserLoginError::InvalidCredentials => ApiError::BadRequestError(err.to_string()),
+        UserLoginError::InternalServerError(msg) => ApiError::InternalServerError(msg),
+        UserLoginError::InvalidToken | UserLoginError::RefreshTokenReused => {
+            ApiError::UnauthorizedError(err.to_string())
+        }
+        UserLoginError::PasswordChangeRequired => ApiError::ForbiddenError(err.to_string()),
     }
 }
 
@@ -168,19 +410,295 @@ pub async fn get_user(
     }
 }
 
+#[derive(Debug, Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct UpdateUserQuotaRequestBody {
+    /// Storage the user may take up in bytes, `null` falls back to the default quota
+    #[validate(range(min = 0, message = "Quota cannot be negative"))]
+    pub quota_bytes: Option<i64>,
+    /// Media files the user may own, `null` falls back to the default quota
+    #[validate(range(min = 0, message = "Quota cannot be negative"))]
+    pub quota_items: Option<i64>,
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/quota",
+    description = "Set the storage quota of a user",
+    tag = "users",
+    params(
+        ("id" = uuid::Uuid, Path, description = "User ID")
+    ),
+    request_body = UpdateUserQuotaRequestBody,
+    responses(
+        (status = 200, description = "Quota updated successfully", body = ApiResponseBody<UpdateUserQuotaResult>),
+        (status = 400, description = "Invalid user ID format or quota", body = ApiErrorBody),
+        (status = 404, description = "User not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn update_user_quota(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    ValidatedJson(body): ValidatedJson<UpdateUserQuotaRequestBody>,
+) -> Result<(StatusCode, Json<ApiResponseBody<UpdateUserQuotaResult>>), ApiError> {
+    let id = uuid::Uuid::parse_str(&id)
+        .map_err(|_| ApiError::BadRequestError("Invalid user ID format".to_string()))?;
+
+    let command = UpdateUserQuotaCommand {
+        id,
+        quota_bytes: body.quota_bytes,
+        quota_items: body.quota_items,
+    };
+    match update_user_quota_command_handler(command, state.user_repository.as_ref()).await {
+        Ok(result) => Ok((StatusCode::OK, ApiResponseBody::new(result).into())),
+        Err(UserRepositoryError::UserNotFound) => {
+            Err(ApiError::NotFoundError("User not found".to_string()))
+        }
+        Err(_) => Err(ApiError::InternalServerError(
+            "Internal server error".to_string(),
+        )),
+    }
+}
+
 // Users api routes
+#[utoipa::path(
+    get,
+    path = "/me/sessions",
+    description = "List the devices the user is logged in from",
+    tag = "users",
+    responses(
+        (status = 200, description = "Sessions retrieved successfully", body = ApiResponseBody<Vec<GetSessionsResult>>),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn get_sessions(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<(StatusCode, Json<ApiResponseBody<Vec<GetSessionsResult>>>), ApiError> {
+    let query = GetSessionsQuery {
+        user_id: claims.sub,
+        current_session_id: claims.sid,
+    };
+    match get_sessions_query_handler(query, state.session_repository.as_ref()).await {
+        Ok(sessions) => Ok((StatusCode::OK, ApiResponseBody::new(sessions).into())),
+        Err(_) => Err(ApiError::InternalServerError(
+            "Internal server error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/me/sessions/{id}",
+    description = "Log the user out of one of their sessions",
+    tag = "users",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 204, description = "Session revoked successfully"),
+        (status = 404, description = "Session not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let command = RevokeSessionCommand {
+        user_id: claims.sub,
+        session_id: id,
+    };
+    match revoke_session_command_handler(
+        command,
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(err) => Err(revoke_session_error_to_api_error(err)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/me/sessions",
+    description = "Log the user out of every session except the one making the request",
+    tag = "users",
+    responses(
+        (status = 200, description = "Other sessions revoked successfully", body = ApiResponseBody<RevokeOtherSessionsResult>),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn revoke_other_sessions(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> Result<(StatusCode, Json<ApiResponseBody<RevokeOtherSessionsResult>>), ApiError> {
+    let command = RevokeOtherSessionsCommand {
+        user_id: claims.sub,
+        current_session_id: claims.sid,
+    };
+    match revoke_other_sessions_command_handler(
+        command,
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+    )
+    .await
+    {
+        Ok(result) => Ok((StatusCode::OK, ApiResponseBody::new(result).into())),
+        Err(err) => Err(revoke_session_error_to_api_error(err)),
+    }
+}
+
+#[derive(Debug, Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct ChangePasswordRequestBody {
+    #[validate(length(min = 1, message = "Current password cannot be empty"))]
+    pub current_password: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub new_password: String,
+}
+
+#[utoipa::path(
+    put,
+    path = "/me/password",
+    description = "Change the password of the user, every other session is logged out",
+    tag = "users",
+    request_body = ChangePasswordRequestBody,
+    responses(
+        (status = 204, description = "Password changed successfully"),
+        (status = 400, description = "Current password is incorrect or new password is invalid", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn change_password(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    ValidatedJson(body): ValidatedJson<ChangePasswordRequestBody>,
+) -> Result<StatusCode, ApiError> {
+    let command = ChangePasswordCommand {
+        user_id: claims.sub,
+        current_session_id: claims.sid,
+        current_password: body.current_password,
+        new_password: body.new_password,
+    };
+    match change_password_command_handler(
+        command,
+        state.user_repository.as_ref(),
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(err) => Err(change_password_error_to_api_error(err)),
+    }
+}
+
+#[derive(Debug, Validate, serde::Deserialize, utoipa::ToSchema)]
+pub struct ResetPasswordRequestBody {
+    /// Temporary password, the user has to replace it at the next login
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub new_password: String,
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/password",
+    description = "Reset the password of a user, the user is logged out everywhere and has to pick a new password at the next login",
+    tag = "users",
+    params(
+        ("id" = uuid::Uuid, Path, description = "User ID")
+    ),
+    request_body = ResetPasswordRequestBody,
+    responses(
+        (status = 204, description = "Password reset successfully"),
+        (status = 400, description = "Invalid user ID format or password", body = ApiErrorBody),
+        (status = 404, description = "User not found", body = ApiErrorBody),
+        (status = 500, description = "Internal server error", body = ApiErrorBody)
+    ),
+    security(("bearer_auth" = [])),
+)]
+pub async fn reset_user_password(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    ValidatedJson(body): ValidatedJson<ResetPasswordRequestBody>,
+) -> Result<StatusCode, ApiError> {
+    let user_id = uuid::Uuid::parse_str(&id)
+        .map_err(|_| ApiError::BadRequestError("Invalid user ID format".to_string()))?;
+
+    let command = ResetPasswordCommand {
+        user_id,
+        new_password: body.new_password,
+    };
+    match reset_password_command_handler(
+        command,
+        state.user_repository.as_ref(),
+        state.session_repository.as_ref(),
+        state.refresh_token_repository.as_ref(),
+        state.session_cache.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(err) => Err(change_password_error_to_api_error(err)),
+    }
+}
+
+fn change_password_error_to_api_error(err: ChangePasswordError) -> ApiError {
+    match err {
+        ChangePasswordError::InvalidCurrentPassword | ChangePasswordError::SamePassword => {
+            ApiError::BadRequestError(err.to_string())
+        }
+        ChangePasswordError::UserNotFound => ApiError::NotFoundError(err.to_string()),
+        ChangePasswordError::InternalServerError(msg) => ApiError::InternalServerError(msg),
+    }
+}
+
+fn revoke_session_error_to_api_error(err: RevokeSessionError) -> ApiError {
+    match err {
+        RevokeSessionError::NotFound => ApiError::NotFoundError(err.to_string()),
+        RevokeSessionError::InternalServerError(msg) => ApiError::InternalServerError(msg),
+    }
+}
+
 pub fn api_routes(state: AppState) -> axum::Router<AppState> {
     axum::Router::new()
         .route("/", post(create_user))
+        .route("/{id}/quota", put(update_user_quota))
+        .route("/{id}/password", put(reset_user_password))
         .route_layer(require_roles!(&[Role::Admin]))
         .route("/", get(get_all_users))
         .route("/{id}", get(get_user))
+        .route(
+            "/me/sessions",
+            get(get_sessions).delete(revoke_other_sessions),
+        )
+        .route("/me/sessions/{id}", delete(revoke_session))
+        .route("/me/password", put(change_password))
         .route_layer(protected!(state.clone()))
 }
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(create_user),
+    paths(
+        create_user,
+        update_user_quota,
+        reset_user_password,
+        change_password,
+        get_sessions,
+        revoke_session,
+        revoke_other_sessions
+    ),
     modifiers(&SecurityAddon),
     tags(
         (name = "users", description = "User management API")
@@ -190,7 +708,13 @@ pub struct ApiDoc;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(login_user),
+    paths(
+        login_user,
+        refresh_token,
+        logout,
+        request_password_reset,
+        confirm_password_reset
+    ),
     tags(
         (name = "auth", description = "Authentication API")
     )
@@ -0,0 +1,347 @@
+use lib::{
+    albums::{
+        application::commands::{
+            add_album_media::{AddAlbumMediaCommand, add_album_media_command_handler},
+            remove_album_media::{RemoveAlbumMediaCommand, remove_album_media_command_handler},
+            reorder_album_media::{ReorderAlbumMediaCommand, reorder_album_media_command_handler},
+            set_album_cover::{SetAlbumCoverCommand, set_album_cover_command_handler},
+        },
+        domain::{Album, AlbumError},
+    },
+    media::domain::{MediaFile, MediaStatus},
+    sharing::domain::{ShareRole, ShareTarget},
+};
+use uuid::Uuid;
+
+use crate::{
+    albums::MockAlbumRepository,
+    media::{MockMediaRepository, media_file},
+    sharing::{MockShareGrantRepository, share_grant, test_authorization_service},
+};
+
+fn album(user_id: Uuid) -> Album {
+    Album {
+        id: Uuid::new_v4(),
+        user_id,
+        name: "Holidays".to_string(),
+        description: None,
+        cover_media_id: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+/// Album of `user_id` that already holds three media files
+async fn album_with_media(user_id: Uuid) -> (MockAlbumRepository, Album, Vec<Uuid>) {
+    let album = album(user_id);
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let media_ids = vec![Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+    lib::albums::domain::AlbumRepository::add_album_media(&repo, album.id, media_ids.clone())
+        .await
+        .unwrap();
+    (repo, album, media_ids)
+}
+
+#[tokio::test]
+async fn test_add_album_media_appends_in_order() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, mut media_ids) = album_with_media(user_id).await;
+    let media = media_file(user_id);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    add_album_media_command_handler(
+        AddAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: vec![media.id, media.id],
+        },
+        &repo,
+        &media_repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await
+    .unwrap();
+
+    media_ids.push(media.id);
+    assert_eq!(repo.media_ids(album.id), media_ids);
+}
+
+#[tokio::test]
+async fn test_add_album_media_of_other_user_not_found() {
+    let user_id = Uuid::new_v4();
+    let album = album(user_id);
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let media = media_file(Uuid::new_v4());
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = add_album_media_command_handler(
+        AddAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: vec![media.id],
+        },
+        &repo,
+        &media_repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::MediaFileNotFound)));
+    assert!(repo.media_ids(album.id).is_empty());
+}
+
+#[tokio::test]
+async fn test_add_pending_media_not_found() {
+    let user_id = Uuid::new_v4();
+    let album = album(user_id);
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let media = MediaFile {
+        status: MediaStatus::Pending,
+        ..media_file(user_id)
+    };
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = add_album_media_command_handler(
+        AddAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: vec![media.id],
+        },
+        &repo,
+        &media_repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_add_media_to_album_of_other_user_not_found() {
+    let album = album(Uuid::new_v4());
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let user_id = Uuid::new_v4();
+    let media = media_file(user_id);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = add_album_media_command_handler(
+        AddAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: vec![media.id],
+        },
+        &repo,
+        &media_repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::AlbumNotFound)));
+}
+
+#[tokio::test]
+async fn test_contributor_adds_own_media_to_shared_album() {
+    let album = album(Uuid::new_v4());
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let user_id = Uuid::new_v4();
+    let media = media_file(user_id);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    let grants = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Album(album.id),
+        user_id,
+        ShareRole::Contributor,
+    )]);
+
+    add_album_media_command_handler(
+        AddAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: vec![media.id],
+        },
+        &repo,
+        &media_repo,
+        &test_authorization_service(grants),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(repo.media_ids(album.id), vec![media.id]);
+}
+
+#[tokio::test]
+async fn test_viewer_cannot_add_media_to_shared_album() {
+    let album = album(Uuid::new_v4());
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let user_id = Uuid::new_v4();
+    let media = media_file(user_id);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    let grants = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Album(album.id),
+        user_id,
+        ShareRole::Viewer,
+    )]);
+
+    let result = add_album_media_command_handler(
+        AddAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: vec![media.id],
+        },
+        &repo,
+        &media_repo,
+        &test_authorization_service(grants),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::AlbumNotFound)));
+    assert!(repo.media_ids(album.id).is_empty());
+}
+
+#[tokio::test]
+async fn test_reorder_album_media() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, media_ids) = album_with_media(user_id).await;
+    let new_order = vec![media_ids[2], media_ids[0], media_ids[1]];
+
+    reorder_album_media_command_handler(
+        ReorderAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_ids: new_order.clone(),
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(repo.media_ids(album.id), new_order);
+}
+
+#[tokio::test]
+async fn test_reorder_album_media_requires_every_media_once() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, media_ids) = album_with_media(user_id).await;
+
+    for media_ids in [
+        vec![media_ids[1], media_ids[0]],
+        vec![media_ids[1], media_ids[0], media_ids[0]],
+        vec![media_ids[2], media_ids[1], media_ids[0], Uuid::new_v4()],
+    ] {
+        let result = reorder_album_media_command_handler(
+            ReorderAlbumMediaCommand {
+                album_id: album.id,
+                user_id,
+                media_ids,
+            },
+            &repo,
+        )
+        .await;
+
+        assert!(matches!(result, Err(AlbumError::InvalidMediaOrder)));
+    }
+    assert_eq!(repo.media_ids(album.id), media_ids);
+}
+
+#[tokio::test]
+async fn test_set_album_cover() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, media_ids) = album_with_media(user_id).await;
+
+    let result = set_album_cover_command_handler(
+        SetAlbumCoverCommand {
+            album_id: album.id,
+            user_id,
+            media_id: Some(media_ids[1]),
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.cover_media_id, Some(media_ids[1]));
+}
+
+#[tokio::test]
+async fn test_set_album_cover_outside_album_not_found() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, _) = album_with_media(user_id).await;
+
+    let result = set_album_cover_command_handler(
+        SetAlbumCoverCommand {
+            album_id: album.id,
+            user_id,
+            media_id: Some(Uuid::new_v4()),
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::MediaFileNotFound)));
+    assert_eq!(repo.album(album.id).unwrap().cover_media_id, None);
+}
+
+#[tokio::test]
+async fn test_remove_cover_media_clears_cover() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, media_ids) = album_with_media(user_id).await;
+    set_album_cover_command_handler(
+        SetAlbumCoverCommand {
+            album_id: album.id,
+            user_id,
+            media_id: Some(media_ids[0]),
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    remove_album_media_command_handler(
+        RemoveAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_id: media_ids[0],
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(repo.media_ids(album.id), media_ids[1..].to_vec());
+    assert_eq!(repo.album(album.id).unwrap().cover_media_id, None);
+}
+
+#[tokio::test]
+async fn test_remove_media_not_in_album_not_found() {
+    let user_id = Uuid::new_v4();
+    let (repo, album, _) = album_with_media(user_id).await;
+
+    let result = remove_album_media_command_handler(
+        RemoveAlbumMediaCommand {
+            album_id: album.id,
+            user_id,
+            media_id: Uuid::new_v4(),
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::MediaFileNotFound)));
+}
@@ -0,0 +1,78 @@
+use lib::albums::{
+    application::commands::{
+        create_album::{CreateAlbumCommand, create_album_command_handler},
+        delete_album::{DeleteAlbumCommand, delete_album_command_handler},
+    },
+    domain::AlbumError,
+};
+use uuid::Uuid;
+
+use crate::albums::MockAlbumRepository;
+
+#[tokio::test]
+async fn test_create_album() {
+    let repo = MockAlbumRepository::default();
+    let user_id = Uuid::new_v4();
+
+    let result = create_album_command_handler(
+        CreateAlbumCommand {
+            user_id,
+            name: "Holidays".to_string(),
+            description: Some("Summer 2024".to_string()),
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.name, "Holidays");
+    assert_eq!(result.cover_media_id, None);
+    assert_eq!(repo.album(result.id).unwrap().user_id, user_id);
+}
+
+#[tokio::test]
+async fn test_create_album_repository_error() {
+    let repo = MockAlbumRepository {
+        fail_save: true,
+        ..MockAlbumRepository::default()
+    };
+
+    let result = create_album_command_handler(
+        CreateAlbumCommand {
+            user_id: Uuid::new_v4(),
+            name: "Holidays".to_string(),
+            description: None,
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::InternalServerError(_))));
+}
+
+#[tokio::test]
+async fn test_delete_album_of_other_user_not_found() {
+    let repo = MockAlbumRepository::default();
+    let album = create_album_command_handler(
+        CreateAlbumCommand {
+            user_id: Uuid::new_v4(),
+            name: "Holidays".to_string(),
+            description: None,
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    let result = delete_album_command_handler(
+        DeleteAlbumCommand {
+            album_id: album.id,
+            user_id: Uuid::new_v4(),
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::AlbumNotFound)));
+    assert!(repo.album(album.id).is_some());
+}
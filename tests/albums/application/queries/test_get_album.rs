@@ -0,0 +1,116 @@
+use lib::{
+    albums::{
+        application::queries::{
+            get_album::{GetAlbumQuery, get_album_query_handler},
+            get_albums::{GetAlbumsQuery, get_albums_query_handler},
+        },
+        domain::{Album, AlbumError, AlbumRepository},
+    },
+    sharing::domain::{ShareRole, ShareTarget},
+};
+use uuid::Uuid;
+
+use crate::{
+    albums::MockAlbumRepository,
+    sharing::{MockShareGrantRepository, share_grant, test_authorization_service},
+};
+
+fn album(user_id: Uuid, name: &str) -> Album {
+    Album {
+        id: Uuid::new_v4(),
+        user_id,
+        name: name.to_string(),
+        description: None,
+        cover_media_id: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[tokio::test]
+async fn test_get_albums_of_user() {
+    let user_id = Uuid::new_v4();
+    let repo = MockAlbumRepository::with_albums(vec![
+        album(user_id, "Holidays"),
+        album(Uuid::new_v4(), "Someone else's"),
+    ]);
+
+    let result = get_albums_query_handler(GetAlbumsQuery { user_id }, &repo)
+        .await
+        .unwrap();
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].name, "Holidays");
+}
+
+#[tokio::test]
+async fn test_get_album_with_media_in_order() {
+    let user_id = Uuid::new_v4();
+    let album = album(user_id, "Holidays");
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let media_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+    repo.add_album_media(album.id, media_ids.clone())
+        .await
+        .unwrap();
+    repo.reorder_album_media(album.id, vec![media_ids[1], media_ids[0]])
+        .await
+        .unwrap();
+
+    let result = get_album_query_handler(
+        GetAlbumQuery {
+            album_id: album.id,
+            user_id,
+        },
+        &repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.name, "Holidays");
+    let ids: Vec<Uuid> = result.media.iter().map(|media| media.media_id).collect();
+    assert_eq!(ids, vec![media_ids[1], media_ids[0]]);
+}
+
+#[tokio::test]
+async fn test_get_album_of_other_user_not_found() {
+    let album = album(Uuid::new_v4(), "Holidays");
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+
+    let result = get_album_query_handler(
+        GetAlbumQuery {
+            album_id: album.id,
+            user_id: Uuid::new_v4(),
+        },
+        &repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await;
+
+    assert!(matches!(result, Err(AlbumError::AlbumNotFound)));
+}
+
+#[tokio::test]
+async fn test_get_album_shared_with_user() {
+    let album = album(Uuid::new_v4(), "Holidays");
+    let repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let user_id = Uuid::new_v4();
+    let grants = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Album(album.id),
+        user_id,
+        ShareRole::Viewer,
+    )]);
+
+    let result = get_album_query_handler(
+        GetAlbumQuery {
+            album_id: album.id,
+            user_id,
+        },
+        &repo,
+        &test_authorization_service(grants),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.id, album.id);
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use crate::{
+    albums::MockAlbumRepository,
+    media::{MockMediaRepository, TestTokenService, get_test_user_id},
+    utils::test_helpers::*,
+};
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use lib::{
+    api::{http_server::AppState, routes::api_routes},
+    media::domain::{MediaFile, MediaStatus},
+};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+fn test_app(state: AppState) -> Router<AppState> {
+    api_routes(state)
+}
+
+async fn send(
+    state: &AppState,
+    method: &str,
+    uri: String,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let app = test_app(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", "Bearer valid_token")
+        .header("Content-Type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+fn test_media(user_id: Uuid) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "image.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 1024,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/image.jpg", user_id),
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+    }
+}
+
+#[tokio::test]
+async fn test_album_lifecycle() {
+    let media = test_media(get_test_user_id());
+    let album_repo = MockAlbumRepository::default();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        }),
+        album_repo: Some(album_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(
+        &state,
+        "POST",
+        "/albums".to_string(),
+        Some(serde_json::json!({ "name": "Holidays" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let album_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        format!("/albums/{}/media", album_id),
+        Some(serde_json::json!({ "media_ids": [media.id] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, json) = send(
+        &state,
+        "PUT",
+        format!("/albums/{}/cover", album_id),
+        Some(serde_json::json!({ "media_id": media.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["cover_media_id"], media.id.to_string());
+
+    let (status, json) = send(&state, "GET", format!("/albums/{}", album_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["name"], "Holidays");
+    assert_eq!(json["data"]["media"][0]["media_id"], media.id.to_string());
+
+    let (status, json) = send(&state, "GET", "/albums".to_string(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+
+    let (status, _) = send(&state, "DELETE", format!("/albums/{}", album_id), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(album_repo.albums.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_create_album_empty_name() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        "/albums".to_string(),
+        Some(serde_json::json!({ "name": "" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_album_not_found() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, _) = send(&state, "GET", format!("/albums/{}", Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&state, "GET", "/albums/not-a-uuid".to_string(), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_albums_unauthorized() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/albums")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
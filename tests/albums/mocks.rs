@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use lib::{
+    albums::domain::{Album, AlbumId, AlbumMedia, AlbumRepository, AlbumRepositoryError, NewAlbum},
+    media::domain::{MediaFile, MediaId, MediaStatus},
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct MockAlbumRepository {
+    pub fail_save: bool,
+    pub albums: Arc<Mutex<Vec<Album>>>,
+    pub album_media: Arc<Mutex<Vec<(AlbumId, AlbumMedia)>>>,
+}
+
+impl MockAlbumRepository {
+    pub fn with_albums(albums: Vec<Album>) -> Self {
+        MockAlbumRepository {
+            albums: Arc::new(Mutex::new(albums)),
+            ..MockAlbumRepository::default()
+        }
+    }
+
+    pub fn album(&self, id: AlbumId) -> Option<Album> {
+        self.albums
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|album| album.id == id)
+            .cloned()
+    }
+
+    /// Media IDs of the album in display order
+    pub fn media_ids(&self, id: AlbumId) -> Vec<MediaId> {
+        let mut album_media: Vec<AlbumMedia> = self
+            .album_media
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(album_id, _)| *album_id == id)
+            .map(|(_, album_media)| album_media.clone())
+            .collect();
+        album_media.sort_by_key(|album_media| album_media.position);
+        album_media
+            .into_iter()
+            .map(|album_media| album_media.media_file.id)
+            .collect()
+    }
+}
+
+/// Media file as stored by the mock, the album repository only needs its ID
+fn media_file(id: MediaId) -> MediaFile {
+    MediaFile {
+        id,
+        user_id: Uuid::nil(),
+        filename: format!("{}.jpg", id),
+        original_filename: format!("{}.jpg", id),
+        file_size: 1024,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}.jpg", id),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[async_trait]
+impl AlbumRepository for MockAlbumRepository {
+    async fn create_album(&self, album: NewAlbum) -> Result<Album, AlbumRepositoryError> {
+        if self.fail_save {
+            return Err(AlbumRepositoryError::InternalServerError);
+        }
+        let album = Album {
+            id: Uuid::new_v4(),
+            user_id: album.user_id,
+            name: album.name,
+            description: album.description,
+            cover_media_id: None,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            updated_at: Some(chrono::Utc::now().naive_utc()),
+        };
+        self.albums.lock().unwrap().push(album.clone());
+        Ok(album)
+    }
+
+    async fn get_album_by_id(&self, id: AlbumId) -> Result<Option<Album>, AlbumRepositoryError> {
+        Ok(self.album(id))
+    }
+
+    async fn get_albums_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<Album>, AlbumRepositoryError> {
+        Ok(self
+            .albums
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|album| album.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_album(&self, id: AlbumId) -> Result<(), AlbumRepositoryError> {
+        if self.fail_save {
+            return Err(AlbumRepositoryError::InternalServerError);
+        }
+        self.albums.lock().unwrap().retain(|album| album.id != id);
+        self.album_media
+            .lock()
+            .unwrap()
+            .retain(|(album_id, _)| *album_id != id);
+        Ok(())
+    }
+
+    async fn set_album_cover(
+        &self,
+        id: AlbumId,
+        cover_media_id: Option<MediaId>,
+    ) -> Result<Album, AlbumRepositoryError> {
+        let mut albums = self.albums.lock().unwrap();
+        let album = albums
+            .iter_mut()
+            .find(|album| album.id == id)
+            .ok_or(AlbumRepositoryError::AlbumNotFound)?;
+        album.cover_media_id = cover_media_id;
+        Ok(album.clone())
+    }
+
+    async fn get_album_media(&self, id: AlbumId) -> Result<Vec<AlbumMedia>, AlbumRepositoryError> {
+        let mut album_media: Vec<AlbumMedia> = self
+            .album_media
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(album_id, _)| *album_id == id)
+            .map(|(_, album_media)| album_media.clone())
+            .collect();
+        album_media.sort_by_key(|album_media| album_media.position);
+        Ok(album_media)
+    }
+
+    async fn add_album_media(
+        &self,
+        id: AlbumId,
+        media_ids: Vec<MediaId>,
+    ) -> Result<(), AlbumRepositoryError> {
+        if self.fail_save {
+            return Err(AlbumRepositoryError::InternalServerError);
+        }
+        let mut album_media = self.album_media.lock().unwrap();
+        let mut next_position = album_media
+            .iter()
+            .filter(|(album_id, _)| *album_id == id)
+            .map(|(_, album_media)| album_media.position + 1)
+            .max()
+            .unwrap_or(0);
+        for media_id in media_ids {
+            if album_media
+                .iter()
+                .any(|(album_id, existing)| *album_id == id && existing.media_file.id == media_id)
+            {
+                continue;
+            }
+            album_media.push((
+                id,
+                AlbumMedia {
+                    media_file: media_file(media_id),
+                    position: next_position,
+                    added_at: Some(chrono::Utc::now().naive_utc()),
+                },
+            ));
+            next_position += 1;
+        }
+        Ok(())
+    }
+
+    async fn remove_album_media(
+        &self,
+        id: AlbumId,
+        media_id: MediaId,
+    ) -> Result<(), AlbumRepositoryError> {
+        let mut album_media = self.album_media.lock().unwrap();
+        let count = album_media.len();
+        album_media.retain(|(album_id, existing)| {
+            !(*album_id == id && existing.media_file.id == media_id)
+        });
+        if album_media.len() == count {
+            return Err(AlbumRepositoryError::MediaFileNotFound);
+        }
+        let mut albums = self.albums.lock().unwrap();
+        if let Some(album) = albums
+            .iter_mut()
+            .find(|album| album.id == id && album.cover_media_id == Some(media_id))
+        {
+            album.cover_media_id = None;
+        }
+        Ok(())
+    }
+
+    async fn reorder_album_media(
+        &self,
+        id: AlbumId,
+        media_ids: Vec<MediaId>,
+    ) -> Result<(), AlbumRepositoryError> {
+        let mut album_media = self.album_media.lock().unwrap();
+        for (album_id, existing) in album_media.iter_mut() {
+            if *album_id != id {
+                continue;
+            }
+            if let Some(position) = media_ids
+                .iter()
+                .position(|media_id| *media_id == existing.media_file.id)
+            {
+                existing.position = position as i32;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use lib::jobs::{
+    application::commands::process_job::{ProcessJobCommand, process_job_command_handler},
+    domain::{Job, JobError, JobRepository, JobRepositoryError, JobRetryPolicy, JobStatus},
+};
+use uuid::Uuid;
+
+use crate::jobs::{MockJobHandler, MockJobRepository, job};
+
+const RETRY_POLICY: JobRetryPolicy = JobRetryPolicy {
+    base_delay_seconds: 30,
+    max_delay_seconds: 3600,
+};
+
+const WORKER_ID: Uuid = Uuid::from_u128(1);
+
+/// Enqueues a pending job and claims it like a worker would
+async fn claimed_job(repo: &MockJobRepository, attempts: i32) -> Job {
+    let mut pending = job(Some(Uuid::new_v4()), JobStatus::Pending);
+    pending.attempts = attempts - 1;
+    repo.jobs.lock().unwrap().push(pending);
+
+    repo.claim_jobs(WORKER_ID, 1, chrono::Duration::minutes(15))
+        .await
+        .unwrap()
+        .remove(0)
+}
+
+#[tokio::test]
+async fn test_process_job_success() {
+    let repo = MockJobRepository::default();
+    let handler = MockJobHandler::default();
+    let claimed = claimed_job(&repo, 1).await;
+
+    let status = process_job_command_handler(
+        ProcessJobCommand {
+            job: claimed.clone(),
+            worker_id: WORKER_ID,
+        },
+        &repo,
+        &handler,
+        &RETRY_POLICY,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(status, JobStatus::Succeeded);
+    assert_eq!(handler.runs(), 1);
+    let stored = repo.job(claimed.id).unwrap();
+    assert_eq!(stored.status, JobStatus::Succeeded);
+    assert!(stored.completed_at.is_some());
+    assert!(stored.locked_by.is_none());
+}
+
+#[tokio::test]
+async fn test_process_job_retryable_error_backs_off() {
+    let repo = MockJobRepository::default();
+    let handler = MockJobHandler::failing(JobError::Retryable);
+    let claimed = claimed_job(&repo, 2).await;
+
+    let before = chrono::Utc::now().naive_utc();
+    let status = process_job_command_handler(
+        ProcessJobCommand {
+            job: claimed.clone(),
+            worker_id: WORKER_ID,
+        },
+        &repo,
+        &handler,
+        &RETRY_POLICY,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(status, JobStatus::Pending);
+    let stored = repo.job(claimed.id).unwrap();
+    assert_eq!(stored.status, JobStatus::Pending);
+    assert_eq!(stored.last_error.as_deref(), Some("Mock job failure"));
+    // Second attempt failed, the third one waits twice the base delay
+    assert!(stored.run_at >= before + chrono::Duration::seconds(60));
+    assert!(stored.run_at < before + chrono::Duration::seconds(120));
+}
+
+#[tokio::test]
+async fn test_process_job_dead_lettered_after_last_attempt() {
+    let repo = MockJobRepository::default();
+    let handler = MockJobHandler::failing(JobError::Retryable);
+    let claimed = claimed_job(&repo, 3).await;
+    assert_eq!(claimed.attempts, claimed.max_attempts);
+
+    let status = process_job_command_handler(
+        ProcessJobCommand {
+            job: claimed.clone(),
+            worker_id: WORKER_ID,
+        },
+        &repo,
+        &handler,
+        &RETRY_POLICY,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(status, JobStatus::Dead);
+    assert_eq!(repo.job(claimed.id).unwrap().status, JobStatus::Dead);
+}
+
+#[tokio::test]
+async fn test_process_job_permanent_error_is_not_retried() {
+    let repo = MockJobRepository::default();
+    let handler = MockJobHandler::failing(JobError::Permanent);
+    let claimed = claimed_job(&repo, 1).await;
+
+    let status = process_job_command_handler(
+        ProcessJobCommand {
+            job: claimed.clone(),
+            worker_id: WORKER_ID,
+        },
+        &repo,
+        &handler,
+        &RETRY_POLICY,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(status, JobStatus::Dead);
+    assert_eq!(repo.job(claimed.id).unwrap().status, JobStatus::Dead);
+}
+
+#[tokio::test]
+async fn test_process_job_taken_over_without_attempts_left_is_not_run() {
+    let repo = MockJobRepository::default();
+    let handler = MockJobHandler::default();
+    let claimed = claimed_job(&repo, 4).await;
+
+    let status = process_job_command_handler(
+        ProcessJobCommand {
+            job: claimed.clone(),
+            worker_id: WORKER_ID,
+        },
+        &repo,
+        &handler,
+        &RETRY_POLICY,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(status, JobStatus::Dead);
+    assert_eq!(handler.runs(), 0);
+}
+
+#[tokio::test]
+async fn test_process_job_taken_over_by_another_worker_keeps_its_outcome() {
+    let repo = MockJobRepository::default();
+    let handler = MockJobHandler::failing(JobError::Permanent);
+    let claimed = claimed_job(&repo, 1).await;
+    // The lock timed out and another worker claimed the job again
+    let other_worker_id = Uuid::new_v4();
+    repo.jobs.lock().unwrap()[0].locked_by = Some(other_worker_id);
+
+    let result = process_job_command_handler(
+        ProcessJobCommand {
+            job: claimed.clone(),
+            worker_id: WORKER_ID,
+        },
+        &repo,
+        &handler,
+        &RETRY_POLICY,
+    )
+    .await;
+
+    assert!(matches!(result, Err(JobRepositoryError::LockLost)));
+    let stored = repo.job(claimed.id).unwrap();
+    assert_eq!(stored.status, JobStatus::Running);
+    assert_eq!(stored.locked_by, Some(other_worker_id));
+    assert!(stored.last_error.is_none());
+}
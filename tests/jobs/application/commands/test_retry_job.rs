@@ -0,0 +1,48 @@
+use lib::jobs::{
+    application::commands::retry_job::{RetryJobCommand, retry_job_command_handler},
+    domain::{JobQueueError, JobStatus},
+};
+use uuid::Uuid;
+
+use crate::jobs::{MockJobRepository, job};
+
+#[tokio::test]
+async fn test_retry_dead_job() {
+    let mut dead = job(None, JobStatus::Dead);
+    dead.attempts = 3;
+    dead.last_error = Some("Storage unavailable".to_string());
+    let repo = MockJobRepository::with_jobs(vec![dead.clone()]);
+
+    let result = retry_job_command_handler(RetryJobCommand { job_id: dead.id }, &repo)
+        .await
+        .unwrap();
+
+    assert_eq!(result.status, JobStatus::Pending);
+    assert_eq!(result.attempts, 0);
+    assert_eq!(repo.job(dead.id).unwrap().status, JobStatus::Pending);
+}
+
+#[tokio::test]
+async fn test_retry_job_that_is_not_dead_conflict() {
+    let pending = job(None, JobStatus::Pending);
+    let repo = MockJobRepository::with_jobs(vec![pending.clone()]);
+
+    let result = retry_job_command_handler(RetryJobCommand { job_id: pending.id }, &repo).await;
+
+    assert!(matches!(result, Err(JobQueueError::JobNotDead)));
+}
+
+#[tokio::test]
+async fn test_retry_unknown_job_not_found() {
+    let repo = MockJobRepository::default();
+
+    let result = retry_job_command_handler(
+        RetryJobCommand {
+            job_id: Uuid::new_v4(),
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(JobQueueError::JobNotFound)));
+}
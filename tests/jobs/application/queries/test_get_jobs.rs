@@ -0,0 +1,88 @@
+use lib::jobs::{
+    application::queries::{
+        get_job::{GetJobQuery, get_job_query_handler},
+        get_jobs::{GetJobsQuery, get_jobs_query_handler},
+    },
+    domain::{JobQueueError, JobStatus},
+};
+use uuid::Uuid;
+
+use crate::jobs::{MockJobRepository, job};
+
+#[tokio::test]
+async fn test_get_jobs_only_returns_own_jobs() {
+    let user_id = Uuid::new_v4();
+    let own = job(Some(user_id), JobStatus::Pending);
+    let other = job(Some(Uuid::new_v4()), JobStatus::Pending);
+    let repo = MockJobRepository::with_jobs(vec![own.clone(), other]);
+
+    let result = get_jobs_query_handler(
+        GetJobsQuery {
+            user_id,
+            is_admin: false,
+            status: None,
+            limit: None,
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].id, own.id);
+    assert_eq!(result[0].job_type, "generate_thumbnail");
+}
+
+#[tokio::test]
+async fn test_get_jobs_admin_filters_by_status() {
+    let dead = job(Some(Uuid::new_v4()), JobStatus::Dead);
+    let repo = MockJobRepository::with_jobs(vec![
+        job(None, JobStatus::Succeeded),
+        dead.clone(),
+        job(Some(Uuid::new_v4()), JobStatus::Pending),
+    ]);
+
+    let result = get_jobs_query_handler(
+        GetJobsQuery {
+            user_id: Uuid::new_v4(),
+            is_admin: true,
+            status: Some(JobStatus::Dead),
+            limit: None,
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].id, dead.id);
+}
+
+#[tokio::test]
+async fn test_get_job_of_other_user_not_found() {
+    let other = job(Some(Uuid::new_v4()), JobStatus::Pending);
+    let repo = MockJobRepository::with_jobs(vec![other.clone()]);
+
+    let result = get_job_query_handler(
+        GetJobQuery {
+            job_id: other.id,
+            user_id: Uuid::new_v4(),
+            is_admin: false,
+        },
+        &repo,
+    )
+    .await;
+    assert!(matches!(result, Err(JobQueueError::JobNotFound)));
+
+    let result = get_job_query_handler(
+        GetJobQuery {
+            job_id: other.id,
+            user_id: Uuid::new_v4(),
+            is_admin: true,
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.id, other.id);
+}
@@ -0,0 +1,42 @@
+use lib::jobs::domain::{JobPayload, JobRetryPolicy};
+use uuid::Uuid;
+
+#[test]
+fn test_retry_delay_doubles_after_each_attempt() {
+    let policy = JobRetryPolicy {
+        base_delay_seconds: 10,
+        max_delay_seconds: 3600,
+    };
+
+    assert_eq!(policy.retry_delay(1), chrono::Duration::seconds(10));
+    assert_eq!(policy.retry_delay(2), chrono::Duration::seconds(20));
+    assert_eq!(policy.retry_delay(4), chrono::Duration::seconds(80));
+}
+
+#[test]
+fn test_retry_delay_is_capped() {
+    let policy = JobRetryPolicy {
+        base_delay_seconds: 10,
+        max_delay_seconds: 60,
+    };
+
+    assert_eq!(policy.retry_delay(4), chrono::Duration::seconds(60));
+    assert_eq!(policy.retry_delay(1000), chrono::Duration::seconds(60));
+}
+
+#[test]
+fn test_job_payload_json() {
+    let media_id = Uuid::new_v4();
+    let payload = JobPayload::GenerateThumbnail { media_id };
+
+    let value = serde_json::to_value(&payload).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({ "type": "generate_thumbnail", "media_id": media_id })
+    );
+    assert_eq!(payload.job_type(), "generate_thumbnail");
+    assert_eq!(
+        serde_json::from_value::<JobPayload>(value).unwrap(),
+        payload
+    );
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use crate::{
+    jobs::{MockJobRepository, job},
+    media::{TestTokenService, get_test_user_id},
+    utils::test_helpers::*,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use lib::{
+    api::{http_server::AppState, routes::api_routes},
+    jobs::domain::JobStatus,
+};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn send(state: &AppState, method: &str, uri: String) -> (StatusCode, serde_json::Value) {
+    let app = api_routes(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_get_jobs_of_user() {
+    let own = job(Some(get_test_user_id()), JobStatus::Running);
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        job_repo: Some(MockJobRepository::with_jobs(vec![
+            own.clone(),
+            job(Some(Uuid::new_v4()), JobStatus::Running),
+        ])),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(&state, "GET", "/jobs".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"][0]["id"], own.id.to_string());
+    assert_eq!(json["data"][0]["status"], "running");
+    assert_eq!(json["data"][0]["payload"]["type"], "generate_thumbnail");
+
+    let (status, json) = send(&state, "GET", format!("/jobs/{}", own.id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["job_type"], "generate_thumbnail");
+}
+
+#[tokio::test]
+async fn test_get_job_of_other_user_not_found() {
+    let other = job(Some(Uuid::new_v4()), JobStatus::Pending);
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        job_repo: Some(MockJobRepository::with_jobs(vec![other.clone()])),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, _) = send(&state, "GET", format!("/jobs/{}", other.id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&state, "GET", "/jobs/not-a-uuid".to_string()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_retry_job_requires_admin() {
+    let dead = job(Some(get_test_user_id()), JobStatus::Dead);
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        job_repo: Some(MockJobRepository::with_jobs(vec![dead.clone()])),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, _) = send(&state, "POST", format!("/jobs/{}/retry", dead.id)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_retry_job_as_admin() {
+    let dead = job(None, JobStatus::Dead);
+    let pending = job(None, JobStatus::Pending);
+    let job_repo = MockJobRepository::with_jobs(vec![dead.clone(), pending.clone()]);
+    // The default token service authenticates an admin
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        job_repo: Some(job_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(&state, "POST", format!("/jobs/{}/retry", dead.id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["status"], "pending");
+    assert_eq!(job_repo.job(dead.id).unwrap().status, JobStatus::Pending);
+
+    let (status, _) = send(&state, "POST", format!("/jobs/{}/retry", pending.id)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Admins see the jobs of every user
+    let (status, json) = send(&state, "GET", "/jobs".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use lib::jobs::domain::{
+    Job, JobError, JobHandler, JobId, JobPayload, JobRepository, JobRepositoryError, JobStatus,
+    NewJob,
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct MockJobRepository {
+    pub fail: bool,
+    pub jobs: Arc<Mutex<Vec<Job>>>,
+}
+
+impl MockJobRepository {
+    pub fn with_jobs(jobs: Vec<Job>) -> Self {
+        MockJobRepository {
+            jobs: Arc::new(Mutex::new(jobs)),
+            ..MockJobRepository::default()
+        }
+    }
+
+    pub fn job(&self, id: JobId) -> Option<Job> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+    }
+
+    fn update_job(
+        &self,
+        id: JobId,
+        update: impl FnOnce(&mut Job),
+    ) -> Result<Job, JobRepositoryError> {
+        if self.fail {
+            return Err(JobRepositoryError::InternalServerError);
+        }
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .iter_mut()
+            .find(|job| job.id == id)
+            .ok_or(JobRepositoryError::JobNotFound)?;
+        update(job);
+        job.updated_at = Some(chrono::Utc::now().naive_utc());
+        Ok(job.clone())
+    }
+
+    /// Updates the job only while `worker_id` still runs it, like the database does
+    fn update_locked_job(
+        &self,
+        id: JobId,
+        worker_id: Uuid,
+        update: impl FnOnce(&mut Job),
+    ) -> Result<Job, JobRepositoryError> {
+        let owned = self.job(id).is_some_and(|job| {
+            job.status == JobStatus::Running && job.locked_by == Some(worker_id)
+        });
+        if !self.fail && !owned {
+            return Err(JobRepositoryError::LockLost);
+        }
+        self.update_job(id, update)
+    }
+}
+
+pub fn job(user_id: Option<Uuid>, status: JobStatus) -> Job {
+    Job {
+        id: Uuid::new_v4(),
+        user_id,
+        payload: JobPayload::GenerateThumbnail {
+            media_id: Uuid::new_v4(),
+        },
+        status,
+        attempts: 0,
+        max_attempts: 3,
+        run_at: chrono::Utc::now().naive_utc(),
+        locked_at: None,
+        locked_by: None,
+        last_error: None,
+        completed_at: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[async_trait]
+impl JobRepository for MockJobRepository {
+    async fn enqueue_job(&self, new_job: NewJob) -> Result<Job, JobRepositoryError> {
+        if self.fail {
+            return Err(JobRepositoryError::InternalServerError);
+        }
+        let created = Job {
+            payload: new_job.payload,
+            max_attempts: new_job.max_attempts,
+            ..job(new_job.user_id, JobStatus::Pending)
+        };
+        self.jobs.lock().unwrap().push(created.clone());
+        Ok(created)
+    }
+
+    async fn get_job_by_id(&self, id: JobId) -> Result<Option<Job>, JobRepositoryError> {
+        if self.fail {
+            return Err(JobRepositoryError::InternalServerError);
+        }
+        Ok(self.job(id))
+    }
+
+    async fn get_jobs(
+        &self,
+        user_id: Option<Uuid>,
+        status: Option<JobStatus>,
+        limit: i64,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        if self.fail {
+            return Err(JobRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|job| user_id.is_none() || job.user_id == user_id)
+            .filter(|job| status.is_none_or(|status| job.status == status))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn claim_jobs(
+        &self,
+        worker_id: Uuid,
+        limit: i64,
+        _lock_timeout: chrono::Duration,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        if self.fail {
+            return Err(JobRepositoryError::InternalServerError);
+        }
+        let now = chrono::Utc::now().naive_utc();
+        let mut jobs = self.jobs.lock().unwrap();
+        Ok(jobs
+            .iter_mut()
+            .filter(|job| job.status == JobStatus::Pending && job.run_at <= now)
+            .take(limit as usize)
+            .map(|job| {
+                job.status = JobStatus::Running;
+                job.locked_at = Some(now);
+                job.locked_by = Some(worker_id);
+                job.attempts += 1;
+                job.clone()
+            })
+            .collect())
+    }
+
+    async fn complete_job(&self, id: JobId, worker_id: Uuid) -> Result<(), JobRepositoryError> {
+        self.update_locked_job(id, worker_id, |job| {
+            job.status = JobStatus::Succeeded;
+            job.locked_at = None;
+            job.locked_by = None;
+            job.completed_at = Some(chrono::Utc::now().naive_utc());
+        })?;
+        Ok(())
+    }
+
+    async fn fail_job(
+        &self,
+        id: JobId,
+        worker_id: Uuid,
+        error: String,
+        retry_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), JobRepositoryError> {
+        self.update_locked_job(id, worker_id, |job| {
+            job.locked_at = None;
+            job.locked_by = None;
+            job.last_error = Some(error);
+            match retry_at {
+                Some(retry_at) => {
+                    job.status = JobStatus::Pending;
+                    job.run_at = retry_at;
+                }
+                None => job.status = JobStatus::Dead,
+            }
+        })?;
+        Ok(())
+    }
+
+    async fn requeue_job(&self, id: JobId) -> Result<Job, JobRepositoryError> {
+        self.update_job(id, |job| {
+            job.status = JobStatus::Pending;
+            job.attempts = 0;
+            job.run_at = chrono::Utc::now().naive_utc();
+            job.locked_at = None;
+            job.locked_by = None;
+            job.completed_at = None;
+        })
+    }
+}
+
+/// Answers every job with the configured outcome and counts the runs
+#[derive(Clone, Default)]
+pub struct MockJobHandler {
+    /// `None` succeeds
+    pub error: Option<fn(String) -> JobError>,
+    pub runs: Arc<Mutex<usize>>,
+}
+
+impl MockJobHandler {
+    pub fn failing(error: fn(String) -> JobError) -> Self {
+        MockJobHandler {
+            error: Some(error),
+            ..MockJobHandler::default()
+        }
+    }
+
+    pub fn runs(&self) -> usize {
+        *self.runs.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl JobHandler for MockJobHandler {
+    async fn handle(&self, _job: &Job) -> Result<(), JobError> {
+        *self.runs.lock().unwrap() += 1;
+        match self.error {
+            Some(error) => Err(error("Mock job failure".to_string())),
+            None => Ok(()),
+        }
+    }
+}
@@ -4,7 +4,7 @@ mod media_delete_tests {
     use crate::media::{MockMediaRepository, MockStorageService};
     use lib::media::{
         application::commands::delete_media::{DeleteMediaCommand, delete_media_command_handler},
-        domain::{MediaDeleteError, MediaFile},
+        domain::{MediaDeleteError, MediaFile, MediaStatus},
     };
     use uuid::Uuid;
 
@@ -23,7 +23,9 @@ mod media_delete_tests {
             file_path: format!("media/{}/test.jpg", user_id),
             uploaded_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: None,
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
         };
 
         let mock_repo = MockMediaRepository {
@@ -80,7 +82,9 @@ mod media_delete_tests {
             file_path: format!("media/{}/test.jpg", other_user_id),
             uploaded_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: None,
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
         };
 
         let mock_repo = MockMediaRepository {
@@ -118,7 +122,9 @@ mod media_delete_tests {
             file_path: format!("media/{}/test.jpg", user_id),
             uploaded_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: None,
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
         };
 
         let mock_repo = MockMediaRepository {
@@ -154,7 +160,9 @@ mod media_delete_tests {
             file_path: format!("media/{}/test.jpg", user_id),
             uploaded_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: None,
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
         };
 
         let mock_repo = MockMediaRepository {
@@ -0,0 +1,105 @@
+use lib::media::{
+    application::commands::generate_thumbnail::{
+        GenerateThumbnailCommand, GenerateThumbnailError, generate_thumbnail_command_handler,
+    },
+    domain::{MediaFile, MediaStatus, ThumbnailError},
+};
+use uuid::Uuid;
+
+use crate::media::{
+    MockMediaRepository, MockMetadataService, MockStorageService, MockThumbnailService,
+};
+
+fn media_file(status: MediaStatus) -> MediaFile {
+    let user_id = Uuid::new_v4();
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "image.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 4,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/image.jpg", user_id),
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+        status,
+        checksum: None,
+        detected_content_type: None,
+    }
+}
+
+async fn generate(
+    media_repo: &MockMediaRepository,
+    thumbnail_service: &MockThumbnailService,
+) -> Result<(), GenerateThumbnailError> {
+    let storage_service = MockStorageService {
+        file_data: vec![1, 2, 3, 4],
+        ..MockStorageService::default()
+    };
+
+    generate_thumbnail_command_handler(
+        GenerateThumbnailCommand {
+            media_id: Uuid::new_v4(),
+        },
+        media_repo,
+        &storage_service,
+        thumbnail_service,
+        &MockMetadataService,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_generate_thumbnail_success() {
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media_file(MediaStatus::Ready)),
+        ..MockMediaRepository::default()
+    };
+
+    let result = generate(&media_repo, &MockThumbnailService::default()).await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_generate_thumbnail_deleted_or_pending_media_not_found() {
+    let result = generate(
+        &MockMediaRepository::default(),
+        &MockThumbnailService::default(),
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(GenerateThumbnailError::MediaFileNotFound)
+    ));
+
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media_file(MediaStatus::Pending)),
+        ..MockMediaRepository::default()
+    };
+    let result = generate(&media_repo, &MockThumbnailService::default()).await;
+    assert!(matches!(
+        result,
+        Err(GenerateThumbnailError::MediaFileNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_generate_thumbnail_failure() {
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media_file(MediaStatus::Ready)),
+        ..MockMediaRepository::default()
+    };
+    let thumbnail_service = MockThumbnailService {
+        fail_generate: true,
+    };
+
+    let result = generate(&media_repo, &thumbnail_service).await;
+
+    assert!(matches!(
+        result,
+        Err(GenerateThumbnailError::ThumbnailError(
+            ThumbnailError::ImageProcessingError(_)
+        ))
+    ));
+}
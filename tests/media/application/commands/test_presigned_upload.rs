@@ -0,0 +1,584 @@
+use crate::{
+    media::{MockMediaRepository, MockStorageService},
+    users::MockUserRepository,
+};
+use lib::media::{
+    application::commands::{
+        cleanup_expired_pending_uploads::cleanup_expired_pending_uploads_command_handler,
+        confirm_upload::{ConfirmUploadCommand, confirm_upload_command_handler},
+        request_upload::{RequestUploadCommand, request_upload_command_handler},
+    },
+    domain::{
+        DEFAULT_MAX_UPLOAD_BYTES, MediaConfirmError, MediaFile, MediaSizeLimits, MediaStatus,
+        MediaTypeAllowlist, MediaUploadError, PENDING_MEDIA_FILE_TTL_SECONDS, StoredFileMetadata,
+    },
+};
+use lib::users::domain::{QuotaConfig, Role, StorageQuota, User};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Repository knowing the uploading user, who has the quota of the config
+fn user_repository(user_id: Uuid) -> MockUserRepository {
+    MockUserRepository {
+        user: Some(User {
+            id: user_id,
+            username: "uploader".to_string(),
+            email: None,
+            password: "hashed".to_string(),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            must_change_password: false,
+            created_at: None,
+            updated_at: None,
+        }),
+        ..MockUserRepository::default()
+    }
+}
+
+fn quota_config(max_bytes: i64) -> QuotaConfig {
+    QuotaConfig {
+        default_quota: StorageQuota {
+            max_bytes,
+            max_items: 10,
+        },
+    }
+}
+
+fn pending_media(user_id: Uuid) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "upload.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 1024,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/upload.jpg", user_id),
+        status: MediaStatus::Pending,
+        checksum: None,
+        detected_content_type: None,
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+/// Pending media file whose presigned upload was abandoned
+fn expired_pending_media(user_id: Uuid) -> MediaFile {
+    MediaFile {
+        uploaded_at: Some(
+            (chrono::Utc::now()
+                - chrono::Duration::seconds(PENDING_MEDIA_FILE_TTL_SECONDS as i64 + 60))
+            .naive_utc(),
+        ),
+        ..pending_media(user_id)
+    }
+}
+
+/// Storage holding a JPEG image of `file_size` bytes, uploaded as `content_type`
+fn stored(file_size: u64, content_type: &str) -> MockStorageService {
+    stored_content(b"\xFF\xD8\xFF\xE0fake image data", file_size, content_type)
+}
+
+/// Storage holding a file of `file_size` bytes that starts with `head`
+fn stored_content(head: &[u8], file_size: u64, content_type: &str) -> MockStorageService {
+    MockStorageService {
+        file_data: head
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(0))
+            .take(file_size as usize)
+            .collect(),
+        file_metadata: Some(StoredFileMetadata {
+            file_size,
+            content_type: Some(content_type.to_string()),
+        }),
+        ..MockStorageService::default()
+    }
+}
+
+#[tokio::test]
+async fn test_request_upload_returns_presigned_url() {
+    let user_id = Uuid::new_v4();
+
+    let result = request_upload_command_handler(
+        &MockMediaRepository::default(),
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        RequestUploadCommand {
+            user_id,
+            filename: "upload.jpg".to_string(),
+            original_filename: "photo.jpg".to_string(),
+            file_size: 1024,
+            content_type: "image/jpeg".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.method, "PUT");
+    assert_eq!(result.content_type, "image/jpeg");
+    assert!(
+        result
+            .upload_url
+            .contains(&format!("media/{}/upload.jpg", user_id))
+    );
+}
+
+#[tokio::test]
+async fn test_request_upload_invalid_file_type() {
+    let user_id = Uuid::new_v4();
+    let result = request_upload_command_handler(
+        &MockMediaRepository::default(),
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        RequestUploadCommand {
+            user_id,
+            filename: "upload.txt".to_string(),
+            original_filename: "notes.txt".to_string(),
+            file_size: 10,
+            content_type: "text/plain".to_string(),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaUploadError::InvalidFileType)));
+}
+
+#[tokio::test]
+async fn test_request_upload_file_too_large() {
+    let user_id = Uuid::new_v4();
+    let result = request_upload_command_handler(
+        &MockMediaRepository::default(),
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        RequestUploadCommand {
+            user_id,
+            filename: "upload.jpg".to_string(),
+            original_filename: "photo.jpg".to_string(),
+            file_size: DEFAULT_MAX_UPLOAD_BYTES + 1,
+            content_type: "image/jpeg".to_string(),
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(MediaUploadError::FileTooLarge {
+            max_bytes: DEFAULT_MAX_UPLOAD_BYTES
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_confirm_upload_marks_media_ready() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let storage = stored(1024, "image/jpeg");
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &storage,
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.id, media.id);
+    assert_eq!(result.file_size, 1024);
+    assert_eq!(result.detected_content_type.as_deref(), Some("image/jpeg"));
+    assert_eq!(
+        result.checksum,
+        Some(hex::encode(Sha256::digest(&storage.file_data)))
+    );
+    assert!(!result.duplicate);
+}
+
+#[tokio::test]
+async fn test_confirm_upload_duplicate() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let storage = stored(1024, "image/jpeg");
+    let existing = MediaFile {
+        id: Uuid::new_v4(),
+        status: MediaStatus::Ready,
+        checksum: Some(hex::encode(Sha256::digest(&storage.file_data))),
+        ..pending_media(user_id)
+    };
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        media_files: vec![existing.clone(), media.clone()],
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &storage,
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(result.duplicate);
+    assert_eq!(result.id, existing.id);
+    assert_eq!(
+        *storage.deleted_files.lock().unwrap(),
+        vec![media.file_path]
+    );
+}
+
+#[tokio::test]
+async fn test_confirm_upload_concurrent_duplicate() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let storage = stored(1024, "image/jpeg");
+    // The same content is stored by another upload after the duplicate lookup
+    let concurrent_media = MediaFile {
+        id: Uuid::new_v4(),
+        status: MediaStatus::Ready,
+        checksum: Some(hex::encode(Sha256::digest(&storage.file_data))),
+        ..pending_media(user_id)
+    };
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        concurrent_media: Some(concurrent_media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &storage,
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(result.duplicate);
+    assert_eq!(result.id, concurrent_media.id);
+    assert_eq!(
+        *storage.deleted_files.lock().unwrap(),
+        vec![media.file_path]
+    );
+    assert_eq!(*repo.deleted_media_ids.lock().unwrap(), vec![media.id]);
+}
+
+#[tokio::test]
+async fn test_confirm_upload_content_type_mismatch() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    // The client sent the declared content type to the storage, but not an image
+    let storage = stored_content(b"#!/bin/sh\n", 1024, "image/jpeg");
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &storage,
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(MediaConfirmError::ContentTypeMismatch { detected: None, .. })
+    ));
+    assert_eq!(
+        *storage.deleted_files.lock().unwrap(),
+        vec![media.file_path]
+    );
+}
+
+#[tokio::test]
+async fn test_confirm_upload_file_not_uploaded() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaConfirmError::FileNotUploaded)));
+}
+
+#[tokio::test]
+async fn test_confirm_upload_size_mismatch() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &stored(2048, "image/jpeg"),
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaConfirmError::FileMismatch)));
+}
+
+#[tokio::test]
+async fn test_confirm_upload_other_users_media_not_found() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &stored(1024, "image/jpeg"),
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id: Uuid::new_v4(),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaConfirmError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_request_upload_exceeds_quota() {
+    let user_id = Uuid::new_v4();
+    let repo = MockMediaRepository {
+        media_files: vec![MediaFile {
+            status: MediaStatus::Ready,
+            ..pending_media(user_id)
+        }],
+        ..MockMediaRepository::default()
+    };
+
+    let result = request_upload_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &quota_config(2048),
+        RequestUploadCommand {
+            user_id,
+            filename: "upload.jpg".to_string(),
+            original_filename: "photo.jpg".to_string(),
+            file_size: 1025,
+            content_type: "image/jpeg".to_string(),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaUploadError::QuotaExceeded)));
+}
+
+#[tokio::test]
+async fn test_confirm_upload_exceeds_lowered_quota() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    // The pending media file already counts towards the usage
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        media_files: vec![media.clone()],
+        ..MockMediaRepository::default()
+    };
+    let storage = stored(1024, "image/jpeg");
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &storage,
+        &user_repository(user_id),
+        &quota_config(1000),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaConfirmError::QuotaExceeded)));
+    assert_eq!(
+        *storage.deleted_files.lock().unwrap(),
+        vec![media.file_path]
+    );
+}
+
+#[tokio::test]
+async fn test_confirm_upload_pending_file_is_not_counted_twice() {
+    let user_id = Uuid::new_v4();
+    let media = pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        media_files: vec![media.clone()],
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &stored(1024, "image/jpeg"),
+        &user_repository(user_id),
+        &quota_config(1024),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_request_upload_expired_pending_file_does_not_reserve_quota() {
+    let user_id = Uuid::new_v4();
+    let repo = MockMediaRepository {
+        media_files: vec![expired_pending_media(user_id)],
+        ..MockMediaRepository::default()
+    };
+
+    let result = request_upload_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &quota_config(1024),
+        RequestUploadCommand {
+            user_id,
+            filename: "upload.jpg".to_string(),
+            original_filename: "photo.jpg".to_string(),
+            file_size: 1024,
+            content_type: "image/jpeg".to_string(),
+        },
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_confirm_upload_expired() {
+    let user_id = Uuid::new_v4();
+    let media = expired_pending_media(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = confirm_upload_command_handler(
+        &repo,
+        &stored(1024, "image/jpeg"),
+        &user_repository(user_id),
+        &QuotaConfig::default(),
+        ConfirmUploadCommand {
+            media_id: media.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(MediaConfirmError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_cleanup_expired_pending_uploads() {
+    let user_id = Uuid::new_v4();
+    let expired = expired_pending_media(user_id);
+    let repo = MockMediaRepository {
+        media_files: vec![
+            expired.clone(),
+            pending_media(user_id),
+            MediaFile {
+                status: MediaStatus::Ready,
+                ..expired_pending_media(user_id)
+            },
+        ],
+        ..MockMediaRepository::default()
+    };
+    let storage = MockStorageService::default();
+
+    let cleaned_up = cleanup_expired_pending_uploads_command_handler(&repo, &storage)
+        .await
+        .unwrap();
+
+    assert_eq!(cleaned_up, 1);
+    assert_eq!(*repo.deleted_media_ids.lock().unwrap(), vec![expired.id]);
+    assert_eq!(
+        *storage.deleted_files.lock().unwrap(),
+        vec![expired.file_path]
+    );
+}
+
+#[tokio::test]
+async fn test_cleanup_expired_pending_uploads_keeps_file_when_delete_fails() {
+    let user_id = Uuid::new_v4();
+    let repo = MockMediaRepository {
+        media_files: vec![expired_pending_media(user_id)],
+        ..MockMediaRepository::default()
+    };
+    let storage = MockStorageService {
+        fail_delete: true,
+        ..MockStorageService::default()
+    };
+
+    let cleaned_up = cleanup_expired_pending_uploads_command_handler(&repo, &storage)
+        .await
+        .unwrap();
+
+    assert_eq!(cleaned_up, 0);
+    assert!(repo.deleted_media_ids.lock().unwrap().is_empty());
+}
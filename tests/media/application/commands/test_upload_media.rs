@@ -2,29 +2,88 @@
 #[cfg(test)]
 mod media_upload_tests {
     use crate::media::{MockMediaRepository, MockStorageService};
+    use crate::users::MockUserRepository;
     use lib::media::{
         application::commands::upload_media::{UploadMediaCommand, upload_media_command_handler},
-        domain::MediaUploadError,
+        domain::{MediaFile, MediaSizeLimits, MediaStatus, MediaTypeAllowlist, MediaUploadError},
     };
+    use lib::users::domain::{QuotaConfig, Role, StorageQuota, User};
     use uuid::Uuid;
     use std::pin::Pin;
     use bytes::Bytes;
     use futures_core::Stream;
     use futures_util::stream;
 
+    // JPEG start marker followed by text
+    const FAKE_JPEG: &[u8] = b"\xFF\xD8\xFF\xE0fake image data";
+    // SHA-256 of FAKE_JPEG
+    const EXPECTED_CHECKSUM: &str =
+        "57d6d8ee608e4f674abfb1a82fa8050fdb86531291dc89cbb5c0e5406b6038a7";
+
     fn create_file_stream(data: Vec<u8>) -> Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync>> {
         Box::pin(stream::once(async move { 
             Ok(Bytes::from(data)) 
         }))
     }
 
+    fn user(user_id: Uuid) -> User {
+        User {
+            id: user_id,
+            username: "uploader".to_string(),
+            email: None,
+            password: "hashed".to_string(),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            created_at: None,
+            updated_at: None,
+            must_change_password: false,
+        }
+    }
+
+    /// Repository knowing the uploading user, who has the default quota
+    fn user_repository(user_id: Uuid) -> MockUserRepository {
+        MockUserRepository {
+            user: Some(user(user_id)),
+            ..MockUserRepository::default()
+        }
+    }
+
+    fn media_file(user_id: Uuid, file_size: i64) -> MediaFile {
+        MediaFile {
+            id: Uuid::new_v4(),
+            user_id,
+            filename: "existing.jpg".to_string(),
+            original_filename: "original.jpg".to_string(),
+            file_size,
+            content_type: "image/jpeg".to_string(),
+            file_path: format!("media/{}/existing.jpg", user_id),
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
+            uploaded_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn upload_command(user_id: Uuid, file_size: Option<u64>) -> UploadMediaCommand {
+        UploadMediaCommand {
+            user_id,
+            filename: "test.jpg".to_string(),
+            original_filename: "original.jpg".to_string(),
+            file_data: create_file_stream(FAKE_JPEG.to_vec()),
+            file_size,
+            content_type: "image/jpeg".to_string(),
+        }
+    }
+
     #[tokio::test]
     async fn test_upload_media_valid_image() {
         let mock_repo = MockMediaRepository::default();
         let mock_storage = MockStorageService::default();
 
         let user_id = Uuid::new_v4();
-        let file_data = b"fake image data".to_vec();
+        let file_data = FAKE_JPEG.to_vec();
         let file_size = file_data.len() as u64;
         
         let command = UploadMediaCommand {
@@ -36,7 +95,16 @@ mod media_upload_tests {
             content_type: "image/jpeg".to_string(),
         };
 
-        let result = upload_media_command_handler(&mock_repo, &mock_storage, command).await;
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(command.user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+        .await;
 
         assert!(result.is_ok());
         let upload_result = result.unwrap();
@@ -64,7 +132,16 @@ mod media_upload_tests {
             content_type: "text/plain".to_string(),
         };
 
-        let result = upload_media_command_handler(&mock_repo, &mock_storage, command).await;
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(command.user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+        .await;
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -72,4 +149,472 @@ mod media_upload_tests {
             _ => panic!("Expected InvalidFileType error"),
         }
     }
+
+    #[tokio::test]
+    async fn test_upload_media_stores_checksum() {
+        let mock_repo = MockMediaRepository::default();
+        let mock_storage = MockStorageService::default();
+
+        let command = UploadMediaCommand {
+            user_id: Uuid::new_v4(),
+            filename: "test.jpg".to_string(),
+            original_filename: "original.jpg".to_string(),
+            file_data: create_file_stream(FAKE_JPEG.to_vec()),
+            file_size: Some(19),
+            content_type: "image/jpeg".to_string(),
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(command.user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+            .await
+            .unwrap();
+
+        assert_eq!(result.checksum.as_deref(), Some(EXPECTED_CHECKSUM));
+        assert!(!result.duplicate);
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_duplicate_returns_existing_media() {
+        let user_id = Uuid::new_v4();
+        let existing = MediaFile {
+            id: Uuid::new_v4(),
+            user_id,
+            filename: "existing.jpg".to_string(),
+            original_filename: "original.jpg".to_string(),
+            file_size: 19,
+            content_type: "image/jpeg".to_string(),
+            file_path: format!("media/{}/existing.jpg", user_id),
+            status: MediaStatus::Ready,
+            checksum: Some(EXPECTED_CHECKSUM.to_string()),
+            detected_content_type: None,
+            uploaded_at: Some(chrono::Utc::now().naive_utc()),
+            updated_at: Some(chrono::Utc::now().naive_utc()),
+        };
+        let mock_repo = MockMediaRepository {
+            media_files: vec![existing.clone()],
+            ..MockMediaRepository::default()
+        };
+        let mock_storage = MockStorageService::default();
+
+        let command = UploadMediaCommand {
+            user_id,
+            filename: "test.jpg".to_string(),
+            original_filename: "copy.jpg".to_string(),
+            file_data: create_file_stream(FAKE_JPEG.to_vec()),
+            file_size: Some(19),
+            content_type: "image/jpeg".to_string(),
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(command.user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+            .await
+            .unwrap();
+
+        assert!(result.duplicate);
+        assert_eq!(result.id, existing.id);
+        assert_eq!(result.filename, "existing.jpg");
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_concurrent_duplicate_returns_stored_media() {
+        let user_id = Uuid::new_v4();
+        // The same content is stored by another upload after the duplicate lookup
+        let concurrent_media = MediaFile {
+            checksum: Some(EXPECTED_CHECKSUM.to_string()),
+            ..media_file(user_id, 19)
+        };
+        let mock_repo = MockMediaRepository {
+            concurrent_media: Some(concurrent_media.clone()),
+            ..MockMediaRepository::default()
+        };
+        let mock_storage = MockStorageService::default();
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            upload_command(user_id, Some(19)),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.duplicate);
+        assert_eq!(result.id, concurrent_media.id);
+        assert_eq!(mock_storage.deleted_files.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_content_type_mismatch() {
+        let mock_repo = MockMediaRepository::default();
+        // Nothing may reach the storage
+        let mock_storage = MockStorageService {
+            fail_upload: true,
+            ..MockStorageService::default()
+        };
+
+        let command = UploadMediaCommand {
+            user_id: Uuid::new_v4(),
+            filename: "test.jpg".to_string(),
+            original_filename: "original.jpg".to_string(),
+            file_data: create_file_stream(b"<script>alert(1)</script>".to_vec()),
+            file_size: Some(25),
+            content_type: "image/jpeg".to_string(),
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(command.user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+        .await;
+
+        match result {
+            Err(MediaUploadError::ContentTypeMismatch { claimed, detected }) => {
+                assert_eq!(claimed, "image/jpeg");
+                assert_eq!(detected, None);
+            }
+            _ => panic!("Expected ContentTypeMismatch error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_stores_detected_content_type() {
+        let mock_repo = MockMediaRepository::default();
+        let mock_storage = MockStorageService::default();
+
+        // The magic bytes arrive split over several chunks
+        let mut data = b"\0\0\0\x1cftypqt  ".to_vec();
+        data.extend(vec![0; 100]);
+        let chunks: Vec<Result<Bytes, std::io::Error>> = data
+            .chunks(5)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+
+        let command = UploadMediaCommand {
+            user_id: Uuid::new_v4(),
+            filename: "clip.mov".to_string(),
+            original_filename: "clip.mov".to_string(),
+            file_data: Box::pin(stream::iter(chunks)),
+            file_size: Some(data.len() as u64),
+            content_type: "video/mp4".to_string(),
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(command.user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+            .await
+            .unwrap();
+
+        // The whole content is stored, including the bytes read for sniffing
+        assert_eq!(result.file_size, data.len() as i64);
+        assert_eq!(result.content_type, "video/mp4");
+        assert_eq!(result.detected_content_type.as_deref(), Some("video/quicktime"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_not_allowed_content_type() {
+        let command = UploadMediaCommand {
+            user_id: Uuid::new_v4(),
+            filename: "test.gif".to_string(),
+            original_filename: "original.gif".to_string(),
+            file_data: create_file_stream(b"GIF89a".to_vec()),
+            file_size: Some(6),
+            content_type: "image/gif".to_string(),
+        };
+        let allowlist = MediaTypeAllowlist::parse("image/jpeg, image/png").unwrap();
+
+        let result = upload_media_command_handler(
+            &MockMediaRepository::default(),
+            &MockStorageService::default(),
+            &user_repository(command.user_id),
+            &allowlist,
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            command,
+        )
+        .await;
+
+        assert!(matches!(result, Err(MediaUploadError::InvalidFileType)));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_declared_size_exceeds_quota() {
+        let user_id = Uuid::new_v4();
+        let mock_repo = MockMediaRepository {
+            media_files: vec![media_file(user_id, 90)],
+            ..MockMediaRepository::default()
+        };
+        let quota_config = QuotaConfig {
+            default_quota: StorageQuota {
+                max_bytes: 100,
+                max_items: 10,
+            },
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &MockStorageService::default(),
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &quota_config,
+            upload_command(user_id, Some(19)),
+        )
+        .await;
+
+        assert!(matches!(result, Err(MediaUploadError::QuotaExceeded)));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_streamed_bytes_exceed_quota() {
+        let user_id = Uuid::new_v4();
+        let mock_repo = MockMediaRepository {
+            media_files: vec![media_file(user_id, 90)],
+            ..MockMediaRepository::default()
+        };
+        let quota_config = QuotaConfig {
+            default_quota: StorageQuota {
+                max_bytes: 100,
+                max_items: 10,
+            },
+        };
+
+        // Without a declared size only the streamed bytes can be counted
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &MockStorageService::default(),
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &quota_config,
+            upload_command(user_id, None),
+        )
+        .await;
+
+        assert!(matches!(result, Err(MediaUploadError::QuotaExceeded)));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_item_quota_exceeded() {
+        let user_id = Uuid::new_v4();
+        let mock_repo = MockMediaRepository {
+            media_files: vec![media_file(user_id, 1), media_file(user_id, 1)],
+            ..MockMediaRepository::default()
+        };
+        let user_repository = MockUserRepository {
+            user: Some(User {
+                quota_items: Some(2),
+                ..user(user_id)
+            }),
+            ..MockUserRepository::default()
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &MockStorageService::default(),
+            &user_repository,
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            upload_command(user_id, Some(19)),
+        )
+        .await;
+
+        assert!(matches!(result, Err(MediaUploadError::QuotaExceeded)));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_user_quota_overrides_default() {
+        let user_id = Uuid::new_v4();
+        let mock_repo = MockMediaRepository {
+            media_files: vec![media_file(user_id, 90)],
+            ..MockMediaRepository::default()
+        };
+        let user_repository = MockUserRepository {
+            user: Some(User {
+                quota_bytes: Some(1000),
+                ..user(user_id)
+            }),
+            ..MockUserRepository::default()
+        };
+        let quota_config = QuotaConfig {
+            default_quota: StorageQuota {
+                max_bytes: 100,
+                max_items: 10,
+            },
+        };
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &MockStorageService::default(),
+            &user_repository,
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &quota_config,
+            upload_command(user_id, None),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    fn size_limits(max_bytes: u64) -> MediaSizeLimits {
+        MediaSizeLimits {
+            default_max_bytes: 1024,
+            content_type_max_bytes: vec![("image/*".to_string(), max_bytes)],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_declared_size_too_large() {
+        let user_id = Uuid::new_v4();
+
+        // Storage is never reached, the mock would fail the upload otherwise
+        let result = upload_media_command_handler(
+            &MockMediaRepository::default(),
+            &MockStorageService {
+                fail_upload: true,
+                ..MockStorageService::default()
+            },
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &size_limits(10),
+            &QuotaConfig::default(),
+            upload_command(user_id, Some(19)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(MediaUploadError::FileTooLarge { max_bytes: 10 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_streamed_bytes_too_large() {
+        let user_id = Uuid::new_v4();
+
+        let result = upload_media_command_handler(
+            &MockMediaRepository::default(),
+            &MockStorageService::default(),
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &size_limits(10),
+            &QuotaConfig::default(),
+            upload_command(user_id, None),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(MediaUploadError::FileTooLarge { max_bytes: 10 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_more_bytes_than_declared() {
+        let user_id = Uuid::new_v4();
+
+        let result = upload_media_command_handler(
+            &MockMediaRepository::default(),
+            &MockStorageService::default(),
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            upload_command(user_id, Some(5)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(MediaUploadError::FileSizeMismatch { declared: 5, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_fewer_bytes_than_declared() {
+        let user_id = Uuid::new_v4();
+        let mock_storage = MockStorageService::default();
+
+        let result = upload_media_command_handler(
+            &MockMediaRepository::default(),
+            &mock_storage,
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            upload_command(user_id, Some(100)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(MediaUploadError::FileSizeMismatch {
+                declared: 100,
+                received: 19
+            })
+        ));
+        assert_eq!(
+            *mock_storage.deleted_files.lock().unwrap(),
+            vec![format!("media/{}/test.jpg", user_id)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_database_error_deletes_stored_file() {
+        let user_id = Uuid::new_v4();
+        let mock_repo = MockMediaRepository {
+            fail_save: true,
+            ..MockMediaRepository::default()
+        };
+        let mock_storage = MockStorageService::default();
+
+        let result = upload_media_command_handler(
+            &mock_repo,
+            &mock_storage,
+            &user_repository(user_id),
+            &MediaTypeAllowlist::default(),
+            &MediaSizeLimits::default(),
+            &QuotaConfig::default(),
+            upload_command(user_id, Some(19)),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(MediaUploadError::InternalServerError(_))
+        ));
+        assert_eq!(
+            *mock_storage.deleted_files.lock().unwrap(),
+            vec![format!("media/{}/test.jpg", user_id)]
+        );
+    }
 }
@@ -0,0 +1,698 @@
+use crate::{
+    media::{MockMediaRepository, MockStorageService, MockUploadSessionRepository},
+    users::MockUserRepository,
+};
+use bytes::Bytes;
+use lib::media::{
+    application::commands::{
+        abort_upload_session::{AbortUploadSessionCommand, abort_upload_session_command_handler},
+        cleanup_expired_upload_sessions::cleanup_expired_upload_sessions_command_handler,
+        cleanup_stale_multipart_uploads::cleanup_stale_multipart_uploads_command_handler,
+        create_upload_session::{CreateUploadSessionCommand, create_upload_session_command_handler},
+        finalize_upload_session::{
+            FinalizeUploadSessionCommand, finalize_upload_session_command_handler,
+        },
+        upload_chunk::{UploadChunkCommand, upload_chunk_command_handler},
+    },
+    domain::{
+        IncompleteMultipartUpload, MediaFile, MediaSizeLimits, MediaStatus, MediaTypeAllowlist,
+        STALE_MULTIPART_UPLOAD_HOURS, StoredFileMetadata, UPLOAD_SESSION_CHUNK_SIZE,
+        UploadSession, UploadSessionError,
+    },
+};
+use lib::users::domain::{QuotaConfig, Role, StorageQuota, User};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+fn upload_session(user_id: Uuid, file_size: i64) -> UploadSession {
+    UploadSession {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "upload.mp4".to_string(),
+        original_filename: "movie.mp4".to_string(),
+        file_size,
+        content_type: "video/mp4".to_string(),
+        file_path: format!("media/{}/upload.mp4", user_id),
+        storage_upload_id: "mock-upload-id".to_string(),
+        upload_offset: 0,
+        part_etags: Vec::new(),
+        expires_at: (chrono::Utc::now() + chrono::Duration::hours(1)).naive_utc(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+/// Repository knowing the uploading user, who has the quota of the config
+fn user_repository(user_id: Uuid) -> MockUserRepository {
+    MockUserRepository {
+        user: Some(User {
+            id: user_id,
+            username: "uploader".to_string(),
+            email: None,
+            password: "hashed".to_string(),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            must_change_password: false,
+            created_at: None,
+            updated_at: None,
+        }),
+        ..MockUserRepository::default()
+    }
+}
+
+fn quota_config(max_bytes: i64) -> QuotaConfig {
+    QuotaConfig {
+        default_quota: StorageQuota {
+            max_bytes,
+            max_items: 10,
+        },
+    }
+}
+
+/// Limits every content type to `max_bytes`
+fn size_limits(max_bytes: u64) -> MediaSizeLimits {
+    MediaSizeLimits {
+        default_max_bytes: max_bytes,
+        content_type_max_bytes: Vec::new(),
+    }
+}
+
+/// Media repository where the user already stores a file of `file_size` bytes
+fn media_repository(user_id: Uuid, file_size: i64) -> MockMediaRepository {
+    MockMediaRepository {
+        media_files: vec![MediaFile {
+            id: Uuid::new_v4(),
+            user_id,
+            filename: "existing.mp4".to_string(),
+            original_filename: "existing.mp4".to_string(),
+            file_size,
+            content_type: "video/mp4".to_string(),
+            file_path: format!("media/{}/existing.mp4", user_id),
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
+            uploaded_at: None,
+            updated_at: None,
+        }],
+        ..MockMediaRepository::default()
+    }
+}
+
+/// Storage holding an assembled MP4 file of `file_size` bytes
+fn stored(file_size: u64) -> MockStorageService {
+    stored_content(b"\x00\x00\x00\x10ftypisom\x00\x00\x02\x00", file_size)
+}
+
+/// Storage holding a file of `file_size` bytes that starts with `head`
+fn stored_content(head: &[u8], file_size: u64) -> MockStorageService {
+    MockStorageService {
+        file_data: head
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(0))
+            .take(file_size as usize)
+            .collect(),
+        file_metadata: Some(StoredFileMetadata {
+            file_size,
+            content_type: Some("video/mp4".to_string()),
+        }),
+        ..MockStorageService::default()
+    }
+}
+
+#[tokio::test]
+async fn test_create_upload_session_success() {
+    let repo = MockUploadSessionRepository::default();
+    let user_id = Uuid::new_v4();
+
+    let result = create_upload_session_command_handler(
+        &repo,
+        &MockMediaRepository::default(),
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        CreateUploadSessionCommand {
+            user_id,
+            filename: "upload.mp4".to_string(),
+            original_filename: "movie.mp4".to_string(),
+            file_size: 20 * 1024 * 1024,
+            content_type: "video/mp4".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.upload_offset, 0);
+    assert_eq!(result.chunk_size, UPLOAD_SESSION_CHUNK_SIZE);
+    let session = repo.session(result.id).unwrap();
+    assert_eq!(session.user_id, user_id);
+    assert_eq!(session.storage_upload_id, "mock-upload-id");
+    assert_eq!(session.file_path, format!("media/{}/upload.mp4", user_id));
+}
+
+#[tokio::test]
+async fn test_create_upload_session_invalid_file_type() {
+    let user_id = Uuid::new_v4();
+    let result = create_upload_session_command_handler(
+        &MockUploadSessionRepository::default(),
+        &MockMediaRepository::default(),
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        CreateUploadSessionCommand {
+            user_id,
+            filename: "upload.txt".to_string(),
+            original_filename: "notes.txt".to_string(),
+            file_size: 10,
+            content_type: "text/plain".to_string(),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadSessionError::InvalidFileType)));
+}
+
+#[tokio::test]
+async fn test_create_upload_session_exceeds_quota() {
+    let user_id = Uuid::new_v4();
+    let storage = MockStorageService::default();
+
+    let result = create_upload_session_command_handler(
+        &MockUploadSessionRepository::default(),
+        &media_repository(user_id, 60),
+        &storage,
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &MediaSizeLimits::default(),
+        &quota_config(100),
+        CreateUploadSessionCommand {
+            user_id,
+            filename: "upload.mp4".to_string(),
+            original_filename: "movie.mp4".to_string(),
+            file_size: 50,
+            content_type: "video/mp4".to_string(),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadSessionError::QuotaExceeded)));
+}
+
+#[tokio::test]
+async fn test_create_upload_session_file_too_large() {
+    let user_id = Uuid::new_v4();
+    let storage = MockStorageService::default();
+
+    let result = create_upload_session_command_handler(
+        &MockUploadSessionRepository::default(),
+        &MockMediaRepository::default(),
+        &storage,
+        &user_repository(user_id),
+        &MediaTypeAllowlist::default(),
+        &size_limits(40),
+        &QuotaConfig::default(),
+        CreateUploadSessionCommand {
+            user_id,
+            filename: "upload.mp4".to_string(),
+            original_filename: "movie.mp4".to_string(),
+            file_size: 50,
+            content_type: "video/mp4".to_string(),
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UploadSessionError::FileTooLarge { max_bytes: 40 })
+    ));
+}
+
+#[tokio::test]
+async fn test_upload_chunk_appends_part() {
+    let user_id = Uuid::new_v4();
+    let session = upload_session(user_id, UPLOAD_SESSION_CHUNK_SIZE as i64 + 10);
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+
+    let result = upload_chunk_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        UploadChunkCommand {
+            upload_session_id: session.id,
+            user_id,
+            offset: 0,
+            data: Bytes::from(vec![0u8; UPLOAD_SESSION_CHUNK_SIZE as usize]),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.upload_offset, UPLOAD_SESSION_CHUNK_SIZE as i64);
+    assert_eq!(repo.session(session.id).unwrap().part_etags, vec!["\"etag-1\""]);
+}
+
+#[tokio::test]
+async fn test_upload_chunk_offset_mismatch() {
+    let user_id = Uuid::new_v4();
+    let session = upload_session(user_id, 10);
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+
+    let result = upload_chunk_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        UploadChunkCommand {
+            upload_session_id: session.id,
+            user_id,
+            offset: 5,
+            data: Bytes::from_static(b"12345"),
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UploadSessionError::OffsetMismatch { current_offset: 0 })
+    ));
+}
+
+#[tokio::test]
+async fn test_upload_chunk_invalid_size() {
+    let user_id = Uuid::new_v4();
+    let session = upload_session(user_id, UPLOAD_SESSION_CHUNK_SIZE as i64 * 2);
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+
+    // Only the last chunk may be smaller than the chunk size
+    let result = upload_chunk_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        UploadChunkCommand {
+            upload_session_id: session.id,
+            user_id,
+            offset: 0,
+            data: Bytes::from_static(b"too small"),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadSessionError::InvalidChunkSize)));
+    assert_eq!(repo.session(session.id).unwrap().upload_offset, 0);
+}
+
+#[tokio::test]
+async fn test_upload_chunk_other_users_session_not_found() {
+    let session = upload_session(Uuid::new_v4(), 10);
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+
+    let result = upload_chunk_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        UploadChunkCommand {
+            upload_session_id: session.id,
+            user_id: Uuid::new_v4(),
+            offset: 0,
+            data: Bytes::from_static(b"0123456789"),
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadSessionError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_finalize_incomplete_upload_session() {
+    let user_id = Uuid::new_v4();
+    let session = upload_session(user_id, 10);
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &MockMediaRepository::default(),
+        &MockStorageService::default(),
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadSessionError::Incomplete)));
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_creates_media_file() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 32,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 32)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    let storage = stored(32);
+
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &MockMediaRepository::default(),
+        &storage,
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.filename, "upload.mp4");
+    assert_eq!(result.original_filename, "movie.mp4");
+    assert_eq!(result.file_size, 32);
+    assert_eq!(result.detected_content_type.as_deref(), Some("video/mp4"));
+    assert_eq!(
+        result.checksum,
+        Some(hex::encode(Sha256::digest(&storage.file_data)))
+    );
+    assert!(!result.duplicate);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_duplicate() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 32,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 32)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    let storage = stored(32);
+    let mut media_repo = media_repository(user_id, 32);
+    media_repo.media_files[0].checksum = Some(hex::encode(Sha256::digest(&storage.file_data)));
+
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &media_repo,
+        &storage,
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(result.duplicate);
+    assert_eq!(result.id, media_repo.media_files[0].id);
+    assert_eq!(*storage.deleted_files.lock().unwrap(), vec![session.file_path]);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_concurrent_duplicate() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 32,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 32)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    let storage = stored(32);
+    let mut media_repo = media_repository(user_id, 32);
+    // The same content is stored by another upload after the duplicate lookup
+    let mut concurrent_media = media_repo.media_files.remove(0);
+    concurrent_media.checksum = Some(hex::encode(Sha256::digest(&storage.file_data)));
+    media_repo.concurrent_media = Some(concurrent_media.clone());
+
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &media_repo,
+        &storage,
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(result.duplicate);
+    assert_eq!(result.id, concurrent_media.id);
+    assert_eq!(*storage.deleted_files.lock().unwrap(), vec![session.file_path]);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_content_type_mismatch() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 32,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 32)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    // Declared as video/mp4, but the chunks were a JPEG image
+    let storage = stored_content(b"\xFF\xD8\xFF\xE0fake image data", 32);
+
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &MockMediaRepository::default(),
+        &storage,
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UploadSessionError::ContentTypeMismatch { detected: Some(ref detected), .. })
+            if detected == "image/jpeg"
+    ));
+    assert_eq!(*storage.deleted_files.lock().unwrap(), vec![session.file_path]);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_exceeds_quota() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 50,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 50)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    let storage = stored(50);
+
+    // Another upload finished after the session was created
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &media_repository(user_id, 60),
+        &storage,
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &quota_config(100),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(result, Err(UploadSessionError::QuotaExceeded)));
+    assert_eq!(*storage.deleted_files.lock().unwrap(), vec![session.file_path]);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_file_too_large() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 50,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 50)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    let storage = stored(50);
+
+    // The limit was lowered after the session was created
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &MockMediaRepository::default(),
+        &storage,
+        &user_repository(user_id),
+        &size_limits(40),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UploadSessionError::FileTooLarge { max_bytes: 40 })
+    ));
+    assert_eq!(*storage.deleted_files.lock().unwrap(), vec![session.file_path]);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_finalize_upload_session_media_file_not_created() {
+    let user_id = Uuid::new_v4();
+    let session = UploadSession {
+        upload_offset: 50,
+        part_etags: vec!["\"etag-1\"".to_string()],
+        ..upload_session(user_id, 50)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+    let storage = stored(50);
+
+    let result = finalize_upload_session_command_handler(
+        &repo,
+        &MockMediaRepository {
+            fail_save: true,
+            ..MockMediaRepository::default()
+        },
+        &storage,
+        &user_repository(user_id),
+        &MediaSizeLimits::default(),
+        &QuotaConfig::default(),
+        FinalizeUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(UploadSessionError::InternalServerError(_))
+    ));
+    assert_eq!(*storage.deleted_files.lock().unwrap(), vec![session.file_path]);
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_abort_upload_session() {
+    let user_id = Uuid::new_v4();
+    let session = upload_session(user_id, 10);
+    let repo = MockUploadSessionRepository::with_sessions(vec![session.clone()]);
+
+    abort_upload_session_command_handler(
+        &repo,
+        &MockStorageService::default(),
+        AbortUploadSessionCommand {
+            upload_session_id: session.id,
+            user_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(repo.session(session.id).is_none());
+}
+
+#[tokio::test]
+async fn test_cleanup_expired_upload_sessions() {
+    let user_id = Uuid::new_v4();
+    let active = upload_session(user_id, 10);
+    let expired = UploadSession {
+        expires_at: (chrono::Utc::now() - chrono::Duration::hours(1)).naive_utc(),
+        ..upload_session(user_id, 10)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![active.clone(), expired.clone()]);
+
+    let cleaned_up =
+        cleanup_expired_upload_sessions_command_handler(&repo, &MockStorageService::default())
+            .await
+            .unwrap();
+
+    assert_eq!(cleaned_up, 1);
+    assert!(repo.session(active.id).is_some());
+    assert!(repo.session(expired.id).is_none());
+}
+
+#[tokio::test]
+async fn test_cleanup_keeps_sessions_when_abort_fails() {
+    let expired = UploadSession {
+        expires_at: (chrono::Utc::now() - chrono::Duration::hours(1)).naive_utc(),
+        ..upload_session(Uuid::new_v4(), 10)
+    };
+    let repo = MockUploadSessionRepository::with_sessions(vec![expired.clone()]);
+    let storage = MockStorageService {
+        fail_delete: true,
+        ..MockStorageService::default()
+    };
+
+    let cleaned_up = cleanup_expired_upload_sessions_command_handler(&repo, &storage)
+        .await
+        .unwrap();
+
+    assert_eq!(cleaned_up, 0);
+    assert!(repo.session(expired.id).is_some());
+}
+
+fn incomplete_upload(upload_id: &str, age_hours: Option<i64>) -> IncompleteMultipartUpload {
+    IncompleteMultipartUpload {
+        file_path: format!("media/{}/upload.mp4", Uuid::new_v4()),
+        upload_id: upload_id.to_string(),
+        initiated_at: age_hours
+            .map(|hours| (chrono::Utc::now() - chrono::Duration::hours(hours)).naive_utc()),
+    }
+}
+
+#[tokio::test]
+async fn test_cleanup_stale_multipart_uploads() {
+    let storage = MockStorageService {
+        incomplete_uploads: vec![
+            incomplete_upload("stale", Some(STALE_MULTIPART_UPLOAD_HOURS + 1)),
+            // Still within the lifetime of a resumable upload
+            incomplete_upload("running", Some(1)),
+            incomplete_upload("unknown-age", None),
+        ],
+        ..MockStorageService::default()
+    };
+
+    let aborted = cleanup_stale_multipart_uploads_command_handler(&storage)
+        .await
+        .unwrap();
+
+    assert_eq!(aborted, 1);
+    assert_eq!(*storage.aborted_uploads.lock().unwrap(), vec!["stale"]);
+}
+
+#[tokio::test]
+async fn test_cleanup_stale_multipart_uploads_continues_when_abort_fails() {
+    let storage = MockStorageService {
+        fail_delete: true,
+        incomplete_uploads: vec![
+            incomplete_upload("stale-1", Some(STALE_MULTIPART_UPLOAD_HOURS + 1)),
+            incomplete_upload("stale-2", Some(STALE_MULTIPART_UPLOAD_HOURS + 2)),
+        ],
+        ..MockStorageService::default()
+    };
+
+    let aborted = cleanup_stale_multipart_uploads_command_handler(&storage)
+        .await
+        .unwrap();
+
+    assert_eq!(aborted, 0);
+}
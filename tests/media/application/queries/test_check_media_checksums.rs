@@ -0,0 +1,55 @@
+use lib::media::{
+    application::queries::check_media_checksums::{
+        CheckMediaChecksumsQuery, check_media_checksums_query_handler,
+    },
+    domain::{MediaFile, MediaStatus},
+};
+use uuid::Uuid;
+
+use crate::media::MockMediaRepository;
+
+fn media_file(user_id: Uuid, checksum: &str) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "photo.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 1024,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/photo.jpg", user_id),
+        status: MediaStatus::Ready,
+        checksum: Some(checksum.to_string()),
+        detected_content_type: None,
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[tokio::test]
+async fn test_check_media_checksums_returns_existing_only() {
+    let user_id = Uuid::new_v4();
+    let existing = media_file(user_id, "aaaa");
+    let repo = MockMediaRepository {
+        media_files: vec![
+            existing.clone(),
+            // Second copy of the same content is reported once
+            media_file(user_id, "aaaa"),
+            // Same content owned by someone else does not count
+            media_file(Uuid::new_v4(), "bbbb"),
+        ],
+        ..MockMediaRepository::default()
+    };
+
+    let query = CheckMediaChecksumsQuery {
+        user_id,
+        checksums: vec!["AAAA".to_string(), "bbbb".to_string(), "cccc".to_string()],
+    };
+
+    let result = check_media_checksums_query_handler(query, &repo)
+        .await
+        .unwrap();
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].checksum, "aaaa");
+    assert_eq!(result[0].media_id, existing.id);
+}
@@ -0,0 +1,112 @@
+use lib::{
+    media::{
+        application::queries::get_media_file::{GetMediaFileQuery, get_media_file_query_handler},
+        domain::{MediaMetadata, MediaRepositoryError},
+    },
+    sharing::domain::{ShareRole, ShareTarget},
+};
+use uuid::Uuid;
+
+use crate::{
+    media::{MockMediaRepository, media_file},
+    sharing::{MockShareGrantRepository, share_grant, test_authorization_service},
+};
+
+fn metadata(media_id: Uuid) -> MediaMetadata {
+    MediaMetadata {
+        media_id,
+        camera_make: Some("Canon".to_string()),
+        width: Some(4000),
+        height: Some(3000),
+        latitude: Some(52.5),
+        longitude: Some(13.4),
+        ..MediaMetadata::default()
+    }
+}
+
+#[tokio::test]
+async fn test_get_media_file_includes_metadata() {
+    let user_id = Uuid::new_v4();
+    let media = media_file(user_id);
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        media_metadata: vec![metadata(media.id)],
+        ..MockMediaRepository::default()
+    };
+
+    let result = get_media_file_query_handler(
+        GetMediaFileQuery {
+            media_id: media.id,
+            user_id,
+        },
+        &repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.id, media.id);
+    let result_metadata = result.metadata.unwrap();
+    assert_eq!(result_metadata.camera_make.as_deref(), Some("Canon"));
+    assert_eq!(result_metadata.width, Some(4000));
+    assert_eq!(result_metadata.latitude, Some(52.5));
+    assert_eq!(result_metadata.longitude, Some(13.4));
+}
+
+#[tokio::test]
+async fn test_get_media_file_of_other_user_not_found() {
+    let media = media_file(Uuid::new_v4());
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = get_media_file_query_handler(
+        GetMediaFileQuery {
+            media_id: media.id,
+            user_id: Uuid::new_v4(),
+        },
+        &repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(MediaRepositoryError::MediaFileNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_get_media_file_shared_with_user() {
+    let media = media_file(Uuid::new_v4());
+    let repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        media_metadata: vec![metadata(media.id)],
+        ..MockMediaRepository::default()
+    };
+    let user_id = Uuid::new_v4();
+    let grants = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Media(media.id),
+        user_id,
+        ShareRole::Viewer,
+    )]);
+
+    let result = get_media_file_query_handler(
+        GetMediaFileQuery {
+            media_id: media.id,
+            user_id,
+        },
+        &repo,
+        &test_authorization_service(grants),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.id, media.id);
+    // Only the owner sees where the photo was taken
+    let result_metadata = result.metadata.unwrap();
+    assert_eq!(result_metadata.camera_make.as_deref(), Some("Canon"));
+    assert_eq!(result_metadata.latitude, None);
+    assert_eq!(result_metadata.longitude, None);
+}
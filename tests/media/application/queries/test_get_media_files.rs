@@ -0,0 +1,219 @@
+use chrono::NaiveDateTime;
+use lib::media::{
+    application::queries::get_media_files::{
+        GetMediaFilesError, GetMediaFilesQuery, get_media_files_query_handler,
+    },
+    domain::{MediaFile, MediaFileFilter, MediaFileSort, MediaMetadata, MediaStatus},
+};
+use uuid::Uuid;
+
+use crate::media::MockMediaRepository;
+
+fn uploaded_at(day: u32) -> NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(2024, 3, day)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+fn media_file(user_id: Uuid, filename: &str, content_type: &str, day: u32) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: filename.to_string(),
+        original_filename: filename.to_string(),
+        file_size: 1024,
+        content_type: content_type.to_string(),
+        file_path: format!("media/{}/{}", user_id, filename),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+        uploaded_at: Some(uploaded_at(day)),
+        updated_at: Some(uploaded_at(day)),
+    }
+}
+
+fn library(user_id: Uuid) -> Vec<MediaFile> {
+    vec![
+        media_file(user_id, "beach.jpg", "image/jpeg", 1),
+        media_file(user_id, "Beach-Party.mp4", "video/mp4", 2),
+        media_file(user_id, "forest.png", "image/png", 3),
+        media_file(user_id, "city.jpg", "image/jpeg", 4),
+        media_file(user_id, "snow.jpg", "image/jpeg", 5),
+    ]
+}
+
+fn filenames(result: &lib::media::application::queries::GetMediaFilesPageResult) -> Vec<&str> {
+    result
+        .items
+        .iter()
+        .map(|item| item.original_filename.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_get_media_files_follows_cursor_until_last_page() {
+    let user_id = Uuid::new_v4();
+    let repo = MockMediaRepository {
+        media_files: library(user_id),
+        ..MockMediaRepository::default()
+    };
+
+    let first_page = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            limit: Some(2),
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(filenames(&first_page), vec!["snow.jpg", "city.jpg"]);
+
+    let second_page = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            limit: Some(2),
+            cursor: first_page.next_cursor.clone(),
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        filenames(&second_page),
+        vec!["forest.png", "Beach-Party.mp4"]
+    );
+
+    let last_page = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            limit: Some(2),
+            cursor: second_page.next_cursor.clone(),
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(filenames(&last_page), vec!["beach.jpg"]);
+    assert_eq!(last_page.next_cursor, None);
+}
+
+#[tokio::test]
+async fn test_get_media_files_oldest_first() {
+    let user_id = Uuid::new_v4();
+    let repo = MockMediaRepository {
+        media_files: library(user_id),
+        ..MockMediaRepository::default()
+    };
+
+    let result = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            sort: MediaFileSort::UploadedAtAsc,
+            limit: Some(3),
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        filenames(&result),
+        vec!["beach.jpg", "Beach-Party.mp4", "forest.png"]
+    );
+    assert!(result.next_cursor.is_some());
+}
+
+#[tokio::test]
+async fn test_get_media_files_filters() {
+    let user_id = Uuid::new_v4();
+    let repo = MockMediaRepository {
+        media_files: library(user_id),
+        ..MockMediaRepository::default()
+    };
+
+    let images = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            filter: MediaFileFilter {
+                content_type_prefix: Some("image/".to_string()),
+                uploaded_from: Some(uploaded_at(3)),
+                uploaded_to: Some(uploaded_at(5)),
+                ..MediaFileFilter::default()
+            },
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(filenames(&images), vec!["city.jpg", "forest.png"]);
+
+    let beach = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            filter: MediaFileFilter {
+                filename_contains: Some("beach".to_string()),
+                ..MediaFileFilter::default()
+            },
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(filenames(&beach), vec!["Beach-Party.mp4", "beach.jpg"]);
+    assert_eq!(beach.next_cursor, None);
+}
+
+#[tokio::test]
+async fn test_get_media_files_invalid_cursor() {
+    let repo = MockMediaRepository::default();
+
+    let result = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id: Uuid::new_v4(),
+            cursor: Some("not-a-cursor".to_string()),
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(GetMediaFilesError::InvalidCursor)));
+}
+
+#[tokio::test]
+async fn test_get_media_files_attaches_metadata() {
+    let user_id = Uuid::new_v4();
+    let with_metadata = media_file(user_id, "new.jpg", "image/jpeg", 2);
+    let without_metadata = media_file(user_id, "old.jpg", "image/jpeg", 1);
+    let repo = MockMediaRepository {
+        media_files: vec![with_metadata.clone(), without_metadata.clone()],
+        media_metadata: vec![MediaMetadata {
+            media_id: with_metadata.id,
+            camera_make: Some("Canon".to_string()),
+            ..MediaMetadata::default()
+        }],
+        ..MockMediaRepository::default()
+    };
+
+    let result = get_media_files_query_handler(
+        GetMediaFilesQuery {
+            user_id,
+            ..GetMediaFilesQuery::default()
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.items.len(), 2);
+    assert!(result.items[0].metadata.is_some());
+    assert!(result.items[1].metadata.is_none());
+}
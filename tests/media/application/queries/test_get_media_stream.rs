@@ -0,0 +1,484 @@
+use futures_util::TryStreamExt;
+use lib::{
+    media::{
+        application::queries::get_media_stream::{
+            GetMediaStreamError, GetMediaStreamQuery, MediaStreamAccess,
+            get_media_stream_query_handler,
+        },
+        domain::{ByteRange, MediaFile, MediaStatus, MediaUrlSigner},
+    },
+    sharing::domain::{ShareGrantAuthorizationService, ShareRole, ShareTarget},
+};
+use uuid::Uuid;
+
+use crate::{
+    media::{MockMediaRepository, MockStorageService, exif_jpeg, get_test_user_id},
+    sharing::{MockShareGrantRepository, share_grant, test_authorization_service},
+    utils::test_helpers::test_media_url_signer,
+};
+
+fn media_file(id: Uuid, file_size: i64) -> MediaFile {
+    let user_id = get_test_user_id();
+    MediaFile {
+        id,
+        user_id,
+        filename: "clip.mp4".to_string(),
+        original_filename: "clip.mp4".to_string(),
+        file_size,
+        content_type: "video/mp4".to_string(),
+        file_path: format!("media/{}/clip.mp4", user_id),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+fn mocks(media_id: Uuid) -> (MockMediaRepository, MockStorageService) {
+    let data: Vec<u8> = (0..100u8).collect();
+    let repo = MockMediaRepository {
+        saved_media: Some(media_file(media_id, data.len() as i64)),
+        ..MockMediaRepository::default()
+    };
+    let storage = MockStorageService {
+        file_data: data,
+        ..MockStorageService::default()
+    };
+    (repo, storage)
+}
+
+fn no_grants() -> ShareGrantAuthorizationService<MockShareGrantRepository> {
+    test_authorization_service(MockShareGrantRepository::default())
+}
+
+#[tokio::test]
+async fn test_get_media_stream_full_file() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.range, None);
+    assert_eq!(result.total_size, 100);
+    assert_eq!(result.content_type, "video/mp4");
+    assert_eq!(result.e_tag, format!("\"{}\"", media_id));
+    let chunks: Vec<_> = result.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat().len(), 100);
+}
+
+#[tokio::test]
+async fn test_get_media_stream_range() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: Some("bytes=10-19".to_string()),
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.range, Some(ByteRange { start: 10, end: 19 }));
+    let chunks: Vec<_> = result.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), (10..20u8).collect::<Vec<u8>>());
+}
+
+#[tokio::test]
+async fn test_get_media_stream_if_range_mismatch_returns_full_file() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: Some("bytes=10-19".to_string()),
+        if_range: Some("\"some-other-etag\"".to_string()),
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.range, None);
+}
+
+#[tokio::test]
+async fn test_get_media_stream_if_range_match_returns_range() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: Some("bytes=90-".to_string()),
+        if_range: Some(format!("\"{}\"", media_id)),
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.range, Some(ByteRange { start: 90, end: 99 }));
+}
+
+#[tokio::test]
+async fn test_get_media_stream_range_not_satisfiable() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: Some("bytes=100-".to_string()),
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await;
+
+    match result {
+        Err(GetMediaStreamError::RangeNotSatisfiable { total_size }) => {
+            assert_eq!(total_size, 100)
+        }
+        _ => panic!("Expected RangeNotSatisfiable error"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_media_stream_not_found() {
+    let repo = MockMediaRepository::default();
+    let storage = MockStorageService::default();
+    let query = GetMediaStreamQuery {
+        media_id: Uuid::new_v4(),
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(GetMediaStreamError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_get_media_stream_other_owner_not_found() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(Uuid::new_v4()),
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(GetMediaStreamError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_get_media_stream_signed_url() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let signer = test_media_url_signer();
+    let signed = signer.sign(media_id);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::SignedUrl {
+            expires_at: signed.expires_at,
+            signature: signed.signature,
+        },
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result =
+        get_media_stream_query_handler(query, &storage, &repo, &signer, &no_grants()).await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_media_stream_signed_url_for_other_media() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let signer = test_media_url_signer();
+    // Signature issued for a different media file must not be accepted
+    let signed = signer.sign(Uuid::new_v4());
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::SignedUrl {
+            expires_at: signed.expires_at,
+            signature: signed.signature,
+        },
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result =
+        get_media_stream_query_handler(query, &storage, &repo, &signer, &no_grants()).await;
+
+    assert!(matches!(result, Err(GetMediaStreamError::InvalidSignature)));
+}
+
+#[tokio::test]
+async fn test_get_media_stream_pending_media_not_found() {
+    let media_id = Uuid::new_v4();
+    let (mut repo, storage) = mocks(media_id);
+    repo.saved_media = repo.saved_media.map(|media_file| MediaFile {
+        status: MediaStatus::Pending,
+        checksum: None,
+        detected_content_type: None,
+        ..media_file
+    });
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(GetMediaStreamError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_get_media_stream_shared_with_user() {
+    let media_id = Uuid::new_v4();
+    let grantee_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let grants = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Media(media_id),
+        grantee_id,
+        ShareRole::Viewer,
+    )]);
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(grantee_id),
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &test_authorization_service(grants),
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_media_stream_shared_through_album() {
+    let media_id = Uuid::new_v4();
+    let album_id = Uuid::new_v4();
+    let grantee_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let grants = MockShareGrantRepository {
+        album_media: vec![(album_id, media_id)],
+        ..MockShareGrantRepository::with_grants(vec![share_grant(
+            ShareTarget::Album(album_id),
+            grantee_id,
+            ShareRole::Viewer,
+        )])
+    };
+    let query = GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(grantee_id),
+        range: None,
+        if_range: None,
+        strip_metadata: false,
+    };
+
+    let result = get_media_stream_query_handler(
+        query,
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &test_authorization_service(grants),
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+fn photo_mocks(media_id: Uuid, data: Vec<u8>) -> (MockMediaRepository, MockStorageService) {
+    let repo = MockMediaRepository {
+        saved_media: Some(MediaFile {
+            content_type: "image/jpeg".to_string(),
+            ..media_file(media_id, data.len() as i64)
+        }),
+        ..MockMediaRepository::default()
+    };
+    let storage = MockStorageService {
+        file_data: data,
+        ..MockStorageService::default()
+    };
+    (repo, storage)
+}
+
+fn strip_query(media_id: Uuid, range: Option<&str>) -> GetMediaStreamQuery {
+    GetMediaStreamQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        range: range.map(|range| range.to_string()),
+        if_range: None,
+        strip_metadata: true,
+    }
+}
+
+#[tokio::test]
+async fn test_get_media_stream_strips_location() {
+    let media_id = Uuid::new_v4();
+    let original = exif_jpeg(32, 32, 1, Some((52, 31)));
+    let (repo, storage) = photo_mocks(media_id, original.clone());
+
+    let result = get_media_stream_query_handler(
+        strip_query(media_id, None),
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.total_size, original.len() as u64);
+    assert_eq!(result.e_tag, format!("\"{}-stripped\"", media_id));
+    let chunks: Vec<_> = result.stream.try_collect().await.unwrap();
+    let stripped = chunks.concat();
+    assert_eq!(stripped.len(), original.len());
+    assert_ne!(stripped, original);
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::Cursor::new(&stripped))
+        .unwrap();
+    assert!(
+        exif.get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY)
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_get_media_stream_strips_location_from_range() {
+    let media_id = Uuid::new_v4();
+    let original = exif_jpeg(32, 32, 1, Some((52, 31)));
+    let (repo, storage) = photo_mocks(media_id, original.clone());
+
+    let full = get_media_stream_query_handler(
+        strip_query(media_id, None),
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+    let partial = get_media_stream_query_handler(
+        strip_query(media_id, Some("bytes=40-99")),
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await
+    .unwrap();
+
+    let full: Vec<_> = full.stream.try_collect().await.unwrap();
+    let partial: Vec<_> = partial.stream.try_collect().await.unwrap();
+    assert_eq!(partial.concat(), full.concat()[40..100]);
+}
+
+#[tokio::test]
+async fn test_get_media_stream_strip_unsupported_format() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+
+    let result = get_media_stream_query_handler(
+        strip_query(media_id, None),
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &no_grants(),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(GetMediaStreamError::MetadataNotStrippable(_))
+    ));
+}
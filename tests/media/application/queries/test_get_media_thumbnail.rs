@@ -0,0 +1,230 @@
+use futures_util::TryStreamExt;
+use lib::media::{
+    application::queries::{
+        get_media_stream::MediaStreamAccess,
+        get_media_thumbnail::{
+            GetMediaThumbnailError, GetMediaThumbnailQuery, get_media_thumbnail_query_handler,
+        },
+    },
+    domain::{MediaFile, MediaRendition, MediaStatus, MediaUrlSigner},
+};
+use uuid::Uuid;
+
+use crate::{
+    media::{MockMediaRepository, MockStorageService, get_test_user_id},
+    sharing::{MockShareGrantRepository, test_authorization_service},
+    utils::test_helpers::test_media_url_signer,
+};
+
+fn media_file(id: Uuid) -> MediaFile {
+    let user_id = get_test_user_id();
+    MediaFile {
+        id,
+        user_id,
+        filename: "photo.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 4096,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/photo.jpg", user_id),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+fn rendition(media_id: Uuid, name: &str, size: i32) -> MediaRendition {
+    MediaRendition {
+        media_id,
+        name: name.to_string(),
+        file_path: format!(
+            "media/{}/thumb_{}_{}.jpg",
+            get_test_user_id(),
+            media_id,
+            name
+        ),
+        content_type: "image/jpeg".to_string(),
+        width: Some(size),
+        height: Some(size / 2),
+        file_size: Some(10),
+    }
+}
+
+fn mocks(media_id: Uuid) -> (MockMediaRepository, MockStorageService) {
+    let repo = MockMediaRepository {
+        saved_media: Some(media_file(media_id)),
+        ..MockMediaRepository::default()
+    }
+    .with_renditions(vec![
+        rendition(media_id, "small", 64),
+        rendition(media_id, "medium", 300),
+    ]);
+    let storage = MockStorageService {
+        file_data: vec![7; 10],
+        ..MockStorageService::default()
+    };
+    (repo, storage)
+}
+
+async fn get_thumbnail(
+    media_id: Uuid,
+    access: MediaStreamAccess,
+    size: Option<&str>,
+) -> Result<lib::media::GetMediaThumbnailResult, GetMediaThumbnailError> {
+    let (repo, storage) = mocks(media_id);
+    get_media_thumbnail_query_handler(
+        GetMediaThumbnailQuery {
+            media_id,
+            access,
+            size: size.map(|size| size.to_string()),
+            accept: None,
+        },
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_defaults_to_medium() {
+    let media_id = Uuid::new_v4();
+
+    let result = get_thumbnail(media_id, MediaStreamAccess::User(get_test_user_id()), None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.content_type, "image/jpeg");
+    assert_eq!(result.content_length, Some(10));
+    assert_eq!(result.e_tag, format!("\"{}-medium-300x150-jpg\"", media_id));
+    let chunks: Vec<_> = result.stream.try_collect().await.unwrap();
+    assert_eq!(chunks.concat(), vec![7; 10]);
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_requested_size() {
+    let media_id = Uuid::new_v4();
+
+    let result = get_thumbnail(
+        media_id,
+        MediaStreamAccess::User(get_test_user_id()),
+        Some("small"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.e_tag, format!("\"{}-small-64x32-jpg\"", media_id));
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_unknown_size() {
+    let result = get_thumbnail(
+        Uuid::new_v4(),
+        MediaStreamAccess::User(get_test_user_id()),
+        Some("huge"),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(GetMediaThumbnailError::ThumbnailNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_of_other_users_media_not_found() {
+    let result = get_thumbnail(
+        Uuid::new_v4(),
+        MediaStreamAccess::User(Uuid::new_v4()),
+        None,
+    )
+    .await;
+
+    assert!(matches!(result, Err(GetMediaThumbnailError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_with_signed_url() {
+    let media_id = Uuid::new_v4();
+    let signed_url = test_media_url_signer().sign(media_id);
+
+    let result = get_thumbnail(
+        media_id,
+        MediaStreamAccess::SignedUrl {
+            expires_at: signed_url.expires_at,
+            signature: signed_url.signature,
+        },
+        Some("small"),
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_with_invalid_signature() {
+    let result = get_thumbnail(
+        Uuid::new_v4(),
+        MediaStreamAccess::SignedUrl {
+            expires_at: chrono::Utc::now().timestamp() as u64 + 300,
+            signature: "deadbeef".to_string(),
+        },
+        None,
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(GetMediaThumbnailError::InvalidSignature)
+    ));
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_in_accepted_format() {
+    let media_id = Uuid::new_v4();
+    let (repo, storage) = mocks(media_id);
+    let repo = repo.with_renditions(vec![
+        rendition(media_id, "medium", 300),
+        MediaRendition {
+            file_path: format!(
+                "media/{}/thumb_{}_medium.webp",
+                get_test_user_id(),
+                media_id
+            ),
+            content_type: "image/webp".to_string(),
+            ..rendition(media_id, "medium", 300)
+        },
+    ]);
+    let query = |accept: &str| GetMediaThumbnailQuery {
+        media_id,
+        access: MediaStreamAccess::User(get_test_user_id()),
+        size: None,
+        accept: Some(accept.to_string()),
+    };
+    let authorization_service = test_authorization_service(MockShareGrantRepository::default());
+
+    let webp = get_media_thumbnail_query_handler(
+        query("image/avif,image/webp,*/*;q=0.8"),
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &authorization_service,
+    )
+    .await
+    .unwrap();
+    let jpeg = get_media_thumbnail_query_handler(
+        query("*/*"),
+        &storage,
+        &repo,
+        &test_media_url_signer(),
+        &authorization_service,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(webp.content_type, "image/webp");
+    assert_eq!(webp.e_tag, format!("\"{}-medium-300x150-webp\"", media_id));
+    assert_eq!(jpeg.content_type, "image/jpeg");
+}
@@ -0,0 +1,53 @@
+// Byte range domain tests
+
+use lib::media::domain::{ByteRange, ByteRangeError};
+
+#[test]
+fn test_parse_closed_range() {
+    let range = ByteRange::parse("bytes=0-99", 1000).unwrap();
+    assert_eq!(range, Some(ByteRange { start: 0, end: 99 }));
+    assert_eq!(range.unwrap().length(), 100);
+}
+
+#[test]
+fn test_parse_open_ended_range() {
+    let range = ByteRange::parse("bytes=500-", 1000).unwrap();
+    assert_eq!(range, Some(ByteRange { start: 500, end: 999 }));
+}
+
+#[test]
+fn test_parse_suffix_range() {
+    let range = ByteRange::parse("bytes=-200", 1000).unwrap();
+    assert_eq!(range, Some(ByteRange { start: 800, end: 999 }));
+
+    // A suffix longer than the file returns the whole file
+    let range = ByteRange::parse("bytes=-5000", 1000).unwrap();
+    assert_eq!(range, Some(ByteRange { start: 0, end: 999 }));
+}
+
+#[test]
+fn test_parse_clamps_end_to_file_size() {
+    let range = ByteRange::parse("bytes=900-5000", 1000).unwrap();
+    assert_eq!(range, Some(ByteRange { start: 900, end: 999 }));
+}
+
+#[test]
+fn test_parse_unsatisfiable_range() {
+    assert_eq!(
+        ByteRange::parse("bytes=1000-", 1000),
+        Err(ByteRangeError::NotSatisfiable)
+    );
+    assert_eq!(
+        ByteRange::parse("bytes=-0", 1000),
+        Err(ByteRangeError::NotSatisfiable)
+    );
+}
+
+#[test]
+fn test_parse_ignored_ranges() {
+    // Unknown unit, malformed values and multiple ranges fall back to the full file
+    assert_eq!(ByteRange::parse("items=0-1", 1000), Ok(None));
+    assert_eq!(ByteRange::parse("bytes=abc-def", 1000), Ok(None));
+    assert_eq!(ByteRange::parse("bytes=50-10", 1000), Ok(None));
+    assert_eq!(ByteRange::parse("bytes=0-1,5-9", 1000), Ok(None));
+}
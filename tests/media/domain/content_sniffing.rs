@@ -0,0 +1,191 @@
+use lib::media::domain::{
+    DEFAULT_ALLOWED_CONTENT_TYPES, MediaTypeAllowlist, content_type_matches, sniff_content_type,
+};
+
+/// ISO base media file starting with an `ftyp` box
+fn ftyp(major_brand: &[u8; 4], compatible_brands: &[&[u8; 4]]) -> Vec<u8> {
+    let size = 16 + 4 * compatible_brands.len() as u32;
+    let mut data = size.to_be_bytes().to_vec();
+    data.extend(b"ftyp");
+    data.extend(major_brand);
+    data.extend([0, 0, 0, 0]);
+    for brand in compatible_brands {
+        data.extend(*brand);
+    }
+    data.extend([0, 0, 0, 8]);
+    data.extend(b"mdat");
+    data
+}
+
+#[test]
+fn test_sniff_image_content_types() {
+    assert_eq!(
+        sniff_content_type(b"\xFF\xD8\xFF\xE0\0\x10JFIF"),
+        Some("image/jpeg")
+    );
+    assert_eq!(
+        sniff_content_type(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"),
+        Some("image/png")
+    );
+    assert_eq!(sniff_content_type(b"GIF89a\x01\0"), Some("image/gif"));
+    assert_eq!(
+        sniff_content_type(b"RIFF\x24\0\0\0WEBPVP8 "),
+        Some("image/webp")
+    );
+    assert_eq!(
+        sniff_content_type(b"BM\x36\0\0\0\0\0\0\0\x36\0"),
+        Some("image/bmp")
+    );
+    assert_eq!(sniff_content_type(b"II*\0\x08\0\0\0"), Some("image/tiff"));
+    assert_eq!(sniff_content_type(b"MM\0*\0\0\0\x08"), Some("image/tiff"));
+}
+
+#[test]
+fn test_sniff_raw_content_types() {
+    assert_eq!(
+        sniff_content_type(b"II*\0\x10\0\0\0CR\x02\0"),
+        Some("image/x-canon-cr2")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"crx ", &[b"crx ", b"isom"])),
+        Some("image/x-canon-cr3")
+    );
+    assert_eq!(
+        sniff_content_type(b"FUJIFILMCCD-RAW 0201"),
+        Some("image/x-fuji-raf")
+    );
+    assert_eq!(
+        sniff_content_type(b"IIRO\x08\0\0\0"),
+        Some("image/x-olympus-orf")
+    );
+    assert_eq!(
+        sniff_content_type(b"IIU\0\x08\0\0\0"),
+        Some("image/x-panasonic-rw2")
+    );
+}
+
+#[test]
+fn test_sniff_iso_media_content_types() {
+    assert_eq!(
+        sniff_content_type(&ftyp(b"heic", &[b"mif1", b"heic"])),
+        Some("image/heic")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"avif", &[b"avif", b"mif1"])),
+        Some("image/avif")
+    );
+    // Generic brands name the codec among the compatible brands
+    assert_eq!(
+        sniff_content_type(&ftyp(b"mif1", &[b"mif1", b"avif"])),
+        Some("image/avif")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"mif1", &[b"mif1", b"heic"])),
+        Some("image/heic")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"mif1", &[b"mif1"])),
+        Some("image/heif")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"isom", &[b"isom", b"mp41"])),
+        Some("video/mp4")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"qt  ", &[b"qt  "])),
+        Some("video/quicktime")
+    );
+    assert_eq!(
+        sniff_content_type(&ftyp(b"3gp5", &[b"3gp5"])),
+        Some("video/3gpp")
+    );
+}
+
+#[test]
+fn test_sniff_video_content_types() {
+    assert_eq!(
+        sniff_content_type(b"\x1aE\xdf\xa3\x9fB\x86\x81\x01B\x82\x84webm"),
+        Some("video/webm")
+    );
+    assert_eq!(
+        sniff_content_type(b"\x1aE\xdf\xa3\xa3B\x86\x81\x01B\x82\x88matroska"),
+        Some("video/x-matroska")
+    );
+    assert_eq!(
+        sniff_content_type(b"RIFF\x24\0\0\0AVI LIST"),
+        Some("video/x-msvideo")
+    );
+    assert_eq!(
+        sniff_content_type(b"\0\0\x01\xba\x44\0\x04"),
+        Some("video/mpeg")
+    );
+}
+
+#[test]
+fn test_sniff_unknown_content() {
+    assert_eq!(sniff_content_type(b""), None);
+    assert_eq!(sniff_content_type(b"<svg xmlns="), None);
+    assert_eq!(sniff_content_type(b"<!DOCTYPE html>"), None);
+    assert_eq!(sniff_content_type(b"%PDF-1.7"), None);
+    assert_eq!(sniff_content_type(b"RIFF\x24\0\0\0WAVEfmt "), None);
+    // Too short to hold the brand
+    assert_eq!(sniff_content_type(b"\0\0\0\x18ftyp"), None);
+}
+
+#[test]
+fn test_content_type_matches() {
+    assert!(content_type_matches("image/jpeg", Some("image/jpeg")));
+    assert!(content_type_matches("Image/JPG; q=1", Some("image/jpeg")));
+    assert!(content_type_matches(
+        "image/x-nikon-nef",
+        Some("image/tiff")
+    ));
+    assert!(content_type_matches(
+        "image/x-dcraw",
+        Some("image/x-fuji-raf")
+    ));
+    assert!(content_type_matches("video/quicktime", Some("video/mp4")));
+    assert!(content_type_matches("image/heif", Some("image/heic")));
+
+    assert!(!content_type_matches("image/jpeg", Some("image/png")));
+    assert!(!content_type_matches("image/jpeg", None));
+    assert!(!content_type_matches(
+        "image/x-nikon-nef",
+        Some("image/x-fuji-raf")
+    ));
+    assert!(!content_type_matches("video/mp4", Some("image/heic")));
+}
+
+#[test]
+fn test_content_type_matches_unknown_magic_bytes() {
+    // Only the allowlist decides about content types without known magic bytes
+    assert!(content_type_matches("video/ogg", None));
+}
+
+#[test]
+fn test_default_allowed_content_types_are_recognized() {
+    let allowlist = MediaTypeAllowlist::default();
+
+    for content_type in DEFAULT_ALLOWED_CONTENT_TYPES {
+        assert!(allowlist.is_allowed(content_type));
+        assert!(
+            !content_type_matches(content_type, None),
+            "{} has no magic bytes",
+            content_type
+        );
+    }
+    assert!(!allowlist.is_allowed("text/html"));
+    assert!(!allowlist.is_allowed("image/svg+xml"));
+}
+
+#[test]
+fn test_parse_media_type_allowlist() {
+    let allowlist =
+        MediaTypeAllowlist::parse(" image/JPEG , video/mp4;codecs=avc1,,image/jpeg").unwrap();
+
+    assert_eq!(allowlist.content_types, vec!["image/jpeg", "video/mp4"]);
+    assert!(allowlist.is_allowed("image/jpeg"));
+    assert!(allowlist.is_allowed("video/MP4"));
+    assert!(!allowlist.is_allowed("image/png"));
+    assert_eq!(MediaTypeAllowlist::parse(" , "), None);
+}
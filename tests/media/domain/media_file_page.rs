@@ -0,0 +1,25 @@
+// Media listing cursor tests
+
+use lib::media::domain::MediaFileCursor;
+use uuid::Uuid;
+
+#[test]
+fn test_cursor_round_trip() {
+    let cursor = MediaFileCursor {
+        uploaded_at: chrono::NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_micro_opt(12, 30, 45, 123_456)
+            .unwrap(),
+        id: Uuid::new_v4(),
+    };
+
+    assert_eq!(MediaFileCursor::decode(&cursor.encode()), Some(cursor));
+}
+
+#[test]
+fn test_cursor_decode_rejects_malformed_tokens() {
+    assert_eq!(MediaFileCursor::decode("not-a-cursor"), None);
+    assert_eq!(MediaFileCursor::decode(""), None);
+    // Valid base64 of something that is not a cursor
+    assert_eq!(MediaFileCursor::decode("MTIzOmFiYw"), None);
+}
@@ -0,0 +1,119 @@
+// Media metadata extraction domain tests
+
+use std::io::Cursor;
+
+use exif::{Field, In, Rational, Tag, Value, experimental::Writer};
+use image::{DynamicImage, ImageFormat};
+use lib::media::domain::extract_image_metadata;
+use uuid::Uuid;
+
+/// Encodes a small JPEG and inserts an APP1 segment with the given EXIF fields after SOI
+fn jpeg_with_exif(fields: &[Field]) -> Vec<u8> {
+    let mut jpeg = Vec::new();
+    DynamicImage::new_rgb8(4, 3)
+        .write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)
+        .unwrap();
+
+    let mut writer = Writer::new();
+    for field in fields {
+        writer.push_field(field);
+    }
+    let mut tiff = Cursor::new(Vec::new());
+    writer.write(&mut tiff, false).unwrap();
+    let tiff = tiff.into_inner();
+
+    let mut segment = b"Exif\0\0".to_vec();
+    segment.extend_from_slice(&tiff);
+    let length = (segment.len() + 2) as u16;
+
+    let mut output = jpeg[..2].to_vec();
+    output.extend_from_slice(&[0xff, 0xe1]);
+    output.extend_from_slice(&length.to_be_bytes());
+    output.extend_from_slice(&segment);
+    output.extend_from_slice(&jpeg[2..]);
+    output
+}
+
+fn field(tag: Tag, value: Value) -> Field {
+    Field {
+        tag,
+        ifd_num: In::PRIMARY,
+        value,
+    }
+}
+
+fn ascii(value: &str) -> Value {
+    Value::Ascii(vec![value.as_bytes().to_vec()])
+}
+
+fn rationals(values: &[(u32, u32)]) -> Value {
+    Value::Rational(
+        values
+            .iter()
+            .map(|&(num, denom)| Rational { num, denom })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_extract_exif_metadata() {
+    let media_id = Uuid::new_v4();
+    let image = jpeg_with_exif(&[
+        field(Tag::Make, ascii("Canon")),
+        field(Tag::Model, ascii("EOS R6")),
+        field(Tag::Orientation, Value::Short(vec![6])),
+        field(Tag::DateTimeOriginal, ascii("2024:06:01 18:30:15")),
+        field(Tag::ExposureTime, rationals(&[(1, 125)])),
+        field(Tag::FNumber, rationals(&[(28, 10)])),
+        field(Tag::PhotographicSensitivity, Value::Short(vec![400])),
+        field(Tag::FocalLength, rationals(&[(50, 1)])),
+        field(Tag::GPSLatitudeRef, ascii("N")),
+        field(Tag::GPSLatitude, rationals(&[(40, 1), (25, 1), (12, 1)])),
+        field(Tag::GPSLongitudeRef, ascii("W")),
+        field(Tag::GPSLongitude, rationals(&[(3, 1), (42, 1), (18, 1)])),
+    ]);
+
+    let metadata = extract_image_metadata(media_id, &image);
+
+    assert_eq!(metadata.media_id, media_id);
+    assert_eq!(metadata.camera_make.as_deref(), Some("Canon"));
+    assert_eq!(metadata.camera_model.as_deref(), Some("EOS R6"));
+    assert_eq!(metadata.orientation, Some(6));
+    assert_eq!(
+        metadata.taken_at,
+        chrono::NaiveDate::from_ymd_opt(2024, 6, 1)
+            .unwrap()
+            .and_hms_opt(18, 30, 15)
+    );
+    assert_eq!(metadata.exposure_time.as_deref(), Some("1/125"));
+    assert_eq!(metadata.f_number, Some(2.8));
+    assert_eq!(metadata.iso, Some(400));
+    assert_eq!(metadata.focal_length, Some(50.0));
+    assert!((metadata.latitude.unwrap() - 40.42).abs() < 1e-6);
+    assert!((metadata.longitude.unwrap() + 3.705).abs() < 1e-6);
+    // Not in the EXIF data, read from the JPEG header instead
+    assert_eq!(metadata.width, Some(4));
+    assert_eq!(metadata.height, Some(3));
+}
+
+#[test]
+fn test_extract_metadata_without_exif_reads_dimensions() {
+    let mut png = Vec::new();
+    DynamicImage::new_rgb8(7, 5)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+
+    let metadata = extract_image_metadata(Uuid::new_v4(), &png);
+
+    assert_eq!(metadata.width, Some(7));
+    assert_eq!(metadata.height, Some(5));
+    assert_eq!(metadata.camera_make, None);
+    assert_eq!(metadata.taken_at, None);
+}
+
+#[test]
+fn test_extract_metadata_from_unknown_data_is_empty() {
+    let metadata = extract_image_metadata(Uuid::new_v4(), b"not an image");
+
+    assert!(metadata.is_empty());
+}
@@ -0,0 +1,130 @@
+use lib::media::domain::{RenditionConfig, RenditionFormat, RenditionSize};
+
+#[test]
+fn test_parse_rendition_config_sorts_by_size() {
+    let config = RenditionConfig::parse("preview:1080, small:64,medium:300").unwrap();
+
+    assert_eq!(
+        config.sizes,
+        vec![
+            RenditionSize::new("small", 64),
+            RenditionSize::new("medium", 300),
+            RenditionSize::new("preview", 1080),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_rendition_config_rejects_invalid_entries() {
+    assert_eq!(RenditionConfig::parse("small"), None);
+    assert_eq!(RenditionConfig::parse("small:0"), None);
+    assert_eq!(RenditionConfig::parse("small:abc"), None);
+    assert_eq!(RenditionConfig::parse(":64"), None);
+    assert_eq!(RenditionConfig::parse("../small:64"), None);
+    assert_eq!(RenditionConfig::parse("small:64,small:128"), None);
+}
+
+#[test]
+fn test_default_rendition_config() {
+    let names: Vec<String> = RenditionConfig::default()
+        .sizes
+        .into_iter()
+        .map(|size| size.name)
+        .collect();
+
+    assert_eq!(names, vec!["small", "medium", "preview"]);
+}
+
+#[test]
+fn test_parse_rendition_formats_keeps_jpeg_first() {
+    assert_eq!(
+        RenditionConfig::parse_formats("jpeg"),
+        Some(vec![RenditionFormat::Jpeg])
+    );
+    assert_eq!(
+        RenditionConfig::parse_formats(""),
+        Some(vec![RenditionFormat::Jpeg])
+    );
+    #[cfg(feature = "webp")]
+    assert_eq!(
+        RenditionConfig::parse_formats("webp, jpg,webp"),
+        Some(vec![RenditionFormat::Jpeg, RenditionFormat::WebP])
+    );
+}
+
+#[test]
+fn test_parse_rendition_formats_rejects_unknown_formats() {
+    assert_eq!(RenditionConfig::parse_formats("gif"), None);
+    assert_eq!(
+        RenditionConfig::parse_formats("jpeg,"),
+        Some(vec![RenditionFormat::Jpeg])
+    );
+}
+
+const ALL_FORMATS: [RenditionFormat; 3] = [
+    RenditionFormat::Jpeg,
+    RenditionFormat::WebP,
+    RenditionFormat::Avif,
+];
+
+#[test]
+fn test_negotiate_without_accept_serves_jpeg() {
+    assert_eq!(
+        RenditionFormat::negotiate(None, &ALL_FORMATS),
+        Some(RenditionFormat::Jpeg)
+    );
+    assert_eq!(
+        RenditionFormat::negotiate(Some("*/*"), &ALL_FORMATS),
+        Some(RenditionFormat::Jpeg)
+    );
+    assert_eq!(
+        RenditionFormat::negotiate(Some("image/*"), &ALL_FORMATS),
+        Some(RenditionFormat::Jpeg)
+    );
+}
+
+#[test]
+fn test_negotiate_prefers_explicitly_accepted_formats() {
+    // Sent by browsers for images
+    let accept = "image/avif,image/webp,image/apng,image/svg+xml,image/*,*/*;q=0.8";
+
+    assert_eq!(
+        RenditionFormat::negotiate(Some(accept), &ALL_FORMATS),
+        Some(RenditionFormat::Avif)
+    );
+    assert_eq!(
+        RenditionFormat::negotiate(
+            Some(accept),
+            &[RenditionFormat::Jpeg, RenditionFormat::WebP]
+        ),
+        Some(RenditionFormat::WebP)
+    );
+    assert_eq!(
+        RenditionFormat::negotiate(Some(accept), &[RenditionFormat::Jpeg]),
+        Some(RenditionFormat::Jpeg)
+    );
+}
+
+#[test]
+fn test_negotiate_honours_quality_values() {
+    assert_eq!(
+        RenditionFormat::negotiate(
+            Some("image/avif;q=0.5, image/webp;q=0.9, */*;q=0.1"),
+            &ALL_FORMATS
+        ),
+        Some(RenditionFormat::WebP)
+    );
+    assert_eq!(
+        RenditionFormat::negotiate(Some("image/webp;q=0, image/*"), &ALL_FORMATS),
+        Some(RenditionFormat::Jpeg)
+    );
+}
+
+#[test]
+fn test_negotiate_falls_back_to_jpeg() {
+    assert_eq!(
+        RenditionFormat::negotiate(Some("image/png"), &ALL_FORMATS),
+        Some(RenditionFormat::Jpeg)
+    );
+    assert_eq!(RenditionFormat::negotiate(Some("image/png"), &[]), None);
+}
@@ -0,0 +1,158 @@
+use std::io::Cursor;
+
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use lib::media::domain::{
+    ByteRange, FileStorageError, MetadataStrippingError, location_metadata_ranges, redact_stream,
+    supports_metadata_stripping,
+};
+
+use crate::media::{exif_jpeg, exif_tiff, file_stream};
+
+async fn strip(data: Vec<u8>) -> Vec<u8> {
+    let ranges = location_metadata_ranges(&data).unwrap();
+    let chunks: Vec<_> = redact_stream(file_stream(data), 0, ranges)
+        .try_collect()
+        .await
+        .unwrap();
+    chunks.concat()
+}
+
+fn read_exif(data: &[u8]) -> exif::Exif {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(data))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_strip_location_from_jpeg() {
+    let original = exif_jpeg(64, 48, 6, Some((52, 31)));
+    assert!(
+        read_exif(&original)
+            .get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY)
+            .is_some()
+    );
+
+    let stripped = strip(original.clone()).await;
+
+    assert_eq!(stripped.len(), original.len());
+    let exif = read_exif(&stripped);
+    assert!(
+        exif.get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY)
+            .is_none()
+    );
+    assert!(
+        exif.get_field(exif::Tag::GPSLatitudeRef, exif::In::PRIMARY)
+            .is_none()
+    );
+    // Everything else is kept
+    assert_eq!(
+        exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0)),
+        Some(6)
+    );
+    assert!(image::load_from_memory(&stripped).is_ok());
+}
+
+#[test]
+fn test_location_ranges_of_jpeg_without_gps() {
+    assert_eq!(
+        location_metadata_ranges(&exif_jpeg(16, 16, 1, None)),
+        Ok(vec![])
+    );
+}
+
+#[test]
+fn test_location_ranges_of_tiff() {
+    let ranges = location_metadata_ranges(&exif_tiff(1, Some((48, 51)))).unwrap();
+
+    assert_eq!(
+        ranges,
+        vec![
+            // Latitude values
+            ByteRange { start: 68, end: 91 },
+            // GPS directory
+            ByteRange { start: 38, end: 67 },
+        ]
+    );
+}
+
+#[test]
+fn test_location_ranges_of_xmp_segment() {
+    let xmp = b"<x:xmpmeta><exif:GPSLatitude>52,31N</exif:GPSLatitude></x:xmpmeta>";
+    let mut payload = b"http://ns.adobe.com/xap/1.0/\0".to_vec();
+    payload.extend(xmp);
+    let mut data = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    data.extend(((payload.len() + 2) as u16).to_be_bytes());
+    data.extend(&payload);
+    data.extend([0xFF, 0xD9]);
+
+    let ranges = location_metadata_ranges(&data).unwrap();
+
+    let xmp_start = 6 + payload.len() as u64 - xmp.len() as u64;
+    assert_eq!(
+        ranges,
+        vec![ByteRange {
+            start: xmp_start,
+            end: xmp_start + xmp.len() as u64 - 1,
+        }]
+    );
+}
+
+#[test]
+fn test_location_ranges_of_unsupported_formats() {
+    assert_eq!(
+        location_metadata_ranges(b"\x89PNG\r\n\x1a\n"),
+        Err(MetadataStrippingError::UnsupportedFormat)
+    );
+    assert_eq!(
+        location_metadata_ranges(b"\0\0\0\x18ftypmp42"),
+        Err(MetadataStrippingError::UnsupportedFormat)
+    );
+}
+
+#[test]
+fn test_location_ranges_of_truncated_headers() {
+    let original = exif_jpeg(16, 16, 1, Some((52, 31)));
+
+    assert!(matches!(
+        location_metadata_ranges(&original[..40]),
+        Err(MetadataStrippingError::InvalidMetadata(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_redact_stream_from_offset() {
+    let data = [1u8; 100];
+    let ranges = vec![
+        ByteRange { start: 10, end: 20 },
+        ByteRange {
+            start: 1055,
+            end: 1064,
+        },
+    ];
+
+    // The stream holds the bytes 1000 to 1099 of the file, in chunks the ranges span
+    let chunks: Vec<Result<Bytes, FileStorageError>> = data
+        .chunks(7)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+    let stream = Box::pin(futures_util::stream::iter(chunks));
+    let chunks: Vec<_> = redact_stream(stream, 1000, ranges)
+        .try_collect()
+        .await
+        .unwrap();
+    let redacted = chunks.concat();
+
+    let zeros: Vec<_> = (0..100).filter(|i| redacted[*i] == 0).collect();
+    assert_eq!(zeros, (55..=64).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_supports_metadata_stripping() {
+    assert!(supports_metadata_stripping("image/jpeg"));
+    assert!(supports_metadata_stripping("Image/TIFF; charset=binary"));
+    assert!(supports_metadata_stripping("image/x-nikon-nef"));
+    assert!(!supports_metadata_stripping("image/png"));
+    assert!(!supports_metadata_stripping("video/mp4"));
+}
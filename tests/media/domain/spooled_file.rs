@@ -0,0 +1,50 @@
+use std::io::Read;
+
+use lib::media::domain::{SpooledFile, TempFile};
+
+use crate::media::file_stream;
+
+fn read_all(file: &SpooledFile) -> Vec<u8> {
+    let mut data = Vec::new();
+    file.reader().unwrap().read_to_end(&mut data).unwrap();
+    data
+}
+
+#[tokio::test]
+async fn test_small_files_are_kept_in_memory() {
+    let data: Vec<u8> = (0..100).collect();
+
+    let file = SpooledFile::from_stream(file_stream(data.clone()), 1024)
+        .await
+        .unwrap();
+
+    assert!(matches!(file, SpooledFile::Memory(_)));
+    assert_eq!(read_all(&file), data);
+}
+
+#[tokio::test]
+async fn test_large_files_are_written_to_disk() {
+    let data: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+
+    let file = SpooledFile::from_stream(file_stream(data.clone()), 1024)
+        .await
+        .unwrap();
+
+    let path = match &file {
+        SpooledFile::Disk(temp_file) => temp_file.path().to_path_buf(),
+        SpooledFile::Memory(_) => panic!("Expected the file to be spooled to disk"),
+    };
+    assert_eq!(read_all(&file), data);
+
+    drop(file);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_temp_file_from_stream() {
+    let temp_file = TempFile::from_stream("test", file_stream(vec![7; 3000]))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(temp_file.path()).unwrap(), vec![7; 3000]);
+}
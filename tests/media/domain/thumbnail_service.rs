@@ -0,0 +1,478 @@
+use std::{io::Cursor, sync::Arc};
+
+use image::{DynamicImage, ImageFormat, RgbImage, RgbaImage};
+use lib::media::domain::{
+    ImageFormatDecoder, ImageThumbnailService, RenditionConfig, RenditionFormat, RenditionSize,
+    SpooledFile, ThumbnailDecodeConfig, ThumbnailError, ThumbnailService,
+};
+use uuid::Uuid;
+
+use crate::media::{MockMediaRepository, MockStorageService, exif_jpeg, file_stream};
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let image = DynamicImage::ImageRgba8(RgbaImage::new(width, height));
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+        .unwrap();
+    data
+}
+
+fn jpeg(width: u32, height: u32) -> Vec<u8> {
+    let image = DynamicImage::ImageRgb8(RgbImage::new(width, height));
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Jpeg)
+        .unwrap();
+    data
+}
+
+fn service(
+    repo: MockMediaRepository,
+) -> ImageThumbnailService<MockMediaRepository, MockStorageService> {
+    service_with_decode_config(repo, ThumbnailDecodeConfig::default())
+}
+
+fn service_with_decode_config(
+    repo: MockMediaRepository,
+    decode_config: ThumbnailDecodeConfig,
+) -> ImageThumbnailService<MockMediaRepository, MockStorageService> {
+    service_with_formats(repo, decode_config, vec![RenditionFormat::Jpeg])
+}
+
+fn service_with_formats(
+    repo: MockMediaRepository,
+    decode_config: ThumbnailDecodeConfig,
+    formats: Vec<RenditionFormat>,
+) -> ImageThumbnailService<MockMediaRepository, MockStorageService> {
+    ImageThumbnailService::new(
+        repo,
+        MockStorageService::default(),
+        RenditionConfig {
+            sizes: vec![
+                RenditionSize::new("small", 16),
+                RenditionSize::new("medium", 40),
+                RenditionSize::new("preview", 200),
+            ],
+            formats,
+        },
+        decode_config,
+    )
+}
+
+/// Reads a made up format whose files start with a marker followed by a PNG image
+struct PrefixedPngDecoder;
+
+impl ImageFormatDecoder for PrefixedPngDecoder {
+    fn supports(&self, content_type: &str) -> bool {
+        content_type == "image/x-prefixed"
+    }
+
+    fn decode(&self, original: &SpooledFile) -> Result<SpooledFile, ThumbnailError> {
+        match original {
+            SpooledFile::Memory(data) => Ok(SpooledFile::Memory(data[8..].to_vec())),
+            SpooledFile::Disk(_) => Err(ThumbnailError::ImageProcessingError(
+                "Expected an original in memory".to_string(),
+            )),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_generate_renditions_scales_down_keeping_aspect_ratio() {
+    let repo = MockMediaRepository::default();
+    let media_id = Uuid::new_v4();
+
+    service(repo.clone())
+        .generate_renditions(
+            media_id,
+            "media/user/photo.png",
+            file_stream(png(80, 40)),
+            "image/png",
+        )
+        .await
+        .unwrap();
+
+    let renditions = repo.renditions();
+    let dimensions: Vec<_> = renditions
+        .iter()
+        .map(|r| (r.name.as_str(), r.width, r.height))
+        .collect();
+    assert_eq!(
+        dimensions,
+        vec![
+            ("small", Some(16), Some(8)),
+            ("medium", Some(40), Some(20)),
+            // Never scaled up
+            ("preview", Some(80), Some(40)),
+        ]
+    );
+    assert!(renditions.iter().all(|r| r.content_type == "image/jpeg"));
+    assert_eq!(
+        renditions[0].file_path,
+        format!("media/user/thumb_{}_small.jpg", media_id)
+    );
+}
+
+#[tokio::test]
+async fn test_generate_renditions_reuses_file_of_identical_sizes() {
+    let repo = MockMediaRepository::default();
+    let media_id = Uuid::new_v4();
+
+    service(repo.clone())
+        .generate_renditions(
+            media_id,
+            "media/user/icon.png",
+            file_stream(png(10, 10)),
+            "image/png",
+        )
+        .await
+        .unwrap();
+
+    let renditions = repo.renditions();
+    assert_eq!(renditions.len(), 3);
+    assert!(
+        renditions
+            .iter()
+            .all(|r| r.file_path == format!("media/user/thumb_{}_small.jpg", media_id))
+    );
+}
+
+#[tokio::test]
+async fn test_generate_renditions_ignores_other_content_types() {
+    let repo = MockMediaRepository::default();
+
+    service(repo.clone())
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/clip.mp4",
+            file_stream(vec![0; 16]),
+            "video/mp4",
+        )
+        .await
+        .unwrap();
+
+    assert!(repo.renditions().is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_renditions_fails_on_invalid_image() {
+    let repo = MockMediaRepository::default();
+
+    let result = service(repo.clone())
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/broken.png",
+            file_stream(vec![0; 16]),
+            "image/png",
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert!(repo.renditions().is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_renditions_decodes_large_originals_from_disk() {
+    let repo = MockMediaRepository::default();
+    let decode_config = ThumbnailDecodeConfig {
+        max_in_memory_bytes: 64,
+        ..ThumbnailDecodeConfig::default()
+    };
+
+    service_with_decode_config(repo.clone(), decode_config)
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/photo.png",
+            file_stream(png(300, 150)),
+            "image/png",
+        )
+        .await
+        .unwrap();
+
+    let dimensions: Vec<_> = repo
+        .renditions()
+        .iter()
+        .map(|r| (r.width, r.height))
+        .collect();
+    assert_eq!(
+        dimensions,
+        vec![
+            (Some(16), Some(8)),
+            (Some(40), Some(20)),
+            (Some(200), Some(100))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_generate_renditions_rejects_images_over_the_decode_cap() {
+    let repo = MockMediaRepository::default();
+    // 80x40 RGBA pixels take 12800 bytes once decoded, PNGs cannot be decoded at a reduced size
+    let decode_config = ThumbnailDecodeConfig {
+        max_decoded_bytes: 10_000,
+        ..ThumbnailDecodeConfig::default()
+    };
+
+    let result = service_with_decode_config(repo.clone(), decode_config)
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/photo.png",
+            file_stream(png(80, 40)),
+            "image/png",
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ThumbnailError::ImageProcessingError(_))
+    ));
+    assert!(repo.renditions().is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_renditions_decodes_jpegs_over_the_decode_cap_at_a_reduced_size() {
+    let repo = MockMediaRepository::default();
+    // 400x200 RGB pixels take 240000 bytes once decoded, 60000 at half the size
+    let decode_config = ThumbnailDecodeConfig {
+        max_decoded_bytes: 100_000,
+        ..ThumbnailDecodeConfig::default()
+    };
+
+    service_with_decode_config(repo.clone(), decode_config)
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/panorama.jpg",
+            file_stream(jpeg(400, 200)),
+            "image/jpeg",
+        )
+        .await
+        .unwrap();
+
+    let dimensions: Vec<_> = repo
+        .renditions()
+        .iter()
+        .map(|r| (r.width, r.height))
+        .collect();
+    assert_eq!(
+        dimensions,
+        vec![
+            (Some(16), Some(8)),
+            (Some(40), Some(20)),
+            (Some(200), Some(100))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_generate_renditions_rejects_jpegs_over_the_decode_cap_at_every_scale() {
+    let repo = MockMediaRepository::default();
+    // An eighth of 400x200 RGB pixels still takes 3750 bytes
+    let decode_config = ThumbnailDecodeConfig {
+        max_decoded_bytes: 3_000,
+        ..ThumbnailDecodeConfig::default()
+    };
+
+    let result = service_with_decode_config(repo.clone(), decode_config)
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/panorama.jpg",
+            file_stream(jpeg(400, 200)),
+            "image/jpeg",
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ThumbnailError::ImageProcessingError(_))
+    ));
+    assert!(repo.renditions().is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_renditions_larger_than_the_decode_budget() {
+    let repo = MockMediaRepository::default();
+    let decode_config = ThumbnailDecodeConfig {
+        decode_memory_budget_bytes: 1024,
+        ..ThumbnailDecodeConfig::default()
+    };
+    let service = service_with_decode_config(repo.clone(), decode_config);
+
+    // Every image takes the whole budget, they are decoded one after the other
+    let (first, second) = tokio::join!(
+        service.generate_renditions(
+            Uuid::new_v4(),
+            "media/user/first.png",
+            file_stream(png(80, 40)),
+            "image/png",
+        ),
+        service.generate_renditions(
+            Uuid::new_v4(),
+            "media/user/second.png",
+            file_stream(png(80, 40)),
+            "image/png",
+        ),
+    );
+
+    first.unwrap();
+    second.unwrap();
+    assert_eq!(repo.renditions().len(), 6);
+}
+
+#[tokio::test]
+async fn test_generate_renditions_in_every_configured_format() {
+    let repo = MockMediaRepository::default();
+    let media_id = Uuid::new_v4();
+    let formats: Vec<_> = [
+        RenditionFormat::Jpeg,
+        RenditionFormat::WebP,
+        RenditionFormat::Avif,
+    ]
+    .into_iter()
+    .filter(RenditionFormat::is_supported)
+    .collect();
+
+    service_with_formats(
+        repo.clone(),
+        ThumbnailDecodeConfig::default(),
+        formats.clone(),
+    )
+    .generate_renditions(
+        media_id,
+        "media/user/photo.png",
+        file_stream(png(80, 40)),
+        "image/png",
+    )
+    .await
+    .unwrap();
+
+    let renditions = repo.renditions();
+    assert_eq!(renditions.len(), 3 * formats.len());
+    for format in formats {
+        let small = renditions
+            .iter()
+            .find(|r| r.name == "small" && r.content_type == format.content_type())
+            .unwrap();
+        assert_eq!(
+            small.file_path,
+            format!("media/user/thumb_{}_small.{}", media_id, format.extension())
+        );
+        assert_eq!((small.width, small.height), (Some(16), Some(8)));
+    }
+}
+
+#[cfg(feature = "webp")]
+#[tokio::test]
+async fn test_generate_renditions_shares_files_of_identical_sizes_per_format() {
+    let repo = MockMediaRepository::default();
+    let media_id = Uuid::new_v4();
+
+    service_with_formats(
+        repo.clone(),
+        ThumbnailDecodeConfig::default(),
+        vec![RenditionFormat::Jpeg, RenditionFormat::WebP],
+    )
+    .generate_renditions(
+        media_id,
+        "media/user/icon.png",
+        file_stream(png(10, 10)),
+        "image/png",
+    )
+    .await
+    .unwrap();
+
+    let renditions = repo.renditions();
+    assert_eq!(renditions.len(), 6);
+    assert!(renditions.iter().all(|r| {
+        let extension = match r.content_type.as_str() {
+            "image/webp" => "webp",
+            _ => "jpg",
+        };
+        r.file_path == format!("media/user/thumb_{}_small.{}", media_id, extension)
+    }));
+}
+
+#[tokio::test]
+async fn test_generate_renditions_with_format_decoder() {
+    let repo = MockMediaRepository::default();
+    let mut original = b"PREFIXED".to_vec();
+    original.extend(png(80, 40));
+
+    service(repo.clone())
+        .with_decoders(vec![Arc::new(PrefixedPngDecoder)])
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/photo.prefixed",
+            file_stream(original),
+            "image/x-prefixed",
+        )
+        .await
+        .unwrap();
+
+    let dimensions: Vec<_> = repo
+        .renditions()
+        .iter()
+        .map(|r| (r.width, r.height))
+        .collect();
+    assert_eq!(
+        dimensions,
+        vec![
+            (Some(16), Some(8)),
+            (Some(40), Some(20)),
+            (Some(80), Some(40))
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_generate_renditions_of_unsupported_format_fails() {
+    let repo = MockMediaRepository::default();
+    let mut original = b"PREFIXED".to_vec();
+    original.extend(png(80, 40));
+
+    // Without the decoder the image crate cannot read the original
+    let result = service(repo.clone())
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/photo.prefixed",
+            file_stream(original),
+            "image/x-prefixed",
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ThumbnailError::ImageProcessingError(_))
+    ));
+    assert!(repo.renditions().is_empty());
+}
+
+#[tokio::test]
+async fn test_generate_renditions_applies_exif_orientation() {
+    let repo = MockMediaRepository::default();
+
+    // Stored sideways, orientation 6 turns it clockwise into a portrait photo
+    service(repo.clone())
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/portrait.jpg",
+            file_stream(exif_jpeg(80, 40, 6, None)),
+            "image/jpeg",
+        )
+        .await
+        .unwrap();
+
+    let dimensions: Vec<_> = repo
+        .renditions()
+        .iter()
+        .map(|r| (r.width, r.height))
+        .collect();
+    assert_eq!(
+        dimensions,
+        vec![
+            (Some(8), Some(16)),
+            (Some(20), Some(40)),
+            (Some(40), Some(80))
+        ]
+    );
+}
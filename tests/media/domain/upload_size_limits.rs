@@ -0,0 +1,39 @@
+use lib::media::domain::{DEFAULT_MAX_UPLOAD_BYTES, MediaSizeLimits};
+
+#[test]
+fn test_max_bytes_for_exact_content_type_wins() {
+    let limits = MediaSizeLimits {
+        default_max_bytes: 100,
+        content_type_max_bytes: vec![("image/*".to_string(), 200), ("image/gif".to_string(), 50)],
+    };
+
+    assert_eq!(limits.max_bytes_for("image/gif"), 50);
+    assert_eq!(limits.max_bytes_for("Image/GIF; charset=binary"), 50);
+    assert_eq!(limits.max_bytes_for("image/jpeg"), 200);
+    assert_eq!(limits.max_bytes_for("video/mp4"), 100);
+}
+
+#[test]
+fn test_default_limits_allow_larger_videos() {
+    let limits = MediaSizeLimits::default();
+
+    assert_eq!(limits.max_bytes_for("image/jpeg"), DEFAULT_MAX_UPLOAD_BYTES);
+    assert!(limits.max_bytes_for("video/mp4") > DEFAULT_MAX_UPLOAD_BYTES);
+}
+
+#[test]
+fn test_parse_content_type_limits() {
+    assert_eq!(
+        MediaSizeLimits::parse_content_type_limits(" Video/* : 1000 ,image/gif:20,"),
+        Some(vec![
+            ("video/*".to_string(), 1000),
+            ("image/gif".to_string(), 20)
+        ])
+    );
+    assert_eq!(
+        MediaSizeLimits::parse_content_type_limits("video/*:lots"),
+        None
+    );
+    assert_eq!(MediaSizeLimits::parse_content_type_limits(":10"), None);
+    assert_eq!(MediaSizeLimits::parse_content_type_limits(" , "), None);
+}
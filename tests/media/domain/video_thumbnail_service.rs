@@ -0,0 +1,173 @@
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageFormat, RgbImage};
+use lib::media::domain::{
+    ExtractedVideo, FileStorageError, FileStream, ImageThumbnailService, RenditionConfig,
+    RenditionFormat, RenditionSize, ThumbnailDecodeConfig, ThumbnailError, ThumbnailService,
+    VideoThumbnailService,
+};
+use uuid::Uuid;
+
+use crate::media::{MockMediaRepository, MockStorageService, MockVideoFrameExtractor, file_stream};
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let image = DynamicImage::ImageRgb8(RgbImage::new(width, height));
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+        .unwrap();
+    data
+}
+
+fn service(repo: MockMediaRepository, video: Option<ExtractedVideo>) -> impl ThumbnailService {
+    VideoThumbnailService::new(
+        ImageThumbnailService::new(
+            repo.clone(),
+            MockStorageService::default(),
+            RenditionConfig {
+                sizes: vec![RenditionSize::new("small", 16)],
+                formats: vec![RenditionFormat::Jpeg],
+            },
+            ThumbnailDecodeConfig::default(),
+        ),
+        MockVideoFrameExtractor { video },
+        repo,
+    )
+}
+
+fn extracted_video() -> ExtractedVideo {
+    ExtractedVideo {
+        poster_frame: png(64, 36),
+        duration_seconds: Some(12.5),
+        video_codec: Some("h264".to_string()),
+        width: Some(1920),
+        height: Some(1080),
+        frame_rate: Some(29.97),
+    }
+}
+
+#[tokio::test]
+async fn test_video_renditions_generated_from_poster_frame() {
+    let repo = MockMediaRepository::default();
+    let media_id = Uuid::new_v4();
+
+    service(repo.clone(), Some(extracted_video()))
+        .generate_renditions(
+            media_id,
+            "media/user/clip.mp4",
+            file_stream(vec![0; 16]),
+            "video/mp4",
+        )
+        .await
+        .unwrap();
+
+    let renditions = repo.renditions();
+    assert_eq!(renditions.len(), 1);
+    assert_eq!(renditions[0].name, "small");
+    assert_eq!(
+        (renditions[0].width, renditions[0].height),
+        (Some(16), Some(9))
+    );
+}
+
+#[tokio::test]
+async fn test_video_metadata_saved() {
+    let repo = MockMediaRepository::default();
+    let media_id = Uuid::new_v4();
+
+    service(repo.clone(), Some(extracted_video()))
+        .generate_renditions(
+            media_id,
+            "media/user/clip.mov",
+            file_stream(vec![0; 16]),
+            "video/quicktime",
+        )
+        .await
+        .unwrap();
+
+    let metadata = repo.saved_metadata.lock().unwrap().clone();
+    assert_eq!(metadata.len(), 1);
+    assert_eq!(metadata[0].media_id, media_id);
+    assert_eq!(metadata[0].duration_seconds, Some(12.5));
+    assert_eq!(metadata[0].video_codec.as_deref(), Some("h264"));
+    assert_eq!(
+        (metadata[0].width, metadata[0].height),
+        (Some(1920), Some(1080))
+    );
+    assert_eq!(metadata[0].frame_rate, Some(29.97));
+}
+
+#[tokio::test]
+async fn test_images_passed_to_image_service() {
+    let repo = MockMediaRepository::default();
+
+    service(repo.clone(), None)
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/photo.png",
+            file_stream(png(32, 32)),
+            "image/png",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(repo.renditions().len(), 1);
+    assert!(repo.saved_metadata.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_unsupported_video_container_ignored() {
+    let repo = MockMediaRepository::default();
+
+    service(repo.clone(), Some(extracted_video()))
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/clip.avi",
+            file_stream(vec![0; 16]),
+            "video/x-msvideo",
+        )
+        .await
+        .unwrap();
+
+    assert!(repo.renditions().is_empty());
+    assert!(repo.saved_metadata.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_video_extraction_failure() {
+    let repo = MockMediaRepository::default();
+
+    let result = service(repo.clone(), None)
+        .generate_renditions(
+            Uuid::new_v4(),
+            "media/user/clip.webm",
+            file_stream(vec![0; 16]),
+            "video/webm",
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ThumbnailError::ImageProcessingError(_))
+    ));
+    assert!(repo.renditions().is_empty());
+    assert!(repo.saved_metadata.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_video_read_failure_is_a_storage_error() {
+    let repo = MockMediaRepository::default();
+    let video: FileStream = Box::pin(futures_util::stream::once(async {
+        Err(FileStorageError::InternalError(
+            "Connection reset".to_string(),
+        ))
+    }));
+
+    let result = service(repo.clone(), Some(extracted_video()))
+        .generate_renditions(Uuid::new_v4(), "media/user/clip.mp4", video, "video/mp4")
+        .await;
+
+    // Reading the video again may succeed, so the job is retried
+    assert!(matches!(result, Err(ThumbnailError::StorageError(_))));
+    assert!(repo.saved_metadata.lock().unwrap().is_empty());
+}
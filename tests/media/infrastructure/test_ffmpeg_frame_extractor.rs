@@ -0,0 +1,38 @@
+use std::{os::unix::fs::PermissionsExt, time::Duration};
+
+use lib::media::{
+    domain::{TempFile, VideoFrameExtractor, VideoFrameExtractorError},
+    infrastructure::{FfmpegFrameExtractor, FfmpegFrameExtractorConfig},
+};
+
+use crate::media::file_stream;
+
+/// Executable standing in for ffprobe and ffmpeg
+fn fake_command(script: &str) -> TempFile {
+    let file = TempFile::new("fake_ffmpeg");
+    std::fs::write(file.path(), format!("#!/bin/sh\n{}\n", script)).unwrap();
+    std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    file
+}
+
+#[tokio::test]
+async fn test_ffmpeg_frame_extractor_kills_runs_that_time_out() {
+    let command = fake_command("sleep 30");
+    let command_path = command.path().to_string_lossy().to_string();
+    let extractor = FfmpegFrameExtractor::new(FfmpegFrameExtractorConfig {
+        ffmpeg_path: command_path.clone(),
+        ffprobe_path: command_path,
+        poster_frame_offset_seconds: 1.0,
+        timeout: Duration::from_millis(200),
+    });
+
+    let started = std::time::Instant::now();
+    let result = extractor.extract(file_stream(vec![0; 16])).await;
+
+    // A container that stalls ffprobe would stall it again, the job is not retried
+    assert!(matches!(
+        result,
+        Err(VideoFrameExtractorError::ExtractionError(_))
+    ));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
@@ -0,0 +1,57 @@
+use std::{io::Read, os::unix::fs::PermissionsExt, time::Duration};
+
+use lib::media::{
+    domain::{ImageFormatDecoder, SpooledFile, TempFile, ThumbnailError},
+    infrastructure::{HeifCommandDecoder, HeifCommandDecoderConfig},
+};
+
+/// Executable standing in for heif-convert, called with `-q 95 <input> <output>`
+fn fake_heif_convert(script: &str) -> TempFile {
+    let file = TempFile::new("fake_heif_convert");
+    std::fs::write(file.path(), format!("#!/bin/sh\n{}\n", script)).unwrap();
+    std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    file
+}
+
+fn decoder(heif_convert: &TempFile, timeout: Duration) -> HeifCommandDecoder {
+    HeifCommandDecoder::new(HeifCommandDecoderConfig {
+        heif_convert_path: heif_convert.path().to_string_lossy().to_string(),
+        timeout,
+    })
+}
+
+#[tokio::test]
+async fn test_heif_command_decoder_reads_the_converted_image() {
+    let heif_convert = fake_heif_convert(r#"cp "$3" "$4""#);
+    let decoder = decoder(&heif_convert, Duration::from_secs(10));
+
+    let converted = tokio::task::spawn_blocking(move || {
+        decoder.decode(&SpooledFile::Memory(b"converted".to_vec()))
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    let mut data = Vec::new();
+    converted.reader().unwrap().read_to_end(&mut data).unwrap();
+    assert_eq!(data, b"converted");
+}
+
+#[tokio::test]
+async fn test_heif_command_decoder_kills_conversions_that_time_out() {
+    let heif_convert = fake_heif_convert("sleep 30");
+    let decoder = decoder(&heif_convert, Duration::from_millis(200));
+
+    let started = std::time::Instant::now();
+    let result = tokio::task::spawn_blocking(move || {
+        decoder.decode(&SpooledFile::Memory(b"malformed".to_vec()))
+    })
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        result,
+        Err(ThumbnailError::ImageProcessingError(_))
+    ));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
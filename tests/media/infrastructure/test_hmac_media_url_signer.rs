@@ -0,0 +1,59 @@
+use lib::media::{
+    domain::MediaUrlSigner,
+    infrastructure::{HmacMediaUrlSigner, HmacMediaUrlSignerConfig, derive_media_url_secret},
+};
+use uuid::Uuid;
+
+fn signer(secret_key: &str) -> HmacMediaUrlSigner {
+    HmacMediaUrlSigner::new(HmacMediaUrlSignerConfig {
+        secret_key: secret_key.to_string(),
+        ttl_seconds: 60,
+    })
+}
+
+#[test]
+fn test_sign_and_verify_success() {
+    let signer = signer("testsecret");
+    let media_id = Uuid::new_v4();
+    let signed = signer.sign(media_id);
+    assert_eq!(signed.media_id, media_id);
+    assert!(signed.expires_at > chrono::Utc::now().timestamp() as u64);
+    assert!(signer.verify(media_id, signed.expires_at, &signed.signature));
+}
+
+#[test]
+fn test_verify_rejects_tampered_expiry() {
+    let signer = signer("testsecret");
+    let media_id = Uuid::new_v4();
+    let signed = signer.sign(media_id);
+    assert!(!signer.verify(media_id, signed.expires_at + 3600, &signed.signature));
+}
+
+#[test]
+fn test_verify_rejects_other_key() {
+    let media_id = Uuid::new_v4();
+    let signed = signer("testsecret").sign(media_id);
+    assert!(!signer("othersecret").verify(media_id, signed.expires_at, &signed.signature));
+}
+
+#[test]
+fn test_verify_rejects_expired_and_malformed() {
+    let signer = signer("testsecret");
+    let media_id = Uuid::new_v4();
+    let signed = signer.sign(media_id);
+    assert!(!signer.verify(media_id, 1, &signed.signature));
+    assert!(!signer.verify(media_id, signed.expires_at, "not-hex"));
+}
+
+#[test]
+fn test_derived_secret_differs_from_jwt_secret() {
+    let derived = derive_media_url_secret("jwtsecret");
+    assert_eq!(derived, derive_media_url_secret("jwtsecret"));
+    assert_ne!(derived, derive_media_url_secret("otherjwtsecret"));
+    assert_ne!(derived, "jwtsecret");
+
+    // URLs signed with the derived key are not signed with the JWT secret itself
+    let media_id = Uuid::new_v4();
+    let signed = signer(&derived).sign(media_id);
+    assert!(!signer("jwtsecret").verify(media_id, signed.expires_at, &signed.signature));
+}
@@ -0,0 +1,103 @@
+use std::io::Cursor;
+
+use image::{DynamicImage, GenericImageView, ImageFormat, RgbImage};
+use lib::media::{
+    domain::{ImageFormatDecoder, SpooledFile, TempFile, ThumbnailError},
+    infrastructure::RawPreviewDecoder,
+};
+
+fn jpeg(width: u32, height: u32) -> Vec<u8> {
+    let image = DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x * 3) as u8, (y * 5) as u8, 128])
+    }));
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Jpeg)
+        .unwrap();
+    data
+}
+
+/// JPEG with another one nested in an APP1 segment, the way cameras embed EXIF thumbnails
+fn jpeg_with_thumbnail(width: u32, height: u32) -> Vec<u8> {
+    let preview = jpeg(width, height);
+    let thumbnail = jpeg(8, 8);
+    let mut segment = b"Exif\0\0".to_vec();
+    segment.extend(&thumbnail);
+
+    let mut data = preview[..2].to_vec();
+    data.extend([0xFF, 0xE1]);
+    data.extend(((segment.len() + 2) as u16).to_be_bytes());
+    data.extend(segment);
+    data.extend(&preview[2..]);
+    data
+}
+
+/// TIFF header, a small JPEG, sensor data with a stray start marker and the large preview
+fn raw_file(preview: &[u8]) -> Vec<u8> {
+    let mut data = b"II*\0".to_vec();
+    data.extend(vec![0x42; 100]);
+    data.extend(jpeg(16, 16));
+    data.extend([0xFF, 0xD8, 0xFF, 0x00, 0x13, 0x37]);
+    data.extend(vec![0x24; 500]);
+    data.extend(preview);
+    data.extend(vec![0x99; 100]);
+    data
+}
+
+fn decoded_dimensions(file: &SpooledFile) -> (u32, u32) {
+    let SpooledFile::Memory(data) = file else {
+        panic!("Previews are kept in memory");
+    };
+    image::load_from_memory_with_format(data, ImageFormat::Jpeg)
+        .unwrap()
+        .dimensions()
+}
+
+#[test]
+fn test_raw_preview_decoder_supports_raw_content_types() {
+    let decoder = RawPreviewDecoder::new();
+
+    assert!(decoder.supports("image/x-canon-cr2"));
+    assert!(decoder.supports("image/X-Nikon-NEF"));
+    assert!(!decoder.supports("image/jpeg"));
+}
+
+#[test]
+fn test_raw_preview_decoder_extracts_largest_preview() {
+    let preview = jpeg_with_thumbnail(64, 32);
+    let original = SpooledFile::Memory(raw_file(&preview));
+
+    let decoded = RawPreviewDecoder::new().decode(&original).unwrap();
+
+    assert_eq!(decoded_dimensions(&decoded), (64, 32));
+    let SpooledFile::Memory(data) = decoded else {
+        unreachable!()
+    };
+    assert_eq!(data, preview);
+}
+
+#[test]
+fn test_raw_preview_decoder_reads_originals_from_disk() {
+    let temp_file = TempFile::new("raw_test");
+    std::fs::write(temp_file.path(), raw_file(&jpeg(48, 40))).unwrap();
+
+    let decoded = RawPreviewDecoder::new()
+        .decode(&SpooledFile::Disk(temp_file))
+        .unwrap();
+
+    assert_eq!(decoded_dimensions(&decoded), (48, 40));
+}
+
+#[test]
+fn test_raw_preview_decoder_without_preview_fails() {
+    let mut data = b"II*\0".to_vec();
+    data.extend([0xFF, 0xD8, 0xFF, 0xE0, 0x00]);
+    data.extend(vec![0x42; 100]);
+
+    let result = RawPreviewDecoder::new().decode(&SpooledFile::Memory(data));
+
+    assert!(matches!(
+        result,
+        Err(ThumbnailError::ImageProcessingError(_))
+    ));
+}
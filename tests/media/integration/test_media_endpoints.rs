@@ -1,7 +1,12 @@
 use std::sync::Arc;
 
 use crate::{
-    media::{MockMediaRepository, TestTokenService, get_test_user_id},
+    jobs::MockJobRepository,
+    media::{
+        MockMediaRepository, MockStorageService, MockUploadSessionRepository, TestTokenService,
+        get_test_user_id,
+    },
+    users::{MockLoginTokenService, MockSessionRepository, MockUserRepository},
     utils::test_helpers::*,
 };
 use axum::{
@@ -9,7 +14,12 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
-use lib::{api::routes::api_routes, media::domain::MediaFile};
+use lib::{
+    api::routes::api_routes,
+    jobs::domain::{JobPayload, JobStatus},
+    media::domain::{MediaFile, MediaRendition, MediaStatus, StoredFileMetadata},
+    users::domain::{Role, User},
+};
 use tower::util::ServiceExt;
 use uuid::Uuid;
 
@@ -17,6 +27,25 @@ fn test_app(state: lib::api::http_server::AppState) -> Router<lib::api::http_ser
     api_routes(state)
 }
 
+/// User repository knowing the user `TestTokenService` authenticates
+fn test_user_repository(quota_bytes: Option<i64>) -> MockUserRepository {
+    MockUserRepository {
+        user: Some(User {
+            id: get_test_user_id(),
+            username: "alice".to_string(),
+            email: None,
+            password: "hashed_password".to_string(),
+            role: Role::User,
+            quota_bytes,
+            quota_items: None,
+            created_at: None,
+            updated_at: None,
+            must_change_password: false,
+        }),
+        ..MockUserRepository::default()
+    }
+}
+
 #[tokio::test]
 async fn test_get_media_files_success() {
     let user_id = get_test_user_id();
@@ -31,7 +60,9 @@ async fn test_get_media_files_success() {
             file_path: format!("media/{}/image1.jpg", user_id),
             uploaded_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: Some(format!("media/{}/thumb_image1.jpg", user_id)),
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
         },
         MediaFile {
             id: Uuid::new_v4(),
@@ -41,9 +72,11 @@ async fn test_get_media_files_success() {
             file_size: 2048,
             content_type: "video/mp4".to_string(),
             file_path: format!("media/{}/video1.mp4", user_id),
-            uploaded_at: Some(chrono::Utc::now().naive_utc()),
+            uploaded_at: Some(chrono::Utc::now().naive_utc() - chrono::Duration::minutes(1)),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: Some(format!("media/{}/thumb_video1.jpg", user_id)),
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
         },
     ];
 
@@ -70,16 +103,90 @@ async fn test_get_media_files_success() {
         .unwrap();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     
-    assert!(json["data"].is_array());
-    assert_eq!(json["data"].as_array().unwrap().len(), 2);
-    assert_eq!(json["data"][0]["original_filename"], "photo1.jpg");
-    assert_eq!(json["data"][1]["original_filename"], "movie1.mp4");
-    assert_eq!(json["data"][0]["content_type"], "image/jpeg");
-    assert_eq!(json["data"][1]["content_type"], "video/mp4");
-    assert_eq!(json["data"][0]["file_size"], 1024);
-    assert_eq!(json["data"][1]["file_size"], 2048);
+    let items = &json["data"]["items"];
+    assert!(items.is_array());
+    assert_eq!(items.as_array().unwrap().len(), 2);
+    assert_eq!(items[0]["original_filename"], "photo1.jpg");
+    assert_eq!(items[1]["original_filename"], "movie1.mp4");
+    assert_eq!(items[0]["content_type"], "image/jpeg");
+    assert_eq!(items[1]["content_type"], "video/mp4");
+    assert_eq!(items[0]["file_size"], 1024);
+    assert_eq!(items[1]["file_size"], 2048);
+    assert!(json["data"]["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn test_get_media_files_paginated_and_filtered() {
+    let user_id = get_test_user_id();
+    let media_files: Vec<MediaFile> = (0..3)
+        .map(|index| MediaFile {
+            id: Uuid::new_v4(),
+            user_id,
+            filename: format!("image{}.jpg", index),
+            original_filename: format!("photo{}.jpg", index),
+            file_size: 1024,
+            content_type: "image/jpeg".to_string(),
+            file_path: format!("media/{}/image{}.jpg", user_id, index),
+            uploaded_at: Some(chrono::Utc::now().naive_utc() - chrono::Duration::minutes(index)),
+            updated_at: Some(chrono::Utc::now().naive_utc()),
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
+        })
+        .collect();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            media_files,
+            ..MockMediaRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let get_page = |uri: String| {
+        let app = test_app(state.clone()).with_state(state.clone());
+        async move {
+            let request = Request::builder()
+                .method("GET")
+                .uri(uri)
+                .header("Authorization", "Bearer valid_token")
+                .body(Body::empty())
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        }
+    };
+
+    let first_page = get_page("/media?limit=2&content_type=image%2F&sort=uploaded_at_desc".to_string()).await;
+    assert_eq!(first_page["data"]["items"].as_array().unwrap().len(), 2);
+    assert_eq!(first_page["data"]["items"][0]["original_filename"], "photo0.jpg");
+    let cursor = first_page["data"]["next_cursor"].as_str().unwrap();
 
+    let second_page = get_page(format!("/media?limit=2&content_type=image%2F&cursor={}", cursor)).await;
+    assert_eq!(second_page["data"]["items"].as_array().unwrap().len(), 1);
+    assert_eq!(second_page["data"]["items"][0]["original_filename"], "photo2.jpg");
+    assert!(second_page["data"]["next_cursor"].is_null());
 
+    let no_videos = get_page("/media?content_type=video%2F".to_string()).await;
+    assert_eq!(no_videos["data"]["items"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_get_media_files_invalid_cursor() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/media?cursor=garbage")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
 #[tokio::test]
@@ -98,8 +205,8 @@ async fn test_get_media_files_empty_list() {
         .await
         .unwrap();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert!(json["data"].is_array());
-    assert_eq!(json["data"].as_array().unwrap().len(), 0);
+    assert!(json["data"]["items"].is_array());
+    assert_eq!(json["data"]["items"].as_array().unwrap().len(), 0);
 }
 
 #[tokio::test]
@@ -130,7 +237,9 @@ async fn test_delete_media_success() {
         file_path: format!("media/{}/test.jpg", user_id),
         uploaded_at: Some(chrono::Utc::now().naive_utc()),
         updated_at: Some(chrono::Utc::now().naive_utc()),
-        thumbnail_path: None,
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
     };
 
     let state = create_test_app_state(CreateTestAppStateArguments {
@@ -227,6 +336,60 @@ async fn test_upload_media_invalid_file_type() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+/// Multipart body with a single `file` field
+fn multipart_file_body(
+    boundary: &str,
+    filename: &str,
+    content_type: &str,
+    content: &[u8],
+) -> Vec<u8> {
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        boundary, filename, content_type
+    )
+    .into_bytes();
+    body.extend(content);
+    body.extend(format!("\r\n--{}--\r\n", boundary).into_bytes());
+    body
+}
+
+#[tokio::test]
+async fn test_upload_media_content_type_mismatch() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+
+    // A PNG declared as JPEG
+    let boundary = "----formdata-test-boundary";
+    let body = multipart_file_body(
+        boundary,
+        "photo.jpg",
+        "image/jpeg",
+        b"\x89PNG\r\n\x1a\npng content",
+    );
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/upload")
+        .header("Authorization", "Bearer valid_token")
+        .header("x-file-size", "19")
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["message"].as_str().unwrap().contains("image/png"));
+}
+
 #[tokio::test]
 async fn test_upload_media_no_file() {
     let state = create_default_test_app_state();
@@ -272,3 +435,775 @@ async fn test_upload_media_unauthorized() {
     let response = app.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
+
+fn stream_test_media(media_id: Uuid) -> MediaFile {
+    let user_id = get_test_user_id();
+    MediaFile {
+        id: media_id,
+        user_id,
+        filename: "clip.mp4".to_string(),
+        original_filename: "clip.mp4".to_string(),
+        file_size: 100,
+        content_type: "video/mp4".to_string(),
+        file_path: format!("media/{}/clip.mp4", user_id),
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+    }
+}
+
+fn stream_test_state(media_id: Uuid) -> lib::api::http_server::AppState {
+    create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(stream_test_media(media_id)),
+            ..MockMediaRepository::default()
+        }),
+        storage_service: Some(MockStorageService {
+            file_data: (0..100u8).collect(),
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    })
+}
+
+#[tokio::test]
+async fn test_stream_media_full_file() {
+    let media_id = Uuid::new_v4();
+    let state = stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/stream/{}", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["accept-ranges"], "bytes");
+    assert_eq!(response.headers()["content-length"], "100");
+    assert_eq!(response.headers()["content-type"], "video/mp4");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body.len(), 100);
+}
+
+#[tokio::test]
+async fn test_stream_media_partial_content() {
+    let media_id = Uuid::new_v4();
+    let state = stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/stream/{}", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .header("Range", "bytes=10-19")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()["content-range"], "bytes 10-19/100");
+    assert_eq!(response.headers()["content-length"], "10");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body.to_vec(), (10..20u8).collect::<Vec<u8>>());
+}
+
+#[tokio::test]
+async fn test_stream_media_range_not_satisfiable() {
+    let media_id = Uuid::new_v4();
+    let state = stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/stream/{}", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .header("Range", "bytes=500-")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()["content-range"], "bytes */100");
+}
+
+#[tokio::test]
+async fn test_stream_media_unauthorized() {
+    let media_id = Uuid::new_v4();
+    let state = stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/stream/{}", media_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+/// Stream test state authenticating the test user with a session that was revoked
+fn revoked_session_stream_test_state(media_id: Uuid) -> lib::api::http_server::AppState {
+    let (session_repo, session_id) = MockSessionRepository::with_session(get_test_user_id());
+    session_repo.sessions.lock().unwrap()[0].revoked_at = Some(chrono::Utc::now().naive_utc());
+    create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(MockLoginTokenService {
+            user_id: Some(get_test_user_id()),
+            sid: Some(session_id),
+            ..MockLoginTokenService::default()
+        })),
+        session_repo: Some(session_repo),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(stream_test_media(media_id)),
+            ..MockMediaRepository::default()
+        }),
+        storage_service: Some(MockStorageService {
+            file_data: (0..100u8).collect(),
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    })
+}
+
+#[tokio::test]
+async fn test_stream_media_revoked_session() {
+    let media_id = Uuid::new_v4();
+    let state = revoked_session_stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/stream/{}", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_revoked_session() {
+    let media_id = Uuid::new_v4();
+    let state = revoked_session_stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/{}/thumbnail", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_stream_media_with_signed_url() {
+    let media_id = Uuid::new_v4();
+    let state = stream_test_state(media_id);
+
+    let app = test_app(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/media/{}/signed-url", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let url = json["data"]["url"].as_str().unwrap();
+    assert!(url.starts_with(&format!("/api/media/stream/{}?", media_id)));
+
+    // The signed URL works without an Authorization header
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(url.trim_start_matches("/api"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_stream_media_with_invalid_signature() {
+    let media_id = Uuid::new_v4();
+    let state = stream_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let expires = chrono::Utc::now().timestamp() + 300;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/media/stream/{}?expires={}&signature=deadbeef",
+            media_id, expires
+        ))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_signed_url_for_other_users_media_not_found() {
+    let media_id = Uuid::new_v4();
+    // Default token service authenticates a random user that does not own the media
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(stream_test_media(media_id)),
+            ..MockMediaRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/media/{}/signed-url", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+/// Smallest ISO base media file header that is recognized as `video/mp4`
+const MP4_HEAD: &[u8] = b"\x00\x00\x00\x10ftypisom\x00\x00\x02\x00";
+
+fn upload_session_test_state(
+    upload_session_repo: MockUploadSessionRepository,
+) -> lib::api::http_server::AppState {
+    create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(None)),
+        upload_session_repo: Some(upload_session_repo),
+        storage_service: Some(MockStorageService {
+            file_data: MP4_HEAD.to_vec(),
+            file_metadata: Some(StoredFileMetadata {
+                file_size: MP4_HEAD.len() as u64,
+                content_type: Some("video/mp4".to_string()),
+            }),
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    })
+}
+
+#[tokio::test]
+async fn test_resumable_upload_flow() {
+    let state = upload_session_test_state(MockUploadSessionRepository::default());
+
+    let app = test_app(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/uploads")
+        .header("Authorization", "Bearer valid_token")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            r#"{"filename":"movie.mp4","file_size":16,"content_type":"video/mp4"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let upload_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let app = test_app(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method("PATCH")
+        .uri(format!("/media/uploads/{}", upload_id))
+        .header("Authorization", "Bearer valid_token")
+        .header("Upload-Offset", "0")
+        .body(Body::from(MP4_HEAD))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()["upload-offset"], "16");
+
+    let app = test_app(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method("HEAD")
+        .uri(format!("/media/uploads/{}", upload_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()["upload-offset"], "16");
+    assert_eq!(response.headers()["upload-length"], "16");
+
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/media/uploads/{}/finalize", upload_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_upload_chunk_offset_mismatch_conflict() {
+    let repo = MockUploadSessionRepository::default();
+    let session = lib::media::domain::UploadSession {
+        id: Uuid::new_v4(),
+        user_id: get_test_user_id(),
+        filename: "upload.mp4".to_string(),
+        original_filename: "movie.mp4".to_string(),
+        file_size: 10,
+        content_type: "video/mp4".to_string(),
+        file_path: format!("media/{}/upload.mp4", get_test_user_id()),
+        storage_upload_id: "mock-upload-id".to_string(),
+        upload_offset: 0,
+        part_etags: Vec::new(),
+        expires_at: (chrono::Utc::now() + chrono::Duration::hours(1)).naive_utc(),
+        created_at: None,
+        updated_at: None,
+    };
+    repo.sessions.lock().unwrap().push(session.clone());
+    let state = upload_session_test_state(repo);
+
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PATCH")
+        .uri(format!("/media/uploads/{}", session.id))
+        .header("Authorization", "Bearer valid_token")
+        .header("Upload-Offset", "4")
+        .body(Body::from("456789"))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_finalize_unknown_upload_session_not_found() {
+    let state = upload_session_test_state(MockUploadSessionRepository::default());
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/media/uploads/{}/finalize", Uuid::new_v4()))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_request_upload_returns_presigned_url() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(None)),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/upload/request")
+        .header("Authorization", "Bearer valid_token")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            r#"{"filename":"photo.jpg","file_size":1024,"content_type":"image/jpeg"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["method"], "PUT");
+    assert!(json["data"]["upload_url"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn test_confirm_upload_not_uploaded_conflict() {
+    let media_id = Uuid::new_v4();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(MediaFile {
+                status: MediaStatus::Pending,
+                checksum: None,
+                detected_content_type: None,
+                ..stream_test_media(media_id)
+            }),
+            ..MockMediaRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/media/{}/confirm", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_check_media_checksums() {
+    let media_id = Uuid::new_v4();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            media_files: vec![MediaFile {
+                checksum: Some("aaaa".to_string()),
+                detected_content_type: None,
+                ..stream_test_media(media_id)
+            }],
+            ..MockMediaRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/checksums")
+        .header("Authorization", "Bearer valid_token")
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"{"checksums":["aaaa","bbbb"]}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"][0]["media_id"], media_id.to_string());
+}
+
+#[tokio::test]
+async fn test_check_media_checksums_empty_list() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/checksums")
+        .header("Authorization", "Bearer valid_token")
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"{"checksums":[]}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_media_file_detail() {
+    let media_id = Uuid::new_v4();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(stream_test_media(media_id)),
+            media_metadata: vec![lib::media::domain::MediaMetadata {
+                media_id,
+                camera_model: Some("Pixel 8".to_string()),
+                ..Default::default()
+            }],
+            ..MockMediaRepository::default()
+        }
+        .with_renditions(vec![
+            rendition(media_id, "preview", 1080),
+            rendition(media_id, "small", 64),
+        ])),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/{}", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["id"], media_id.to_string());
+    assert_eq!(json["data"]["metadata"]["camera_model"], "Pixel 8");
+    assert_eq!(json["data"]["renditions"], serde_json::json!(["small", "preview"]));
+}
+
+fn rendition(media_id: Uuid, name: &str, size: i32) -> MediaRendition {
+    MediaRendition {
+        media_id,
+        name: name.to_string(),
+        file_path: format!("media/{}/thumb_{}_{}.jpg", get_test_user_id(), media_id, name),
+        content_type: "image/jpeg".to_string(),
+        width: Some(size),
+        height: Some(size),
+        file_size: Some(100),
+    }
+}
+
+fn thumbnail_test_state(media_id: Uuid) -> lib::api::http_server::AppState {
+    create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(
+            MockMediaRepository {
+                saved_media: Some(stream_test_media(media_id)),
+                ..MockMediaRepository::default()
+            }
+            .with_renditions(vec![rendition(media_id, "medium", 300)]),
+        ),
+        storage_service: Some(MockStorageService {
+            file_data: (0..100u8).collect(),
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    })
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_default_size() {
+    let media_id = Uuid::new_v4();
+    let state = thumbnail_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/{}/thumbnail", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/jpeg");
+    assert_eq!(response.headers()["content-length"], "100");
+    assert!(response.headers().contains_key("etag"));
+    assert!(response.headers().contains_key("cache-control"));
+    assert_eq!(response.headers()["vary"], "accept");
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_missing_size_not_found() {
+    let media_id = Uuid::new_v4();
+    let state = thumbnail_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/{}/thumbnail?size=preview", media_id))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_media_thumbnail_unauthorized() {
+    let media_id = Uuid::new_v4();
+    let state = thumbnail_test_state(media_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/media/{}/thumbnail", media_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_upload_media_enqueues_processing_job() {
+    let job_repo = MockJobRepository::default();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(None)),
+        job_repo: Some(job_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+
+    let boundary = "----formdata-test-boundary";
+    let body = multipart_file_body(
+        boundary,
+        "photo.jpg",
+        "image/jpeg",
+        b"\xFF\xD8\xFFjpeg content",
+    );
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/upload")
+        .header("Authorization", "Bearer valid_token")
+        .header("x-file-size", "15")
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let media_id = Uuid::parse_str(json["data"]["id"].as_str().unwrap()).unwrap();
+
+    let jobs = job_repo.jobs.lock().unwrap().clone();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].payload, JobPayload::GenerateThumbnail { media_id });
+    assert_eq!(jobs[0].user_id, Some(get_test_user_id()));
+    assert_eq!(jobs[0].status, JobStatus::Pending);
+}
+
+#[tokio::test]
+async fn test_upload_media_quota_exceeded() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(Some(10))),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+
+    let boundary = "----formdata-test-boundary";
+    let body = multipart_file_body(
+        boundary,
+        "photo.jpg",
+        "image/jpeg",
+        b"\xFF\xD8\xFFjpeg content",
+    );
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/upload")
+        .header("Authorization", "Bearer valid_token")
+        .header("x-file-size", "15")
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_get_storage_usage() {
+    let user_id = get_test_user_id();
+    let media_files: Vec<MediaFile> = [1024, 2048]
+        .into_iter()
+        .map(|file_size| MediaFile {
+            id: Uuid::new_v4(),
+            user_id,
+            filename: "image.jpg".to_string(),
+            original_filename: "photo.jpg".to_string(),
+            file_size,
+            content_type: "image/jpeg".to_string(),
+            file_path: format!("media/{}/image.jpg", user_id),
+            uploaded_at: None,
+            updated_at: None,
+            status: MediaStatus::Ready,
+            checksum: None,
+            detected_content_type: None,
+        })
+        .collect();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(Some(1_000_000))),
+        media_repo: Some(MockMediaRepository {
+            media_files,
+            ..MockMediaRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/media/usage")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["used_bytes"], 3072);
+    assert_eq!(json["data"]["used_items"], 2);
+    assert_eq!(json["data"]["quota_bytes"], 1_000_000);
+    assert_eq!(json["data"]["quota_items"], lib::users::domain::DEFAULT_QUOTA_ITEMS);
+}
+
+#[tokio::test]
+async fn test_upload_media_declared_size_too_large() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(None)),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+
+    let boundary = "----formdata-test-boundary";
+    let body = multipart_file_body(
+        boundary,
+        "photo.jpg",
+        "image/jpeg",
+        b"\xFF\xD8\xFFjpeg content",
+    );
+
+    // Rejected before the content is stored
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/upload")
+        .header("Authorization", "Bearer valid_token")
+        .header("x-file-size", "1099511627776")
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_upload_media_size_mismatch() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(test_user_repository(None)),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+
+    let boundary = "----formdata-test-boundary";
+    let body = multipart_file_body(
+        boundary,
+        "photo.jpg",
+        "image/jpeg",
+        b"\xFF\xD8\xFFjpeg content",
+    );
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/media/upload")
+        .header("Authorization", "Bearer valid_token")
+        .header("x-file-size", "1000")
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -1,12 +1,19 @@
 use async_trait::async_trait;
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use bytes::Bytes;
 use futures_core::Stream;
+use futures_util::TryStreamExt;
 use lib::{
     media::{
-        FileStorageError, FileStream, MediaId, ThumbnailError, ThumbnailService, UploadedFileMetadata,
+        ByteRange, FileStorageError, FileStream, MediaId, ThumbnailError, ThumbnailService, UploadedFileMetadata,
         domain::{
-            FileStorageService, MediaFile, MediaRepository, MediaRepositoryError, NewMediaFile,
+            FileStorageService, IncompleteMultipartUpload, MediaFile, MediaFileCursor, MediaFilePage, MediaFilePageRequest,
+            MediaFileSort, MediaMetadata, MediaMetadataError,
+            MediaMetadataService, MediaRendition, MediaRepository, MediaRepositoryError, MediaStatus,
+            NewMediaFile, NewUploadSession, PresignedUpload, StorageUsage, StoredFileMetadata, UploadSession, UploadSessionId, UploadSessionRepository,
+            UploadSessionRepositoryError, UploadedPart, ExtractedVideo, VideoFrameExtractor,
+            VideoFrameExtractorError,
         },
     },
     users::domain::{Claims, LoginTokenService, Token, user::UserLoginError},
@@ -19,6 +26,41 @@ pub struct MockMediaRepository {
     pub fail_get: bool,
     pub saved_media: Option<MediaFile>,
     pub media_files: Vec<MediaFile>,
+    pub media_metadata: Vec<MediaMetadata>,
+    /// Shared between clones so renditions saved by a service can be inspected
+    pub media_renditions: Arc<Mutex<Vec<MediaRendition>>>,
+    pub saved_metadata: Arc<Mutex<Vec<MediaMetadata>>>,
+    pub deleted_media_ids: Arc<Mutex<Vec<Uuid>>>,
+    /// Ready media file a concurrent upload of the same content stores, checksum lookups only
+    /// find it once storing another media file ran into it
+    pub concurrent_media: Option<MediaFile>,
+    pub concurrent_media_stored: Arc<Mutex<bool>>,
+}
+
+impl MockMediaRepository {
+    /// Fails like the unique checksum index when the concurrent media file holds the content
+    fn check_concurrent_media(
+        &self,
+        user_id: Uuid,
+        checksum: Option<&String>,
+    ) -> Result<(), MediaRepositoryError> {
+        match &self.concurrent_media {
+            Some(media) if media.user_id == user_id && media.checksum.as_ref() == checksum => {
+                *self.concurrent_media_stored.lock().unwrap() = true;
+                Err(MediaRepositoryError::DuplicateChecksum)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn with_renditions(mut self, renditions: Vec<MediaRendition>) -> Self {
+        self.media_renditions = Arc::new(Mutex::new(renditions));
+        self
+    }
+
+    pub fn renditions(&self) -> Vec<MediaRendition> {
+        self.media_renditions.lock().unwrap().clone()
+    }
 }
 
 #[async_trait]
@@ -30,6 +72,7 @@ impl MediaRepository for MockMediaRepository {
         if self.fail_save {
             return Err(MediaRepositoryError::InternalServerError);
         }
+        self.check_concurrent_media(media_file.user_id, media_file.checksum.as_ref())?;
         Ok(MediaFile {
             id: Uuid::new_v4(),
             user_id: media_file.user_id,
@@ -40,7 +83,9 @@ impl MediaRepository for MockMediaRepository {
             file_path: media_file.file_path,
             uploaded_at: Some(chrono::Utc::now().naive_utc()),
             updated_at: Some(chrono::Utc::now().naive_utc()),
-            thumbnail_path: media_file.thumbnail_path,
+            status: media_file.status,
+            checksum: media_file.checksum,
+            detected_content_type: media_file.detected_content_type,
         })
     }
 
@@ -57,47 +102,204 @@ impl MediaRepository for MockMediaRepository {
     async fn get_media_files_by_user_id(
         &self,
         user_id: Uuid,
-    ) -> Result<Vec<MediaFile>, MediaRepositoryError> {
+        page: MediaFilePageRequest,
+    ) -> Result<MediaFilePage, MediaRepositoryError> {
         if self.fail_get {
             return Err(MediaRepositoryError::InternalServerError);
         }
-        Ok(self
+        let filter = &page.filter;
+        let key = |f: &MediaFile| (f.uploaded_at.unwrap_or_default(), f.id);
+        let mut media_files: Vec<MediaFile> = self
             .media_files
             .clone()
             .into_iter()
-            .filter(|f| f.user_id == user_id)
+            .filter(|f| f.user_id == user_id && f.status == MediaStatus::Ready)
+            .filter(|f| {
+                filter
+                    .content_type_prefix
+                    .as_ref()
+                    .is_none_or(|prefix| f.content_type.starts_with(prefix.as_str()))
+            })
+            .filter(|f| filter.uploaded_from.is_none_or(|from| key(f).0 >= from))
+            .filter(|f| filter.uploaded_to.is_none_or(|to| key(f).0 < to))
+            .filter(|f| {
+                filter.filename_contains.as_ref().is_none_or(|substring| {
+                    f.original_filename
+                        .to_lowercase()
+                        .contains(&substring.to_lowercase())
+                })
+            })
+            .filter(|f| {
+                page.cursor.is_none_or(|cursor| match page.sort {
+                    MediaFileSort::UploadedAtDesc => key(f) < (cursor.uploaded_at, cursor.id),
+                    MediaFileSort::UploadedAtAsc => key(f) > (cursor.uploaded_at, cursor.id),
+                })
+            })
+            .collect();
+        media_files.sort_by_key(key);
+        if page.sort == MediaFileSort::UploadedAtDesc {
+            media_files.reverse();
+        }
+        let next_cursor = if media_files.len() as i64 > page.limit {
+            media_files.truncate(page.limit as usize);
+            media_files.last().and_then(MediaFileCursor::from_media_file)
+        } else {
+            None
+        };
+        Ok(MediaFilePage {
+            media_files,
+            next_cursor,
+        })
+    }
+
+    async fn delete_media_file(&self, id: Uuid) -> Result<(), MediaRepositoryError> {
+        if self.fail_save {
+            return Err(MediaRepositoryError::InternalServerError);
+        }
+        self.deleted_media_ids.lock().unwrap().push(id);
+        Ok(())
+    }
+
+    async fn get_media_path_by_id(&self, id: MediaId) -> Result<String, MediaRepositoryError> {
+        if self.fail_get {
+            return Err(MediaRepositoryError::InternalServerError);
+        }
+        match self.media_files.iter().find(|f| f.id == id) {
+            Some(file) => Ok(file.file_path.clone()),
+            None => Err(MediaRepositoryError::MediaFileNotFound),
+        }
+    }
+
+    async fn get_media_files_by_checksums(
+        &self,
+        user_id: Uuid,
+        checksums: Vec<String>,
+    ) -> Result<Vec<MediaFile>, MediaRepositoryError> {
+        if self.fail_get {
+            return Err(MediaRepositoryError::InternalServerError);
+        }
+        let concurrent_media = self
+            .concurrent_media
+            .iter()
+            .filter(|_| *self.concurrent_media_stored.lock().unwrap());
+        Ok(self
+            .media_files
+            .iter()
+            .chain(concurrent_media)
+            .filter(|f| f.user_id == user_id && f.status == MediaStatus::Ready)
+            .filter(|f| f.checksum.as_ref().is_some_and(|c| checksums.contains(c)))
+            .cloned()
             .collect())
     }
 
-    async fn delete_media_file(&self, _id: Uuid) -> Result<(), MediaRepositoryError> {
+    async fn save_media_metadata(
+        &self,
+        metadata: MediaMetadata,
+    ) -> Result<(), MediaRepositoryError> {
         if self.fail_save {
             return Err(MediaRepositoryError::InternalServerError);
         }
+        self.saved_metadata.lock().unwrap().push(metadata);
         Ok(())
     }
 
-    async fn update_thumbnail_path(
+    async fn get_media_metadata_by_media_ids(
         &self,
-        id: MediaId,
-        _thumbnail_path: Option<String>,
+        media_ids: Vec<MediaId>,
+    ) -> Result<Vec<MediaMetadata>, MediaRepositoryError> {
+        if self.fail_get {
+            return Err(MediaRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .media_metadata
+            .iter()
+            .filter(|metadata| media_ids.contains(&metadata.media_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn save_media_rendition(
+        &self,
+        rendition: MediaRendition,
     ) -> Result<(), MediaRepositoryError> {
         if self.fail_save {
             return Err(MediaRepositoryError::InternalServerError);
         }
-        match self.media_files.iter().find(|f| f.id == id) {
-            Some(_) => Ok(()),
-            None => Err(MediaRepositoryError::MediaFileNotFound),
+        let mut renditions = self.media_renditions.lock().unwrap();
+        renditions.retain(|r| {
+            !(r.media_id == rendition.media_id
+                && r.name == rendition.name
+                && r.content_type == rendition.content_type)
+        });
+        renditions.push(rendition);
+        Ok(())
+    }
+
+    async fn get_media_renditions_by_media_ids(
+        &self,
+        media_ids: Vec<MediaId>,
+    ) -> Result<Vec<MediaRendition>, MediaRepositoryError> {
+        if self.fail_get {
+            return Err(MediaRepositoryError::InternalServerError);
         }
+        Ok(self
+            .renditions()
+            .into_iter()
+            .filter(|rendition| media_ids.contains(&rendition.media_id))
+            .collect())
     }
 
-    async fn get_media_path_by_id(&self, id: MediaId) -> Result<String, MediaRepositoryError> {
+    async fn mark_media_file_ready(
+        &self,
+        id: MediaId,
+        checksum: String,
+        detected_content_type: Option<String>,
+    ) -> Result<MediaFile, MediaRepositoryError> {
+        if self.fail_save {
+            return Err(MediaRepositoryError::InternalServerError);
+        }
+        match &self.saved_media {
+            Some(file) if file.id == id => {
+                self.check_concurrent_media(file.user_id, Some(&checksum))?;
+                Ok(MediaFile {
+                    status: MediaStatus::Ready,
+                    checksum: Some(checksum),
+                    detected_content_type,
+                    ..file.clone()
+                })
+            }
+            _ => Err(MediaRepositoryError::MediaFileNotFound),
+        }
+    }
+
+    async fn get_pending_media_files_uploaded_before(
+        &self,
+        uploaded_before: chrono::NaiveDateTime,
+    ) -> Result<Vec<MediaFile>, MediaRepositoryError> {
         if self.fail_get {
             return Err(MediaRepositoryError::InternalServerError);
         }
-        match self.media_files.iter().find(|f| f.id == id) {
-            Some(file) => Ok(file.file_path.clone()),
-            None => Err(MediaRepositoryError::MediaFileNotFound),
+        Ok(self
+            .media_files
+            .iter()
+            .filter(|file| file.status == MediaStatus::Pending)
+            .filter(|file| file.uploaded_at.is_some_and(|at| at < uploaded_before))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_storage_usage(&self, user_id: Uuid) -> Result<StorageUsage, MediaRepositoryError> {
+        if self.fail_get {
+            return Err(MediaRepositoryError::InternalServerError);
         }
+        let files = self
+            .media_files
+            .iter()
+            .filter(|file| file.user_id == user_id && !file.is_expired_pending());
+        Ok(StorageUsage {
+            bytes: files.clone().map(|file| file.file_size).sum(),
+            items: files.count() as i64,
+        })
     }
 }
 
@@ -106,6 +308,14 @@ pub struct MockStorageService {
     pub fail_upload: bool,
     pub fail_delete: bool,
     pub uploaded_files: Vec<String>,
+    pub file_data: Vec<u8>,
+    /// Metadata returned for stored objects, `None` behaves as a missing object
+    pub file_metadata: Option<StoredFileMetadata>,
+    pub incomplete_uploads: Vec<IncompleteMultipartUpload>,
+    /// Shared between clones so deletions can be inspected
+    pub deleted_files: Arc<Mutex<Vec<String>>>,
+    /// Upload ids of the aborted multipart uploads, shared between clones
+    pub aborted_uploads: Arc<Mutex<Vec<String>>>,
 }
 
 #[async_trait]
@@ -115,7 +325,7 @@ impl FileStorageService for MockStorageService {
         file_path: &str,
         _content_type: &str,
         _file_size: Option<u64>,
-        mut _file_data: Pin<
+        file_data: Pin<
             Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync + 'static>,
         >,
     ) -> Result<UploadedFileMetadata, FileStorageError> {
@@ -124,18 +334,24 @@ impl FileStorageService for MockStorageService {
                 "Mock upload failure".to_string(),
             ));
         }
+        // Consume the data like a real storage would, callers may observe the stream
+        let chunks = file_data
+            .try_collect::<Vec<Bytes>>()
+            .await
+            .map_err(|e| FileStorageError::InternalError(e.to_string()))?;
         Ok(UploadedFileMetadata {
             file_path: file_path.to_string(),
-            file_size: _file_size.unwrap_or(1024),
+            file_size: chunks.iter().map(|chunk| chunk.len() as u64).sum(),
         })
     }
 
-    async fn delete_file(&self, _file_path: &str) -> Result<(), FileStorageError> {
+    async fn delete_file(&self, file_path: &str) -> Result<(), FileStorageError> {
         if self.fail_delete {
             return Err(FileStorageError::InternalError(
                 "Mock delete failure".to_string(),
             ));
         }
+        self.deleted_files.lock().unwrap().push(file_path.to_string());
         Ok(())
     }
 
@@ -144,8 +360,209 @@ impl FileStorageService for MockStorageService {
     }
 
     async fn get_file_stream(&self, _file_path: &str) -> Result<FileStream, FileStorageError> {
-        // Mock implementation returns an empty stream
-        Ok(Box::pin(futures_util::stream::empty()))
+        // Mock implementation returns the configured file data (empty by default)
+        let data = Bytes::from(self.file_data.clone());
+        Ok(Box::pin(futures_util::stream::once(async move { Ok(data) })))
+    }
+
+    async fn get_file_range_stream(
+        &self,
+        _file_path: &str,
+        range: ByteRange,
+    ) -> Result<FileStream, FileStorageError> {
+        let data = Bytes::copy_from_slice(
+            &self.file_data[range.start as usize..=range.end as usize],
+        );
+        Ok(Box::pin(futures_util::stream::once(async move { Ok(data) })))
+    }
+
+    async fn create_multipart_upload(
+        &self,
+        _file_path: &str,
+        _content_type: &str,
+    ) -> Result<String, FileStorageError> {
+        if self.fail_upload {
+            return Err(FileStorageError::InternalError(
+                "Mock upload failure".to_string(),
+            ));
+        }
+        Ok("mock-upload-id".to_string())
+    }
+
+    async fn upload_part(
+        &self,
+        _file_path: &str,
+        _upload_id: &str,
+        part_number: i32,
+        _data: Bytes,
+    ) -> Result<String, FileStorageError> {
+        if self.fail_upload {
+            return Err(FileStorageError::InternalError(
+                "Mock upload failure".to_string(),
+            ));
+        }
+        Ok(format!("\"etag-{}\"", part_number))
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        _file_path: &str,
+        _upload_id: &str,
+        _parts: Vec<UploadedPart>,
+    ) -> Result<(), FileStorageError> {
+        if self.fail_upload {
+            return Err(FileStorageError::InternalError(
+                "Mock upload failure".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        _file_path: &str,
+        upload_id: &str,
+    ) -> Result<(), FileStorageError> {
+        if self.fail_delete {
+            return Err(FileStorageError::InternalError(
+                "Mock delete failure".to_string(),
+            ));
+        }
+        self.aborted_uploads.lock().unwrap().push(upload_id.to_string());
+        Ok(())
+    }
+
+    async fn list_incomplete_multipart_uploads(
+        &self,
+    ) -> Result<Vec<IncompleteMultipartUpload>, FileStorageError> {
+        Ok(self.incomplete_uploads.clone())
+    }
+
+    async fn create_presigned_upload(
+        &self,
+        file_path: &str,
+        _content_type: &str,
+        _file_size: u64,
+        _expires_in: std::time::Duration,
+    ) -> Result<PresignedUpload, FileStorageError> {
+        Ok(PresignedUpload {
+            url: format!("https://mock-storage.example.com/{}?signature=mock", file_path),
+            method: "PUT".to_string(),
+        })
+    }
+
+    async fn get_file_metadata(
+        &self,
+        _file_path: &str,
+    ) -> Result<StoredFileMetadata, FileStorageError> {
+        self.file_metadata.clone().ok_or(FileStorageError::NotFound)
+    }
+}
+
+/// In-memory upload session repository, clones share the same sessions
+#[derive(Debug, Clone, Default)]
+pub struct MockUploadSessionRepository {
+    pub fail_save: bool,
+    pub sessions: Arc<Mutex<Vec<UploadSession>>>,
+}
+
+impl MockUploadSessionRepository {
+    pub fn with_sessions(sessions: Vec<UploadSession>) -> Self {
+        MockUploadSessionRepository {
+            fail_save: false,
+            sessions: Arc::new(Mutex::new(sessions)),
+        }
+    }
+
+    pub fn session(&self, id: UploadSessionId) -> Option<UploadSession> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|s| s.id == id)
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl UploadSessionRepository for MockUploadSessionRepository {
+    async fn create_upload_session(
+        &self,
+        upload_session: NewUploadSession,
+    ) -> Result<UploadSession, UploadSessionRepositoryError> {
+        if self.fail_save {
+            return Err(UploadSessionRepositoryError::InternalServerError);
+        }
+        let session = UploadSession {
+            id: Uuid::new_v4(),
+            user_id: upload_session.user_id,
+            filename: upload_session.filename,
+            original_filename: upload_session.original_filename,
+            file_size: upload_session.file_size,
+            content_type: upload_session.content_type,
+            file_path: upload_session.file_path,
+            storage_upload_id: upload_session.storage_upload_id,
+            upload_offset: 0,
+            part_etags: Vec::new(),
+            expires_at: upload_session.expires_at,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            updated_at: Some(chrono::Utc::now().naive_utc()),
+        };
+        self.sessions.lock().unwrap().push(session.clone());
+        Ok(session)
+    }
+
+    async fn get_upload_session_by_id(
+        &self,
+        id: UploadSessionId,
+    ) -> Result<Option<UploadSession>, UploadSessionRepositoryError> {
+        Ok(self.session(id))
+    }
+
+    async fn append_uploaded_part(
+        &self,
+        id: UploadSessionId,
+        expected_offset: i64,
+        part_size: i64,
+        part_etag: String,
+    ) -> Result<UploadSession, UploadSessionRepositoryError> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or(UploadSessionRepositoryError::UploadSessionNotFound)?;
+        if session.upload_offset != expected_offset {
+            return Err(UploadSessionRepositoryError::Conflict);
+        }
+        session.upload_offset += part_size;
+        session.part_etags.push(part_etag);
+        Ok(session.clone())
+    }
+
+    async fn get_expired_upload_sessions(
+        &self,
+    ) -> Result<Vec<UploadSession>, UploadSessionRepositoryError> {
+        Ok(self
+            .sessions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.is_expired())
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_upload_session(
+        &self,
+        id: UploadSessionId,
+    ) -> Result<(), UploadSessionRepositoryError> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let length = sessions.len();
+        sessions.retain(|s| s.id != id);
+        if sessions.len() == length {
+            return Err(UploadSessionRepositoryError::UploadSessionNotFound);
+        }
+        Ok(())
     }
 }
 
@@ -156,6 +573,24 @@ pub fn get_test_user_id() -> Uuid {
     Uuid::parse_str(TEST_USER_ID).unwrap()
 }
 
+/// Ready JPEG of the given user
+pub fn media_file(user_id: Uuid) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "photo.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 1024,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/photo.jpg", user_id),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
 // Custom token service that returns our test user ID
 #[derive(Clone, Default)]
 pub struct TestTokenService;
@@ -171,10 +606,85 @@ impl LoginTokenService for TestTokenService {
             role: lib::users::domain::Role::User,
             username: "testuser".to_string(),
             exp: (chrono::Utc::now().timestamp() + 3600) as u64,
+            sid: None,
         })
     }
 }
 
+/// Streams the data in small chunks, the way the storage service returns large files
+pub fn file_stream(data: Vec<u8>) -> FileStream {
+    let chunks: Vec<Result<Bytes, FileStorageError>> = data
+        .chunks(1024)
+        .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+        .collect();
+    Box::pin(futures_util::stream::iter(chunks))
+}
+
+/// Little endian TIFF structure with an orientation and, when a latitude in degrees and minutes
+/// is given, a GPS directory. The latitude values are stored at the end.
+pub fn exif_tiff(orientation: u16, latitude: Option<(u32, u32)>) -> Vec<u8> {
+    let entry = |tag: u16, field_type: u16, count: u32, value: [u8; 4]| {
+        let mut entry = tag.to_le_bytes().to_vec();
+        entry.extend(field_type.to_le_bytes());
+        entry.extend(count.to_le_bytes());
+        entry.extend(value);
+        entry
+    };
+
+    let mut tiff = b"II*\0".to_vec();
+    tiff.extend(8u32.to_le_bytes());
+    let entries: u16 = if latitude.is_some() { 2 } else { 1 };
+    tiff.extend(entries.to_le_bytes());
+    let orientation = orientation.to_le_bytes();
+    tiff.extend(entry(0x0112, 3, 1, [orientation[0], orientation[1], 0, 0]));
+    // The GPS directory follows IFD0, which ends at 8 + 2 + 2 * 12 + 4
+    if latitude.is_some() {
+        tiff.extend(entry(0x8825, 4, 1, 38u32.to_le_bytes()));
+    }
+    tiff.extend(0u32.to_le_bytes());
+
+    if let Some((degrees, minutes)) = latitude {
+        tiff.extend(2u16.to_le_bytes());
+        tiff.extend(entry(0x0001, 2, 2, [b'N', 0, 0, 0]));
+        // Three rationals do not fit in the entry, they follow the directory at 38 + 2 + 24 + 4
+        tiff.extend(entry(0x0002, 5, 3, 68u32.to_le_bytes()));
+        tiff.extend(0u32.to_le_bytes());
+        for (numerator, denominator) in [(degrees, 1u32), (minutes, 1), (0, 1)] {
+            tiff.extend(numerator.to_le_bytes());
+            tiff.extend(denominator.to_le_bytes());
+        }
+    }
+    tiff
+}
+
+/// JPEG photo with the EXIF segment of [`exif_tiff`], the way phones store them
+pub fn exif_jpeg(
+    width: u32,
+    height: u32,
+    orientation: u16,
+    latitude: Option<(u32, u32)>,
+) -> Vec<u8> {
+    let image = image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x * 3) as u8, (y * 5) as u8, 128])
+    }));
+    let mut encoded = Vec::new();
+    image
+        .write_to(
+            &mut std::io::Cursor::new(&mut encoded),
+            image::ImageFormat::Jpeg,
+        )
+        .unwrap();
+
+    let mut segment = b"Exif\0\0".to_vec();
+    segment.extend(exif_tiff(orientation, latitude));
+    let mut data = encoded[..2].to_vec();
+    data.extend([0xFF, 0xE1]);
+    data.extend(((segment.len() + 2) as u16).to_be_bytes());
+    data.extend(segment);
+    data.extend(&encoded[2..]);
+    data
+}
+
 #[derive(Clone, Default)]
 pub struct MockThumbnailService {
     pub fail_generate: bool,
@@ -182,11 +692,11 @@ pub struct MockThumbnailService {
 
 #[async_trait]
 impl ThumbnailService for MockThumbnailService {
-    async fn generate_thumbnail(
+    async fn generate_renditions(
         &self,
         _media_id: Uuid,
         _original_path: &str,
-        _image_data: Vec<u8>,
+        _file_stream: FileStream,
         _content_type: &str,
     ) -> Result<(), ThumbnailError> {
         // Mock implementation does nothing
@@ -198,3 +708,39 @@ impl ThumbnailService for MockThumbnailService {
         Ok(())
     }
 }
+
+/// Reads the video and returns the configured one, `None` fails like a file ffmpeg cannot decode
+#[derive(Clone, Default)]
+pub struct MockVideoFrameExtractor {
+    pub video: Option<ExtractedVideo>,
+}
+
+#[async_trait]
+impl VideoFrameExtractor for MockVideoFrameExtractor {
+    async fn extract(&self, mut video: FileStream) -> Result<ExtractedVideo, VideoFrameExtractorError> {
+        while video
+            .try_next()
+            .await
+            .map_err(|e| VideoFrameExtractorError::IoError(e.to_string()))?
+            .is_some()
+        {}
+        self.video.clone().ok_or(VideoFrameExtractorError::ExtractionError(
+            "Mock extraction failure".to_string(),
+        ))
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MockMetadataService;
+
+#[async_trait]
+impl MediaMetadataService for MockMetadataService {
+    async fn extract_metadata(
+        &self,
+        _media_id: MediaId,
+        _image_data: &[u8],
+        _content_type: &str,
+    ) -> Result<(), MediaMetadataError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+use lib::{
+    media::domain::{MediaFile, MediaStatus},
+    sharing::{
+        application::commands::{
+            create_share_grant::{
+                CreateShareGrantCommand, ShareGrantResult, create_share_grant_command_handler,
+            },
+            revoke_share_grant::{RevokeShareGrantCommand, revoke_share_grant_command_handler},
+        },
+        domain::{ShareError, ShareRole, ShareTarget},
+    },
+    users::domain::{Role, User},
+};
+use uuid::Uuid;
+
+use crate::{
+    albums::MockAlbumRepository,
+    media::{MockMediaRepository, media_file},
+    sharing::{MockShareGrantRepository, share_grant, test_authorization_service},
+    users::MockUserRepository,
+};
+
+fn user_repo(user_id: Uuid) -> MockUserRepository {
+    MockUserRepository {
+        user_exists: true,
+        user: Some(User {
+            id: user_id,
+            username: "friend".to_string(),
+            email: None,
+            password: "hashed".to_string(),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            created_at: None,
+            updated_at: None,
+            must_change_password: false,
+        }),
+        ..MockUserRepository::default()
+    }
+}
+
+async fn share_media(
+    media: &MediaFile,
+    owner_id: Uuid,
+    grantee_id: Uuid,
+    role: ShareRole,
+    share_grant_repo: &MockShareGrantRepository,
+) -> Result<ShareGrantResult, ShareError> {
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    create_share_grant_command_handler(
+        CreateShareGrantCommand {
+            target: ShareTarget::Media(media.id),
+            owner_id,
+            grantee_username: "friend".to_string(),
+            role,
+        },
+        &media_repo,
+        &MockAlbumRepository::default(),
+        &user_repo(grantee_id),
+        share_grant_repo,
+        &test_authorization_service(share_grant_repo.clone()),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_share_media() {
+    let owner_id = Uuid::new_v4();
+    let grantee_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let repo = MockShareGrantRepository::default();
+
+    let result = share_media(&media, owner_id, grantee_id, ShareRole::Viewer, &repo)
+        .await
+        .unwrap();
+
+    assert_eq!(result.grantee_id, grantee_id);
+    assert_eq!(result.grantee_username, "friend");
+    assert_eq!(result.role, ShareRole::Viewer);
+    assert_eq!(repo.grants().len(), 1);
+}
+
+#[tokio::test]
+async fn test_share_media_again_replaces_role() {
+    let owner_id = Uuid::new_v4();
+    let grantee_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let repo = MockShareGrantRepository::default();
+
+    share_media(&media, owner_id, grantee_id, ShareRole::Viewer, &repo)
+        .await
+        .unwrap();
+    share_media(&media, owner_id, grantee_id, ShareRole::Contributor, &repo)
+        .await
+        .unwrap();
+
+    let grants = repo.grants();
+    assert_eq!(grants.len(), 1);
+    assert_eq!(grants[0].role, ShareRole::Contributor);
+}
+
+#[tokio::test]
+async fn test_share_media_with_owner_rejected() {
+    let owner_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let repo = MockShareGrantRepository::default();
+
+    let result = share_media(&media, owner_id, owner_id, ShareRole::Viewer, &repo).await;
+
+    assert!(matches!(result, Err(ShareError::CannotShareWithOwner)));
+    assert!(repo.grants().is_empty());
+}
+
+#[tokio::test]
+async fn test_share_media_of_other_user_not_found() {
+    let media = media_file(Uuid::new_v4());
+    let repo = MockShareGrantRepository::default();
+
+    let result = share_media(
+        &media,
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        ShareRole::Viewer,
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_grantee_cannot_reshare_media() {
+    let media = media_file(Uuid::new_v4());
+    let grantee_id = Uuid::new_v4();
+    let repo = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Media(media.id),
+        grantee_id,
+        ShareRole::Contributor,
+    )]);
+
+    let result = share_media(&media, grantee_id, Uuid::new_v4(), ShareRole::Viewer, &repo).await;
+
+    assert!(matches!(result, Err(ShareError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_share_pending_media_not_found() {
+    let owner_id = Uuid::new_v4();
+    let media = MediaFile {
+        status: MediaStatus::Pending,
+        ..media_file(owner_id)
+    };
+    let repo = MockShareGrantRepository::default();
+
+    let result = share_media(&media, owner_id, Uuid::new_v4(), ShareRole::Viewer, &repo).await;
+
+    assert!(matches!(result, Err(ShareError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_share_media_with_unknown_user() {
+    let owner_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    let repo = MockShareGrantRepository::default();
+
+    let result = create_share_grant_command_handler(
+        CreateShareGrantCommand {
+            target: ShareTarget::Media(media.id),
+            owner_id,
+            grantee_username: "nobody".to_string(),
+            role: ShareRole::Viewer,
+        },
+        &media_repo,
+        &MockAlbumRepository::default(),
+        &MockUserRepository::default(),
+        &repo,
+        &test_authorization_service(repo.clone()),
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn test_revoke_share_grant() {
+    let owner_id = Uuid::new_v4();
+    let grantee_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    let repo = MockShareGrantRepository::with_grants(vec![share_grant(
+        ShareTarget::Media(media.id),
+        grantee_id,
+        ShareRole::Viewer,
+    )]);
+    let command = || RevokeShareGrantCommand {
+        target: ShareTarget::Media(media.id),
+        owner_id,
+        grantee_id,
+    };
+
+    revoke_share_grant_command_handler(
+        command(),
+        &media_repo,
+        &MockAlbumRepository::default(),
+        &repo,
+        &test_authorization_service(repo.clone()),
+    )
+    .await
+    .unwrap();
+    assert!(repo.grants().is_empty());
+
+    let result = revoke_share_grant_command_handler(
+        command(),
+        &media_repo,
+        &MockAlbumRepository::default(),
+        &repo,
+        &test_authorization_service(repo.clone()),
+    )
+    .await;
+    assert!(matches!(result, Err(ShareError::ShareGrantNotFound)));
+}
@@ -0,0 +1,171 @@
+use lib::{
+    media::domain::MediaFile,
+    sharing::{
+        application::{
+            commands::{
+                create_share_link::{
+                    CreateShareLinkCommand, ShareLinkResult, create_share_link_command_handler,
+                },
+                delete_share_link::{DeleteShareLinkCommand, delete_share_link_command_handler},
+            },
+            queries::get_share_links::{GetShareLinksQuery, get_share_links_query_handler},
+        },
+        domain::{ShareError, ShareTarget},
+    },
+    users::domain::verify_password,
+};
+use uuid::Uuid;
+
+use crate::{
+    albums::MockAlbumRepository,
+    media::{MockMediaRepository, media_file},
+    sharing::{
+        MockShareGrantRepository, MockShareLinkRepository, share_link, test_authorization_service,
+    },
+};
+
+async fn create_link(
+    media: &MediaFile,
+    command: CreateShareLinkCommand,
+    repo: &MockShareLinkRepository,
+) -> Result<ShareLinkResult, ShareError> {
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    create_share_link_command_handler(
+        command,
+        &media_repo,
+        &MockAlbumRepository::default(),
+        repo,
+        &test_authorization_service(MockShareGrantRepository::default()),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_create_share_link_with_password() {
+    let owner_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let repo = MockShareLinkRepository::default();
+    let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::days(7);
+
+    let result = create_link(
+        &media,
+        CreateShareLinkCommand {
+            target: ShareTarget::Media(media.id),
+            owner_id,
+            password: Some("family".to_string()),
+            allow_download: true,
+            expires_at: Some(expires_at),
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.media_id, Some(media.id));
+    assert_eq!(result.album_id, None);
+    assert!(result.has_password);
+    assert!(result.allow_download);
+    assert_eq!(result.expires_at, Some(expires_at));
+    assert_eq!(result.view_count, 0);
+
+    let link = repo.link(result.id).unwrap();
+    assert_eq!(link.slug, result.slug);
+    let password_hash = link.password_hash.unwrap();
+    assert_ne!(password_hash, "family");
+    assert!(verify_password("family", &password_hash));
+}
+
+#[tokio::test]
+async fn test_create_share_link_expiring_in_the_past() {
+    let owner_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let repo = MockShareLinkRepository::default();
+
+    let result = create_link(
+        &media,
+        CreateShareLinkCommand {
+            target: ShareTarget::Media(media.id),
+            owner_id,
+            password: None,
+            allow_download: false,
+            expires_at: Some(chrono::Utc::now().naive_utc() - chrono::Duration::hours(1)),
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::InvalidExpiry)));
+    assert!(repo.links.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_create_share_link_for_media_of_other_user() {
+    let media = media_file(Uuid::new_v4());
+    let repo = MockShareLinkRepository::default();
+
+    let result = create_link(
+        &media,
+        CreateShareLinkCommand {
+            target: ShareTarget::Media(media.id),
+            owner_id: Uuid::new_v4(),
+            password: None,
+            allow_download: false,
+            expires_at: None,
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_get_share_links_of_owner() {
+    let owner_id = Uuid::new_v4();
+    let link = share_link(owner_id, ShareTarget::Media(Uuid::new_v4()));
+    let repo = MockShareLinkRepository::with_links(vec![
+        link.clone(),
+        share_link(Uuid::new_v4(), ShareTarget::Media(Uuid::new_v4())),
+    ]);
+
+    let result = get_share_links_query_handler(GetShareLinksQuery { owner_id }, &repo)
+        .await
+        .unwrap();
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].id, link.id);
+}
+
+#[tokio::test]
+async fn test_delete_share_link() {
+    let owner_id = Uuid::new_v4();
+    let link = share_link(owner_id, ShareTarget::Media(Uuid::new_v4()));
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+
+    // Only the owner can delete the link
+    let result = delete_share_link_command_handler(
+        DeleteShareLinkCommand {
+            link_id: link.id,
+            owner_id: Uuid::new_v4(),
+        },
+        &repo,
+    )
+    .await;
+    assert!(matches!(result, Err(ShareError::ShareLinkNotFound)));
+    assert!(repo.link(link.id).is_some());
+
+    delete_share_link_command_handler(
+        DeleteShareLinkCommand {
+            link_id: link.id,
+            owner_id,
+        },
+        &repo,
+    )
+    .await
+    .unwrap();
+    assert!(repo.link(link.id).is_none());
+}
@@ -0,0 +1,82 @@
+use lib::{
+    albums::domain::Album,
+    sharing::{
+        application::queries::get_shared_with_me::{
+            GetSharedWithMeQuery, get_shared_with_me_query_handler,
+        },
+        domain::{ShareError, ShareRole, ShareTarget},
+    },
+};
+use uuid::Uuid;
+
+use crate::{
+    media::media_file,
+    sharing::{MockShareGrantRepository, share_grant},
+};
+
+fn album(user_id: Uuid) -> Album {
+    Album {
+        id: Uuid::new_v4(),
+        user_id,
+        name: "Holidays".to_string(),
+        description: None,
+        cover_media_id: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[tokio::test]
+async fn test_get_shared_with_me() {
+    let owner_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let album = album(owner_id);
+    let repo = MockShareGrantRepository {
+        media_files: vec![media.clone()],
+        albums: vec![album.clone()],
+        ..MockShareGrantRepository::with_grants(vec![
+            share_grant(ShareTarget::Media(media.id), user_id, ShareRole::Viewer),
+            share_grant(
+                ShareTarget::Album(album.id),
+                user_id,
+                ShareRole::Contributor,
+            ),
+            share_grant(
+                ShareTarget::Media(media.id),
+                Uuid::new_v4(),
+                ShareRole::Viewer,
+            ),
+        ])
+    };
+
+    let result = get_shared_with_me_query_handler(GetSharedWithMeQuery { user_id }, &repo)
+        .await
+        .unwrap();
+
+    assert_eq!(result.media.len(), 1);
+    assert_eq!(result.media[0].media_id, media.id);
+    assert_eq!(result.media[0].owner_id, owner_id);
+    assert_eq!(result.media[0].role, ShareRole::Viewer);
+    assert_eq!(result.albums.len(), 1);
+    assert_eq!(result.albums[0].album_id, album.id);
+    assert_eq!(result.albums[0].role, ShareRole::Contributor);
+}
+
+#[tokio::test]
+async fn test_get_shared_with_me_repository_error() {
+    let repo = MockShareGrantRepository {
+        fail: true,
+        ..MockShareGrantRepository::default()
+    };
+
+    let result = get_shared_with_me_query_handler(
+        GetSharedWithMeQuery {
+            user_id: Uuid::new_v4(),
+        },
+        &repo,
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::InternalServerError(_))));
+}
@@ -0,0 +1,269 @@
+use lib::{
+    albums::domain::{Album, AlbumRepository},
+    media::domain::MediaFile,
+    sharing::{
+        application::queries::{
+            get_share_link_media::{GetShareLinkMediaQuery, get_share_link_media_query_handler},
+            open_share_link::{
+                OpenShareLinkQuery, ShareLinkContentResult, open_share_link_query_handler,
+            },
+        },
+        domain::{ShareError, ShareLink, ShareTarget},
+    },
+    users::domain::hash_password,
+};
+use uuid::Uuid;
+
+use crate::{
+    albums::MockAlbumRepository,
+    media::{MockMediaRepository, media_file},
+    sharing::{MockShareLinkRepository, share_link},
+};
+
+fn album(user_id: Uuid) -> Album {
+    Album {
+        id: Uuid::new_v4(),
+        user_id,
+        name: "Holidays".to_string(),
+        description: Some("Summer 2024".to_string()),
+        cover_media_id: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+async fn open(
+    link: &ShareLink,
+    password: Option<&str>,
+    repo: &MockShareLinkRepository,
+    media_repo: &MockMediaRepository,
+    album_repo: &MockAlbumRepository,
+) -> Result<ShareLinkContentResult, ShareError> {
+    open_share_link_query_handler(
+        OpenShareLinkQuery {
+            slug: link.slug.clone(),
+            password: password.map(|password| password.to_string()),
+        },
+        repo,
+        media_repo,
+        album_repo,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_open_media_share_link_counts_views() {
+    let media = media_file(Uuid::new_v4());
+    let link = share_link(media.user_id, ShareTarget::Media(media.id));
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    let album_repo = MockAlbumRepository::default();
+
+    let result = open(&link, None, &repo, &media_repo, &album_repo)
+        .await
+        .unwrap();
+    open(&link, None, &repo, &media_repo, &album_repo)
+        .await
+        .unwrap();
+
+    assert!(result.album.is_none());
+    assert_eq!(result.media.len(), 1);
+    assert_eq!(result.media[0].media_id, media.id);
+    let link = repo.link(link.id).unwrap();
+    assert_eq!(link.view_count, 2);
+    assert!(link.last_viewed_at.is_some());
+}
+
+#[tokio::test]
+async fn test_open_album_share_link() {
+    let album = album(Uuid::new_v4());
+    let album_repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let media_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+    album_repo
+        .add_album_media(album.id, media_ids.clone())
+        .await
+        .unwrap();
+    let link = share_link(album.user_id, ShareTarget::Album(album.id));
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+
+    let result = open(
+        &link,
+        None,
+        &repo,
+        &MockMediaRepository::default(),
+        &album_repo,
+    )
+    .await
+    .unwrap();
+
+    let public_album = result.album.unwrap();
+    assert_eq!(public_album.name, "Holidays");
+    let ids: Vec<Uuid> = result.media.iter().map(|media| media.media_id).collect();
+    assert_eq!(ids, media_ids);
+}
+
+#[tokio::test]
+async fn test_open_expired_share_link() {
+    let media = media_file(Uuid::new_v4());
+    let link = ShareLink {
+        expires_at: Some(chrono::Utc::now().naive_utc() - chrono::Duration::minutes(1)),
+        ..share_link(media.user_id, ShareTarget::Media(media.id))
+    };
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+
+    let result = open(
+        &link,
+        None,
+        &repo,
+        &MockMediaRepository::default(),
+        &MockAlbumRepository::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::ShareLinkExpired)));
+    assert_eq!(repo.link(link.id).unwrap().view_count, 0);
+}
+
+#[tokio::test]
+async fn test_open_password_protected_share_link() {
+    let media = media_file(Uuid::new_v4());
+    let link = ShareLink {
+        password_hash: Some(hash_password("family").unwrap()),
+        ..share_link(media.user_id, ShareTarget::Media(media.id))
+    };
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+    let album_repo = MockAlbumRepository::default();
+
+    let missing = open(&link, None, &repo, &media_repo, &album_repo).await;
+    assert!(matches!(missing, Err(ShareError::InvalidSharePassword)));
+
+    let wrong = open(&link, Some("friends"), &repo, &media_repo, &album_repo).await;
+    assert!(matches!(wrong, Err(ShareError::InvalidSharePassword)));
+
+    let result = open(&link, Some("family"), &repo, &media_repo, &album_repo).await;
+    assert!(result.is_ok());
+    assert_eq!(repo.link(link.id).unwrap().view_count, 1);
+}
+
+#[tokio::test]
+async fn test_open_unknown_share_link() {
+    let link = share_link(Uuid::new_v4(), ShareTarget::Media(Uuid::new_v4()));
+
+    let result = open(
+        &link,
+        None,
+        &MockShareLinkRepository::default(),
+        &MockMediaRepository::default(),
+        &MockAlbumRepository::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::ShareLinkNotFound)));
+}
+
+#[tokio::test]
+async fn test_get_share_link_media_of_album() {
+    let album = album(Uuid::new_v4());
+    let album_repo = MockAlbumRepository::with_albums(vec![album.clone()]);
+    let media_id = Uuid::new_v4();
+    album_repo
+        .add_album_media(album.id, vec![media_id])
+        .await
+        .unwrap();
+    let link = share_link(album.user_id, ShareTarget::Album(album.id));
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+    let query = |media_id| GetShareLinkMediaQuery {
+        slug: link.slug.clone(),
+        password: None,
+        media_id,
+        download: false,
+    };
+
+    let share_link_media = get_share_link_media_query_handler(
+        query(media_id),
+        &repo,
+        &MockMediaRepository::default(),
+        &album_repo,
+    )
+    .await
+    .unwrap();
+    assert_eq!(share_link_media.media_file.id, media_id);
+    assert_eq!(share_link_media.allow_download, link.allow_download);
+
+    // Media files outside of the album are not reachable through the link
+    let result = get_share_link_media_query_handler(
+        query(Uuid::new_v4()),
+        &repo,
+        &MockMediaRepository::default(),
+        &album_repo,
+    )
+    .await;
+    assert!(matches!(result, Err(ShareError::MediaFileNotFound)));
+}
+
+#[tokio::test]
+async fn test_get_share_link_media_download_not_allowed() {
+    let media = media_file(Uuid::new_v4());
+    let link = share_link(media.user_id, ShareTarget::Media(media.id));
+    let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+    let media_repo = MockMediaRepository {
+        saved_media: Some(media.clone()),
+        ..MockMediaRepository::default()
+    };
+
+    let result = get_share_link_media_query_handler(
+        GetShareLinkMediaQuery {
+            slug: link.slug.clone(),
+            password: None,
+            media_id: media.id,
+            download: true,
+        },
+        &repo,
+        &media_repo,
+        &MockAlbumRepository::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(ShareError::DownloadNotAllowed)));
+}
+
+#[tokio::test]
+async fn test_get_share_link_media_streams_videos_without_download() {
+    for (content_type, stream_original) in [("image/jpeg", false), ("video/mp4", true)] {
+        let media = MediaFile {
+            content_type: content_type.to_string(),
+            ..media_file(Uuid::new_v4())
+        };
+        let link = share_link(media.user_id, ShareTarget::Media(media.id));
+        let repo = MockShareLinkRepository::with_links(vec![link.clone()]);
+        let media_repo = MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        };
+
+        let share_link_media = get_share_link_media_query_handler(
+            GetShareLinkMediaQuery {
+                slug: link.slug.clone(),
+                password: None,
+                media_id: media.id,
+                download: false,
+            },
+            &repo,
+            &media_repo,
+            &MockAlbumRepository::default(),
+        )
+        .await
+        .unwrap();
+
+        // Videos have no playable rendition, so they are streamed even without downloads
+        assert!(!share_link_media.allow_download);
+        assert_eq!(share_link_media.stream_original, stream_original);
+    }
+}
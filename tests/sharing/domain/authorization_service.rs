@@ -0,0 +1,135 @@
+use lib::{
+    albums::domain::Album,
+    sharing::domain::{AuthorizationService, Permission, ShareRole, ShareTarget},
+};
+use uuid::Uuid;
+
+use crate::{
+    media::media_file,
+    sharing::{MockShareGrantRepository, share_grant, test_authorization_service},
+};
+
+fn album(user_id: Uuid) -> Album {
+    Album {
+        id: Uuid::new_v4(),
+        user_id,
+        name: "Holidays".to_string(),
+        description: None,
+        cover_media_id: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[test]
+fn test_share_role_permissions() {
+    assert!(ShareRole::Viewer.allows(Permission::View));
+    assert!(!ShareRole::Viewer.allows(Permission::Contribute));
+    assert!(ShareRole::Contributor.allows(Permission::View));
+    assert!(ShareRole::Contributor.allows(Permission::Contribute));
+    assert!(!ShareRole::Contributor.allows(Permission::Manage));
+}
+
+#[tokio::test]
+async fn test_owner_has_every_permission() {
+    let owner_id = Uuid::new_v4();
+    let media = media_file(owner_id);
+    let service = test_authorization_service(MockShareGrantRepository::default());
+
+    for permission in [Permission::View, Permission::Contribute, Permission::Manage] {
+        assert!(
+            service
+                .can_access_media(owner_id, &media, permission)
+                .await
+                .unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_user_without_grant_is_denied() {
+    let media = media_file(Uuid::new_v4());
+    let service = test_authorization_service(MockShareGrantRepository::default());
+
+    let allowed = service
+        .can_access_media(Uuid::new_v4(), &media, Permission::View)
+        .await
+        .unwrap();
+
+    assert!(!allowed);
+}
+
+#[tokio::test]
+async fn test_viewer_can_only_view_media() {
+    let media = media_file(Uuid::new_v4());
+    let user_id = Uuid::new_v4();
+    let service =
+        test_authorization_service(MockShareGrantRepository::with_grants(vec![share_grant(
+            ShareTarget::Media(media.id),
+            user_id,
+            ShareRole::Viewer,
+        )]));
+
+    assert!(
+        service
+            .can_access_media(user_id, &media, Permission::View)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !service
+            .can_access_media(user_id, &media, Permission::Manage)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_album_grant_covers_album_media() {
+    let owner_id = Uuid::new_v4();
+    let album = album(owner_id);
+    let media = media_file(owner_id);
+    let user_id = Uuid::new_v4();
+    let service = test_authorization_service(MockShareGrantRepository {
+        album_media: vec![(album.id, media.id)],
+        ..MockShareGrantRepository::with_grants(vec![share_grant(
+            ShareTarget::Album(album.id),
+            user_id,
+            ShareRole::Contributor,
+        )])
+    });
+
+    assert!(
+        service
+            .can_access_media(user_id, &media, Permission::View)
+            .await
+            .unwrap()
+    );
+    assert!(
+        service
+            .can_access_album(user_id, &album, Permission::Contribute)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !service
+            .can_access_album(user_id, &album, Permission::Manage)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_repository_error_is_reported() {
+    let media = media_file(Uuid::new_v4());
+    let service = test_authorization_service(MockShareGrantRepository {
+        fail: true,
+        ..MockShareGrantRepository::default()
+    });
+
+    let result = service
+        .can_access_media(Uuid::new_v4(), &media, Permission::View)
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,34 @@
+use lib::sharing::domain::{ShareLink, ShareTarget};
+use uuid::Uuid;
+
+use crate::sharing::share_link;
+
+#[test]
+fn test_generated_slugs_are_url_safe_and_unique() {
+    let slug = ShareLink::generate_slug();
+
+    assert_eq!(slug.len(), 24);
+    assert!(
+        slug.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    );
+    assert_ne!(slug, ShareLink::generate_slug());
+}
+
+#[test]
+fn test_share_link_expiry() {
+    let link = share_link(Uuid::new_v4(), ShareTarget::Media(Uuid::new_v4()));
+    assert!(!link.is_expired());
+
+    let expired = ShareLink {
+        expires_at: Some(chrono::Utc::now().naive_utc() - chrono::Duration::minutes(1)),
+        ..link.clone()
+    };
+    assert!(expired.is_expired());
+
+    let valid = ShareLink {
+        expires_at: Some(chrono::Utc::now().naive_utc() + chrono::Duration::days(1)),
+        ..link
+    };
+    assert!(!valid.is_expired());
+}
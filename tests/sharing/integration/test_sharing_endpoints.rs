@@ -0,0 +1,396 @@
+use std::sync::Arc;
+
+use crate::{
+    media::{
+        MockMediaRepository, MockStorageService, TestTokenService, exif_jpeg, get_test_user_id,
+    },
+    sharing::{MockShareGrantRepository, share_grant},
+    users::MockUserRepository,
+    utils::test_helpers::*,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use lib::{
+    api::{http_server::AppState, routes::api_routes},
+    media::domain::{MediaFile, MediaRendition, MediaStatus},
+    sharing::domain::{ShareRole, ShareTarget},
+    users::domain::{Role, User},
+};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn send(
+    state: &AppState,
+    method: &str,
+    uri: String,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let app = api_routes(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", "Bearer valid_token")
+        .header("Content-Type", "application/json")
+        .body(body.map_or_else(Body::empty, |body| Body::from(body.to_string())))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+fn test_media(user_id: Uuid) -> MediaFile {
+    MediaFile {
+        id: Uuid::new_v4(),
+        user_id,
+        filename: "image.jpg".to_string(),
+        original_filename: "photo.jpg".to_string(),
+        file_size: 4,
+        content_type: "image/jpeg".to_string(),
+        file_path: format!("media/{}/image.jpg", user_id),
+        uploaded_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+        status: MediaStatus::Ready,
+        checksum: None,
+        detected_content_type: None,
+    }
+}
+
+#[tokio::test]
+async fn test_share_media_lifecycle() {
+    let media = test_media(get_test_user_id());
+    let friend_id = Uuid::new_v4();
+    let share_grant_repo = MockShareGrantRepository::default();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        user_repo: Some(MockUserRepository {
+            user_exists: true,
+            user: Some(User {
+                id: friend_id,
+                username: "friend".to_string(),
+                email: None,
+                password: "hashed".to_string(),
+                role: Role::User,
+                quota_bytes: None,
+                quota_items: None,
+                created_at: None,
+                updated_at: None,
+                must_change_password: false,
+            }),
+            ..MockUserRepository::default()
+        }),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        }),
+        share_grant_repo: Some(share_grant_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(
+        &state,
+        "POST",
+        format!("/shares/media/{}", media.id),
+        Some(serde_json::json!({ "username": "friend", "role": "Viewer" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["grantee_id"], friend_id.to_string());
+    assert_eq!(json["data"]["role"], "Viewer");
+
+    let (status, json) = send(&state, "GET", format!("/shares/media/{}", media.id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"][0]["grantee_username"], "friend");
+
+    let (status, _) = send(
+        &state,
+        "DELETE",
+        format!("/shares/media/{}/{}", media.id, friend_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(share_grant_repo.grants().is_empty());
+}
+
+#[tokio::test]
+async fn test_share_media_invalid_role() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, _) = send(
+        &state,
+        "POST",
+        format!("/shares/media/{}", Uuid::new_v4()),
+        Some(serde_json::json!({ "username": "friend", "role": "Owner" })),
+    )
+    .await;
+
+    assert!(status.is_client_error());
+}
+
+#[tokio::test]
+async fn test_shared_media_visible_to_grantee() {
+    let media = test_media(Uuid::new_v4());
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        }),
+        storage_service: Some(MockStorageService {
+            file_data: vec![1, 2, 3, 4],
+            ..MockStorageService::default()
+        }),
+        share_grant_repo: Some(MockShareGrantRepository {
+            media_files: vec![media.clone()],
+            ..MockShareGrantRepository::with_grants(vec![share_grant(
+                ShareTarget::Media(media.id),
+                get_test_user_id(),
+                ShareRole::Viewer,
+            )])
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(&state, "GET", "/shares/with-me".to_string(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["media"][0]["media_id"], media.id.to_string());
+    assert_eq!(json["data"]["albums"].as_array().unwrap().len(), 0);
+
+    let (status, _) = send(&state, "GET", format!("/media/stream/{}", media.id), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Viewers cannot manage the media file
+    let (status, _) = send(&state, "GET", format!("/shares/media/{}", media.id), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_media_of_other_user_not_streamed_without_grant() {
+    let media = test_media(Uuid::new_v4());
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, _) = send(&state, "GET", format!("/media/stream/{}", media.id), None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_public_share_link() {
+    let photo = exif_jpeg(16, 16, 1, Some((52, 31)));
+    let media = MediaFile {
+        file_size: photo.len() as i64,
+        ..test_media(get_test_user_id())
+    };
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        }),
+        storage_service: Some(MockStorageService {
+            file_data: photo.clone(),
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(
+        &state,
+        "POST",
+        "/shares/links".to_string(),
+        Some(serde_json::json!({
+            "media_id": media.id,
+            "password": "family",
+            "allow_download": true
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(json["data"]["has_password"], true);
+    let slug = json["data"]["slug"].as_str().unwrap().to_string();
+
+    let public_request = |uri: String, password: Option<&str>| {
+        let mut request = Request::builder().method("GET").uri(uri);
+        if let Some(password) = password {
+            request = request.header("x-share-password", password);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+    let app = || api_routes(state.clone()).with_state(state.clone());
+
+    let response = app()
+        .oneshot(public_request(format!("/public/links/{}", slug), None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app()
+        .oneshot(public_request(
+            format!("/public/links/{}", slug),
+            Some("family"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["media"][0]["media_id"], media.id.to_string());
+    assert!(json["data"]["media"][0].get("owner_id").is_none());
+
+    let response = app()
+        .oneshot(public_request(
+            format!("/public/links/{}/media/{}?download=true", slug, media.id),
+            Some("family"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-disposition").unwrap(),
+        "attachment; filename=\"photo.jpg\""
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    // The location is removed from the original, nothing else changes
+    assert_eq!(body.len(), photo.len());
+    assert_ne!(body.as_ref(), photo.as_slice());
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::Cursor::new(body.as_ref()))
+        .unwrap();
+    assert!(
+        exif.get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY)
+            .is_none()
+    );
+
+    let (status, json) = send(&state, "GET", "/shares/links".to_string(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"][0]["view_count"], 1);
+}
+
+#[tokio::test]
+async fn test_public_share_link_without_download_serves_preview() {
+    let media = test_media(get_test_user_id());
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(
+            MockMediaRepository {
+                saved_media: Some(media.clone()),
+                ..MockMediaRepository::default()
+            }
+            .with_renditions(vec![MediaRendition {
+                media_id: media.id,
+                name: "preview".to_string(),
+                file_path: format!("media/{}/thumb_{}_preview.jpg", media.user_id, media.id),
+                content_type: "image/jpeg".to_string(),
+                width: Some(1080),
+                height: Some(720),
+                file_size: Some(4),
+            }]),
+        ),
+        storage_service: Some(MockStorageService {
+            file_data: vec![1, 2, 3, 4],
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(
+        &state,
+        "POST",
+        "/shares/links".to_string(),
+        Some(serde_json::json!({ "media_id": media.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let slug = json["data"]["slug"].as_str().unwrap().to_string();
+
+    let app = api_routes(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/public/links/{}/media/{}", slug, media.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let e_tag = response.headers().get("etag").unwrap().to_str().unwrap();
+    assert!(e_tag.contains("-preview-"));
+    assert!(response.headers().get("accept-ranges").is_none());
+    assert!(response.headers().get("content-disposition").is_none());
+}
+
+#[tokio::test]
+async fn test_public_share_link_without_download_streams_video() {
+    let media = MediaFile {
+        content_type: "video/mp4".to_string(),
+        ..test_media(get_test_user_id())
+    };
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(TestTokenService)),
+        media_repo: Some(MockMediaRepository {
+            saved_media: Some(media.clone()),
+            ..MockMediaRepository::default()
+        }),
+        storage_service: Some(MockStorageService {
+            file_data: vec![1, 2, 3, 4],
+            ..MockStorageService::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+
+    let (status, json) = send(
+        &state,
+        "POST",
+        "/shares/links".to_string(),
+        Some(serde_json::json!({ "media_id": media.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let slug = json["data"]["slug"].as_str().unwrap().to_string();
+
+    let app = api_routes(state.clone()).with_state(state.clone());
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/public/links/{}/media/{}", slug, media.id))
+        .header("Range", "bytes=0-1")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers().get("content-type").unwrap(), "video/mp4");
+    assert!(response.headers().get("content-disposition").is_none());
+
+    // Downloading it as an attachment stays forbidden
+    let app = api_routes(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/public/links/{}/media/{}?download=true",
+            slug, media.id
+        ))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
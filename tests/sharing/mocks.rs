@@ -0,0 +1,327 @@
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use lib::{
+    albums::domain::{Album, AlbumId},
+    media::domain::{MediaFile, MediaId},
+    sharing::domain::{
+        NewShareGrant, NewShareLink, ShareGrant, ShareGrantAuthorizationService,
+        ShareGrantRepository, ShareGrantRepositoryError, ShareLink, ShareLinkId,
+        ShareLinkRepository, ShareLinkRepositoryError, ShareRole, ShareTarget, SharedAlbum,
+        SharedMedia,
+    },
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct MockShareGrantRepository {
+    pub fail: bool,
+    pub grants: Arc<Mutex<Vec<ShareGrant>>>,
+    /// Media files and albums the grants point to, used to answer the "shared with me" queries
+    pub media_files: Vec<MediaFile>,
+    pub albums: Vec<Album>,
+    /// Media files of each album, so album grants also cover the media files in the album
+    pub album_media: Vec<(AlbumId, MediaId)>,
+}
+
+impl MockShareGrantRepository {
+    pub fn with_grants(grants: Vec<ShareGrant>) -> Self {
+        MockShareGrantRepository {
+            grants: Arc::new(Mutex::new(grants)),
+            ..MockShareGrantRepository::default()
+        }
+    }
+
+    pub fn grants(&self) -> Vec<ShareGrant> {
+        self.grants.lock().unwrap().clone()
+    }
+
+    fn role(&self, target: ShareTarget, user_id: Uuid) -> Option<ShareRole> {
+        self.grants
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|grant| grant.target == target && grant.grantee_id == user_id)
+            .map(|grant| grant.role)
+    }
+}
+
+pub fn share_grant(target: ShareTarget, grantee_id: Uuid, role: ShareRole) -> ShareGrant {
+    ShareGrant {
+        target,
+        grantee_id,
+        role,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+/// Authorization service backed by the grants of `repo`
+pub fn test_authorization_service(
+    repo: MockShareGrantRepository,
+) -> ShareGrantAuthorizationService<MockShareGrantRepository> {
+    ShareGrantAuthorizationService::new(repo)
+}
+
+#[async_trait]
+impl ShareGrantRepository for MockShareGrantRepository {
+    async fn save_share_grant(
+        &self,
+        grant: NewShareGrant,
+    ) -> Result<ShareGrant, ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        let mut grants = self.grants.lock().unwrap();
+        grants.retain(|existing| {
+            existing.target != grant.target || existing.grantee_id != grant.grantee_id
+        });
+        let saved = share_grant(grant.target, grant.grantee_id, grant.role);
+        grants.push(saved.clone());
+        Ok(saved)
+    }
+
+    async fn delete_share_grant(
+        &self,
+        target: ShareTarget,
+        grantee_id: Uuid,
+    ) -> Result<(), ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        let mut grants = self.grants.lock().unwrap();
+        let count = grants.len();
+        grants.retain(|grant| grant.target != target || grant.grantee_id != grantee_id);
+        if grants.len() == count {
+            return Err(ShareGrantRepositoryError::ShareGrantNotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_share_grants_by_target(
+        &self,
+        target: ShareTarget,
+    ) -> Result<Vec<ShareGrant>, ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .grants()
+            .into_iter()
+            .filter(|grant| grant.target == target)
+            .collect())
+    }
+
+    async fn get_shared_media_by_grantee_id(
+        &self,
+        grantee_id: Uuid,
+    ) -> Result<Vec<SharedMedia>, ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .grants()
+            .into_iter()
+            .filter(|grant| grant.grantee_id == grantee_id)
+            .filter_map(|grant| match grant.target {
+                ShareTarget::Media(media_id) => self
+                    .media_files
+                    .iter()
+                    .find(|media_file| media_file.id == media_id)
+                    .map(|media_file| SharedMedia {
+                        grant: grant.clone(),
+                        media_file: media_file.clone(),
+                    }),
+                ShareTarget::Album(_) => None,
+            })
+            .collect())
+    }
+
+    async fn get_shared_albums_by_grantee_id(
+        &self,
+        grantee_id: Uuid,
+    ) -> Result<Vec<SharedAlbum>, ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .grants()
+            .into_iter()
+            .filter(|grant| grant.grantee_id == grantee_id)
+            .filter_map(|grant| match grant.target {
+                ShareTarget::Album(album_id) => self
+                    .albums
+                    .iter()
+                    .find(|album| album.id == album_id)
+                    .map(|album| SharedAlbum {
+                        grant: grant.clone(),
+                        album: album.clone(),
+                    }),
+                ShareTarget::Media(_) => None,
+            })
+            .collect())
+    }
+
+    async fn get_media_role(
+        &self,
+        media_id: MediaId,
+        user_id: Uuid,
+    ) -> Result<Option<ShareRole>, ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        let direct = self.role(ShareTarget::Media(media_id), user_id);
+        let through_albums = self
+            .album_media
+            .iter()
+            .filter(|(_, id)| *id == media_id)
+            .filter_map(|(album_id, _)| self.role(ShareTarget::Album(*album_id), user_id));
+        Ok(direct.into_iter().chain(through_albums).max())
+    }
+
+    async fn get_album_role(
+        &self,
+        album_id: AlbumId,
+        user_id: Uuid,
+    ) -> Result<Option<ShareRole>, ShareGrantRepositoryError> {
+        if self.fail {
+            return Err(ShareGrantRepositoryError::InternalServerError);
+        }
+        Ok(self.role(ShareTarget::Album(album_id), user_id))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockShareLinkRepository {
+    pub fail: bool,
+    pub links: Arc<Mutex<Vec<ShareLink>>>,
+}
+
+impl MockShareLinkRepository {
+    pub fn with_links(links: Vec<ShareLink>) -> Self {
+        MockShareLinkRepository {
+            links: Arc::new(Mutex::new(links)),
+            ..MockShareLinkRepository::default()
+        }
+    }
+
+    pub fn link(&self, id: ShareLinkId) -> Option<ShareLink> {
+        self.links
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|link| link.id == id)
+            .cloned()
+    }
+}
+
+pub fn share_link(owner_id: Uuid, target: ShareTarget) -> ShareLink {
+    ShareLink {
+        id: Uuid::new_v4(),
+        slug: ShareLink::generate_slug(),
+        owner_id,
+        target,
+        password_hash: None,
+        allow_download: false,
+        expires_at: None,
+        view_count: 0,
+        last_viewed_at: None,
+        created_at: Some(chrono::Utc::now().naive_utc()),
+        updated_at: Some(chrono::Utc::now().naive_utc()),
+    }
+}
+
+#[async_trait]
+impl ShareLinkRepository for MockShareLinkRepository {
+    async fn create_share_link(
+        &self,
+        link: NewShareLink,
+    ) -> Result<ShareLink, ShareLinkRepositoryError> {
+        if self.fail {
+            return Err(ShareLinkRepositoryError::InternalServerError);
+        }
+        let created = ShareLink {
+            slug: link.slug,
+            password_hash: link.password_hash,
+            allow_download: link.allow_download,
+            expires_at: link.expires_at,
+            ..share_link(link.owner_id, link.target)
+        };
+        self.links.lock().unwrap().push(created.clone());
+        Ok(created)
+    }
+
+    async fn get_share_link_by_id(
+        &self,
+        id: ShareLinkId,
+    ) -> Result<Option<ShareLink>, ShareLinkRepositoryError> {
+        if self.fail {
+            return Err(ShareLinkRepositoryError::InternalServerError);
+        }
+        Ok(self.link(id))
+    }
+
+    async fn get_share_link_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<ShareLink>, ShareLinkRepositoryError> {
+        if self.fail {
+            return Err(ShareLinkRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .links
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|link| link.slug == slug)
+            .cloned())
+    }
+
+    async fn get_share_links_by_owner_id(
+        &self,
+        owner_id: Uuid,
+    ) -> Result<Vec<ShareLink>, ShareLinkRepositoryError> {
+        if self.fail {
+            return Err(ShareLinkRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .links
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|link| link.owner_id == owner_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_share_link(&self, id: ShareLinkId) -> Result<(), ShareLinkRepositoryError> {
+        if self.fail {
+            return Err(ShareLinkRepositoryError::InternalServerError);
+        }
+        let mut links = self.links.lock().unwrap();
+        let count = links.len();
+        links.retain(|link| link.id != id);
+        if links.len() == count {
+            return Err(ShareLinkRepositoryError::ShareLinkNotFound);
+        }
+        Ok(())
+    }
+
+    async fn record_share_link_view(
+        &self,
+        id: ShareLinkId,
+    ) -> Result<(), ShareLinkRepositoryError> {
+        if self.fail {
+            return Err(ShareLinkRepositoryError::InternalServerError);
+        }
+        let mut links = self.links.lock().unwrap();
+        let link = links
+            .iter_mut()
+            .find(|link| link.id == id)
+            .ok_or(ShareLinkRepositoryError::ShareLinkNotFound)?;
+        link.view_count += 1;
+        link.last_viewed_at = Some(chrono::Utc::now().naive_utc());
+        Ok(())
+    }
+}
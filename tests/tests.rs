@@ -5,21 +5,32 @@ mod users {
     // Application layer tests
     pub mod application {
         pub mod commands {
+            mod test_change_password;
             mod test_create_user;
             mod test_login;
+            mod test_password_reset;
+            mod test_refresh_token;
+            mod test_revoke_session;
+            mod test_update_user_quota;
         }
     }
 
     // Domain layer tests
     pub mod domain {
         mod auth;
+        mod password;
+        mod quota;
+        mod refresh_token;
         mod roles;
+        mod session_cache;
         mod user;
         mod user_repository;
     }
 
     // Infrastructure layer tests
     pub mod infrastructure {
+        mod test_file_mailer;
+        mod test_jwt_keys;
         mod test_jwt_token_service;
         mod test_mappers;
         mod test_models;
@@ -33,14 +44,117 @@ mod users {
     pub use mocks::*;
 }
 
+mod albums {
+    pub mod application {
+        pub mod commands {
+            mod test_album_media;
+            mod test_create_album;
+        }
+
+        pub mod queries {
+            mod test_get_album;
+        }
+    }
+
+    pub mod integration {
+        mod test_album_endpoints;
+    }
+
+    pub mod mocks;
+    pub use mocks::*;
+}
+
+mod sharing {
+    pub mod application {
+        pub mod commands {
+            mod test_share_grants;
+            mod test_share_links;
+        }
+
+        pub mod queries {
+            mod test_get_shared_with_me;
+            mod test_open_share_link;
+        }
+    }
+
+    pub mod domain {
+        mod authorization_service;
+        mod share_link;
+    }
+
+    pub mod integration {
+        mod test_sharing_endpoints;
+    }
+
+    pub mod mocks;
+    pub use mocks::*;
+}
+
+mod jobs {
+    pub mod application {
+        pub mod commands {
+            mod test_process_job;
+            mod test_retry_job;
+        }
+
+        pub mod queries {
+            mod test_get_jobs;
+        }
+    }
+
+    pub mod domain {
+        mod job;
+    }
+
+    pub mod integration {
+        mod test_job_endpoints;
+    }
+
+    pub mod mocks;
+    pub use mocks::*;
+}
+
 mod media {
     pub mod application {
         pub mod commands {
             mod test_delete_media;
+            mod test_generate_thumbnail;
+            mod test_presigned_upload;
             mod test_upload_media;
+            mod test_upload_sessions;
+        }
+
+        pub mod queries {
+            mod test_check_media_checksums;
+            mod test_get_media_file;
+            mod test_get_media_files;
+            mod test_get_media_stream;
+            mod test_get_media_thumbnail;
         }
     }
 
+    pub mod domain {
+        mod byte_range;
+        mod content_sniffing;
+        mod media_file_page;
+        mod media_metadata_service;
+        mod media_rendition;
+        mod metadata_stripping;
+        mod spooled_file;
+        mod thumbnail_service;
+        mod upload_size_limits;
+        mod video_thumbnail_service;
+    }
+
+    pub mod infrastructure {
+        mod test_ffmpeg_frame_extractor;
+        #[cfg(feature = "heif")]
+        mod test_heif_command_decoder;
+        mod test_hmac_media_url_signer;
+        #[cfg(feature = "raw")]
+        mod test_raw_preview_decoder;
+    }
+
     pub mod integration {
         mod test_media_endpoints;
     }
@@ -0,0 +1,174 @@
+use lib::users::{
+    application::commands::change_password::{
+        ChangePasswordCommand, ChangePasswordError, ResetPasswordCommand,
+        change_password_command_handler, reset_password_command_handler,
+    },
+    domain::{Role, SessionCache, User, verify_password},
+};
+use uuid::Uuid;
+
+use crate::{
+    users::{MockRefreshTokenRepository, MockSessionRepository, MockUserRepository},
+    utils::functions::hash_password,
+};
+
+fn in_one_day() -> chrono::NaiveDateTime {
+    (chrono::Utc::now() + chrono::Duration::days(1)).naive_utc()
+}
+
+fn user_repository(password: &str) -> (MockUserRepository, Uuid) {
+    let user = User {
+        id: Uuid::new_v4(),
+        username: "alice".to_string(),
+        email: None,
+        password: hash_password(password),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        must_change_password: false,
+        created_at: None,
+        updated_at: None,
+    };
+    let user_id = user.id;
+    (
+        MockUserRepository {
+            user: Some(user),
+            user_exists: true,
+            ..MockUserRepository::default()
+        },
+        user_id,
+    )
+}
+
+#[tokio::test]
+async fn test_change_password_revokes_other_sessions() {
+    let (user_repo, user_id) = user_repository("password123");
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let other_session_id = session_repo.add_session(user_id);
+    let (refresh_token_repo, _) =
+        MockRefreshTokenRepository::with_token(user_id, other_session_id, in_one_day());
+    let session_cache = SessionCache::default();
+    session_cache.mark_active(other_session_id);
+
+    let result = change_password_command_handler(
+        ChangePasswordCommand {
+            user_id,
+            current_session_id: Some(session_id),
+            current_password: "password123".to_string(),
+            new_password: "new_password456".to_string(),
+        },
+        &user_repo,
+        &session_repo,
+        &refresh_token_repo,
+        &session_cache,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    let (password_hash, must_change_password) =
+        user_repo.updated_password.lock().unwrap().clone().unwrap();
+    assert!(verify_password("new_password456", &password_hash));
+    assert!(!must_change_password);
+
+    let sessions = session_repo.sessions();
+    let session = |id| sessions.iter().find(|session| session.id == id).unwrap();
+    assert!(session(session_id).revoked_at.is_none());
+    assert!(session(other_session_id).revoked_at.is_some());
+    assert!(refresh_token_repo.tokens()[0].revoked_at.is_some());
+    assert!(!session_cache.is_active(other_session_id));
+}
+
+#[tokio::test]
+async fn test_change_password_wrong_current_password() {
+    let (user_repo, user_id) = user_repository("password123");
+    let (session_repo, _) = MockSessionRepository::with_session(user_id);
+
+    let result = change_password_command_handler(
+        ChangePasswordCommand {
+            user_id,
+            current_session_id: None,
+            current_password: "wrong_password".to_string(),
+            new_password: "new_password456".to_string(),
+        },
+        &user_repo,
+        &session_repo,
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(ChangePasswordError::InvalidCurrentPassword)
+    ));
+    assert!(user_repo.updated_password.lock().unwrap().is_none());
+    assert!(session_repo.sessions()[0].revoked_at.is_none());
+}
+
+#[tokio::test]
+async fn test_change_password_same_password() {
+    let (user_repo, user_id) = user_repository("password123");
+
+    let result = change_password_command_handler(
+        ChangePasswordCommand {
+            user_id,
+            current_session_id: None,
+            current_password: "password123".to_string(),
+            new_password: "password123".to_string(),
+        },
+        &user_repo,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(ChangePasswordError::SamePassword)));
+}
+
+#[tokio::test]
+async fn test_reset_password_requires_change_and_revokes_all_sessions() {
+    let (user_repo, user_id) = user_repository("password123");
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+
+    let result = reset_password_command_handler(
+        ResetPasswordCommand {
+            user_id,
+            new_password: "temporary123".to_string(),
+        },
+        &user_repo,
+        &session_repo,
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(result.is_ok());
+    let (password_hash, must_change_password) =
+        user_repo.updated_password.lock().unwrap().clone().unwrap();
+    assert!(verify_password("temporary123", &password_hash));
+    assert!(must_change_password);
+    assert!(
+        session_repo
+            .sessions()
+            .iter()
+            .all(|session| session.id != session_id || session.revoked_at.is_some())
+    );
+}
+
+#[tokio::test]
+async fn test_reset_password_user_not_found() {
+    let result = reset_password_command_handler(
+        ResetPasswordCommand {
+            user_id: Uuid::new_v4(),
+            new_password: "temporary123".to_string(),
+        },
+        &MockUserRepository::default(),
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(ChangePasswordError::UserNotFound)));
+}
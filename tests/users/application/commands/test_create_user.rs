@@ -1,8 +1,10 @@
 use crate::users::MockUserRepository;
 use lib::users::{
     application::commands::create_user::{CreateUserCommand, create_user_command_handler},
-    domain::UserRepositoryError,
+    domain::{Role, User, UserRepositoryError},
 };
+use uuid::Uuid;
+use validator::Validate;
 
 #[tokio::test]
 async fn test_create_user_success() {
@@ -14,8 +16,10 @@ async fn test_create_user_success() {
     };
     let cmd = CreateUserCommand {
         username: "alice".to_string(),
+        email: None,
         password: "password123".to_string(),
         role: None,
+        must_change_password: false,
     };
     let result = create_user_command_handler(cmd, &repo).await;
     assert!(result.is_ok());
@@ -33,8 +37,10 @@ async fn test_create_user_already_exists() {
     };
     let cmd = CreateUserCommand {
         username: "bob".to_string(),
+        email: None,
         password: "password123".to_string(),
         role: None,
+        must_change_password: false,
     };
     let result = create_user_command_handler(cmd, &repo).await;
     assert!(matches!(
@@ -53,8 +59,10 @@ async fn test_create_user_repo_error_on_get() {
     };
     let cmd = CreateUserCommand {
         username: "bob".to_string(),
+        email: None,
         password: "password123".to_string(),
         role: None,
+        must_change_password: false,
     };
     let result = create_user_command_handler(cmd, &repo).await;
     assert!(matches!(
@@ -73,8 +81,10 @@ async fn test_create_user_repo_error_on_create() {
     };
     let cmd = CreateUserCommand {
         username: "bob".to_string(),
+        email: None,
         password: "password123".to_string(),
         role: None,
+        must_change_password: false,
     };
     let result = create_user_command_handler(cmd, &repo).await;
     assert!(matches!(
@@ -82,3 +92,61 @@ async fn test_create_user_repo_error_on_create() {
         Err(UserRepositoryError::InternalServerError)
     ));
 }
+
+#[tokio::test]
+async fn test_create_user_normalizes_email() {
+    let repo = MockUserRepository::default();
+    let cmd = CreateUserCommand {
+        username: "alice".to_string(),
+        email: Some(" Alice@Example.com ".to_string()),
+        password: "password123".to_string(),
+        role: None,
+        must_change_password: false,
+    };
+    let user = create_user_command_handler(cmd, &repo).await.unwrap();
+    assert_eq!(user.email.as_deref(), Some("alice@example.com"));
+}
+
+#[tokio::test]
+async fn test_create_user_email_already_exists() {
+    let repo = MockUserRepository {
+        users_list: vec![User {
+            id: Uuid::new_v4(),
+            username: "bob".to_string(),
+            email: Some("shared@example.com".to_string()),
+            password: "hashed".to_string(),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            must_change_password: false,
+            created_at: None,
+            updated_at: None,
+        }],
+        ..MockUserRepository::default()
+    };
+    let cmd = CreateUserCommand {
+        username: "alice".to_string(),
+        email: Some("Shared@example.com".to_string()),
+        password: "password123".to_string(),
+        role: None,
+        must_change_password: false,
+    };
+    let result = create_user_command_handler(cmd, &repo).await;
+    assert!(matches!(
+        result,
+        Err(UserRepositoryError::EmailAlreadyExists)
+    ));
+}
+
+#[test]
+fn test_create_user_invalid_email() {
+    let cmd = CreateUserCommand {
+        username: "alice".to_string(),
+        email: Some("not-an-email".to_string()),
+        password: "password123".to_string(),
+        role: None,
+        must_change_password: false,
+    };
+    let errors = cmd.validate().unwrap_err();
+    assert!(errors.field_errors().contains_key("email"));
+}
@@ -1,12 +1,15 @@
 use chrono::DateTime;
 use lib::users::{
     application::login::{LoginCommand, login_command_handler},
-    domain::{Role, User, user::UserLoginError},
+    domain::{AuthTokenConfig, RefreshToken, Role, SessionClient, User, user::UserLoginError},
 };
 use uuid::Uuid;
 
 use crate::{
-    users::{MockLoginTokenService, MockUserRepository},
+    users::{
+        MockLoginTokenService, MockRefreshTokenRepository, MockSessionRepository,
+        MockUserRepository,
+    },
     utils::functions::hash_password,
 };
 
@@ -16,10 +19,14 @@ async fn test_login_success() {
     let user = User {
         id: Uuid::new_v4(),
         username: "alice".to_string(),
+        email: None,
         password: hashed,
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: None,
+        must_change_password: false,
     };
     let repo = MockUserRepository {
         user: Some(user),
@@ -28,15 +35,53 @@ async fn test_login_success() {
     };
     let token_service = MockLoginTokenService {
         fail: false,
-        validation_fail: false,
+        ..MockLoginTokenService::default()
     };
     let cmd = LoginCommand {
         username: "alice".to_string(),
         password: "password123".to_string(),
+        device_name: Some("Alice's phone".to_string()),
+        new_password: None,
     };
-    let result = login_command_handler(cmd, &repo, &token_service).await;
+    let session_repo = MockSessionRepository::default();
+    let refresh_token_repo = MockRefreshTokenRepository::default();
+    let result = login_command_handler(
+        cmd,
+        SessionClient {
+            user_agent: Some("TestAgent/1.0".to_string()),
+            ip_address: Some("192.0.2.1".to_string()),
+        },
+        &repo,
+        &token_service,
+        &session_repo,
+        &refresh_token_repo,
+        &AuthTokenConfig::default(),
+    )
+    .await;
     assert!(result.is_ok());
-    assert_eq!(result.unwrap().0, "mocktoken");
+    let tokens = result.unwrap();
+    assert_eq!(tokens.access_token.0, "mocktoken");
+    assert_eq!(
+        tokens.expires_in,
+        AuthTokenConfig::default().access_token_ttl_seconds
+    );
+
+    // Only the hash of the refresh token is stored
+    let stored = refresh_token_repo.tokens();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(
+        stored[0].token_hash,
+        RefreshToken::hash(&tokens.refresh_token)
+    );
+    assert_ne!(stored[0].token_hash, tokens.refresh_token);
+
+    // The login is recorded as a session of the device, its refresh tokens share its id
+    let sessions = session_repo.sessions();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, stored[0].family_id);
+    assert_eq!(sessions[0].device_name.as_deref(), Some("Alice's phone"));
+    assert_eq!(sessions[0].user_agent.as_deref(), Some("TestAgent/1.0"));
+    assert_eq!(sessions[0].ip_address.as_deref(), Some("192.0.2.1"));
 }
 
 #[tokio::test]
@@ -50,8 +95,19 @@ async fn test_login_user_not_found() {
     let cmd = LoginCommand {
         username: "bob".to_string(),
         password: "password123".to_string(),
+        device_name: None,
+        new_password: None,
     };
-    let result = login_command_handler(cmd, &repo, &token_service).await;
+    let result = login_command_handler(
+        cmd,
+        SessionClient::default(),
+        &repo,
+        &token_service,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &AuthTokenConfig::default(),
+    )
+    .await;
     assert!(matches!(result, Err(UserLoginError::InvalidCredentials)));
 }
 
@@ -61,10 +117,14 @@ async fn test_login_wrong_password() {
     let user = User {
         id: Uuid::new_v4(),
         username: "alice".to_string(),
+        email: None,
         password: hashed,
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: None,
+        must_change_password: false,
     };
     let repo = MockUserRepository {
         user: Some(user),
@@ -75,8 +135,19 @@ async fn test_login_wrong_password() {
     let cmd = LoginCommand {
         username: "alice".to_string(),
         password: "wrongpassword".to_string(),
+        device_name: None,
+        new_password: None,
     };
-    let result = login_command_handler(cmd, &repo, &token_service).await;
+    let result = login_command_handler(
+        cmd,
+        SessionClient::default(),
+        &repo,
+        &token_service,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &AuthTokenConfig::default(),
+    )
+    .await;
     assert!(matches!(result, Err(UserLoginError::InvalidCredentials)));
 }
 
@@ -85,10 +156,14 @@ async fn test_login_invalid_hash() {
     let user = User {
         id: Uuid::new_v4(),
         username: "alice".to_string(),
+        email: None,
         password: "not_a_valid_hash".to_string(),
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: None,
+        must_change_password: false,
     };
     let repo = MockUserRepository {
         user: Some(user),
@@ -99,8 +174,19 @@ async fn test_login_invalid_hash() {
     let cmd = LoginCommand {
         username: "alice".to_string(),
         password: "password123".to_string(),
+        device_name: None,
+        new_password: None,
     };
-    let result = login_command_handler(cmd, &repo, &token_service).await;
+    let result = login_command_handler(
+        cmd,
+        SessionClient::default(),
+        &repo,
+        &token_service,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &AuthTokenConfig::default(),
+    )
+    .await;
     assert!(matches!(result, Err(UserLoginError::InvalidCredentials)));
 }
 
@@ -110,10 +196,14 @@ async fn test_login_token_creation_failure() {
     let user = User {
         id: Uuid::new_v4(),
         username: "alice".to_string(),
+        email: None,
         password: hashed,
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: None,
+        must_change_password: false,
     };
     let repo = MockUserRepository {
         user: Some(user),
@@ -122,15 +212,107 @@ async fn test_login_token_creation_failure() {
     };
     let token_service = MockLoginTokenService {
         fail: true,
-        validation_fail: false,
+        ..MockLoginTokenService::default()
     };
     let cmd = LoginCommand {
         username: "alice".to_string(),
         password: "password123".to_string(),
+        device_name: None,
+        new_password: None,
     };
-    let result = login_command_handler(cmd, &repo, &token_service).await;
+    let result = login_command_handler(
+        cmd,
+        SessionClient::default(),
+        &repo,
+        &token_service,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &AuthTokenConfig::default(),
+    )
+    .await;
     assert!(matches!(
         result,
         Err(UserLoginError::InternalServerError(_))
     ));
 }
+
+fn user_with_reset_password() -> MockUserRepository {
+    let user = User {
+        id: Uuid::new_v4(),
+        username: "alice".to_string(),
+        email: None,
+        password: hash_password("temporary123"),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+        updated_at: None,
+        must_change_password: true,
+    };
+    MockUserRepository {
+        user: Some(user),
+        user_exists: true,
+        ..MockUserRepository::default()
+    }
+}
+
+#[tokio::test]
+async fn test_login_password_change_required() {
+    let repo = user_with_reset_password();
+    let session_repo = MockSessionRepository::default();
+    for new_password in [None, Some("temporary123".to_string())] {
+        let cmd = LoginCommand {
+            username: "alice".to_string(),
+            password: "temporary123".to_string(),
+            device_name: None,
+            new_password,
+        };
+        let result = login_command_handler(
+            cmd,
+            SessionClient::default(),
+            &repo,
+            &MockLoginTokenService::default(),
+            &session_repo,
+            &MockRefreshTokenRepository::default(),
+            &AuthTokenConfig::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(UserLoginError::PasswordChangeRequired)
+        ));
+    }
+    assert!(repo.updated_password.lock().unwrap().is_none());
+    assert!(session_repo.sessions().is_empty());
+}
+
+#[tokio::test]
+async fn test_login_with_new_password_after_reset() {
+    let repo = user_with_reset_password();
+    let session_repo = MockSessionRepository::default();
+    let cmd = LoginCommand {
+        username: "alice".to_string(),
+        password: "temporary123".to_string(),
+        device_name: None,
+        new_password: Some("new_password456".to_string()),
+    };
+    let result = login_command_handler(
+        cmd,
+        SessionClient::default(),
+        &repo,
+        &MockLoginTokenService::default(),
+        &session_repo,
+        &MockRefreshTokenRepository::default(),
+        &AuthTokenConfig::default(),
+    )
+    .await;
+    assert!(result.is_ok());
+    let (password_hash, must_change_password) =
+        repo.updated_password.lock().unwrap().clone().unwrap();
+    assert!(lib::users::domain::verify_password(
+        "new_password456",
+        &password_hash
+    ));
+    assert!(!must_change_password);
+    assert_eq!(session_repo.sessions().len(), 1);
+}
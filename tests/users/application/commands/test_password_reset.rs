@@ -0,0 +1,240 @@
+use lib::users::{
+    application::commands::password_reset::{
+        ConfirmPasswordResetCommand, PasswordResetError, RequestPasswordResetCommand,
+        confirm_password_reset_command_handler, request_password_reset_command_handler,
+    },
+    domain::{PasswordResetConfig, PasswordResetToken, Role, SessionCache, User, verify_password},
+};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    users::{
+        MockMailer, MockPasswordResetTokenRepository, MockRefreshTokenRepository,
+        MockSessionRepository, MockUserRepository,
+    },
+    utils::functions::hash_password,
+};
+
+fn in_one_hour() -> chrono::NaiveDateTime {
+    (chrono::Utc::now() + chrono::Duration::hours(1)).naive_utc()
+}
+
+fn user_repository() -> (MockUserRepository, Uuid) {
+    let user = User {
+        id: Uuid::new_v4(),
+        username: "alice".to_string(),
+        email: Some("alice@example.com".to_string()),
+        password: hash_password("password123"),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        must_change_password: true,
+        created_at: None,
+        updated_at: None,
+    };
+    let user_id = user.id;
+    (
+        MockUserRepository {
+            user: Some(user),
+            user_exists: true,
+            ..MockUserRepository::default()
+        },
+        user_id,
+    )
+}
+
+#[tokio::test]
+async fn test_request_password_reset_sends_token() {
+    let (user_repo, user_id) = user_repository();
+    let token_repo = MockPasswordResetTokenRepository::default();
+    let mailer = MockMailer::default();
+    let config = PasswordResetConfig {
+        reset_url: Some("https://photos.example.com/reset?token={token}".to_string()),
+        ..PasswordResetConfig::default()
+    };
+
+    let result = request_password_reset_command_handler(
+        RequestPasswordResetCommand {
+            email: " Alice@Example.com ".to_string(),
+        },
+        &user_repo,
+        Arc::new(token_repo.clone()),
+        Arc::new(mailer.clone()),
+        &config,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    // The token is stored and sent in the background
+    let sent = mailer.wait_for_sent(1).await;
+    assert_eq!(sent.len(), 1);
+    let tokens = token_repo.tokens();
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0].user_id, user_id);
+    assert!(!tokens[0].is_expired());
+
+    assert_eq!(sent[0].to, "alice@example.com");
+    // Only the hash of the emailed token is stored
+    let token = sent[0]
+        .body
+        .split("?token=")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .unwrap();
+    assert_eq!(PasswordResetToken::hash(token), tokens[0].token_hash);
+}
+
+#[tokio::test]
+async fn test_request_password_reset_unknown_email() {
+    let (user_repo, _) = user_repository();
+    let token_repo = MockPasswordResetTokenRepository::default();
+    let mailer = MockMailer::default();
+
+    let result = request_password_reset_command_handler(
+        RequestPasswordResetCommand {
+            email: "bob@example.com".to_string(),
+        },
+        &user_repo,
+        Arc::new(token_repo.clone()),
+        Arc::new(mailer.clone()),
+        &PasswordResetConfig::default(),
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(mailer.wait_for_sent(1).await.is_empty());
+    assert!(token_repo.tokens().is_empty());
+}
+
+#[tokio::test]
+async fn test_request_password_reset_mailer_failure() {
+    let (user_repo, _) = user_repository();
+    let token_repo = MockPasswordResetTokenRepository::default();
+    let mailer = MockMailer {
+        fail: true,
+        ..MockMailer::default()
+    };
+
+    let result = request_password_reset_command_handler(
+        RequestPasswordResetCommand {
+            email: "alice@example.com".to_string(),
+        },
+        &user_repo,
+        Arc::new(token_repo.clone()),
+        Arc::new(mailer.clone()),
+        &PasswordResetConfig::default(),
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(mailer.wait_for_sent(1).await.is_empty());
+    assert_eq!(token_repo.tokens().len(), 1);
+}
+
+#[tokio::test]
+async fn test_confirm_password_reset_sets_password() {
+    let (user_repo, user_id) = user_repository();
+    let (token_repo, token) = MockPasswordResetTokenRepository::with_token(user_id, in_one_hour());
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let session_cache = SessionCache::default();
+    session_cache.mark_active(session_id);
+
+    let result = confirm_password_reset_command_handler(
+        ConfirmPasswordResetCommand {
+            token,
+            new_password: "new_password456".to_string(),
+        },
+        &user_repo,
+        &token_repo,
+        &session_repo,
+        &MockRefreshTokenRepository::default(),
+        &session_cache,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    let (password_hash, must_change_password) =
+        user_repo.updated_password.lock().unwrap().clone().unwrap();
+    assert!(verify_password("new_password456", &password_hash));
+    assert!(!must_change_password);
+    assert!(token_repo.tokens()[0].used_at.is_some());
+    assert!(session_repo.sessions()[0].revoked_at.is_some());
+    assert!(!session_cache.is_active(session_id));
+}
+
+#[tokio::test]
+async fn test_confirm_password_reset_token_is_single_use() {
+    let (user_repo, user_id) = user_repository();
+    let (token_repo, token) = MockPasswordResetTokenRepository::with_token(user_id, in_one_hour());
+    let session_repo = MockSessionRepository::default();
+    let refresh_token_repo = MockRefreshTokenRepository::default();
+    let session_cache = SessionCache::default();
+    let confirm = |token: String| {
+        confirm_password_reset_command_handler(
+            ConfirmPasswordResetCommand {
+                token,
+                new_password: "new_password456".to_string(),
+            },
+            &user_repo,
+            &token_repo,
+            &session_repo,
+            &refresh_token_repo,
+            &session_cache,
+        )
+    };
+
+    assert!(confirm(token.clone()).await.is_ok());
+    assert!(matches!(
+        confirm(token).await,
+        Err(PasswordResetError::InvalidToken)
+    ));
+}
+
+#[tokio::test]
+async fn test_confirm_password_reset_expired_token() {
+    let (user_repo, user_id) = user_repository();
+    let (token_repo, token) = MockPasswordResetTokenRepository::with_token(
+        user_id,
+        (chrono::Utc::now() - chrono::Duration::minutes(1)).naive_utc(),
+    );
+
+    let result = confirm_password_reset_command_handler(
+        ConfirmPasswordResetCommand {
+            token,
+            new_password: "new_password456".to_string(),
+        },
+        &user_repo,
+        &token_repo,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(PasswordResetError::InvalidToken)));
+    assert!(user_repo.updated_password.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_confirm_password_reset_unknown_token() {
+    let (user_repo, user_id) = user_repository();
+    let (token_repo, _) = MockPasswordResetTokenRepository::with_token(user_id, in_one_hour());
+
+    let result = confirm_password_reset_command_handler(
+        ConfirmPasswordResetCommand {
+            token: PasswordResetToken::generate(),
+            new_password: "new_password456".to_string(),
+        },
+        &user_repo,
+        &token_repo,
+        &MockSessionRepository::default(),
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(PasswordResetError::InvalidToken)));
+    assert!(token_repo.tokens()[0].used_at.is_none());
+}
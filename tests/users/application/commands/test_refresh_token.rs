@@ -0,0 +1,220 @@
+use chrono::DateTime;
+use lib::users::{
+    application::commands::{
+        logout::{LogoutCommand, logout_command_handler},
+        refresh_token::{RefreshTokenCommand, refresh_token_command_handler},
+    },
+    domain::{AuthTokenConfig, RefreshToken, Role, SessionCache, User, user::UserLoginError},
+};
+use uuid::Uuid;
+
+use crate::users::{
+    MockLoginTokenService, MockRefreshTokenRepository, MockSessionRepository, MockUserRepository,
+};
+
+fn test_user() -> User {
+    User {
+        id: Uuid::new_v4(),
+        username: "alice".to_string(),
+        email: None,
+        password: "hashed".to_string(),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+        updated_at: None,
+        must_change_password: false,
+    }
+}
+
+fn in_one_day() -> chrono::NaiveDateTime {
+    (chrono::Utc::now() + chrono::Duration::days(1)).naive_utc()
+}
+
+/// Repositories holding an active session of the user with a single refresh token
+fn session_with_token(
+    user_id: Uuid,
+    expires_at: chrono::NaiveDateTime,
+) -> (MockSessionRepository, MockRefreshTokenRepository, String) {
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let (refresh_token_repo, token) =
+        MockRefreshTokenRepository::with_token(user_id, session_id, expires_at);
+    (session_repo, refresh_token_repo, token)
+}
+
+async fn refresh(
+    user_repo: &MockUserRepository,
+    session_repo: &MockSessionRepository,
+    refresh_token_repo: &MockRefreshTokenRepository,
+    refresh_token: &str,
+) -> Result<lib::users::domain::AuthTokens, UserLoginError> {
+    refresh_token_command_handler(
+        RefreshTokenCommand {
+            refresh_token: refresh_token.to_string(),
+        },
+        user_repo,
+        &MockLoginTokenService::default(),
+        session_repo,
+        refresh_token_repo,
+        &SessionCache::default(),
+        &AuthTokenConfig::default(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_refresh_token_rotates_token() {
+    let user = test_user();
+    let user_repo = MockUserRepository {
+        user: Some(user.clone()),
+        ..MockUserRepository::default()
+    };
+    let (session_repo, refresh_token_repo, token) = session_with_token(user.id, in_one_day());
+
+    let tokens = refresh(&user_repo, &session_repo, &refresh_token_repo, &token)
+        .await
+        .unwrap();
+
+    assert_eq!(tokens.access_token.0, "mocktoken");
+    assert_ne!(tokens.refresh_token, token);
+    let stored = refresh_token_repo.tokens();
+    assert_eq!(stored.len(), 2);
+    assert!(stored[0].used_at.is_some());
+    // The replacement stays in the family of the login
+    assert_eq!(stored[1].family_id, stored[0].family_id);
+    assert_eq!(
+        stored[1].token_hash,
+        RefreshToken::hash(&tokens.refresh_token)
+    );
+    assert!(stored[1].used_at.is_none());
+}
+
+#[tokio::test]
+async fn test_refresh_token_reuse_revokes_session() {
+    let user = test_user();
+    let user_repo = MockUserRepository {
+        user: Some(user.clone()),
+        ..MockUserRepository::default()
+    };
+    let (session_repo, refresh_token_repo, token) = session_with_token(user.id, in_one_day());
+    let tokens = refresh(&user_repo, &session_repo, &refresh_token_repo, &token)
+        .await
+        .unwrap();
+
+    let result = refresh(&user_repo, &session_repo, &refresh_token_repo, &token).await;
+
+    assert!(matches!(result, Err(UserLoginError::RefreshTokenReused)));
+    assert!(session_repo.sessions()[0].revoked_at.is_some());
+    assert!(
+        refresh_token_repo
+            .tokens()
+            .iter()
+            .all(|token| token.revoked_at.is_some())
+    );
+    // The token handed out by the legitimate refresh is revoked as well
+    let result = refresh(
+        &user_repo,
+        &session_repo,
+        &refresh_token_repo,
+        &tokens.refresh_token,
+    )
+    .await;
+    assert!(matches!(result, Err(UserLoginError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn test_refresh_token_expired() {
+    let user = test_user();
+    let user_repo = MockUserRepository {
+        user: Some(user.clone()),
+        ..MockUserRepository::default()
+    };
+    let expired_at = (chrono::Utc::now() - chrono::Duration::minutes(1)).naive_utc();
+    let (session_repo, refresh_token_repo, token) = session_with_token(user.id, expired_at);
+
+    let result = refresh(&user_repo, &session_repo, &refresh_token_repo, &token).await;
+
+    assert!(matches!(result, Err(UserLoginError::InvalidToken)));
+    assert_eq!(refresh_token_repo.tokens().len(), 1);
+}
+
+#[tokio::test]
+async fn test_refresh_token_unknown() {
+    let user_repo = MockUserRepository::default();
+    let session_repo = MockSessionRepository::default();
+    let refresh_token_repo = MockRefreshTokenRepository::default();
+
+    let result = refresh(&user_repo, &session_repo, &refresh_token_repo, "unknown").await;
+
+    assert!(matches!(result, Err(UserLoginError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn test_refresh_token_deleted_user() {
+    let user_repo = MockUserRepository::default();
+    let (session_repo, refresh_token_repo, token) =
+        session_with_token(Uuid::new_v4(), in_one_day());
+
+    let result = refresh(&user_repo, &session_repo, &refresh_token_repo, &token).await;
+
+    assert!(matches!(result, Err(UserLoginError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn test_refresh_token_revoked_session() {
+    let user = test_user();
+    let user_repo = MockUserRepository {
+        user: Some(user.clone()),
+        ..MockUserRepository::default()
+    };
+    let (session_repo, refresh_token_repo, token) = session_with_token(user.id, in_one_day());
+    session_repo.sessions.lock().unwrap()[0].revoked_at = Some(chrono::Utc::now().naive_utc());
+
+    let result = refresh(&user_repo, &session_repo, &refresh_token_repo, &token).await;
+
+    assert!(matches!(result, Err(UserLoginError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn test_logout_revokes_session() {
+    let user = test_user();
+    let user_repo = MockUserRepository {
+        user: Some(user.clone()),
+        ..MockUserRepository::default()
+    };
+    let (session_repo, refresh_token_repo, token) = session_with_token(user.id, in_one_day());
+
+    let result = logout_command_handler(
+        LogoutCommand {
+            refresh_token: token.clone(),
+        },
+        &session_repo,
+        &refresh_token_repo,
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(session_repo.sessions()[0].revoked_at.is_some());
+    assert!(refresh_token_repo.tokens()[0].revoked_at.is_some());
+    let result = refresh(&user_repo, &session_repo, &refresh_token_repo, &token).await;
+    assert!(matches!(result, Err(UserLoginError::InvalidToken)));
+}
+
+#[tokio::test]
+async fn test_logout_unknown_token() {
+    let session_repo = MockSessionRepository::default();
+    let refresh_token_repo = MockRefreshTokenRepository::default();
+
+    let result = logout_command_handler(
+        LogoutCommand {
+            refresh_token: "unknown".to_string(),
+        },
+        &session_repo,
+        &refresh_token_repo,
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
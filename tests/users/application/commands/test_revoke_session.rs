@@ -0,0 +1,109 @@
+use lib::users::{
+    application::commands::revoke_session::{
+        RevokeOtherSessionsCommand, RevokeSessionCommand, RevokeSessionError,
+        revoke_other_sessions_command_handler, revoke_session_command_handler,
+    },
+    domain::SessionCache,
+};
+use uuid::Uuid;
+
+use crate::users::{MockRefreshTokenRepository, MockSessionRepository};
+
+fn in_one_day() -> chrono::NaiveDateTime {
+    (chrono::Utc::now() + chrono::Duration::days(1)).naive_utc()
+}
+
+#[tokio::test]
+async fn test_revoke_session_revokes_refresh_tokens() {
+    let user_id = Uuid::new_v4();
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let (refresh_token_repo, _) =
+        MockRefreshTokenRepository::with_token(user_id, session_id, in_one_day());
+    let session_cache = SessionCache::default();
+    session_cache.mark_active(session_id);
+
+    let result = revoke_session_command_handler(
+        RevokeSessionCommand {
+            user_id,
+            session_id,
+        },
+        &session_repo,
+        &refresh_token_repo,
+        &session_cache,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(session_repo.sessions()[0].revoked_at.is_some());
+    assert!(refresh_token_repo.tokens()[0].revoked_at.is_some());
+    // Requests with the session's access tokens are checked against the repository again
+    assert!(!session_cache.is_active(session_id));
+}
+
+#[tokio::test]
+async fn test_revoke_session_not_found() {
+    let (session_repo, session_id) = MockSessionRepository::with_session(Uuid::new_v4());
+    let refresh_token_repo = MockRefreshTokenRepository::default();
+
+    let result = revoke_session_command_handler(
+        RevokeSessionCommand {
+            user_id: Uuid::new_v4(),
+            session_id,
+        },
+        &session_repo,
+        &refresh_token_repo,
+        &SessionCache::default(),
+    )
+    .await;
+
+    assert!(matches!(result, Err(RevokeSessionError::NotFound)));
+    assert!(session_repo.sessions()[0].revoked_at.is_none());
+}
+
+#[tokio::test]
+async fn test_revoke_other_sessions_keeps_current_session() {
+    let user_id = Uuid::new_v4();
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let other_session_id = session_repo.add_session(user_id);
+    let (refresh_token_repo, _) =
+        MockRefreshTokenRepository::with_token(user_id, other_session_id, in_one_day());
+
+    let result = revoke_other_sessions_command_handler(
+        RevokeOtherSessionsCommand {
+            user_id,
+            current_session_id: Some(session_id),
+        },
+        &session_repo,
+        &refresh_token_repo,
+        &SessionCache::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.revoked_sessions, 1);
+    let sessions = session_repo.sessions();
+    assert!(sessions[0].revoked_at.is_none());
+    assert!(sessions[1].revoked_at.is_some());
+    assert!(refresh_token_repo.tokens()[0].revoked_at.is_some());
+}
+
+#[tokio::test]
+async fn test_revoke_other_sessions_without_current_session() {
+    let user_id = Uuid::new_v4();
+    let (session_repo, _) = MockSessionRepository::with_session(user_id);
+    session_repo.add_session(user_id);
+
+    let result = revoke_other_sessions_command_handler(
+        RevokeOtherSessionsCommand {
+            user_id,
+            current_session_id: None,
+        },
+        &session_repo,
+        &MockRefreshTokenRepository::default(),
+        &SessionCache::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.revoked_sessions, 2);
+}
@@ -0,0 +1,53 @@
+use crate::users::MockUserRepository;
+use lib::users::{
+    application::commands::update_user_quota::{
+        UpdateUserQuotaCommand, update_user_quota_command_handler,
+    },
+    domain::{Role, User, UserRepositoryError},
+};
+use uuid::Uuid;
+
+fn user(id: Uuid) -> User {
+    User {
+        id,
+        username: "alice".to_string(),
+        email: None,
+        password: "hashed_password".to_string(),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        created_at: None,
+        updated_at: None,
+        must_change_password: false,
+    }
+}
+
+#[tokio::test]
+async fn test_update_user_quota_success() {
+    let id = Uuid::new_v4();
+    let repo = MockUserRepository {
+        user: Some(user(id)),
+        ..MockUserRepository::default()
+    };
+    let cmd = UpdateUserQuotaCommand {
+        id,
+        quota_bytes: Some(1024),
+        quota_items: None,
+    };
+    let result = update_user_quota_command_handler(cmd, &repo).await.unwrap();
+    assert_eq!(result.id, id);
+    assert_eq!(result.quota_bytes, Some(1024));
+    assert_eq!(result.quota_items, None);
+}
+
+#[tokio::test]
+async fn test_update_user_quota_user_not_found() {
+    let repo = MockUserRepository::default();
+    let cmd = UpdateUserQuotaCommand {
+        id: Uuid::new_v4(),
+        quota_bytes: Some(1024),
+        quota_items: Some(10),
+    };
+    let result = update_user_quota_command_handler(cmd, &repo).await;
+    assert!(matches!(result, Err(UserRepositoryError::UserNotFound)));
+}
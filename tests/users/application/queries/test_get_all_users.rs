@@ -14,6 +14,8 @@ async fn test_get_all_users_success() {
             username: "alice".to_string(),
             password: "hashed1".to_string(),
             role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         },
@@ -22,6 +24,8 @@ async fn test_get_all_users_success() {
             username: "bob".to_string(),
             password: "hashed2".to_string(),
             role: Role::Admin,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
         },
@@ -73,6 +77,8 @@ async fn test_get_all_users_result_excludes_sensitive_data() {
         username: "testuser".to_string(),
         password: "secret_password".to_string(),
         role: Role::Admin,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
     };
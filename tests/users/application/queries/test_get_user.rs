@@ -14,6 +14,8 @@ async fn test_get_user_success() {
         username: "alice".to_string(),
         password: "hashed_password".to_string(),
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
     };
@@ -70,6 +72,8 @@ async fn test_get_user_result_excludes_sensitive_data() {
         username: "testuser".to_string(),
         password: "super_secret_password".to_string(),
         role: Role::Admin,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(123456789, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(987654321, 0).unwrap().naive_utc()),
     };
@@ -104,6 +108,8 @@ async fn test_get_user_from_users_list() {
             username: "alice".to_string(),
             password: "hashed1".to_string(),
             role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         },
@@ -112,6 +118,8 @@ async fn test_get_user_from_users_list() {
             username: "bob".to_string(),
             password: "hashed2".to_string(),
             role: Role::Admin,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
         },
@@ -10,6 +10,7 @@ fn test_claims_struct_serde() {
         username: "alice".to_string(),
         role: Role::Admin,
         exp: 123456,
+        sid: Some(Uuid::new_v4()),
     };
     let json = serde_json::to_string(&claims).unwrap();
     let de: Claims = serde_json::from_str(&json).unwrap();
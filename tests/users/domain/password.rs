@@ -0,0 +1,24 @@
+use lib::users::domain::{hash_password, verify_password};
+
+#[test]
+fn test_hash_and_verify_password() {
+    let hash = hash_password("correct horse").unwrap();
+
+    assert_ne!(hash, "correct horse");
+    assert!(hash.starts_with("$argon2"));
+    assert!(verify_password("correct horse", &hash));
+    assert!(!verify_password("wrong horse", &hash));
+}
+
+#[test]
+fn test_hash_password_is_salted() {
+    assert_ne!(
+        hash_password("password").unwrap(),
+        hash_password("password").unwrap()
+    );
+}
+
+#[test]
+fn test_verify_password_with_invalid_hash() {
+    assert!(!verify_password("password", "not a hash"));
+}
@@ -0,0 +1,43 @@
+// Quota domain tests
+
+use lib::users::domain::{
+    DEFAULT_QUOTA_BYTES, DEFAULT_QUOTA_ITEMS, QuotaConfig, Role, StorageQuota, User,
+};
+
+fn user(quota_bytes: Option<i64>, quota_items: Option<i64>) -> User {
+    User {
+        id: uuid::Uuid::new_v4(),
+        username: "alice".to_string(),
+        email: None,
+        password: "hashed_password".to_string(),
+        role: Role::User,
+        quota_bytes,
+        quota_items,
+        created_at: None,
+        updated_at: None,
+        must_change_password: false,
+    }
+}
+
+#[test]
+fn test_quota_for_user_without_quota_uses_defaults() {
+    let quota = QuotaConfig::default().quota_for(&user(None, None));
+    assert_eq!(
+        quota,
+        StorageQuota {
+            max_bytes: DEFAULT_QUOTA_BYTES,
+            max_items: DEFAULT_QUOTA_ITEMS,
+        }
+    );
+}
+
+#[test]
+fn test_quota_for_user_overrides_defaults_per_limit() {
+    let quota = QuotaConfig::default().quota_for(&user(Some(1024), None));
+    assert_eq!(quota.max_bytes, 1024);
+    assert_eq!(quota.max_items, DEFAULT_QUOTA_ITEMS);
+
+    let quota = QuotaConfig::default().quota_for(&user(None, Some(5)));
+    assert_eq!(quota.max_bytes, DEFAULT_QUOTA_BYTES);
+    assert_eq!(quota.max_items, 5);
+}
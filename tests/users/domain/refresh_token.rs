@@ -0,0 +1,38 @@
+use lib::users::domain::{AuthTokenConfig, RefreshToken};
+use uuid::Uuid;
+
+#[test]
+fn test_generate_refresh_token_is_unique() {
+    let first = RefreshToken::generate();
+    let second = RefreshToken::generate();
+    assert_ne!(first, second);
+    // 32 random bytes, base64 encoded without padding
+    assert_eq!(first.len(), 43);
+}
+
+#[test]
+fn test_hash_refresh_token() {
+    let hash = RefreshToken::hash("token");
+    assert_eq!(hash, RefreshToken::hash("token"));
+    assert_ne!(hash, RefreshToken::hash("other"));
+    assert_eq!(hash.len(), 64);
+}
+
+#[test]
+fn test_refresh_token_is_expired() {
+    let config = AuthTokenConfig::default();
+    let mut token = RefreshToken {
+        id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        family_id: Uuid::new_v4(),
+        token_hash: RefreshToken::hash("token"),
+        expires_at: config.refresh_token_expires_at(),
+        used_at: None,
+        revoked_at: None,
+        created_at: None,
+    };
+    assert!(!token.is_expired());
+
+    token.expires_at = (chrono::Utc::now() - chrono::Duration::seconds(1)).naive_utc();
+    assert!(token.is_expired());
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use lib::users::domain::SessionCache;
+use uuid::Uuid;
+
+#[test]
+fn test_session_cache_marks_sessions_active() {
+    let cache = SessionCache::default();
+    let session_id = Uuid::new_v4();
+    assert!(!cache.is_active(session_id));
+
+    cache.mark_active(session_id);
+    assert!(cache.is_active(session_id));
+    assert!(!cache.is_active(Uuid::new_v4()));
+}
+
+#[test]
+fn test_session_cache_invalidate() {
+    let cache = SessionCache::default();
+    let session_id = Uuid::new_v4();
+    cache.mark_active(session_id);
+
+    cache.invalidate(session_id);
+    assert!(!cache.is_active(session_id));
+}
+
+#[test]
+fn test_session_cache_entries_go_stale() {
+    let cache = SessionCache::with_ttl(Duration::from_millis(10));
+    let session_id = Uuid::new_v4();
+    cache.mark_active(session_id);
+    assert!(cache.is_active(session_id));
+
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(!cache.is_active(session_id));
+}
+
+#[test]
+fn test_session_cache_disabled() {
+    let cache = SessionCache::with_ttl(Duration::ZERO);
+    let session_id = Uuid::new_v4();
+    cache.mark_active(session_id);
+    assert!(!cache.is_active(session_id));
+}
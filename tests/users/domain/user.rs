@@ -14,10 +14,14 @@ fn test_user_struct_equality() {
     let user1 = User {
         id,
         username: "alice".to_string(),
+        email: None,
         password: "hashed_pw".to_string(),
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: None,
+        must_change_password: false,
     };
     let user2 = user1.clone();
     assert_eq!(user1, user2);
@@ -27,8 +31,10 @@ fn test_user_struct_equality() {
 fn test_new_user_struct() {
     let new_user = NewUser {
         username: "bob".to_string(),
+        email: None,
         password: "pw".to_string(),
         role: Some(Role::User),
+        must_change_password: false,
     };
     assert_eq!(new_user.username, "bob");
     assert_eq!(new_user.password, "pw");
@@ -60,10 +66,14 @@ fn test_user_to_get_all_users_result_conversion() {
     let user = User {
         id: user_id,
         username: "alice".to_string(),
+        email: None,
         password: "secret_password".to_string(),
         role: Role::Admin,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(123456789, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(987654321, 0).unwrap().naive_utc()),
+        must_change_password: false,
     };
 
     let result: GetAllUsersResult = user.clone().into();
@@ -84,10 +94,14 @@ fn test_user_to_get_user_result_conversion() {
     let user = User {
         id: user_id,
         username: "bob".to_string(),
+        email: None,
         password: "another_secret".to_string(),
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(111111111, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(222222222, 0).unwrap().naive_utc()),
+        must_change_password: false,
     };
 
     let result: GetUserResult = user.clone().into();
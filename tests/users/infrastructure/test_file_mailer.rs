@@ -0,0 +1,35 @@
+use lib::users::{
+    domain::{Email, Mailer},
+    infrastructure::{FileMailer, FileMailerConfig},
+};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_file_mailer_writes_email() {
+    let output_dir = std::env::temp_dir().join(format!("mail-{}", Uuid::new_v4()));
+    let mailer = FileMailer::new(FileMailerConfig {
+        output_dir: output_dir.clone(),
+    });
+
+    mailer
+        .send(Email {
+            to: "alice@example.com".to_string(),
+            subject: "Reset your password".to_string(),
+            body: "token".to_string(),
+        })
+        .await
+        .unwrap();
+
+    let files = std::fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].extension().unwrap(), "eml");
+    let content = std::fs::read_to_string(&files[0]).unwrap();
+    assert!(content.contains("To: alice@example.com\r\n"));
+    assert!(content.contains("Subject: Reset your password\r\n"));
+    assert!(content.ends_with("\r\n\r\ntoken"));
+
+    std::fs::remove_dir_all(output_dir).unwrap();
+}
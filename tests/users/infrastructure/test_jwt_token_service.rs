@@ -10,14 +10,13 @@ fn test_claims() -> Claims {
         username: "alice".to_string(),
         role: Role::User,
         exp: 9999999999,
+        sid: None,
     }
 }
 
 #[test]
 fn test_create_token_success() {
-    let config = JwtTokenConfig {
-        secret_key: "testsecret".to_string(),
-    };
+    let config = JwtTokenConfig::from_secret("testsecret");
     let service = JwtTokenService::new(config.clone());
     let claims = test_claims();
     let token = service.create_token(claims.clone());
@@ -31,9 +30,7 @@ fn test_create_token_success() {
 
 #[test]
 fn test_validate_token_invalid() {
-    let config = JwtTokenConfig {
-        secret_key: "testsecret".to_string(),
-    };
+    let config = JwtTokenConfig::from_secret("testsecret");
     let service = JwtTokenService::new(config);
     let result = service.validate_token("not_a_real_token");
     assert!(matches!(result, Err(UserLoginError::InvalidToken)));
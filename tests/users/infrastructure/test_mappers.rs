@@ -10,10 +10,14 @@ fn test_userrow_to_user_from_impl() {
     let row = UserRow {
         id: Uuid::new_v4(),
         username: "alice".to_string(),
+        email: None,
         password: "pw".to_string(),
         role: RowRole::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+        must_change_password: false,
     };
     let user = User::from(row);
     assert_eq!(user.username, "alice");
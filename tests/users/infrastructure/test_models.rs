@@ -10,10 +10,14 @@ fn test_userrow_to_user_mapping() {
     let row = UserRow {
         id: Uuid::new_v4(),
         username: "alice".to_string(),
+        email: None,
         password: "pw".to_string(),
         role: RowRole::Admin,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+        must_change_password: false,
     };
     let user = User::from(row);
     assert_eq!(user.username, "alice");
@@ -24,8 +28,10 @@ fn test_userrow_to_user_mapping() {
 fn test_newuser_to_createuserrow_mapping() {
     let new_user = NewUser {
         username: "bob".to_string(),
+        email: None,
         password: "pw".to_string(),
         role: Some(Role::User),
+        must_change_password: false,
     };
     let row = CreateUserRow::from(new_user);
     assert_eq!(row.username, "bob");
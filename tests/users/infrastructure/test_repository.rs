@@ -14,6 +14,8 @@ async fn test_repository_get_all_users_interface() {
             username: "alice".to_string(),
             password: "hashed1".to_string(),
             role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         },
@@ -22,6 +24,8 @@ async fn test_repository_get_all_users_interface() {
             username: "bob".to_string(),
             password: "hashed2".to_string(),
             role: Role::Admin,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
         },
@@ -69,6 +73,8 @@ async fn test_repository_get_by_id_interface() {
         username: "alice".to_string(),
         password: "hashed".to_string(),
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
     };
@@ -120,6 +126,8 @@ async fn test_repository_get_by_id_from_list() {
             username: "alice".to_string(),
             password: "hashed1".to_string(),
             role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
         },
@@ -128,6 +136,8 @@ async fn test_repository_get_by_id_from_list() {
             username: "bob".to_string(),
             password: "hashed2".to_string(),
             role: Role::Admin,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
             updated_at: Some(DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
         },
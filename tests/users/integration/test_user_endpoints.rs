@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use crate::{
-    users::{MockLoginTokenService, MockUserRepository},
+    users::{
+        MockLoginTokenService, MockMailer, MockPasswordResetTokenRepository,
+        MockRefreshTokenRepository, MockSessionRepository, MockUserRepository,
+    },
     utils::functions::hash_password,
     utils::test_helpers::*,
 };
@@ -40,8 +43,11 @@ async fn test_get_user_by_id_success() {
     let user = lib::users::domain::User {
         id: user_id,
         username: "alice".to_string(),
+        email: None,
         password: "hashed_password".to_string(),
         role: lib::users::domain::Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(
             chrono::DateTime::from_timestamp(123456789, 0)
                 .unwrap()
@@ -52,6 +58,7 @@ async fn test_get_user_by_id_success() {
                 .unwrap()
                 .naive_utc(),
         ),
+        must_change_password: false,
     };
     let state = create_test_app_state(CreateTestAppStateArguments {
         user_repo: Some(MockUserRepository {
@@ -147,18 +154,26 @@ async fn test_get_all_users_success() {
         lib::users::domain::User {
             id: uuid::Uuid::new_v4(),
             username: "alice".to_string(),
+            email: None,
             password: "hashed1".to_string(),
             role: lib::users::domain::Role::User,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
             updated_at: Some(chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+            must_change_password: false,
         },
         lib::users::domain::User {
             id: uuid::Uuid::new_v4(),
             username: "bob".to_string(),
+            email: None,
             password: "hashed2".to_string(),
             role: lib::users::domain::Role::Admin,
+            quota_bytes: None,
+            quota_items: None,
             created_at: Some(chrono::DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
             updated_at: Some(chrono::DateTime::from_timestamp(1, 0).unwrap().naive_utc()),
+            must_change_password: false,
         },
     ];
     let state = create_test_app_state(CreateTestAppStateArguments {
@@ -266,6 +281,7 @@ async fn test_user_registration_duplicate() {
             fail_get: false,
             user: None,
             users_list: vec![],
+            ..MockUserRepository::default()
         }),
         ..CreateTestAppStateArguments::default()
     });
@@ -292,6 +308,7 @@ async fn test_user_registration_invalid_payload() {
             fail_get: false,
             user: None,
             users_list: vec![],
+            ..MockUserRepository::default()
         }),
         ..CreateTestAppStateArguments::default()
     });
@@ -312,10 +329,14 @@ async fn test_login_success() {
     let user = User {
         id: Uuid::new_v4(),
         username: "newuser".to_string(),
+        email: None,
         password: hash_password("password123"),
         role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
         created_at: Some(chrono::naive::NaiveDateTime::default()),
         updated_at: Some(chrono::naive::NaiveDateTime::default()),
+        must_change_password: false,
     };
     let user_repository = MockUserRepository {
         user_exists: true,
@@ -323,6 +344,7 @@ async fn test_login_success() {
         fail_get: false,
         user: Some(user.clone()),
         users_list: vec![user],
+        ..MockUserRepository::default()
     };
     let state = create_test_app_state(CreateTestAppStateArguments {
         user_repo: Some(user_repository),
@@ -345,6 +367,8 @@ async fn test_login_success() {
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     
     assert!(json["data"]["token"].is_string());
+    assert!(json["data"]["refresh_token"].is_string());
+    assert_eq!(json["data"]["expires_in"], 900);
 }
 
 #[tokio::test]
@@ -362,3 +386,724 @@ async fn test_login_invalid_credentials() {
     let response = app.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
+
+#[tokio::test]
+async fn test_refresh_token_success() {
+    let user = User {
+        id: Uuid::new_v4(),
+        username: "alice".to_string(),
+        email: None,
+        password: "hashed_password".to_string(),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        created_at: None,
+        updated_at: None,
+        must_change_password: false,
+    };
+    let expires_at = (chrono::Utc::now() + chrono::Duration::days(1)).naive_utc();
+    let (session_repo, session_id) = MockSessionRepository::with_session(user.id);
+    let (refresh_token_repo, token) =
+        MockRefreshTokenRepository::with_token(user.id, session_id, expires_at);
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(MockUserRepository {
+            user: Some(user),
+            ..MockUserRepository::default()
+        }),
+        refresh_token_repo: Some(refresh_token_repo),
+        session_repo: Some(session_repo),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/refresh")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(r#"{{"refresh_token": "{token}"}}"#)))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["data"]["token"].is_string());
+    assert!(json["data"]["refresh_token"].is_string());
+    assert_ne!(json["data"]["refresh_token"], token);
+}
+
+#[tokio::test]
+async fn test_refresh_token_reused() {
+    let user_id = Uuid::new_v4();
+    let expires_at = (chrono::Utc::now() + chrono::Duration::days(1)).naive_utc();
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let (refresh_token_repo, token) =
+        MockRefreshTokenRepository::with_token(user_id, session_id, expires_at);
+    refresh_token_repo.tokens.lock().unwrap()[0].used_at = Some(chrono::Utc::now().naive_utc());
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        refresh_token_repo: Some(refresh_token_repo.clone()),
+        session_repo: Some(session_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/refresh")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(r#"{{"refresh_token": "{token}"}}"#)))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(refresh_token_repo.tokens()[0].revoked_at.is_some());
+    assert!(session_repo.sessions()[0].revoked_at.is_some());
+}
+
+#[tokio::test]
+async fn test_refresh_token_invalid() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/refresh")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"refresh_token": "unknown"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_logout_rejects_access_tokens_of_session() {
+    let user_id = Uuid::new_v4();
+    let expires_at = (chrono::Utc::now() + chrono::Duration::days(1)).naive_utc();
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    let (refresh_token_repo, token) =
+        MockRefreshTokenRepository::with_token(user_id, session_id, expires_at);
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(MockLoginTokenService {
+            user_id: Some(user_id),
+            sid: Some(session_id),
+            ..MockLoginTokenService::default()
+        })),
+        refresh_token_repo: Some(refresh_token_repo),
+        session_repo: Some(session_repo),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let get_users = || {
+        Request::builder()
+            .method("GET")
+            .uri("/user")
+            .header("Authorization", "Bearer valid_token")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(get_users()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/logout")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(r#"{{"refresh_token": "{token}"}}"#)))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app.oneshot(get_users()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+/// State authenticating requests as a session of a user who has two more sessions, returns
+/// the session repository and the id of the request's session
+fn session_test_state(
+    user_id: Uuid,
+) -> (lib::api::http_server::AppState, MockSessionRepository, Uuid) {
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    session_repo.add_session(user_id);
+    session_repo.add_session(user_id);
+    // Sessions of other users are never listed or revoked
+    session_repo.add_session(Uuid::new_v4());
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(MockLoginTokenService {
+            user_id: Some(user_id),
+            sid: Some(session_id),
+            ..MockLoginTokenService::default()
+        })),
+        session_repo: Some(session_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    (state, session_repo, session_id)
+}
+
+#[tokio::test]
+async fn test_get_sessions() {
+    let user_id = Uuid::new_v4();
+    let (state, _, session_id) = session_test_state(user_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/user/me/sessions")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let sessions = json["data"].as_array().unwrap();
+    assert_eq!(sessions.len(), 3);
+    let current: Vec<_> = sessions
+        .iter()
+        .filter(|session| session["current"] == true)
+        .collect();
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0]["id"], session_id.to_string());
+    assert_eq!(current[0]["device_name"], "Phone");
+    assert_eq!(current[0]["user_agent"], "TestAgent/1.0");
+}
+
+#[tokio::test]
+async fn test_revoke_session() {
+    let user_id = Uuid::new_v4();
+    let (state, session_repo, _) = session_test_state(user_id);
+    let other_session_id = session_repo.sessions()[1].id;
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!("/user/me/sessions/{other_session_id}"))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let sessions = session_repo.sessions();
+    assert!(sessions[1].revoked_at.is_some());
+    assert!(sessions[0].revoked_at.is_none());
+    assert!(sessions[2].revoked_at.is_none());
+}
+
+#[tokio::test]
+async fn test_revoke_session_of_other_user() {
+    let user_id = Uuid::new_v4();
+    let (state, session_repo, _) = session_test_state(user_id);
+    let foreign_session_id = session_repo.sessions()[3].id;
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!("/user/me/sessions/{foreign_session_id}"))
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(session_repo.sessions()[3].revoked_at.is_none());
+}
+
+#[tokio::test]
+async fn test_revoke_other_sessions() {
+    let user_id = Uuid::new_v4();
+    let (state, session_repo, session_id) = session_test_state(user_id);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("DELETE")
+        .uri("/user/me/sessions")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["revoked_sessions"], 2);
+
+    let sessions = session_repo.sessions();
+    assert!(sessions[0].revoked_at.is_none());
+    assert_eq!(sessions[0].id, session_id);
+    assert!(sessions[1].revoked_at.is_some());
+    assert!(sessions[2].revoked_at.is_some());
+    assert!(sessions[3].revoked_at.is_none());
+
+    // The session making the request keeps working
+    let request = Request::builder()
+        .method("GET")
+        .uri("/user/me/sessions")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_revoked_session_is_rejected() {
+    let user_id = Uuid::new_v4();
+    let (state, session_repo, _) = session_test_state(user_id);
+    session_repo.sessions.lock().unwrap()[0].revoked_at = Some(chrono::Utc::now().naive_utc());
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/user/me/sessions")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_session_of_other_user_is_rejected() {
+    let (session_repo, session_id) = MockSessionRepository::with_session(Uuid::new_v4());
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        token_service: Some(Arc::new(MockLoginTokenService {
+            user_id: Some(Uuid::new_v4()),
+            sid: Some(session_id),
+            ..MockLoginTokenService::default()
+        })),
+        session_repo: Some(session_repo),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/user/me/sessions")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_login_records_session_client() {
+    let user = User {
+        id: Uuid::new_v4(),
+        username: "newuser".to_string(),
+        email: None,
+        password: hash_password("password123"),
+        role: Role::User,
+        quota_bytes: None,
+        quota_items: None,
+        created_at: None,
+        updated_at: None,
+        must_change_password: false,
+    };
+    let session_repo = MockSessionRepository::default();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(MockUserRepository {
+            user_exists: true,
+            user: Some(user),
+            ..MockUserRepository::default()
+        }),
+        session_repo: Some(session_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/login")
+        .header("content-type", "application/json")
+        .header("user-agent", "PhotoApp/2.1 (iPhone)")
+        .header("x-forwarded-for", "203.0.113.7, 10.0.0.1")
+        .body(Body::from(
+            r#"{"username": "newuser", "password": "password123", "device_name": "Alice's phone"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let sessions = session_repo.sessions();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].device_name.as_deref(), Some("Alice's phone"));
+    assert_eq!(sessions[0].user_agent.as_deref(), Some("PhotoApp/2.1 (iPhone)"));
+    assert_eq!(sessions[0].ip_address.as_deref(), Some("203.0.113.7"));
+}
+
+#[tokio::test]
+async fn test_update_user_quota_success() {
+    let user_id = Uuid::new_v4();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(MockUserRepository {
+            user: Some(User {
+                id: user_id,
+                username: "alice".to_string(),
+                email: None,
+                password: "hashed_password".to_string(),
+                role: Role::User,
+                quota_bytes: None,
+                quota_items: None,
+                created_at: None,
+                updated_at: None,
+                must_change_password: false,
+            }),
+            ..MockUserRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/user/{user_id}/quota"))
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(r#"{"quota_bytes": 1048576, "quota_items": null}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["id"], user_id.to_string());
+    assert_eq!(json["data"]["quota_bytes"], 1048576);
+    assert!(json["data"]["quota_items"].is_null());
+}
+
+#[tokio::test]
+async fn test_update_user_quota_not_found() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/user/{}/quota", Uuid::new_v4()))
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(r#"{"quota_bytes": 1048576}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_update_user_quota_negative() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/user/{}/quota", Uuid::new_v4()))
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(r#"{"quota_items": -1}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_jwks() {
+    let state = create_default_test_app_state();
+    let app = lib::api::routes::well_known_routes().with_state(state);
+    let request = Request::builder()
+        .method("GET")
+        .uri("/jwks.json")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["cache-control"], "public, max-age=300");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json, serde_json::json!({ "keys": [] }));
+}
+
+fn password_test_state(
+    user_id: Uuid,
+    must_change_password: bool,
+) -> (
+    lib::api::http_server::AppState,
+    MockUserRepository,
+    MockSessionRepository,
+) {
+    let user_repo = MockUserRepository {
+        user: Some(User {
+            id: user_id,
+            username: "alice".to_string(),
+            email: None,
+            password: hash_password("password123"),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            must_change_password,
+            created_at: None,
+            updated_at: None,
+        }),
+        user_exists: true,
+        ..MockUserRepository::default()
+    };
+    let (session_repo, session_id) = MockSessionRepository::with_session(user_id);
+    session_repo.add_session(user_id);
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(user_repo.clone()),
+        token_service: Some(Arc::new(MockLoginTokenService {
+            user_id: Some(user_id),
+            sid: Some(session_id),
+            ..MockLoginTokenService::default()
+        })),
+        session_repo: Some(session_repo.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    (state, user_repo, session_repo)
+}
+
+#[tokio::test]
+async fn test_change_password() {
+    let user_id = Uuid::new_v4();
+    let (state, user_repo, session_repo) = password_test_state(user_id, false);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri("/user/me/password")
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(
+            r#"{"current_password": "password123", "new_password": "new_password456"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(user_repo.updated_password.lock().unwrap().is_some());
+    // The session of the request stays logged in
+    let sessions = session_repo.sessions();
+    assert!(sessions[0].revoked_at.is_none());
+    assert!(sessions[1].revoked_at.is_some());
+}
+
+#[tokio::test]
+async fn test_change_password_wrong_current_password() {
+    let (state, user_repo, _) = password_test_state(Uuid::new_v4(), false);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri("/user/me/password")
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(
+            r#"{"current_password": "wrong_password", "new_password": "new_password456"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(user_repo.updated_password.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_reset_user_password() {
+    let user_id = Uuid::new_v4();
+    let (state, user_repo, session_repo) = password_test_state(user_id, false);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/user/{user_id}/password"))
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(r#"{"new_password": "temporary123"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let (_, must_change_password) = user_repo.updated_password.lock().unwrap().clone().unwrap();
+    assert!(must_change_password);
+    assert!(
+        session_repo
+            .sessions()
+            .iter()
+            .all(|session| session.revoked_at.is_some())
+    );
+}
+
+#[tokio::test]
+async fn test_reset_user_password_not_found() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/user/{}/password", Uuid::new_v4()))
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(r#"{"new_password": "temporary123"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_login_password_change_required() {
+    let (state, _, _) = password_test_state(Uuid::new_v4(), true);
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/login")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"username": "alice", "password": "password123"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_user_registration_duplicate_email() {
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(MockUserRepository {
+            users_list: vec![User {
+                id: Uuid::new_v4(),
+                username: "bob".to_string(),
+                email: Some("shared@example.com".to_string()),
+                password: "hashed".to_string(),
+                role: Role::User,
+                quota_bytes: None,
+                quota_items: None,
+                must_change_password: false,
+                created_at: None,
+                updated_at: None,
+            }],
+            ..MockUserRepository::default()
+        }),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/user")
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(
+            r#"{"username": "alice", "email": "shared@example.com", "password": "password123"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_user_registration_invalid_email() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/user")
+        .header("content-type", "application/json")
+        .header("Authorization", "Bearer valid_token")
+        .body(Body::from(
+            r#"{"username": "alice", "email": "not-an-email", "password": "password123"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_request_password_reset() {
+    let user_id = Uuid::new_v4();
+    let user_repo = MockUserRepository {
+        user: Some(User {
+            id: user_id,
+            username: "alice".to_string(),
+            email: Some("alice@example.com".to_string()),
+            password: hash_password("password123"),
+            role: Role::User,
+            quota_bytes: None,
+            quota_items: None,
+            must_change_password: false,
+            created_at: None,
+            updated_at: None,
+        }),
+        ..MockUserRepository::default()
+    };
+    let token_repo = MockPasswordResetTokenRepository::default();
+    let mailer = MockMailer::default();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(user_repo),
+        password_reset_token_repo: Some(token_repo.clone()),
+        mailer: Some(mailer.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/password-reset")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"email": "alice@example.com"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(mailer.wait_for_sent(1).await[0].to, "alice@example.com");
+    assert_eq!(token_repo.tokens()[0].user_id, user_id);
+}
+
+#[tokio::test]
+async fn test_request_password_reset_unknown_email() {
+    let mailer = MockMailer::default();
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        mailer: Some(mailer.clone()),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/password-reset")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"email": "nobody@example.com"}"#))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    // Same answer as for a registered address
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert!(mailer.wait_for_sent(1).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_confirm_password_reset() {
+    let user_id = Uuid::new_v4();
+    let (_, user_repo, session_repo) = password_test_state(user_id, true);
+    let (token_repo, token) = MockPasswordResetTokenRepository::with_token(
+        user_id,
+        (chrono::Utc::now() + chrono::Duration::hours(1)).naive_utc(),
+    );
+    let state = create_test_app_state(CreateTestAppStateArguments {
+        user_repo: Some(user_repo.clone()),
+        session_repo: Some(session_repo.clone()),
+        password_reset_token_repo: Some(token_repo),
+        ..CreateTestAppStateArguments::default()
+    });
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/password-reset/confirm")
+        .header("content-type", "application/json")
+        .body(Body::from(format!(
+            r#"{{"token": "{token}", "new_password": "new_password456"}}"#
+        )))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let (_, must_change_password) = user_repo.updated_password.lock().unwrap().clone().unwrap();
+    assert!(!must_change_password);
+    assert!(
+        session_repo
+            .sessions()
+            .iter()
+            .all(|session| session.revoked_at.is_some())
+    );
+}
+
+#[tokio::test]
+async fn test_confirm_password_reset_invalid_token() {
+    let state = create_default_test_app_state();
+    let app = test_app(state.clone()).with_state(state);
+    let request = Request::builder()
+        .method("POST")
+        .uri("/auth/password-reset/confirm")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"token": "unknown", "new_password": "new_password456"}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -1,7 +1,13 @@
+use std::sync::{Arc, Mutex};
+
 use async_trait::async_trait;
 use chrono::DateTime;
 use lib::users::domain::{
-    Claims, LoginTokenService, Role, Token, UserRepository, UserRepositoryError,
+    Claims, Email, LoginTokenService, Mailer, MailerError, NewPasswordResetToken, NewRefreshToken,
+    NewSession, PasswordResetToken, PasswordResetTokenRepository,
+    PasswordResetTokenRepositoryError, RefreshToken, RefreshTokenId, RefreshTokenRepository,
+    RefreshTokenRepositoryError, Role, Session, SessionId, SessionRepository,
+    SessionRepositoryError, Token, UserRepository, UserRepositoryError,
     user::{NewUser, User, UserLoginError},
 };
 use uuid::Uuid;
@@ -13,6 +19,8 @@ pub struct MockUserRepository {
     pub fail_get: bool,
     pub user: Option<User>,
     pub users_list: Vec<User>,
+    /// Password hash and must-change flag of the last `update_password` call
+    pub updated_password: Arc<Mutex<Option<(String, bool)>>>,
 }
 
 #[async_trait]
@@ -31,10 +39,14 @@ impl UserRepository for MockUserRepository {
                 Ok(Some(User {
                     id: Uuid::new_v4(),
                     username,
+                    email: None,
                     password: "hashed".to_string(),
                     role: Role::User,
+                    quota_bytes: None,
+                    quota_items: None,
                     created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
                     updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+                    must_change_password: false,
                 }))
             }
         } else {
@@ -49,14 +61,29 @@ impl UserRepository for MockUserRepository {
         if self.fail_get {
             return Err(UserRepositoryError::InternalServerError);
         }
-        if let Some(u) = &self.user {
-            if u.id == id {
-                return Ok(Some(u.clone()));
-            }
+        if let Some(u) = &self.user
+            && u.id == id
+        {
+            return Ok(Some(u.clone()));
         }
         Ok(self.users_list.iter().find(|u| u.id == id).cloned())
     }
 
+    async fn get_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Option<lib::users::domain::User>, UserRepositoryError> {
+        if self.fail_get {
+            return Err(UserRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .user
+            .iter()
+            .chain(self.users_list.iter())
+            .find(|u| u.email.as_deref() == Some(email))
+            .cloned())
+    }
+
     async fn get_all_users(&self) -> Result<Vec<lib::users::domain::User>, UserRepositoryError> {
         if self.fail_get {
             return Err(UserRepositoryError::InternalServerError);
@@ -77,19 +104,62 @@ impl UserRepository for MockUserRepository {
             Ok(User {
                 id: Uuid::new_v4(),
                 username: user.username,
+                email: user.email,
                 password: user.password,
                 role: Role::User,
+                quota_bytes: None,
+                quota_items: None,
                 created_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
                 updated_at: Some(DateTime::from_timestamp(0, 0).unwrap().naive_utc()),
+                must_change_password: false,
             })
         }
     }
+    async fn update_quota(
+        &self,
+        id: Uuid,
+        quota_bytes: Option<i64>,
+        quota_items: Option<i64>,
+    ) -> Result<User, UserRepositoryError> {
+        if self.fail_create {
+            return Err(UserRepositoryError::InternalServerError);
+        }
+        let user = self.get_by_id(id).await?.ok_or(UserRepositoryError::UserNotFound)?;
+        Ok(User {
+            quota_bytes,
+            quota_items,
+            ..user
+        })
+    }
+
+    async fn update_password(
+        &self,
+        id: Uuid,
+        password_hash: String,
+        must_change_password: bool,
+    ) -> Result<User, UserRepositoryError> {
+        if self.fail_create {
+            return Err(UserRepositoryError::InternalServerError);
+        }
+        let user = self.get_by_id(id).await?.ok_or(UserRepositoryError::UserNotFound)?;
+        *self.updated_password.lock().unwrap() =
+            Some((password_hash.clone(), must_change_password));
+        Ok(User {
+            password: password_hash,
+            must_change_password,
+            ..user
+        })
+    }
 }
 
 #[derive(Clone, Default)]
 pub struct MockLoginTokenService {
     pub fail: bool,
     pub validation_fail: bool,
+    /// User the validated claims belong to, a random one when `None`
+    pub user_id: Option<Uuid>,
+    /// Session the validated claims belong to
+    pub sid: Option<Uuid>,
 }
 
 impl LoginTokenService for MockLoginTokenService {
@@ -107,11 +177,358 @@ impl LoginTokenService for MockLoginTokenService {
             ))
         } else {
             Ok(Claims {
-                sub: Uuid::new_v4(),
+                sub: self.user_id.unwrap_or_else(Uuid::new_v4),
                 role: Role::Admin,
                 username: "mockuser".to_string(),
                 exp: (chrono::Utc::now().timestamp() + 3600) as u64, // 1 hour expiration
+                sid: self.sid,
+            })
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MockRefreshTokenRepository {
+    pub fail: bool,
+    pub tokens: Arc<Mutex<Vec<RefreshToken>>>,
+}
+
+impl MockRefreshTokenRepository {
+    /// Repository holding a single token of the session, returns the token along with the
+    /// repository
+    pub fn with_token(
+        user_id: Uuid,
+        session_id: SessionId,
+        expires_at: chrono::NaiveDateTime,
+    ) -> (Self, String) {
+        let token = RefreshToken::generate();
+        let repository = MockRefreshTokenRepository::default();
+        repository.tokens.lock().unwrap().push(RefreshToken {
+            id: Uuid::new_v4(),
+            user_id,
+            family_id: session_id,
+            token_hash: RefreshToken::hash(&token),
+            expires_at,
+            used_at: None,
+            revoked_at: None,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+        });
+        (repository, token)
+    }
+
+    pub fn tokens(&self) -> Vec<RefreshToken> {
+        self.tokens.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for MockRefreshTokenRepository {
+    async fn create_refresh_token(
+        &self,
+        token: NewRefreshToken,
+    ) -> Result<RefreshToken, RefreshTokenRepositoryError> {
+        if self.fail {
+            return Err(RefreshTokenRepositoryError::InternalServerError);
+        }
+        let token = RefreshToken {
+            id: Uuid::new_v4(),
+            user_id: token.user_id,
+            family_id: token.family_id,
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+            used_at: None,
+            revoked_at: None,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+        };
+        self.tokens.lock().unwrap().push(token.clone());
+        Ok(token)
+    }
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, RefreshTokenRepositoryError> {
+        if self.fail {
+            return Err(RefreshTokenRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|token| token.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        id: RefreshTokenId,
+        replacement: NewRefreshToken,
+    ) -> Result<RefreshToken, RefreshTokenRepositoryError> {
+        {
+            let mut tokens = self.tokens.lock().unwrap();
+            let token = tokens
+                .iter_mut()
+                .find(|token| {
+                    token.id == id && token.used_at.is_none() && token.revoked_at.is_none()
+                })
+                .ok_or(RefreshTokenRepositoryError::AlreadyUsed)?;
+            token.used_at = Some(chrono::Utc::now().naive_utc());
+        }
+        self.create_refresh_token(replacement).await
+    }
+
+    async fn revoke_refresh_token_family(
+        &self,
+        family_id: Uuid,
+    ) -> Result<(), RefreshTokenRepositoryError> {
+        if self.fail {
+            return Err(RefreshTokenRepositoryError::InternalServerError);
+        }
+        for token in self.tokens.lock().unwrap().iter_mut() {
+            if token.family_id == family_id && token.revoked_at.is_none() {
+                token.revoked_at = Some(chrono::Utc::now().naive_utc());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MockSessionRepository {
+    pub fail: bool,
+    pub sessions: Arc<Mutex<Vec<Session>>>,
+}
+
+impl MockSessionRepository {
+    /// Repository holding a single active session of the user, returns its id along with the
+    /// repository
+    pub fn with_session(user_id: Uuid) -> (Self, SessionId) {
+        let repository = MockSessionRepository::default();
+        let session_id = repository.add_session(user_id);
+        (repository, session_id)
+    }
+
+    pub fn add_session(&self, user_id: Uuid) -> SessionId {
+        let session = Session {
+            id: Uuid::new_v4(),
+            user_id,
+            device_name: Some("Phone".to_string()),
+            user_agent: Some("TestAgent/1.0".to_string()),
+            ip_address: Some("127.0.0.1".to_string()),
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            last_seen_at: Some(chrono::Utc::now().naive_utc()),
+            revoked_at: None,
+        };
+        let session_id = session.id;
+        self.sessions.lock().unwrap().push(session);
+        session_id
+    }
+
+    pub fn sessions(&self) -> Vec<Session> {
+        self.sessions.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl SessionRepository for MockSessionRepository {
+    async fn create_session(&self, session: NewSession) -> Result<Session, SessionRepositoryError> {
+        if self.fail {
+            return Err(SessionRepositoryError::InternalServerError);
+        }
+        let session = Session {
+            id: Uuid::new_v4(),
+            user_id: session.user_id,
+            device_name: session.device_name,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            last_seen_at: Some(chrono::Utc::now().naive_utc()),
+            revoked_at: None,
+        };
+        self.sessions.lock().unwrap().push(session.clone());
+        Ok(session)
+    }
+
+    async fn get_active_sessions_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<Session>, SessionRepositoryError> {
+        if self.fail {
+            return Err(SessionRepositoryError::InternalServerError);
+        }
+        Ok(self
+            .sessions()
+            .into_iter()
+            .filter(|session| session.user_id == user_id && session.revoked_at.is_none())
+            .collect())
+    }
+
+    async fn touch_session(
+        &self,
+        id: SessionId,
+    ) -> Result<Option<Session>, SessionRepositoryError> {
+        if self.fail {
+            return Err(SessionRepositoryError::InternalServerError);
+        }
+        let mut sessions = self.sessions.lock().unwrap();
+        Ok(sessions
+            .iter_mut()
+            .find(|session| session.id == id && session.revoked_at.is_none())
+            .map(|session| {
+                session.last_seen_at = Some(chrono::Utc::now().naive_utc());
+                session.clone()
+            }))
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: Uuid,
+        id: SessionId,
+    ) -> Result<bool, SessionRepositoryError> {
+        if self.fail {
+            return Err(SessionRepositoryError::InternalServerError);
+        }
+        let mut sessions = self.sessions.lock().unwrap();
+        match sessions.iter_mut().find(|session| {
+            session.id == id && session.user_id == user_id && session.revoked_at.is_none()
+        }) {
+            Some(session) => {
+                session.revoked_at = Some(chrono::Utc::now().naive_utc());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn revoke_other_sessions(
+        &self,
+        user_id: Uuid,
+        keep_id: Option<SessionId>,
+    ) -> Result<Vec<SessionId>, SessionRepositoryError> {
+        if self.fail {
+            return Err(SessionRepositoryError::InternalServerError);
+        }
+        let mut revoked = Vec::new();
+        for session in self.sessions.lock().unwrap().iter_mut() {
+            if session.user_id == user_id
+                && session.revoked_at.is_none()
+                && Some(session.id) != keep_id
+            {
+                session.revoked_at = Some(chrono::Utc::now().naive_utc());
+                revoked.push(session.id);
+            }
+        }
+        Ok(revoked)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MockPasswordResetTokenRepository {
+    pub fail: bool,
+    pub tokens: Arc<Mutex<Vec<PasswordResetToken>>>,
+}
+
+impl MockPasswordResetTokenRepository {
+    /// Repository holding a single token of the user, returns the token along with the
+    /// repository
+    pub fn with_token(user_id: Uuid, expires_at: chrono::NaiveDateTime) -> (Self, String) {
+        let token = PasswordResetToken::generate();
+        let repository = MockPasswordResetTokenRepository::default();
+        repository.tokens.lock().unwrap().push(PasswordResetToken {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash: PasswordResetToken::hash(&token),
+            expires_at,
+            used_at: None,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+        });
+        (repository, token)
+    }
+
+    pub fn tokens(&self) -> Vec<PasswordResetToken> {
+        self.tokens.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for MockPasswordResetTokenRepository {
+    async fn create_password_reset_token(
+        &self,
+        token: NewPasswordResetToken,
+    ) -> Result<PasswordResetToken, PasswordResetTokenRepositoryError> {
+        if self.fail {
+            return Err(PasswordResetTokenRepositoryError::InternalServerError);
+        }
+        let token = PasswordResetToken {
+            id: Uuid::new_v4(),
+            user_id: token.user_id,
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+            used_at: None,
+            created_at: Some(chrono::Utc::now().naive_utc()),
+        };
+        self.tokens.lock().unwrap().push(token.clone());
+        Ok(token)
+    }
+
+    async fn consume_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, PasswordResetTokenRepositoryError> {
+        if self.fail {
+            return Err(PasswordResetTokenRepositoryError::InternalServerError);
+        }
+        let mut tokens = self.tokens.lock().unwrap();
+        let Some(token) = tokens
+            .iter()
+            .find(|token| {
+                token.token_hash == token_hash && token.used_at.is_none() && !token.is_expired()
             })
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        for other in tokens.iter_mut() {
+            if other.user_id == token.user_id && other.used_at.is_none() {
+                other.used_at = Some(chrono::Utc::now().naive_utc());
+            }
+        }
+        Ok(Some(token))
+    }
+}
+
+/// Keeps the emails instead of sending them
+#[derive(Clone, Default)]
+pub struct MockMailer {
+    pub fail: bool,
+    pub sent: Arc<Mutex<Vec<Email>>>,
+}
+
+impl MockMailer {
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Lets background tasks run until `count` emails were sent, or gives up after a while
+    pub async fn wait_for_sent(&self, count: usize) -> Vec<Email> {
+        for _ in 0..100 {
+            if self.sent.lock().unwrap().len() >= count {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        self.sent()
+    }
+}
+
+#[async_trait]
+impl Mailer for MockMailer {
+    async fn send(&self, email: Email) -> Result<(), MailerError> {
+        if self.fail {
+            return Err(MailerError::SendFailed("mock failure".to_string()));
         }
+        self.sent.lock().unwrap().push(email);
+        Ok(())
     }
 }
@@ -1,16 +1,36 @@
-use crate::media::{MockMediaRepository, MockStorageService, MockThumbnailService};
-use crate::users::{MockLoginTokenService, MockUserRepository};
+use crate::albums::MockAlbumRepository;
+use crate::jobs::MockJobRepository;
+use crate::media::{MockMediaRepository, MockStorageService, MockUploadSessionRepository};
+use crate::sharing::{
+    MockShareGrantRepository, MockShareLinkRepository, test_authorization_service,
+};
+use crate::users::{
+    MockLoginTokenService, MockMailer, MockPasswordResetTokenRepository,
+    MockRefreshTokenRepository, MockSessionRepository, MockUserRepository,
+};
 use lib::api::http_server::AppState;
-use lib::users::domain::LoginTokenService;
+use lib::media::domain::{MediaSizeLimits, MediaTypeAllowlist};
+use lib::media::infrastructure::{HmacMediaUrlSigner, HmacMediaUrlSignerConfig};
+use lib::users::domain::{
+    AuthTokenConfig, LoginTokenService, PasswordResetConfig, QuotaConfig, SessionCache,
+};
 use std::sync::Arc;
 
 #[derive(Default)]
 pub struct CreateTestAppStateArguments {
     pub user_repo: Option<MockUserRepository>,
     pub token_service: Option<Arc<dyn LoginTokenService>>,
+    pub refresh_token_repo: Option<MockRefreshTokenRepository>,
+    pub session_repo: Option<MockSessionRepository>,
+    pub password_reset_token_repo: Option<MockPasswordResetTokenRepository>,
+    pub mailer: Option<MockMailer>,
     pub media_repo: Option<MockMediaRepository>,
     pub storage_service: Option<MockStorageService>,
-    pub thumbnail_service: Option<MockThumbnailService>,
+    pub upload_session_repo: Option<MockUploadSessionRepository>,
+    pub album_repo: Option<MockAlbumRepository>,
+    pub share_grant_repo: Option<MockShareGrantRepository>,
+    pub share_link_repo: Option<MockShareLinkRepository>,
+    pub job_repo: Option<MockJobRepository>,
 }
 
 /// Creates an AppState for testing with optional custom implementations
@@ -36,17 +56,42 @@ pub fn create_test_app_state(arguments: CreateTestAppStateArguments) -> AppState
     let CreateTestAppStateArguments {
         user_repo,
         token_service,
+        refresh_token_repo,
+        session_repo,
+        password_reset_token_repo,
+        mailer,
         media_repo,
         storage_service,
-        thumbnail_service,
+        upload_session_repo,
+        album_repo,
+        share_grant_repo,
+        share_link_repo,
+        job_repo,
     } = arguments;
+    let share_grant_repo = share_grant_repo.unwrap_or_default();
 
     AppState {
         user_repository: Arc::new(user_repo.unwrap_or_default()),
         login_token_service: token_service.unwrap_or(Arc::new(MockLoginTokenService::default())),
+        refresh_token_repository: Arc::new(refresh_token_repo.unwrap_or_default()),
+        session_repository: Arc::new(session_repo.unwrap_or_default()),
+        password_reset_token_repository: Arc::new(password_reset_token_repo.unwrap_or_default()),
+        mailer: Arc::new(mailer.unwrap_or_default()),
         media_repository: Arc::new(media_repo.unwrap_or_default()),
         storage_service: Arc::new(storage_service.unwrap_or_default()),
-        thumbnail_service: Arc::new(thumbnail_service.unwrap_or_default()),
+        media_url_signer: Arc::new(test_media_url_signer()),
+        upload_session_repository: Arc::new(upload_session_repo.unwrap_or_default()),
+        album_repository: Arc::new(album_repo.unwrap_or_default()),
+        authorization_service: Arc::new(test_authorization_service(share_grant_repo.clone())),
+        share_grant_repository: Arc::new(share_grant_repo),
+        share_link_repository: Arc::new(share_link_repo.unwrap_or_default()),
+        job_repository: Arc::new(job_repo.unwrap_or_default()),
+        media_type_allowlist: Arc::new(MediaTypeAllowlist::default()),
+        media_size_limits: Arc::new(MediaSizeLimits::default()),
+        quota_config: Arc::new(QuotaConfig::default()),
+        auth_token_config: Arc::new(AuthTokenConfig::default()),
+        session_cache: Arc::new(SessionCache::default()),
+        password_reset_config: Arc::new(PasswordResetConfig::default()),
         max_concurrent_requests_semaphore: Arc::new(tokio::sync::Semaphore::new(100)),
     }
 }
@@ -58,3 +103,11 @@ pub fn create_test_app_state(arguments: CreateTestAppStateArguments) -> AppState
 pub fn create_default_test_app_state() -> AppState {
     create_test_app_state(CreateTestAppStateArguments::default())
 }
+
+/// Signer with a fixed key, so tests can issue and verify signed media URLs
+pub fn test_media_url_signer() -> HmacMediaUrlSigner {
+    HmacMediaUrlSigner::new(HmacMediaUrlSignerConfig {
+        secret_key: "test_media_url_secret".to_string(),
+        ttl_seconds: 300,
+    })
+}